@@ -0,0 +1,29 @@
+-- Issue lifecycle status (mirrors domain::entities::issue::IssueStatus)
+CREATE TYPE issue_status AS ENUM (
+    'open',
+    'in_review',
+    'approved',
+    'disputed'
+);
+
+-- Create issues table
+CREATE TABLE IF NOT EXISTS issues (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id      UUID NOT NULL,
+    title           VARCHAR(200) NOT NULL,
+    description     TEXT,
+    bounty_value    DOUBLE PRECISION NOT NULL DEFAULT 0,
+    status          issue_status NOT NULL DEFAULT 'open',
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMP WITH TIME ZONE
+);
+
+-- Foreign key for project_id -> projects(id)
+ALTER TABLE issues
+ADD CONSTRAINT fk_issues_project
+FOREIGN KEY (project_id)
+REFERENCES projects(id)
+ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_issues_project_id ON issues (project_id);
+CREATE INDEX IF NOT EXISTS idx_issues_status ON issues (status);
@@ -0,0 +1,30 @@
+-- Terminal states for the issue lifecycle
+ALTER TYPE issue_status ADD VALUE IF NOT EXISTS 'closed';
+ALTER TYPE issue_status ADD VALUE IF NOT EXISTS 'cancelled';
+
+-- Audit trail of every status change applied to an issue
+CREATE TABLE IF NOT EXISTS issue_transitions (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id        UUID NOT NULL,
+    from_status     issue_status NOT NULL,
+    to_status       issue_status NOT NULL,
+    actor_id        UUID, -- NULL when the transition was performed by the system
+    note            TEXT,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Foreign key for issue_id -> issues(id)
+ALTER TABLE issue_transitions
+ADD CONSTRAINT fk_issue_transitions_issue
+FOREIGN KEY (issue_id)
+REFERENCES issues(id)
+ON DELETE CASCADE;
+
+-- Foreign key for actor_id -> users(id)
+ALTER TABLE issue_transitions
+ADD CONSTRAINT fk_issue_transitions_actor
+FOREIGN KEY (actor_id)
+REFERENCES users(id)
+ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_issue_transitions_issue_id ON issue_transitions (issue_id, created_at);
@@ -0,0 +1,21 @@
+-- Opaque refresh tokens (only the SHA-256 hash is stored)
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         UUID NOT NULL,
+    family_id       UUID NOT NULL, -- shared by every token rotated from the same login
+    token_hash      VARCHAR(64) NOT NULL UNIQUE,
+    expires_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    revoked_at      TIMESTAMP WITH TIME ZONE,
+    replaced_by     UUID
+);
+
+-- Foreign key for user_id -> users(id)
+ALTER TABLE refresh_tokens
+ADD CONSTRAINT fk_refresh_tokens_user
+FOREIGN KEY (user_id)
+REFERENCES users(id)
+ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_user_id ON refresh_tokens (user_id);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family_id ON refresh_tokens (family_id);
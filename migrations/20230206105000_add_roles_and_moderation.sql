@@ -0,0 +1,16 @@
+-- Platform-wide roles (mirrors domain::entities::user::Role)
+CREATE TYPE user_role AS ENUM (
+    'user',
+    'moderator',
+    'admin'
+);
+
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS role user_role NOT NULL DEFAULT 'user';
+
+-- Content hidden by a moderator is left out of listings
+ALTER TABLE projects
+ADD COLUMN IF NOT EXISTS hidden BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE issues
+ADD COLUMN IF NOT EXISTS hidden BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,11 @@
+-- Keyset pagination indexes (sort column + id tie-breaker)
+CREATE INDEX IF NOT EXISTS idx_users_created_at_id ON users (created_at, id);
+CREATE INDEX IF NOT EXISTS idx_users_username_id ON users (username, id);
+
+CREATE INDEX IF NOT EXISTS idx_projects_created_at_id ON projects (created_at, id);
+CREATE INDEX IF NOT EXISTS idx_projects_name_id ON projects (name, id);
+CREATE INDEX IF NOT EXISTS idx_projects_owner_id ON projects (owner_id);
+CREATE INDEX IF NOT EXISTS idx_projects_tags ON projects USING GIN (tags);
+
+CREATE INDEX IF NOT EXISTS idx_issues_created_at_id ON issues (created_at, id);
+CREATE INDEX IF NOT EXISTS idx_issues_bounty_value_id ON issues (bounty_value, id);
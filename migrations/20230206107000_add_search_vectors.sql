@@ -0,0 +1,24 @@
+-- Full-text search over projects and issues.
+-- array_to_string is only STABLE, so tags go through an IMMUTABLE wrapper to be
+-- usable in a generated column.
+CREATE OR REPLACE FUNCTION tags_to_text(tags TEXT[]) RETURNS TEXT
+LANGUAGE SQL IMMUTABLE PARALLEL SAFE AS $$
+    SELECT COALESCE(array_to_string(tags, ' '), '')
+$$;
+
+-- Weights: A = name/title, B = tags, C = description
+ALTER TABLE projects
+ADD COLUMN IF NOT EXISTS search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', COALESCE(name, '')), 'A') ||
+    setweight(to_tsvector('english', tags_to_text(tags)), 'B') ||
+    setweight(to_tsvector('english', COALESCE(description, '')), 'C')
+) STORED;
+
+ALTER TABLE issues
+ADD COLUMN IF NOT EXISTS search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', COALESCE(title, '')), 'A') ||
+    setweight(to_tsvector('english', COALESCE(description, '')), 'C')
+) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_projects_search_vector ON projects USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_issues_search_vector ON issues USING GIN (search_vector);
@@ -0,0 +1,18 @@
+-- Store bounties exactly: integer minor units + currency code
+-- (mirrors domain::value_objects::money::Money)
+ALTER TABLE issues
+ADD COLUMN IF NOT EXISTS bounty_amount BIGINT,
+ADD COLUMN IF NOT EXISTS bounty_currency VARCHAR(10) NOT NULL DEFAULT 'USD';
+
+-- Existing float bounties were implicitly USD
+UPDATE issues SET bounty_amount = GREATEST(ROUND(bounty_value::NUMERIC * 100), 0)::BIGINT;
+
+ALTER TABLE issues
+ALTER COLUMN bounty_amount SET NOT NULL,
+ALTER COLUMN bounty_currency DROP DEFAULT,
+ADD CONSTRAINT chk_issues_bounty_amount CHECK (bounty_amount >= 0),
+ADD CONSTRAINT chk_issues_bounty_currency CHECK (bounty_currency IN ('USD', 'EUR', 'BRL', 'DOT', 'KSM'));
+
+ALTER TABLE issues DROP COLUMN IF EXISTS bounty_value;
+
+CREATE INDEX IF NOT EXISTS idx_issues_bounty_amount_id ON issues (bounty_amount, id);
@@ -0,0 +1,87 @@
+-- Double-entry ledger for bounty escrow (mirrors domain::entities::ledger)
+CREATE TYPE ledger_account_kind AS ENUM (
+    'external',
+    'user',
+    'escrow',
+    'platform_fee'
+);
+
+CREATE TYPE journal_entry_kind AS ENUM (
+    'fund',
+    'release',
+    'refund'
+);
+
+-- owner_id is the user (kind = user) or issue (kind = escrow); NULL for platform-wide accounts.
+-- No foreign keys: ledger history must outlive the users and issues it refers to.
+CREATE TABLE IF NOT EXISTS ledger_accounts (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kind            ledger_account_kind NOT NULL,
+    owner_id        UUID,
+    currency        VARCHAR(10) NOT NULL,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_ledger_accounts_owner
+ON ledger_accounts (kind, COALESCE(owner_id, '00000000-0000-0000-0000-000000000000'::UUID), currency);
+
+CREATE TABLE IF NOT EXISTS journal_entries (
+    id              UUID PRIMARY KEY,
+    kind            journal_entry_kind NOT NULL,
+    issue_id        UUID,
+    actor_id        UUID,
+    memo            TEXT,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_journal_entries_issue_id ON journal_entries (issue_id, created_at);
+
+-- Signed amounts in minor units of the account's currency
+CREATE TABLE IF NOT EXISTS journal_postings (
+    id              BIGSERIAL PRIMARY KEY,
+    entry_id        UUID NOT NULL REFERENCES journal_entries(id),
+    account_id      UUID NOT NULL REFERENCES ledger_accounts(id),
+    amount          BIGINT NOT NULL CHECK (amount <> 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_journal_postings_entry_id ON journal_postings (entry_id);
+CREATE INDEX IF NOT EXISTS idx_journal_postings_account_id ON journal_postings (account_id);
+
+-- Every entry must balance per currency; checked at commit so all postings can be inserted first.
+CREATE OR REPLACE FUNCTION check_journal_entry_balanced() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+    IF EXISTS (
+        SELECT 1
+        FROM journal_postings jp
+        JOIN ledger_accounts la ON la.id = jp.account_id
+        WHERE jp.entry_id = NEW.entry_id
+        GROUP BY la.currency
+        HAVING SUM(jp.amount) <> 0
+    ) THEN
+        RAISE EXCEPTION 'journal entry % does not balance', NEW.entry_id;
+    END IF;
+    RETURN NULL;
+END;
+$$;
+
+CREATE CONSTRAINT TRIGGER trg_journal_postings_balanced
+AFTER INSERT ON journal_postings
+DEFERRABLE INITIALLY DEFERRED
+FOR EACH ROW EXECUTE FUNCTION check_journal_entry_balanced();
+
+-- The journal is append-only
+CREATE OR REPLACE FUNCTION reject_journal_mutation() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+    RAISE EXCEPTION 'journal is append-only: % on % is not allowed', TG_OP, TG_TABLE_NAME;
+END;
+$$;
+
+CREATE TRIGGER trg_journal_entries_immutable
+BEFORE UPDATE OR DELETE ON journal_entries
+FOR EACH ROW EXECUTE FUNCTION reject_journal_mutation();
+
+CREATE TRIGGER trg_journal_postings_immutable
+BEFORE UPDATE OR DELETE ON journal_postings
+FOR EACH ROW EXECUTE FUNCTION reject_journal_mutation();
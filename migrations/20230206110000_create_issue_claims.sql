@@ -0,0 +1,43 @@
+-- Contributors applying for bounties (mirrors domain::entities::issue_claim)
+CREATE TYPE claim_status AS ENUM (
+    'pending',
+    'accepted',
+    'rejected',
+    'withdrawn',
+    'expired'
+);
+
+-- exclusive: accepting a claim assigns the issue and rejects the other pending claims.
+-- competitive: several claims can be accepted; the assignee is whoever gets their work approved.
+CREATE TYPE claim_mode AS ENUM (
+    'exclusive',
+    'competitive'
+);
+
+ALTER TABLE issues
+ADD COLUMN IF NOT EXISTS assignee_id UUID REFERENCES users(id) ON DELETE SET NULL,
+ADD COLUMN IF NOT EXISTS claim_mode claim_mode NOT NULL DEFAULT 'exclusive';
+
+CREATE INDEX IF NOT EXISTS idx_issues_assignee_id ON issues (assignee_id);
+
+CREATE TABLE IF NOT EXISTS issue_claims (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id            UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    claimant_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    message             TEXT NOT NULL,
+    eta                 TIMESTAMP WITH TIME ZONE,
+    status              claim_status NOT NULL DEFAULT 'pending',
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMP WITH TIME ZONE,
+    last_activity_at    TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- At most one live claim per contributor and issue
+CREATE UNIQUE INDEX IF NOT EXISTS uq_issue_claims_active
+ON issue_claims (issue_id, claimant_id)
+WHERE status IN ('pending', 'accepted');
+
+CREATE INDEX IF NOT EXISTS idx_issue_claims_issue_id ON issue_claims (issue_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_issue_claims_active_activity
+ON issue_claims (last_activity_at)
+WHERE status IN ('pending', 'accepted');
@@ -0,0 +1,43 @@
+-- Work handed in for an issue and the owner's reviews of it
+-- (mirrors domain::entities::submission)
+CREATE TYPE submission_status AS ENUM (
+    'pending',
+    'changes_requested',
+    'approved',
+    'superseded'
+);
+
+CREATE TYPE review_decision AS ENUM (
+    'approved',
+    'changes_requested'
+);
+
+CREATE TABLE IF NOT EXISTS submissions (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id        UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    submitter_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    pr_url          TEXT NOT NULL,
+    notes           TEXT,
+    status          submission_status NOT NULL DEFAULT 'pending',
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMP WITH TIME ZONE
+);
+
+-- A contributor waits for a review before submitting again
+CREATE UNIQUE INDEX IF NOT EXISTS uq_submissions_pending
+ON submissions (issue_id, submitter_id)
+WHERE status = 'pending';
+
+CREATE INDEX IF NOT EXISTS idx_submissions_issue_id ON submissions (issue_id, created_at);
+
+CREATE TABLE IF NOT EXISTS submission_reviews (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    submission_id   UUID NOT NULL REFERENCES submissions(id) ON DELETE CASCADE,
+    reviewer_id     UUID REFERENCES users(id) ON DELETE SET NULL,
+    decision        review_decision NOT NULL,
+    comment         TEXT,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_submission_reviews_submission_id
+ON submission_reviews (submission_id, created_at);
@@ -0,0 +1,71 @@
+-- Disputes over submitted work (mirrors domain::entities::dispute)
+CREATE TYPE dispute_status AS ENUM (
+    'open',
+    'resolved'
+);
+
+CREATE TYPE dispute_ruling AS ENUM (
+    'full_payout',
+    'split',
+    'refund'
+);
+
+CREATE TYPE dispute_event_kind AS ENUM (
+    'opened',
+    'evidence',
+    'ruling'
+);
+
+-- Work lost in a dispute
+ALTER TYPE submission_status ADD VALUE IF NOT EXISTS 'rejected';
+
+CREATE TABLE IF NOT EXISTS disputes (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id            UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    submission_id       UUID NOT NULL REFERENCES submissions(id) ON DELETE CASCADE,
+    contributor_id      UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    opened_by           UUID REFERENCES users(id) ON DELETE SET NULL,
+    reason              TEXT NOT NULL,
+    status              dispute_status NOT NULL DEFAULT 'open',
+    ruling              dispute_ruling,
+    -- Gross amount awarded to the contributor, in minor units of the bounty currency
+    contributor_amount  BIGINT CHECK (contributor_amount >= 0),
+    resolved_by         UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    resolved_at         TIMESTAMP WITH TIME ZONE,
+    CHECK ((status = 'open') = (ruling IS NULL))
+);
+
+-- An issue has at most one open dispute
+CREATE UNIQUE INDEX IF NOT EXISTS uq_disputes_open
+ON disputes (issue_id)
+WHERE status = 'open';
+
+CREATE TABLE IF NOT EXISTS dispute_events (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    dispute_id  UUID NOT NULL REFERENCES disputes(id) ON DELETE CASCADE,
+    actor_id    UUID REFERENCES users(id) ON DELETE SET NULL,
+    kind        dispute_event_kind NOT NULL,
+    body        TEXT NOT NULL,
+    url         TEXT,
+    created_at  TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_dispute_events_dispute_id ON dispute_events (dispute_id, created_at);
+
+-- The dispute log is append-only. Rows only go away when their dispute is deleted
+-- together with its issue (a cascade, i.e. a nested trigger), and the user FK may
+-- still be nulled by its own cascade.
+CREATE OR REPLACE FUNCTION reject_dispute_event_mutation() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+    IF pg_trigger_depth() > 1 THEN
+        RETURN CASE WHEN TG_OP = 'DELETE' THEN OLD ELSE NEW END;
+    END IF;
+    RAISE EXCEPTION 'dispute log is append-only: % on % is not allowed', TG_OP, TG_TABLE_NAME;
+END;
+$$;
+
+CREATE TRIGGER trg_dispute_events_immutable
+BEFORE UPDATE OR DELETE ON dispute_events
+FOR EACH ROW EXECUTE FUNCTION reject_dispute_event_mutation();
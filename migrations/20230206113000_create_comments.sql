@@ -0,0 +1,44 @@
+-- Discussion on projects and issues (mirrors domain::entities::comment)
+CREATE TABLE IF NOT EXISTS comments (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id  UUID REFERENCES projects(id) ON DELETE CASCADE,
+    issue_id    UUID REFERENCES issues(id) ON DELETE CASCADE,
+    -- Replies point at a top-level comment of the same target (one level of threading)
+    parent_id   UUID REFERENCES comments(id) ON DELETE CASCADE,
+    author_id   UUID REFERENCES users(id) ON DELETE SET NULL,
+    -- Markdown source
+    body        TEXT NOT NULL,
+    created_at  TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMP WITH TIME ZONE,
+    deleted_at  TIMESTAMP WITH TIME ZONE,
+    CHECK (num_nonnulls(project_id, issue_id) = 1)
+);
+
+CREATE INDEX IF NOT EXISTS idx_comments_project_threads
+ON comments (project_id, created_at, id)
+WHERE parent_id IS NULL AND project_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_comments_issue_threads
+ON comments (issue_id, created_at, id)
+WHERE parent_id IS NULL AND issue_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_comments_parent_id ON comments (parent_id, created_at);
+
+-- Previous bodies, one row per edit
+CREATE TABLE IF NOT EXISTS comment_edits (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    comment_id      UUID NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    previous_body   TEXT NOT NULL,
+    edited_at       TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_comment_edits_comment_id ON comment_edits (comment_id, edited_at);
+
+-- Users @mentioned in the current body of a comment
+CREATE TABLE IF NOT EXISTS comment_mentions (
+    comment_id  UUID NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    user_id     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (comment_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_comment_mentions_user_id ON comment_mentions (user_id);
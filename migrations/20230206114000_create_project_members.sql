@@ -0,0 +1,31 @@
+-- Per-project roles (mirrors domain::entities::project_member::ProjectRole)
+CREATE TYPE project_role AS ENUM (
+    'owner',
+    'maintainer',
+    'triager'
+);
+
+-- An invitation is a row with accepted_at still NULL; it grants nothing until accepted.
+CREATE TABLE IF NOT EXISTS project_members (
+    project_id      UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role            project_role NOT NULL,
+    invited_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    accepted_at     TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (project_id, user_id),
+    CONSTRAINT chk_project_members_owner_accepted
+        CHECK (role <> 'owner' OR accepted_at IS NOT NULL)
+);
+
+-- projects.owner_id stays the owner of record; this keeps exactly one owner row next to it.
+CREATE UNIQUE INDEX IF NOT EXISTS uq_project_members_owner
+ON project_members (project_id)
+WHERE role = 'owner';
+
+CREATE INDEX IF NOT EXISTS idx_project_members_user_id ON project_members (user_id);
+
+INSERT INTO project_members (project_id, user_id, role, created_at, accepted_at)
+SELECT id, owner_id, 'owner', COALESCE(created_at, NOW()), COALESCE(created_at, NOW())
+FROM projects
+ON CONFLICT DO NOTHING;
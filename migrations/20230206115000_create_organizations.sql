@@ -0,0 +1,44 @@
+-- Roles inside an organization (mirrors domain::entities::organization::OrganizationRole)
+CREATE TYPE organization_role AS ENUM (
+    'owner',
+    'admin',
+    'member'
+);
+
+CREATE TABLE IF NOT EXISTS organizations (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug            VARCHAR(39) NOT NULL,
+    display_name    VARCHAR(100) NOT NULL,
+    description     TEXT,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMP WITH TIME ZONE,
+    CONSTRAINT chk_organizations_slug CHECK (slug ~ '^[a-z0-9]([a-z0-9-]*[a-z0-9])?$')
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_organizations_slug ON organizations (slug);
+
+-- Same invitation model as project_members: a NULL accepted_at grants nothing yet.
+CREATE TABLE IF NOT EXISTS organization_members (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role            organization_role NOT NULL,
+    invited_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    accepted_at     TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (organization_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_organization_members_user_id ON organization_members (user_id);
+
+-- A project is owned by exactly one user or one organization. An organization
+-- cannot be deleted while it still owns projects.
+ALTER TABLE projects
+ALTER COLUMN owner_id DROP NOT NULL,
+ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id) ON DELETE RESTRICT,
+ADD CONSTRAINT chk_projects_single_owner CHECK (num_nonnulls(owner_id, organization_id) = 1);
+
+CREATE INDEX IF NOT EXISTS idx_projects_organization_id ON projects (organization_id, created_at);
+
+-- Organizations hold their own balance and can fund bounties from it.
+ALTER TYPE ledger_account_kind ADD VALUE IF NOT EXISTS 'organization';
+ALTER TYPE journal_entry_kind ADD VALUE IF NOT EXISTS 'deposit';
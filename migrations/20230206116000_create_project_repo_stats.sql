@@ -0,0 +1,27 @@
+-- Repository metadata pulled from the code host of each project's github_link.
+-- A row is written on every sync attempt: checked_at/sync_error track the last
+-- attempt, synced_at the last successful one. Stats keep their last good values
+-- when an attempt fails.
+CREATE TABLE IF NOT EXISTS project_repo_stats (
+    project_id          UUID PRIMARY KEY,
+    stars               INTEGER,
+    forks               INTEGER,
+    open_issues         INTEGER,
+    primary_language    VARCHAR(100),
+    license             VARCHAR(100),
+    topics              TEXT[] NOT NULL DEFAULT '{}',
+    pushed_at           TIMESTAMP WITH TIME ZONE,
+    synced_at           TIMESTAMP WITH TIME ZONE,
+    checked_at          TIMESTAMP WITH TIME ZONE NOT NULL,
+    sync_error          TEXT,
+    CHECK (synced_at IS NULL OR (stars IS NOT NULL AND forks IS NOT NULL AND open_issues IS NOT NULL))
+);
+
+-- Foreign key for project_id -> projects(id)
+ALTER TABLE project_repo_stats
+ADD CONSTRAINT fk_project_repo_stats_project
+FOREIGN KEY (project_id)
+REFERENCES projects(id)
+ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_project_repo_stats_checked_at ON project_repo_stats (checked_at);
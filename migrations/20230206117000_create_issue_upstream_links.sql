@@ -0,0 +1,35 @@
+-- Issues imported from a project's code host, linked to the upstream issue they
+-- mirror. The repository is stored with the link so it keeps working if the
+-- project's github_link changes later.
+CREATE TYPE upstream_issue_state AS ENUM (
+    'open',
+    'closed'
+);
+
+CREATE TABLE IF NOT EXISTS issue_upstream_links (
+    issue_id        UUID PRIMARY KEY,
+    project_id      UUID NOT NULL,
+    repo_owner      VARCHAR(100) NOT NULL,
+    repo_name       VARCHAR(100) NOT NULL,
+    number          INTEGER NOT NULL CHECK (number > 0),
+    url             TEXT NOT NULL,
+    state           upstream_issue_state NOT NULL,
+    synced_at       TIMESTAMP WITH TIME ZONE NOT NULL,
+    UNIQUE (project_id, repo_owner, repo_name, number)
+);
+
+-- Foreign key for issue_id -> issues(id)
+ALTER TABLE issue_upstream_links
+ADD CONSTRAINT fk_issue_upstream_links_issue
+FOREIGN KEY (issue_id)
+REFERENCES issues(id)
+ON DELETE CASCADE;
+
+-- Foreign key for project_id -> projects(id)
+ALTER TABLE issue_upstream_links
+ADD CONSTRAINT fk_issue_upstream_links_project
+FOREIGN KEY (project_id)
+REFERENCES projects(id)
+ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_issue_upstream_links_synced_at ON issue_upstream_links (synced_at);
@@ -0,0 +1,40 @@
+-- GitHub webhook deliveries already handled, by `X-GitHub-Delivery` id, so a
+-- redelivered event is not applied twice.
+CREATE TABLE IF NOT EXISTS github_webhook_deliveries (
+    delivery_id     VARCHAR(100) PRIMARY KEY,
+    event           VARCHAR(50) NOT NULL,
+    received_at     TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+-- Upstream pull requests working on an issue, either by closing its imported
+-- upstream issue ("Fixes #12") or by being handed in as a submission.
+CREATE TYPE pull_request_state AS ENUM (
+    'open',
+    'closed',
+    'merged'
+);
+
+CREATE TABLE IF NOT EXISTS issue_pull_requests (
+    issue_id        UUID NOT NULL,
+    repo_owner      VARCHAR(100) NOT NULL,
+    repo_name       VARCHAR(100) NOT NULL,
+    number          INTEGER NOT NULL CHECK (number > 0),
+    url             TEXT NOT NULL,
+    title           TEXT NOT NULL,
+    author          VARCHAR(100),
+    state           pull_request_state NOT NULL,
+    draft           BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (issue_id, repo_owner, repo_name, number)
+);
+
+-- Foreign key for issue_id -> issues(id)
+ALTER TABLE issue_pull_requests
+ADD CONSTRAINT fk_issue_pull_requests_issue
+FOREIGN KEY (issue_id)
+REFERENCES issues(id)
+ON DELETE CASCADE;
+
+-- Webhooks look imported issues up by their upstream number.
+CREATE INDEX IF NOT EXISTS idx_issue_upstream_links_upstream
+ON issue_upstream_links (repo_owner, repo_name, number);
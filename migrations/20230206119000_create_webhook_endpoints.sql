@@ -0,0 +1,79 @@
+-- Outgoing webhooks: endpoints registered by a user or an organization, and the
+-- queue of deliveries to them. A delivery is retried with backoff until the
+-- endpoint answers 2xx or the attempts run out, and stays as the delivery log.
+CREATE TABLE IF NOT EXISTS webhook_endpoints (
+    id                  UUID PRIMARY KEY,
+    owner_id            UUID,
+    organization_id     UUID,
+    url                 TEXT NOT NULL,
+    secret              VARCHAR(100) NOT NULL,
+    events              TEXT[] NOT NULL CHECK (cardinality(events) > 0),
+    description         TEXT,
+    active              BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL,
+    updated_at          TIMESTAMP WITH TIME ZONE,
+    CHECK ((owner_id IS NULL) <> (organization_id IS NULL))
+);
+
+-- Foreign key for owner_id -> users(id)
+ALTER TABLE webhook_endpoints
+ADD CONSTRAINT fk_webhook_endpoints_owner
+FOREIGN KEY (owner_id)
+REFERENCES users(id)
+ON DELETE CASCADE;
+
+-- Foreign key for organization_id -> organizations(id)
+ALTER TABLE webhook_endpoints
+ADD CONSTRAINT fk_webhook_endpoints_organization
+FOREIGN KEY (organization_id)
+REFERENCES organizations(id)
+ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_owner_id ON webhook_endpoints (owner_id);
+CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_organization_id ON webhook_endpoints (organization_id);
+
+CREATE TYPE webhook_delivery_status AS ENUM (
+    'pending',
+    'delivered',
+    'failed'
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id                  UUID PRIMARY KEY,
+    endpoint_id         UUID NOT NULL,
+    event_id            UUID NOT NULL,
+    event_type          VARCHAR(50) NOT NULL,
+    payload             TEXT NOT NULL,
+    status              webhook_delivery_status NOT NULL,
+    attempts            INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    next_attempt_at     TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_attempt_at     TIMESTAMP WITH TIME ZONE,
+    response_status     INTEGER,
+    last_error          TEXT,
+    redelivery_of       UUID,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL,
+    delivered_at        TIMESTAMP WITH TIME ZONE
+);
+
+-- Foreign key for endpoint_id -> webhook_endpoints(id)
+ALTER TABLE webhook_deliveries
+ADD CONSTRAINT fk_webhook_deliveries_endpoint
+FOREIGN KEY (endpoint_id)
+REFERENCES webhook_endpoints(id)
+ON DELETE CASCADE;
+
+-- Foreign key for redelivery_of -> webhook_deliveries(id)
+ALTER TABLE webhook_deliveries
+ADD CONSTRAINT fk_webhook_deliveries_redelivery_of
+FOREIGN KEY (redelivery_of)
+REFERENCES webhook_deliveries(id)
+ON DELETE SET NULL;
+
+-- The deliverer polls pending deliveries that are due.
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due
+ON webhook_deliveries (next_attempt_at)
+WHERE status = 'pending';
+
+-- Delivery log of an endpoint, newest first.
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_endpoint
+ON webhook_deliveries (endpoint_id, created_at DESC, id DESC);
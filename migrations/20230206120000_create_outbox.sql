@@ -0,0 +1,27 @@
+-- Transactional outbox: domain events written in the same transaction as the
+-- change they describe, and handed to in-process subscribers by a background
+-- dispatcher. An event stays until every subscriber handled it.
+CREATE TABLE IF NOT EXISTS outbox (
+    id                  UUID PRIMARY KEY,
+    -- Insertion order; events of one transaction share their created_at.
+    sequence            BIGSERIAL NOT NULL,
+    event_type          VARCHAR(50) NOT NULL,
+    payload             TEXT NOT NULL,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL,
+    attempts            INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    next_attempt_at     TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Subscribers that handled the event in an attempt that failed for others.
+    handled_by          TEXT[] NOT NULL DEFAULT '{}',
+    last_error          TEXT,
+    dispatched_at       TIMESTAMP WITH TIME ZONE
+);
+
+-- The dispatcher polls undispatched events that are due.
+CREATE INDEX IF NOT EXISTS idx_outbox_due ON outbox (next_attempt_at) WHERE dispatched_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_outbox_dispatched ON outbox (dispatched_at) WHERE dispatched_at IS NOT NULL;
+
+-- Outgoing webhooks are queued by an outbox subscriber, which may see an event
+-- more than once; each endpoint gets one original delivery per event.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_webhook_deliveries_event
+ON webhook_deliveries (endpoint_id, event_id)
+WHERE redelivery_of IS NULL;
@@ -0,0 +1,28 @@
+-- Background jobs: typed units of work run by the worker outside any request. A
+-- failed job is retried with backoff; once its attempts run out it stays as
+-- `dead` until an admin retries it.
+CREATE TYPE job_status AS ENUM (
+    'pending',
+    'running',
+    'completed',
+    'dead'
+);
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id              UUID PRIMARY KEY,
+    kind            VARCHAR(100) NOT NULL,
+    payload         TEXT NOT NULL,
+    status          job_status NOT NULL,
+    attempts        INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    max_attempts    INTEGER NOT NULL CHECK (max_attempts > 0),
+    -- When a pending job is due; for a running job, when its worker's lease ends.
+    run_at          TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_error      TEXT,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    finished_at     TIMESTAMP WITH TIME ZONE
+);
+
+-- Workers poll pending jobs that are due, and running jobs whose lease ran out.
+CREATE INDEX IF NOT EXISTS idx_jobs_due ON jobs (run_at) WHERE status IN ('pending', 'running');
+-- Admin listing by status, newest first.
+CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs (status, created_at DESC, id DESC);
@@ -0,0 +1,63 @@
+-- In-app notifications, created from domain events for the users they concern,
+-- and each user's choice of channel per notification type.
+CREATE TYPE notification_type AS ENUM (
+    'claim_created',
+    'claim_accepted',
+    'claim_rejected',
+    'submission_created',
+    'submission_approved',
+    'changes_requested',
+    'comment_created',
+    'comment_reply',
+    'mentioned',
+    'bounty_paid'
+);
+
+CREATE TYPE notification_channel AS ENUM (
+    'in_app',
+    'email',
+    'off'
+);
+
+CREATE TABLE IF NOT EXISTS notifications (
+    id                  UUID PRIMARY KEY,
+    user_id             UUID NOT NULL,
+    notification_type   notification_type NOT NULL,
+    -- The outbox event it was created from; a user gets at most one notification
+    -- per event, however often the event is dispatched.
+    event_id            UUID NOT NULL,
+    -- What it is about. Not foreign keys: notifications outlive what they point at.
+    actor_id            UUID,
+    project_id          UUID,
+    issue_id            UUID,
+    -- The claim, submission, comment or journal entry, depending on the type.
+    subject_id          UUID,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL,
+    read_at             TIMESTAMP WITH TIME ZONE,
+    UNIQUE (event_id, user_id)
+);
+
+-- Foreign key for user_id -> users(id)
+ALTER TABLE notifications
+ADD CONSTRAINT fk_notifications_user
+FOREIGN KEY (user_id)
+REFERENCES users(id)
+ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user ON notifications (user_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread ON notifications (user_id) WHERE read_at IS NULL;
+
+-- Types a user has not chosen a channel for use their default.
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id             UUID NOT NULL,
+    notification_type   notification_type NOT NULL,
+    channel             notification_channel NOT NULL,
+    PRIMARY KEY (user_id, notification_type)
+);
+
+-- Foreign key for user_id -> users(id)
+ALTER TABLE notification_preferences
+ADD CONSTRAINT fk_notification_preferences_user
+FOREIGN KEY (user_id)
+REFERENCES users(id)
+ON DELETE CASCADE;
@@ -0,0 +1,6 @@
+-- Jobs queued in reaction to an event carry a key derived from it, so the event
+-- being handled again does not queue the same work twice.
+ALTER TABLE jobs
+ADD COLUMN IF NOT EXISTS dedup_key VARCHAR(200);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_jobs_dedup_key ON jobs (dedup_key);
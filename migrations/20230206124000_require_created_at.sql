@@ -0,0 +1,11 @@
+-- Users and projects are paged by (created_at, id); a row with a NULL
+-- created_at would fall out of every page. Both columns had a default,
+-- so only rows inserted with an explicit NULL need a value.
+UPDATE users SET created_at = COALESCE(updated_at, NOW()) WHERE created_at IS NULL;
+UPDATE projects SET created_at = COALESCE(updated_at, NOW()) WHERE created_at IS NULL;
+
+ALTER TABLE users
+ALTER COLUMN created_at SET NOT NULL;
+
+ALTER TABLE projects
+ALTER COLUMN created_at SET NOT NULL;
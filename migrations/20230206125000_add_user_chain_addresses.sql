@@ -0,0 +1,4 @@
+-- The SS58 address on-chain bounties are paid to. Releasing an on-chain escrow
+-- pays the author of the approved submission, so they must register one first.
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS chain_address VARCHAR(64);
@@ -0,0 +1,77 @@
+use crate::application::policies::Actor;
+use crate::domain::entities::user::Role;
+use crate::domain::errors::domain_error::DomainError;
+use crate::infra::jwt::validate_jwt;
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap},
+};
+use uuid::Uuid;
+
+/// The caller identified by a valid `Authorization: Bearer <jwt>` header.
+/// Rejects the request with 401 when the header is missing or the token is invalid.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub role: Role,
+}
+
+impl AuthUser {
+    pub fn actor(&self) -> Actor {
+        Actor::new(self.id, self.role)
+    }
+}
+
+impl OptionalAuthUser {
+    pub fn actor(&self) -> Option<Actor> {
+        self.0.map(|user| user.actor())
+    }
+}
+
+/// Like `AuthUser`, but anonymous requests are let through as `None`.
+/// A header carrying an invalid token is still rejected.
+#[derive(Debug, Clone, Copy)]
+pub struct OptionalAuthUser(pub Option<AuthUser>);
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(&parts.headers)
+            .ok_or_else(|| DomainError::Unauthorized("Missing bearer token".to_string()))?;
+        let claims = validate_jwt(token)?;
+        Ok(AuthUser {
+            id: claims.sub,
+            role: claims.role,
+        })
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for OptionalAuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if !parts.headers.contains_key(header::AUTHORIZATION) {
+            return Ok(OptionalAuthUser(None));
+        }
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        Ok(OptionalAuthUser(Some(user)))
+    }
+}
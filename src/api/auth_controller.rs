@@ -0,0 +1,76 @@
+use super::auth::AuthUser;
+use super::extract::Json;
+use crate::application::usecases::session_usecases::SessionUsecases;
+use crate::application::usecases::user_usecases::UserUsecases;
+use crate::domain::errors::domain_error::DomainError;
+use crate::infra::db::refresh_token_repository_sql::RefreshTokenRepositorySql;
+use crate::infra::db::user_repository_sql::UserRepositorySql;
+use crate::infra::jwt::{access_token_ttl, generate_jwt, refresh_token_ttl};
+use axum::{extract::State, http::StatusCode, routing::post, Router};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+
+#[derive(Deserialize)]
+struct RefreshPayload {
+    refresh_token: String,
+}
+
+#[derive(Serialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    token_type: &'static str,
+    expires_in: i64,
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/refresh", post(refresh))
+        .route("/logout", post(logout))
+        .route("/logout-all", post(logout_all))
+        .with_state(pool)
+}
+
+async fn refresh(
+    State(pool): State<Pool<Postgres>>,
+    Json(payload): Json<RefreshPayload>,
+) -> Result<Json<TokenResponse>, DomainError> {
+    let repo = RefreshTokenRepositorySql::new(pool.clone());
+    let usecases = SessionUsecases::new(repo, refresh_token_ttl());
+    let (user_id, refresh_token) = usecases
+        .rotate_refresh_token(&payload.refresh_token)
+        .await?;
+    // Recarrega o usuário para que o novo token reflita o papel atual
+    let user = UserUsecases::new(UserRepositorySql::new(pool))
+        .get_user(user_id)
+        .await?;
+    let access_token = generate_jwt(user.id, user.role)?;
+    Ok(Json(TokenResponse {
+        access_token,
+        refresh_token,
+        token_type: "Bearer",
+        expires_in: access_token_ttl().num_seconds(),
+    }))
+}
+
+async fn logout(
+    State(pool): State<Pool<Postgres>>,
+    Json(payload): Json<RefreshPayload>,
+) -> Result<StatusCode, DomainError> {
+    let repo = RefreshTokenRepositorySql::new(pool);
+    let usecases = SessionUsecases::new(repo, refresh_token_ttl());
+    usecases
+        .revoke_refresh_token(&payload.refresh_token)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn logout_all(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    let repo = RefreshTokenRepositorySql::new(pool);
+    let usecases = SessionUsecases::new(repo, refresh_token_ttl());
+    usecases.revoke_all_sessions(auth.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,162 @@
+use super::auth::{AuthUser, OptionalAuthUser};
+use super::extract::{Json, Path};
+use crate::application::usecases::claim_usecases::ClaimUsecases;
+use crate::domain::entities::issue_claim::{ClaimStatus, IssueClaim};
+use crate::domain::errors::domain_error::DomainError;
+use crate::infra::config::claim_inactivity_ttl;
+use crate::infra::db::issue_claim_repository_sql::IssueClaimRepositorySql;
+use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{post, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct CreateClaimPayload {
+    message: String,
+    eta: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct UpdateClaimPayload {
+    message: Option<String>,
+    eta: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+struct ClaimResponse {
+    id: Uuid,
+    issue_id: Uuid,
+    claimant_id: Uuid,
+    message: String,
+    eta: Option<DateTime<Utc>>,
+    status: ClaimStatus,
+    created_at: DateTime<Utc>,
+    last_activity_at: DateTime<Utc>,
+}
+
+impl ClaimResponse {
+    fn from_entity(claim: IssueClaim) -> Self {
+        Self {
+            id: claim.id,
+            issue_id: claim.issue_id,
+            claimant_id: claim.claimant_id,
+            message: claim.message,
+            eta: claim.eta,
+            status: claim.status,
+            created_at: claim.created_at,
+            last_activity_at: claim.last_activity_at,
+        }
+    }
+}
+
+/// Mounted under `/issues` next to the issue routes.
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/:id/claims", post(create_claim).get(list_claims))
+        .route("/:id/claims/:claim_id", put(update_claim))
+        .route("/:id/claims/:claim_id/accept", post(accept_claim))
+        .route("/:id/claims/:claim_id/reject", post(reject_claim))
+        .route("/:id/claims/:claim_id/withdraw", post(withdraw_claim))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn create_claim(
+    State(pool): State<Pool<Postgres>>,
+    Path(issue_id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<CreateClaimPayload>,
+) -> Result<(StatusCode, Json<ClaimResponse>), DomainError> {
+    let claim = claim_usecases(pool)
+        .create_claim(issue_id, &auth.actor(), payload.message, payload.eta)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ClaimResponse::from_entity(claim))))
+}
+
+async fn list_claims(
+    State(pool): State<Pool<Postgres>>,
+    Path(issue_id): Path<Uuid>,
+    auth: OptionalAuthUser,
+) -> Result<Json<Vec<ClaimResponse>>, DomainError> {
+    let claims = claim_usecases(pool)
+        .get_issue_claims(issue_id, auth.actor().as_ref())
+        .await?;
+    Ok(Json(
+        claims.into_iter().map(ClaimResponse::from_entity).collect(),
+    ))
+}
+
+async fn update_claim(
+    State(pool): State<Pool<Postgres>>,
+    Path((issue_id, claim_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateClaimPayload>,
+) -> Result<Json<ClaimResponse>, DomainError> {
+    let claim = claim_usecases(pool)
+        .update_claim(
+            issue_id,
+            claim_id,
+            &auth.actor(),
+            payload.message,
+            payload.eta,
+        )
+        .await?;
+    Ok(Json(ClaimResponse::from_entity(claim)))
+}
+
+async fn accept_claim(
+    State(pool): State<Pool<Postgres>>,
+    Path((issue_id, claim_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+) -> Result<Json<ClaimResponse>, DomainError> {
+    let claim = claim_usecases(pool)
+        .accept_claim(issue_id, claim_id, &auth.actor())
+        .await?;
+    Ok(Json(ClaimResponse::from_entity(claim)))
+}
+
+async fn reject_claim(
+    State(pool): State<Pool<Postgres>>,
+    Path((issue_id, claim_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+) -> Result<Json<ClaimResponse>, DomainError> {
+    let claim = claim_usecases(pool)
+        .reject_claim(issue_id, claim_id, &auth.actor())
+        .await?;
+    Ok(Json(ClaimResponse::from_entity(claim)))
+}
+
+async fn withdraw_claim(
+    State(pool): State<Pool<Postgres>>,
+    Path((issue_id, claim_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+) -> Result<Json<ClaimResponse>, DomainError> {
+    let claim = claim_usecases(pool)
+        .withdraw_claim(issue_id, claim_id, &auth.actor())
+        .await?;
+    Ok(Json(ClaimResponse::from_entity(claim)))
+}
+
+// ------------------------
+// Aux Functions
+
+fn claim_usecases(
+    pool: Pool<Postgres>,
+) -> ClaimUsecases<IssueClaimRepositorySql, IssueRepositorySql, ProjectRepositorySql> {
+    ClaimUsecases::new(
+        IssueClaimRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool),
+        claim_inactivity_ttl(),
+    )
+}
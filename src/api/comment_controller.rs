@@ -0,0 +1,269 @@
+use super::auth::{AuthUser, OptionalAuthUser};
+use super::extract::{Json, Path, Query};
+use super::pagination::{decode_cursor, PageResponse};
+use crate::application::usecases::comment_usecases::{CommentThread, CommentUsecases};
+use crate::domain::entities::comment::{Comment, CommentEdit, CommentTarget};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::comment_repository::{CommentQuery, CommentSort};
+use crate::infra::db::comment_repository_sql::CommentRepositorySql;
+use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use crate::infra::db::user_repository_sql::UserRepositorySql;
+use crate::infra::markdown::render_markdown;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct CreateCommentPayload {
+    body: String,
+    parent_id: Option<Uuid>,
+}
+
+#[derive(Deserialize)]
+struct EditCommentPayload {
+    body: String,
+}
+
+#[derive(Deserialize)]
+struct ListCommentsParams {
+    sort: Option<CommentSort>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+/// Deleted comments keep their place in the thread but lose their body.
+#[derive(Serialize)]
+struct CommentResponse {
+    id: Uuid,
+    parent_id: Option<Uuid>,
+    author_id: Option<Uuid>,
+    body: Option<String>,
+    body_html: Option<String>,
+    mentions: Vec<Uuid>,
+    deleted: bool,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replies: Option<Vec<CommentResponse>>,
+}
+
+impl CommentResponse {
+    fn from_entity(comment: Comment) -> Self {
+        let deleted = comment.is_deleted();
+        let body = (!deleted).then_some(comment.body);
+        Self {
+            id: comment.id,
+            parent_id: comment.parent_id,
+            author_id: comment.author_id,
+            body_html: body.as_deref().map(render_markdown),
+            body,
+            mentions: if deleted {
+                Vec::new()
+            } else {
+                comment.mentions
+            },
+            deleted,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+            replies: None,
+        }
+    }
+
+    fn from_thread(thread: CommentThread) -> Self {
+        Self {
+            replies: Some(
+                thread
+                    .replies
+                    .into_iter()
+                    .map(CommentResponse::from_entity)
+                    .collect(),
+            ),
+            ..Self::from_entity(thread.comment)
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CommentEditResponse {
+    id: Uuid,
+    previous_body: String,
+    edited_at: DateTime<Utc>,
+}
+
+impl CommentEditResponse {
+    fn from_entity(edit: CommentEdit) -> Self {
+        Self {
+            id: edit.id,
+            previous_body: edit.previous_body,
+            edited_at: edit.edited_at,
+        }
+    }
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/:id", put(edit_comment).delete(delete_comment))
+        .route("/:id/edits", get(list_comment_edits))
+        .with_state(pool)
+}
+
+/// Mounted under `/projects` next to the project routes.
+pub fn project_routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route(
+            "/:id/comments",
+            get(list_project_comments).post(create_project_comment),
+        )
+        .with_state(pool)
+}
+
+/// Mounted under `/issues` next to the issue routes.
+pub fn issue_routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route(
+            "/:id/comments",
+            get(list_issue_comments).post(create_issue_comment),
+        )
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn list_project_comments(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: OptionalAuthUser,
+    Query(params): Query<ListCommentsParams>,
+) -> Result<Json<PageResponse<CommentResponse>>, DomainError> {
+    list_comments(pool, CommentTarget::Project(id), auth, params).await
+}
+
+async fn create_project_comment(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<CreateCommentPayload>,
+) -> Result<(StatusCode, Json<CommentResponse>), DomainError> {
+    create_comment(pool, CommentTarget::Project(id), auth, payload).await
+}
+
+async fn list_issue_comments(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: OptionalAuthUser,
+    Query(params): Query<ListCommentsParams>,
+) -> Result<Json<PageResponse<CommentResponse>>, DomainError> {
+    list_comments(pool, CommentTarget::Issue(id), auth, params).await
+}
+
+async fn create_issue_comment(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<CreateCommentPayload>,
+) -> Result<(StatusCode, Json<CommentResponse>), DomainError> {
+    create_comment(pool, CommentTarget::Issue(id), auth, payload).await
+}
+
+async fn edit_comment(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<EditCommentPayload>,
+) -> Result<Json<CommentResponse>, DomainError> {
+    let comment = comment_usecases(pool)
+        .edit_comment(id, &auth.actor(), payload.body)
+        .await?;
+    Ok(Json(CommentResponse::from_entity(comment)))
+}
+
+async fn delete_comment(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    comment_usecases(pool)
+        .delete_comment(id, &auth.actor())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_comment_edits(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: OptionalAuthUser,
+) -> Result<Json<Vec<CommentEditResponse>>, DomainError> {
+    let edits = comment_usecases(pool)
+        .get_comment_edits(id, auth.actor().as_ref())
+        .await?;
+    Ok(Json(
+        edits
+            .into_iter()
+            .map(CommentEditResponse::from_entity)
+            .collect(),
+    ))
+}
+
+// ------------------------
+// Aux Functions
+
+fn comment_usecases(
+    pool: Pool<Postgres>,
+) -> CommentUsecases<
+    CommentRepositorySql,
+    UserRepositorySql,
+    IssueRepositorySql,
+    ProjectRepositorySql,
+> {
+    CommentUsecases::new(
+        CommentRepositorySql::new(pool.clone()),
+        UserRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool),
+    )
+}
+
+async fn list_comments(
+    pool: Pool<Postgres>,
+    target: CommentTarget,
+    auth: OptionalAuthUser,
+    params: ListCommentsParams,
+) -> Result<Json<PageResponse<CommentResponse>>, DomainError> {
+    let query = CommentQuery {
+        sort: params.sort.unwrap_or_default(),
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+    };
+    let page = comment_usecases(pool)
+        .list_comments(target, auth.actor().as_ref(), query)
+        .await?;
+    Ok(Json(PageResponse::from_page(
+        page,
+        CommentResponse::from_thread,
+    )))
+}
+
+async fn create_comment(
+    pool: Pool<Postgres>,
+    target: CommentTarget,
+    auth: AuthUser,
+    payload: CreateCommentPayload,
+) -> Result<(StatusCode, Json<CommentResponse>), DomainError> {
+    let comment = comment_usecases(pool)
+        .create_comment(target, &auth.actor(), payload.body, payload.parent_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(CommentResponse::from_entity(comment)),
+    ))
+}
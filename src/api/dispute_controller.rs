@@ -0,0 +1,263 @@
+use super::auth::AuthUser;
+use super::extract::{Json, Path};
+use super::issue_controller::JournalEntryResponse;
+use crate::application::usecases::dispute_usecases::{
+    DisputeRecord, DisputeResolution, DisputeUsecases,
+};
+use crate::application::usecases::ledger_usecases::LedgerUsecases;
+use crate::domain::entities::dispute::{
+    Dispute, DisputeEvent, DisputeEventKind, DisputeRuling, DisputeStatus,
+};
+use crate::domain::errors::domain_error::DomainError;
+use crate::infra::config::platform_fee_bps;
+use crate::infra::db::dispute_repository_sql::DisputeRepositorySql;
+use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::ledger_repository_sql::LedgerRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use crate::infra::db::submission_repository_sql::SubmissionRepositorySql;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct OpenDisputePayload {
+    submission_id: Uuid,
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct EvidencePayload {
+    body: String,
+    url: Option<String>,
+}
+
+/// `contributor_amount` is a decimal string in the bounty currency, only used by `split`.
+#[derive(Deserialize)]
+struct RulingPayload {
+    ruling: DisputeRuling,
+    contributor_amount: Option<String>,
+    note: String,
+}
+
+#[derive(Serialize)]
+struct DisputeResponse {
+    id: Uuid,
+    issue_id: Uuid,
+    submission_id: Uuid,
+    contributor_id: Uuid,
+    opened_by: Option<Uuid>,
+    reason: String,
+    status: DisputeStatus,
+    ruling: Option<DisputeRuling>,
+    resolved_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    resolved_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    events: Option<Vec<DisputeEventResponse>>,
+}
+
+impl DisputeResponse {
+    fn from_entity(dispute: Dispute) -> Self {
+        Self {
+            id: dispute.id,
+            issue_id: dispute.issue_id,
+            submission_id: dispute.submission_id,
+            contributor_id: dispute.contributor_id,
+            opened_by: dispute.opened_by,
+            reason: dispute.reason,
+            status: dispute.status,
+            ruling: dispute.ruling,
+            resolved_by: dispute.resolved_by,
+            created_at: dispute.created_at,
+            resolved_at: dispute.resolved_at,
+            events: None,
+        }
+    }
+
+    fn from_record(record: DisputeRecord) -> Self {
+        Self {
+            events: Some(
+                record
+                    .events
+                    .into_iter()
+                    .map(DisputeEventResponse::from_entity)
+                    .collect(),
+            ),
+            ..Self::from_entity(record.dispute)
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DisputeEventResponse {
+    id: Uuid,
+    actor_id: Option<Uuid>,
+    kind: DisputeEventKind,
+    body: String,
+    url: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl DisputeEventResponse {
+    fn from_entity(event: DisputeEvent) -> Self {
+        Self {
+            id: event.id,
+            actor_id: event.actor_id,
+            kind: event.kind,
+            body: event.body,
+            url: event.url,
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ResolutionResponse {
+    dispute: DisputeResponse,
+    entries: Vec<JournalEntryResponse>,
+}
+
+impl ResolutionResponse {
+    fn from_entity(resolution: DisputeResolution) -> Self {
+        Self {
+            dispute: DisputeResponse::from_entity(resolution.dispute),
+            entries: resolution
+                .entries
+                .into_iter()
+                .map(JournalEntryResponse::from_entity)
+                .collect(),
+        }
+    }
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/:id", get(get_dispute))
+        .route("/:id/evidence", post(add_evidence))
+        .route("/:id/ruling", post(rule_dispute))
+        .with_state(pool)
+}
+
+/// Mounted under `/issues` next to the issue routes.
+pub fn issue_routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/:id/disputes", post(open_dispute).get(list_issue_disputes))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn open_dispute(
+    State(pool): State<Pool<Postgres>>,
+    Path(issue_id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<OpenDisputePayload>,
+) -> Result<(StatusCode, Json<DisputeResponse>), DomainError> {
+    let record = dispute_usecases(pool)
+        .open_dispute(
+            issue_id,
+            &auth.actor(),
+            payload.submission_id,
+            payload.reason,
+        )
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(DisputeResponse::from_record(record)),
+    ))
+}
+
+async fn list_issue_disputes(
+    State(pool): State<Pool<Postgres>>,
+    Path(issue_id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Vec<DisputeResponse>>, DomainError> {
+    let disputes = dispute_usecases(pool)
+        .get_issue_disputes(issue_id, &auth.actor())
+        .await?;
+    Ok(Json(
+        disputes
+            .into_iter()
+            .map(DisputeResponse::from_entity)
+            .collect(),
+    ))
+}
+
+async fn get_dispute(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<DisputeResponse>, DomainError> {
+    let record = dispute_usecases(pool)
+        .get_dispute(id, &auth.actor())
+        .await?;
+    Ok(Json(DisputeResponse::from_record(record)))
+}
+
+async fn add_evidence(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<EvidencePayload>,
+) -> Result<(StatusCode, Json<DisputeEventResponse>), DomainError> {
+    let event = dispute_usecases(pool)
+        .add_evidence(id, &auth.actor(), payload.body, payload.url)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(DisputeEventResponse::from_entity(event)),
+    ))
+}
+
+async fn rule_dispute(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<RulingPayload>,
+) -> Result<Json<ResolutionResponse>, DomainError> {
+    let resolution = dispute_usecases(pool)
+        .rule(
+            id,
+            &auth.actor(),
+            payload.ruling,
+            payload.contributor_amount,
+            payload.note,
+        )
+        .await?;
+    Ok(Json(ResolutionResponse::from_entity(resolution)))
+}
+
+// ------------------------
+// Aux Functions
+
+fn dispute_usecases(
+    pool: Pool<Postgres>,
+) -> DisputeUsecases<
+    DisputeRepositorySql,
+    SubmissionRepositorySql,
+    IssueRepositorySql,
+    ProjectRepositorySql,
+    LedgerRepositorySql,
+> {
+    let ledger = LedgerUsecases::new(
+        LedgerRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool.clone()),
+        platform_fee_bps(),
+    );
+    DisputeUsecases::new(
+        DisputeRepositorySql::new(pool.clone()),
+        SubmissionRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool),
+        ledger,
+    )
+}
@@ -0,0 +1,88 @@
+use super::request_id::current_request_id;
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use axum::{
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// RFC 7807 `application/problem+json` body.
+#[derive(Serialize)]
+struct ProblemDetails {
+    #[serde(rename = "type")]
+    problem_type: String,
+    title: &'static str,
+    status: u16,
+    detail: String,
+    code: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<FieldError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+impl DomainError {
+    /// Stable, machine-readable identifier clients can match on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::InvalidData(_) => "invalid_data",
+            DomainError::Validation(_) => "validation_failed",
+            DomainError::NotFound(_) => "not_found",
+            DomainError::Conflict(_) => "conflict",
+            DomainError::Unauthorized(_) => "unauthorized",
+            DomainError::Forbidden(_) => "forbidden",
+            DomainError::Infra(_) => "internal_error",
+        }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            DomainError::InvalidData(_) => StatusCode::BAD_REQUEST,
+            DomainError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            DomainError::Conflict(_) => StatusCode::CONFLICT,
+            DomainError::NotFound(_) => StatusCode::NOT_FOUND,
+            DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
+            DomainError::Infra(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for DomainError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let code = self.code();
+        let request_id = current_request_id();
+
+        let (detail, errors) = match self {
+            DomainError::Infra(message) => {
+                tracing::error!(request_id = ?request_id, "infra error: {}", message);
+                ("An internal error occurred".to_string(), Vec::new())
+            }
+            DomainError::Validation(errors) => ("Request validation failed".to_string(), errors),
+            DomainError::InvalidData(message)
+            | DomainError::NotFound(message)
+            | DomainError::Conflict(message)
+            | DomainError::Unauthorized(message)
+            | DomainError::Forbidden(message) => (message, Vec::new()),
+        };
+
+        let body = ProblemDetails {
+            problem_type: format!("/problems/{}", code.replace('_', "-")),
+            title: status.canonical_reason().unwrap_or("Error"),
+            status: status.as_u16(),
+            detail,
+            code,
+            errors,
+            request_id,
+        };
+
+        let mut response = (status, Json(body)).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/problem+json"),
+        );
+        response
+    }
+}
@@ -0,0 +1,216 @@
+//! Drop-in replacements for axum's `Json`, `Path` and `Query` extractors.
+//! Their rejections are `DomainError`s, so a malformed body, path or query
+//! string gets the same problem+json response as every other error.
+
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use async_trait::async_trait;
+use axum::{
+    body::{Bytes, HttpBody},
+    extract::{FromRequest, FromRequestParts},
+    http::{header, request::Parts, HeaderMap, Request, StatusCode},
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::error::Category;
+
+/// JSON request body. Fields of the wrong type or shape become a `Validation`
+/// error naming the field; anything that is not JSON is `InvalidData`.
+pub struct Json<T>(pub T);
+
+#[async_trait]
+impl<T, S, B> FromRequest<S, B> for Json<T>
+where
+    T: DeserializeOwned,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        if !json_content_type(req.headers()) {
+            return Err(DomainError::InvalidData(
+                "Expected a request with `Content-Type: application/json`".to_string(),
+            ));
+        }
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|rejection| DomainError::InvalidData(rejection.body_text()))?;
+
+        let deserializer = &mut serde_json::Deserializer::from_slice(&bytes);
+        serde_path_to_error::deserialize(deserializer)
+            .map(Json)
+            .map_err(|err| {
+                let path = err.path().to_string();
+                let err = err.into_inner();
+                match err.classify() {
+                    Category::Data => {
+                        // Errors about the document itself, like a missing field, have no path
+                        let field = if path == "." { "body" } else { path.as_str() };
+                        DomainError::Validation(vec![FieldError::new(field, &err.to_string())])
+                    }
+                    Category::Syntax | Category::Eof | Category::Io => {
+                        DomainError::InvalidData(format!("Malformed JSON body: {}", err))
+                    }
+                }
+            })
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// Path parameters; one that does not parse, like a bad UUID, is `InvalidData`.
+pub struct Path<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Path(value)),
+            Err(rejection) => Err(rejection_error(rejection.status(), rejection.body_text())),
+        }
+    }
+}
+
+/// Query string parameters; an unknown value or a wrong type is `InvalidData`.
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Query(value)) => Ok(Query(value)),
+            Err(rejection) => Err(rejection_error(rejection.status(), rejection.body_text())),
+        }
+    }
+}
+
+/// Server-side rejections, like a route without the parameters its handler
+/// extracts, are bugs rather than bad requests.
+fn rejection_error(status: StatusCode, message: String) -> DomainError {
+    if status.is_server_error() {
+        DomainError::Infra(message)
+    } else {
+        DomainError::InvalidData(message)
+    }
+}
+
+fn json_content_type(headers: &HeaderMap) -> bool {
+    let essence = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|essence| essence.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    essence == "application/json"
+        || (essence.starts_with("application/") && essence.ends_with("+json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use serde::Deserialize;
+
+    #[allow(dead_code)]
+    #[derive(Deserialize)]
+    struct Payload {
+        title: String,
+        bounty: Bounty,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Deserialize)]
+    struct Bounty {
+        amount: String,
+    }
+
+    async fn extract(content_type: Option<&str>, body: &str) -> Result<Payload, DomainError> {
+        let mut request = Request::builder().method("POST").uri("/");
+        if let Some(content_type) = content_type {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+        let request = request.body(Body::from(body.to_string())).unwrap();
+        Json::<Payload>::from_request(request, &())
+            .await
+            .map(|Json(payload)| payload)
+    }
+
+    fn field_errors(result: Result<Payload, DomainError>) -> Vec<(String, String)> {
+        match result {
+            Err(DomainError::Validation(errors)) => {
+                errors.into_iter().map(|e| (e.field, e.message)).collect()
+            }
+            Err(other) => panic!("expected a validation error, got {:?}", other),
+            Ok(_) => panic!("expected a validation error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn json_rejections_are_domain_errors() {
+        let valid = r#"{"title": "t", "bounty": {"amount": "1.00"}}"#;
+        assert!(extract(Some("application/json"), valid).await.is_ok());
+        assert!(extract(Some("application/json; charset=utf-8"), valid)
+            .await
+            .is_ok());
+        assert!(extract(Some("application/vnd.api+json"), valid)
+            .await
+            .is_ok());
+
+        for content_type in [None, Some("text/plain"), Some("application/jsonp")] {
+            assert!(
+                matches!(
+                    extract(content_type, valid).await,
+                    Err(DomainError::InvalidData(_))
+                ),
+                "{:?}",
+                content_type
+            );
+        }
+        for body in ["", "{", r#"{"title": "t",}"#, "not json"] {
+            assert!(
+                matches!(
+                    extract(Some("application/json"), body).await,
+                    Err(DomainError::InvalidData(_))
+                ),
+                "{:?}",
+                body
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn json_data_errors_name_the_field() {
+        let errors = field_errors(
+            extract(
+                Some("application/json"),
+                r#"{"title": "t", "bounty": {"amount": 5}}"#,
+            )
+            .await,
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "bounty.amount");
+
+        let errors = field_errors(extract(Some("application/json"), r#"{"title": "t"}"#).await);
+        assert_eq!(errors[0].0, "body");
+        assert!(errors[0].1.contains("missing field `bounty`"));
+    }
+}
@@ -1,51 +1,219 @@
-use crate::application::usecases::issue_usecases::IssueUsecases;
-use crate::domain::errors::domain_error::DomainError;
+use super::auth::{AuthUser, OptionalAuthUser};
+use super::extract::{Json, Path, Query};
+use super::pagination::{decode_cursor, PageResponse};
+use crate::application::usecases::issue_usecases::{BountyInput, IssueUsecases};
+use crate::application::usecases::ledger_usecases::{IssueLedger, LedgerUsecases};
+use crate::application::usecases::organization_usecases::OrganizationUsecases;
+use crate::domain::entities::issue::{ClaimMode, IssueStatus};
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::{AccountKind, EntryKind, JournalEntry};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::gateways::escrow_gateway::{
+    ChainTransaction, EscrowStatus, SharedEscrowGateway,
+};
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::issue_repository::{IssueQuery, IssueSort};
+use crate::domain::value_objects::money::{Currency, Money};
+use crate::infra::config::platform_fee_bps;
 use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::ledger_repository_sql::LedgerRepositorySql;
+use crate::infra::db::organization_repository_sql::OrganizationRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
 use axum::{
-    extract::{Path, State},
+    extract::{Extension, State},
     http::StatusCode,
-    routing::{delete, get, post, put},
-    Json, Router,
+    routing::{get, post, put},
+    Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Postgres};
 use uuid::Uuid;
 
+/// Amounts are decimal strings in major units (`"12.50"`), never JSON numbers.
+#[derive(Deserialize)]
+pub(super) struct BountyPayload {
+    amount: String,
+    currency: String,
+}
+
+impl BountyPayload {
+    pub(super) fn into_input(self) -> BountyInput {
+        BountyInput {
+            amount: self.amount,
+            currency: self.currency,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct CreateIssuePayload {
     project_id: Uuid,
     title: String,
     description: Option<String>,
-    bounty_value: f64,
+    bounty: BountyPayload,
+    #[serde(default)]
+    claim_mode: ClaimMode,
 }
 
 #[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
 struct UpdateIssuePayload {
     title: Option<String>,
     description: Option<String>,
-    bounty_value: Option<f64>,
-    status: Option<String>,
+    bounty: Option<BountyPayload>,
+    claim_mode: Option<ClaimMode>,
+    // Accepted only so it can be rejected with a pointer to the transitions endpoint.
+    status: Option<serde::de::IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+struct TransitionPayload {
+    to: IssueStatus,
+    note: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ListIssuesParams {
+    status: Option<IssueStatus>,
+    project_id: Option<Uuid>,
+    currency: Option<String>,
+    min_bounty: Option<String>,
+    assignee_id: Option<Uuid>,
+    tag: Option<String>,
+    owner_id: Option<Uuid>,
+    sort: Option<IssueSort>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct FundPayload {
+    amount: String,
+    currency: String,
+    /// Slug of an organization to fund from instead of the caller's account.
+    organization: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReleasePayload {
+    solver_id: Uuid,
+}
+
+#[derive(Deserialize)]
+struct VisibilityPayload {
+    hidden: bool,
 }
 
 #[derive(Serialize)]
-struct IssueResponse {
+pub(super) struct IssueResponse {
     id: Uuid,
     project_id: Uuid,
     title: String,
     description: Option<String>,
-    bounty_value: f64,
-    status: String,
+    bounty: Money,
+    status: IssueStatus,
+    assignee_id: Option<Uuid>,
+    claim_mode: ClaimMode,
+    hidden: bool,
 }
 
 impl IssueResponse {
-    fn from_entity(issue: crate::domain::entities::issue::Issue) -> Self {
+    pub(super) fn from_entity(issue: crate::domain::entities::issue::Issue) -> Self {
         Self {
             id: issue.id,
             project_id: issue.project_id,
             title: issue.title,
             description: issue.description,
-            bounty_value: issue.bounty_value,
-            status: issue.status.to_string(),
+            bounty: issue.bounty,
+            status: issue.status,
+            assignee_id: issue.assignee_id,
+            claim_mode: issue.claim_mode,
+            hidden: issue.hidden,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TransitionResponse {
+    id: Uuid,
+    issue_id: Uuid,
+    from_status: IssueStatus,
+    to_status: IssueStatus,
+    actor_id: Option<Uuid>,
+    note: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl TransitionResponse {
+    fn from_entity(transition: IssueTransition) -> Self {
+        Self {
+            id: transition.id,
+            issue_id: transition.issue_id,
+            from_status: transition.from_status,
+            to_status: transition.to_status,
+            actor_id: transition.actor_id,
+            note: transition.note,
+            created_at: transition.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct PostingResponse {
+    account: AccountKind,
+    owner_id: Option<Uuid>,
+    amount: Money,
+}
+
+#[derive(Serialize)]
+pub(super) struct JournalEntryResponse {
+    id: Uuid,
+    kind: EntryKind,
+    actor_id: Option<Uuid>,
+    memo: Option<String>,
+    postings: Vec<PostingResponse>,
+    created_at: DateTime<Utc>,
+}
+
+impl JournalEntryResponse {
+    pub(super) fn from_entity(entry: JournalEntry) -> Self {
+        Self {
+            id: entry.id,
+            kind: entry.kind,
+            actor_id: entry.actor_id,
+            memo: entry.memo,
+            postings: entry
+                .postings
+                .into_iter()
+                .map(|p| PostingResponse {
+                    account: p.account.kind,
+                    owner_id: p.account.owner_id,
+                    amount: p.amount,
+                })
+                .collect(),
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct IssueLedgerResponse {
+    issue_id: Uuid,
+    escrow: Money,
+    entries: Vec<JournalEntryResponse>,
+}
+
+impl IssueLedgerResponse {
+    fn from_entity(issue_id: Uuid, ledger: IssueLedger) -> Self {
+        Self {
+            issue_id,
+            escrow: ledger.escrow,
+            entries: ledger
+                .entries
+                .into_iter()
+                .map(JournalEntryResponse::from_entity)
+                .collect(),
         }
     }
 }
@@ -57,83 +225,328 @@ pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
             "/:id",
             get(get_issue).put(update_issue).delete(delete_issue),
         )
+        .route("/:id/visibility", put(set_issue_visibility))
+        .route(
+            "/:id/transitions",
+            post(transition_issue).get(list_issue_transitions),
+        )
+        .route("/:id/ledger", get(get_issue_ledger))
+        .route("/:id/fund", post(fund_issue))
+        .route("/:id/release", post(release_escrow))
+        .route("/:id/refund", post(refund_escrow))
+        .route("/:id/escrow", get(get_escrow_status))
+        .route("/:id/escrow/lock", post(lock_escrow))
+        .route("/:id/escrow/release", post(release_chain_escrow))
+        .route("/:id/escrow/refund", post(refund_chain_escrow))
         .with_state(pool)
 }
 
 async fn create_issue(
     State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
     Json(payload): Json<CreateIssuePayload>,
-) -> Result<Json<IssueResponse>, StatusCode> {
-    let repo = IssueRepositorySql::new(pool);
-    let usecases = IssueUsecases::new(repo);
+) -> Result<Json<IssueResponse>, DomainError> {
+    let usecases = issue_usecases(pool);
     let issue_entity = usecases
         .create_issue(
+            &auth.actor(),
             payload.project_id,
             payload.title,
             payload.description,
-            payload.bounty_value,
+            payload.bounty.into_input(),
+            payload.claim_mode,
         )
-        .await
-        .map_err(map_domain_error)?;
+        .await?;
     Ok(Json(IssueResponse::from_entity(issue_entity)))
 }
 
 async fn get_issue(
     State(pool): State<Pool<Postgres>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<IssueResponse>, StatusCode> {
-    let repo = IssueRepositorySql::new(pool);
-    let usecases = IssueUsecases::new(repo);
-    let issue_entity = usecases.get_issue(id).await.map_err(map_domain_error)?;
+    auth: OptionalAuthUser,
+) -> Result<Json<IssueResponse>, DomainError> {
+    let usecases = issue_usecases(pool);
+    let issue_entity = usecases.view_issue(id, auth.actor().as_ref()).await?;
     Ok(Json(IssueResponse::from_entity(issue_entity)))
 }
 
 async fn update_issue(
     State(pool): State<Pool<Postgres>>,
+    Extension(escrow): Extension<SharedEscrowGateway>,
     Path(id): Path<Uuid>,
+    auth: AuthUser,
     Json(payload): Json<UpdateIssuePayload>,
-) -> Result<Json<IssueResponse>, StatusCode> {
-    let repo = IssueRepositorySql::new(pool);
-    let usecases = IssueUsecases::new(repo);
+) -> Result<Json<IssueResponse>, DomainError> {
+    if payload.status.is_some() {
+        return Err(DomainError::Validation(vec![FieldError::new(
+            "status",
+            &format!("cannot be updated here; use POST /issues/{}/transitions", id),
+        )]));
+    }
+    let usecases = issue_usecases(pool).with_escrow_gateway(escrow);
     let issue_entity = usecases
         .update_issue(
             id,
+            &auth.actor(),
             payload.title,
             payload.description,
-            payload.bounty_value,
-            None,
+            payload.bounty.map(BountyPayload::into_input),
+            payload.claim_mode,
         )
-        .await
-        .map_err(map_domain_error)?;
+        .await?;
+    Ok(Json(IssueResponse::from_entity(issue_entity)))
+}
+
+async fn transition_issue(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<TransitionPayload>,
+) -> Result<Json<IssueResponse>, DomainError> {
+    let usecases = issue_usecases(pool);
+    let issue_entity = usecases
+        .transition_issue(id, &auth.actor(), payload.to, payload.note)
+        .await?;
+    Ok(Json(IssueResponse::from_entity(issue_entity)))
+}
+
+async fn list_issue_transitions(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: OptionalAuthUser,
+) -> Result<Json<Vec<TransitionResponse>>, DomainError> {
+    let usecases = issue_usecases(pool);
+    let transitions = usecases
+        .get_issue_transitions(id, auth.actor().as_ref())
+        .await?;
+    let response = transitions
+        .into_iter()
+        .map(TransitionResponse::from_entity)
+        .collect();
+    Ok(Json(response))
+}
+
+async fn set_issue_visibility(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<VisibilityPayload>,
+) -> Result<Json<IssueResponse>, DomainError> {
+    let usecases = issue_usecases(pool);
+    let issue_entity = usecases
+        .set_issue_hidden(id, &auth.actor(), payload.hidden)
+        .await?;
     Ok(Json(IssueResponse::from_entity(issue_entity)))
 }
 
 async fn delete_issue(
     State(pool): State<Pool<Postgres>>,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, StatusCode> {
-    let repo = IssueRepositorySql::new(pool);
-    let usecases = IssueUsecases::new(repo);
-    usecases.delete_issue(id).await.map_err(map_domain_error)?;
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    let usecases = issue_usecases(pool);
+    usecases.delete_issue(id, &auth.actor()).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
 async fn list_issues(
     State(pool): State<Pool<Postgres>>,
-) -> Result<Json<Vec<IssueResponse>>, StatusCode> {
-    let repo = IssueRepositorySql::new(pool);
-    let usecases = IssueUsecases::new(repo);
-    let issues = usecases.list_issues().await.map_err(map_domain_error)?;
-    let response = issues.into_iter().map(IssueResponse::from_entity).collect();
-    Ok(Json(response))
+    auth: OptionalAuthUser,
+    Query(params): Query<ListIssuesParams>,
+) -> Result<Json<PageResponse<IssueResponse>>, DomainError> {
+    let (currency, min_bounty) =
+        parse_bounty_filter(params.currency.as_deref(), params.min_bounty.as_deref())?;
+    let query = IssueQuery {
+        status: params.status,
+        project_id: params.project_id,
+        currency,
+        min_bounty,
+        assignee_id: params.assignee_id,
+        tag: params.tag,
+        owner_id: params.owner_id,
+        include_hidden: false,
+        sort: params.sort.unwrap_or_default(),
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+    };
+
+    let usecases = issue_usecases(pool);
+    let page = usecases.list_issues(auth.actor().as_ref(), query).await?;
+    Ok(Json(PageResponse::from_page(
+        page,
+        IssueResponse::from_entity,
+    )))
 }
 
-fn map_domain_error(err: DomainError) -> StatusCode {
-    match err {
-        DomainError::InvalidData(_) => StatusCode::BAD_REQUEST,
-        DomainError::Conflict(_) => StatusCode::CONFLICT,
-        DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-        DomainError::Infra(_) => StatusCode::INTERNAL_SERVER_ERROR,
-    }
+async fn get_issue_ledger(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: OptionalAuthUser,
+) -> Result<Json<IssueLedgerResponse>, DomainError> {
+    let usecases = ledger_usecases(pool);
+    let ledger = usecases
+        .get_issue_ledger(id, auth.actor().as_ref())
+        .await?;
+    Ok(Json(IssueLedgerResponse::from_entity(id, ledger)))
+}
+
+async fn fund_issue(
+    State(pool): State<Pool<Postgres>>,
+    Extension(escrow): Extension<SharedEscrowGateway>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<FundPayload>,
+) -> Result<(StatusCode, Json<JournalEntryResponse>), DomainError> {
+    let ledger = ledger_usecases(pool.clone()).with_escrow_gateway(escrow);
+    let entry = match payload.organization {
+        Some(slug) => {
+            OrganizationUsecases::new(OrganizationRepositorySql::new(pool), ledger)
+                .fund_issue(&slug, id, &auth.actor(), &payload.amount, &payload.currency)
+                .await?
+        }
+        None => {
+            ledger
+                .fund_issue(id, &auth.actor(), &payload.amount, &payload.currency)
+                .await?
+        }
+    };
+    Ok((
+        StatusCode::CREATED,
+        Json(JournalEntryResponse::from_entity(entry)),
+    ))
+}
+
+async fn release_escrow(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ReleasePayload>,
+) -> Result<(StatusCode, Json<JournalEntryResponse>), DomainError> {
+    let usecases = ledger_usecases(pool);
+    let entry = usecases
+        .release_escrow(id, Some(&auth.actor()), payload.solver_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(JournalEntryResponse::from_entity(entry)),
+    ))
+}
+
+async fn refund_escrow(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<(StatusCode, Json<JournalEntryResponse>), DomainError> {
+    let usecases = ledger_usecases(pool);
+    let entry = usecases.refund_escrow(id, Some(&auth.actor())).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(JournalEntryResponse::from_entity(entry)),
+    ))
+}
+
+async fn get_escrow_status(
+    State(pool): State<Pool<Postgres>>,
+    Extension(escrow): Extension<SharedEscrowGateway>,
+    Path(id): Path<Uuid>,
+    auth: OptionalAuthUser,
+) -> Result<Json<EscrowStatus>, DomainError> {
+    let usecases = escrow_usecases(pool, escrow);
+    let status = usecases
+        .get_escrow_status(id, auth.actor().as_ref())
+        .await?;
+    Ok(Json(status))
+}
+
+async fn lock_escrow(
+    State(pool): State<Pool<Postgres>>,
+    Extension(escrow): Extension<SharedEscrowGateway>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<(StatusCode, Json<ChainTransaction>), DomainError> {
+    let usecases = escrow_usecases(pool, escrow);
+    let transaction = usecases.lock_bounty_on_chain(id, &auth.actor()).await?;
+    Ok((StatusCode::ACCEPTED, Json(transaction)))
+}
+
+async fn release_chain_escrow(
+    State(pool): State<Pool<Postgres>>,
+    Extension(escrow): Extension<SharedEscrowGateway>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<(StatusCode, Json<ChainTransaction>), DomainError> {
+    let usecases = escrow_usecases(pool, escrow);
+    let transaction = usecases.release_bounty_on_chain(id, &auth.actor()).await?;
+    Ok((StatusCode::ACCEPTED, Json(transaction)))
+}
+
+async fn refund_chain_escrow(
+    State(pool): State<Pool<Postgres>>,
+    Extension(escrow): Extension<SharedEscrowGateway>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<(StatusCode, Json<ChainTransaction>), DomainError> {
+    let usecases = escrow_usecases(pool, escrow);
+    let transaction = usecases.refund_bounty_on_chain(id, &auth.actor()).await?;
+    Ok((StatusCode::ACCEPTED, Json(transaction)))
+}
+
+// ------------------------
+// Aux Functions
+
+pub(super) fn issue_usecases(
+    pool: Pool<Postgres>,
+) -> IssueUsecases<IssueRepositorySql, ProjectRepositorySql, LedgerRepositorySql> {
+    IssueUsecases::new(
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool.clone()),
+        ledger_usecases(pool),
+    )
+}
+
+fn escrow_usecases(
+    pool: Pool<Postgres>,
+    escrow: SharedEscrowGateway,
+) -> IssueUsecases<IssueRepositorySql, ProjectRepositorySql, LedgerRepositorySql> {
+    issue_usecases(pool).with_escrow_gateway(escrow)
+}
+
+pub(super) fn ledger_usecases(
+    pool: Pool<Postgres>,
+) -> LedgerUsecases<LedgerRepositorySql, IssueRepositorySql, ProjectRepositorySql> {
+    LedgerUsecases::new(
+        LedgerRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool),
+        platform_fee_bps(),
+    )
+}
+
+/// `min_bounty` is a decimal string and only makes sense together with `currency`.
+fn parse_bounty_filter(
+    currency: Option<&str>,
+    min_bounty: Option<&str>,
+) -> Result<(Option<Currency>, Option<Money>), DomainError> {
+    let currency = currency
+        .map(|code| {
+            Currency::from_code(code).ok_or_else(|| {
+                DomainError::Validation(vec![FieldError::new("currency", "is not supported")])
+            })
+        })
+        .transpose()?;
+    let min_bounty = match (min_bounty, currency) {
+        (None, _) => None,
+        (Some(_), None) => {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "currency",
+                "is required when filtering by min_bounty",
+            )]))
+        }
+        (Some(amount), Some(currency)) => {
+            Some(Money::parse(amount, currency).map_err(|message| {
+                DomainError::Validation(vec![FieldError::new("min_bounty", &message)])
+            })?)
+        }
+    };
+    Ok((currency, min_bounty))
 }
@@ -0,0 +1,115 @@
+use super::auth::AuthUser;
+use super::extract::{Json, Path, Query};
+use super::pagination::{decode_cursor, PageResponse};
+use crate::application::usecases::job_usecases::JobUsecases;
+use crate::domain::entities::job::{Job, JobStatus};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::job_repository::JobQuery;
+use crate::infra::db::job_repository_sql::JobRepositorySql;
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct ListJobsParams {
+    status: Option<JobStatus>,
+    kind: Option<String>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JobResponse {
+    id: Uuid,
+    kind: String,
+    payload: serde_json::Value,
+    status: JobStatus,
+    attempts: i32,
+    max_attempts: i32,
+    /// When a pending job is due, or a running job's lease ends.
+    run_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    created_at: DateTime<Utc>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
+impl JobResponse {
+    fn from_entity(job: Job) -> Self {
+        let payload =
+            serde_json::from_str(&job.payload).unwrap_or(serde_json::Value::String(job.payload));
+        Self {
+            run_at: matches!(job.status, JobStatus::Pending | JobStatus::Running)
+                .then_some(job.run_at),
+            id: job.id,
+            kind: job.kind,
+            payload,
+            status: job.status,
+            attempts: job.attempts,
+            max_attempts: job.max_attempts,
+            last_error: job.last_error,
+            created_at: job.created_at,
+            finished_at: job.finished_at,
+        }
+    }
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/", get(list_jobs))
+        .route("/:id", get(get_job))
+        .route("/:id/retry", post(retry_job))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn list_jobs(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+    Query(params): Query<ListJobsParams>,
+) -> Result<Json<PageResponse<JobResponse>>, DomainError> {
+    let query = JobQuery {
+        status: params.status,
+        kind: params.kind,
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+    };
+    let page = job_usecases(pool).list_jobs(&auth.actor(), query).await?;
+    Ok(Json(PageResponse::from_page(
+        page,
+        JobResponse::from_entity,
+    )))
+}
+
+async fn get_job(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<JobResponse>, DomainError> {
+    let job = job_usecases(pool).get_job(id, &auth.actor()).await?;
+    Ok(Json(JobResponse::from_entity(job)))
+}
+
+async fn retry_job(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<JobResponse>, DomainError> {
+    let job = job_usecases(pool).retry_job(id, &auth.actor()).await?;
+    Ok(Json(JobResponse::from_entity(job)))
+}
+
+// ------------------------
+// Aux Functions
+
+fn job_usecases(pool: Pool<Postgres>) -> JobUsecases<JobRepositorySql> {
+    JobUsecases::new(JobRepositorySql::new(pool))
+}
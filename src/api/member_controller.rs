@@ -0,0 +1,139 @@
+use super::auth::{AuthUser, OptionalAuthUser};
+use super::extract::{Json, Path};
+use crate::application::usecases::project_usecases::ProjectUsecases;
+use crate::domain::entities::project_member::{ProjectMember, ProjectRole};
+use crate::domain::errors::domain_error::DomainError;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{post, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct InviteMemberPayload {
+    user_id: Uuid,
+    role: ProjectRole,
+}
+
+#[derive(Deserialize)]
+struct UpdateMemberPayload {
+    role: ProjectRole,
+}
+
+#[derive(Serialize)]
+struct MemberResponse {
+    project_id: Uuid,
+    user_id: Uuid,
+    role: ProjectRole,
+    invited_by: Option<Uuid>,
+    pending: bool,
+    created_at: DateTime<Utc>,
+    accepted_at: Option<DateTime<Utc>>,
+}
+
+impl MemberResponse {
+    fn from_entity(member: ProjectMember) -> Self {
+        Self {
+            pending: !member.is_active(),
+            project_id: member.project_id,
+            user_id: member.user_id,
+            role: member.role,
+            invited_by: member.invited_by,
+            created_at: member.created_at,
+            accepted_at: member.accepted_at,
+        }
+    }
+}
+
+/// Mounted under `/projects` next to the project routes.
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/:id/members", post(invite_member).get(list_members))
+        .route(
+            "/:id/members/:user_id",
+            put(update_member).delete(remove_member),
+        )
+        .route("/:id/members/:user_id/accept", post(accept_invitation))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn invite_member(
+    State(pool): State<Pool<Postgres>>,
+    Path(project_id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<InviteMemberPayload>,
+) -> Result<(StatusCode, Json<MemberResponse>), DomainError> {
+    let member = project_usecases(pool)
+        .invite_member(project_id, &auth.actor(), payload.user_id, payload.role)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(MemberResponse::from_entity(member)),
+    ))
+}
+
+async fn list_members(
+    State(pool): State<Pool<Postgres>>,
+    Path(project_id): Path<Uuid>,
+    auth: OptionalAuthUser,
+) -> Result<Json<Vec<MemberResponse>>, DomainError> {
+    let members = project_usecases(pool)
+        .get_members(project_id, auth.actor().as_ref())
+        .await?;
+    Ok(Json(
+        members
+            .into_iter()
+            .map(MemberResponse::from_entity)
+            .collect(),
+    ))
+}
+
+async fn update_member(
+    State(pool): State<Pool<Postgres>>,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateMemberPayload>,
+) -> Result<Json<MemberResponse>, DomainError> {
+    let member = project_usecases(pool)
+        .change_member_role(project_id, &auth.actor(), user_id, payload.role)
+        .await?;
+    Ok(Json(MemberResponse::from_entity(member)))
+}
+
+async fn accept_invitation(
+    State(pool): State<Pool<Postgres>>,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+) -> Result<Json<MemberResponse>, DomainError> {
+    let member = project_usecases(pool)
+        .accept_invitation(project_id, &auth.actor(), user_id)
+        .await?;
+    Ok(Json(MemberResponse::from_entity(member)))
+}
+
+async fn remove_member(
+    State(pool): State<Pool<Postgres>>,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    project_usecases(pool)
+        .remove_member(project_id, &auth.actor(), user_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ------------------------
+// Aux Functions
+
+fn project_usecases(pool: Pool<Postgres>) -> ProjectUsecases<ProjectRepositorySql> {
+    ProjectUsecases::new(ProjectRepositorySql::new(pool))
+}
@@ -1,15 +1,72 @@
-use axum::Router;
+use crate::domain::gateways::escrow_gateway::SharedEscrowGateway;
+use crate::domain::gateways::repository_host::SharedRepositoryHost;
+use axum::middleware;
+use axum::{Extension, Router};
 use sqlx::Pool;
 use sqlx::Postgres;
 
+pub mod auth;
+mod auth_controller;
+mod claim_controller;
+mod comment_controller;
+mod dispute_controller;
+pub mod error;
+mod extract;
 mod issue_controller;
+mod job_controller;
+mod member_controller;
+mod notification_controller;
+mod organization_controller;
+pub mod pagination;
 mod project_controller;
+pub mod request_id;
+mod search_controller;
+mod submission_controller;
+mod upstream_issue_controller;
 mod user_controller;
+mod webhook_controller;
+mod webhook_endpoint_controller;
 
-pub fn create_routes(pool: Pool<Postgres>) -> Router {
+pub fn create_routes(
+    pool: Pool<Postgres>,
+    escrow: SharedEscrowGateway,
+    repository_host: SharedRepositoryHost,
+) -> Router {
     Router::new()
+        .nest("/auth", auth_controller::routes(pool.clone()))
         .nest("/users", user_controller::routes(pool.clone()))
-        .nest("/projects", project_controller::routes(pool.clone()))
-        .nest("/issues", issue_controller::routes(pool.clone()))
+        .nest(
+            "/projects",
+            project_controller::routes(pool.clone())
+                .merge(member_controller::routes(pool.clone()))
+                .merge(upstream_issue_controller::routes(pool.clone()))
+                .merge(comment_controller::project_routes(pool.clone())),
+        )
+        .nest(
+            "/issues",
+            issue_controller::routes(pool.clone())
+                .merge(claim_controller::routes(pool.clone()))
+                .merge(submission_controller::routes(pool.clone()))
+                .merge(dispute_controller::issue_routes(pool.clone()))
+                .merge(comment_controller::issue_routes(pool.clone()))
+                .merge(upstream_issue_controller::issue_routes(pool.clone())),
+        )
+        .nest("/orgs", organization_controller::routes(pool.clone()))
+        .nest("/comments", comment_controller::routes(pool.clone()))
+        .nest("/disputes", dispute_controller::routes(pool.clone()))
+        .nest("/search", search_controller::routes(pool.clone()))
+        .nest(
+            "/notifications",
+            notification_controller::routes(pool.clone()),
+        )
+        .nest("/webhooks", webhook_controller::routes(pool.clone()))
+        .nest(
+            "/webhook-endpoints",
+            webhook_endpoint_controller::routes(pool.clone()),
+        )
+        .nest("/admin/jobs", job_controller::routes(pool.clone()))
         .with_state(pool)
+        .layer(Extension(escrow))
+        .layer(Extension(repository_host))
+        .layer(middleware::from_fn(request_id::request_id))
 }
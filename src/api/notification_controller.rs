@@ -0,0 +1,209 @@
+use super::auth::AuthUser;
+use super::extract::{Json, Path, Query};
+use super::pagination::{decode_cursor, PageResponse};
+use crate::application::usecases::notification_usecases::NotificationUsecases;
+use crate::domain::entities::notification::{
+    Notification, NotificationChannel, NotificationPreference, NotificationType,
+};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::notification_repository::NotificationQuery;
+use crate::infra::db::notification_repository_sql::NotificationRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct ListNotificationsParams {
+    #[serde(default)]
+    unread: bool,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UpdatePreferencesPayload {
+    preferences: Vec<PreferenceItem>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PreferenceItem {
+    #[serde(rename = "type")]
+    notification_type: NotificationType,
+    channel: NotificationChannel,
+}
+
+impl PreferenceItem {
+    fn from_entity(preference: NotificationPreference) -> Self {
+        Self {
+            notification_type: preference.notification_type,
+            channel: preference.channel,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct NotificationResponse {
+    id: Uuid,
+    #[serde(rename = "type")]
+    notification_type: NotificationType,
+    actor_id: Option<Uuid>,
+    project_id: Option<Uuid>,
+    issue_id: Option<Uuid>,
+    subject_id: Option<Uuid>,
+    read: bool,
+    created_at: DateTime<Utc>,
+    read_at: Option<DateTime<Utc>>,
+}
+
+impl NotificationResponse {
+    fn from_entity(notification: Notification) -> Self {
+        Self {
+            id: notification.id,
+            notification_type: notification.notification_type,
+            actor_id: notification.actor_id,
+            project_id: notification.project_id,
+            issue_id: notification.issue_id,
+            subject_id: notification.subject_id,
+            read: notification.read_at.is_some(),
+            created_at: notification.created_at,
+            read_at: notification.read_at,
+        }
+    }
+}
+
+/// A page of notifications, with the total unread count for badges.
+#[derive(Serialize)]
+struct NotificationPageResponse {
+    #[serde(flatten)]
+    page: PageResponse<NotificationResponse>,
+    unread_count: i64,
+}
+
+#[derive(Serialize)]
+struct UnreadCountResponse {
+    unread_count: i64,
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/", get(list_notifications))
+        .route("/unread-count", get(get_unread_count))
+        .route("/read", post(mark_all_read))
+        .route("/:id/read", post(mark_read))
+        .route("/preferences", get(get_preferences).put(update_preferences))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn list_notifications(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+    Query(params): Query<ListNotificationsParams>,
+) -> Result<Json<NotificationPageResponse>, DomainError> {
+    let query = NotificationQuery {
+        unread_only: params.unread,
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+    };
+    let actor = auth.actor();
+    let notifications = notification_usecases(pool);
+    let page = notifications.list_notifications(&actor, query).await?;
+    let unread_count = notifications.count_unread(&actor).await?;
+    Ok(Json(NotificationPageResponse {
+        page: PageResponse::from_page(page, NotificationResponse::from_entity),
+        unread_count,
+    }))
+}
+
+async fn get_unread_count(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+) -> Result<Json<UnreadCountResponse>, DomainError> {
+    let unread_count = notification_usecases(pool)
+        .count_unread(&auth.actor())
+        .await?;
+    Ok(Json(UnreadCountResponse { unread_count }))
+}
+
+async fn mark_read(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    notification_usecases(pool)
+        .mark_read(id, &auth.actor())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn mark_all_read(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    notification_usecases(pool)
+        .mark_all_read(&auth.actor())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_preferences(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<PreferenceItem>>, DomainError> {
+    let preferences = notification_usecases(pool)
+        .get_preferences(&auth.actor())
+        .await?;
+    Ok(Json(
+        preferences
+            .into_iter()
+            .map(PreferenceItem::from_entity)
+            .collect(),
+    ))
+}
+
+async fn update_preferences(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+    Json(payload): Json<UpdatePreferencesPayload>,
+) -> Result<Json<Vec<PreferenceItem>>, DomainError> {
+    let changes = payload
+        .preferences
+        .into_iter()
+        .map(|item| NotificationPreference {
+            notification_type: item.notification_type,
+            channel: item.channel,
+        })
+        .collect();
+    let preferences = notification_usecases(pool)
+        .update_preferences(&auth.actor(), changes)
+        .await?;
+    Ok(Json(
+        preferences
+            .into_iter()
+            .map(PreferenceItem::from_entity)
+            .collect(),
+    ))
+}
+
+// ------------------------
+// Aux Functions
+
+fn notification_usecases(
+    pool: Pool<Postgres>,
+) -> NotificationUsecases<NotificationRepositorySql, ProjectRepositorySql> {
+    NotificationUsecases::new(
+        NotificationRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool),
+    )
+}
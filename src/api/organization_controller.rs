@@ -0,0 +1,376 @@
+use super::auth::{AuthUser, OptionalAuthUser};
+use super::extract::{Json, Path, Query};
+use super::issue_controller::JournalEntryResponse;
+use super::pagination::{decode_cursor, PageResponse};
+use super::project_controller::ProjectResponse;
+use crate::application::usecases::ledger_usecases::LedgerUsecases;
+use crate::application::usecases::organization_usecases::OrganizationUsecases;
+use crate::application::usecases::project_usecases::ProjectUsecases;
+use crate::domain::entities::organization::{Organization, OrganizationMember, OrganizationRole};
+use crate::domain::entities::project::ProjectOwner;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::repository_host::SharedRepositoryHost;
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::project_repository::{ProjectQuery, ProjectSort};
+use crate::domain::value_objects::money::Money;
+use crate::infra::config::platform_fee_bps;
+use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::ledger_repository_sql::LedgerRepositorySql;
+use crate::infra::db::organization_repository_sql::OrganizationRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post, put},
+    Extension, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct CreateOrganizationPayload {
+    slug: String,
+    display_name: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UpdateOrganizationPayload {
+    display_name: Option<String>,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateProjectPayload {
+    name: String,
+    description: Option<String>,
+    github_link: Option<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ListProjectsParams {
+    tag: Option<String>,
+    sort: Option<ProjectSort>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct InviteMemberPayload {
+    user_id: Uuid,
+    role: OrganizationRole,
+}
+
+#[derive(Deserialize)]
+struct UpdateMemberPayload {
+    role: OrganizationRole,
+}
+
+#[derive(Deserialize)]
+struct DepositPayload {
+    amount: String,
+    currency: String,
+}
+
+#[derive(Serialize)]
+struct OrganizationResponse {
+    id: Uuid,
+    slug: String,
+    display_name: String,
+    description: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl OrganizationResponse {
+    fn from_entity(organization: Organization) -> Self {
+        Self {
+            id: organization.id,
+            slug: organization.slug,
+            display_name: organization.display_name,
+            description: organization.description,
+            created_at: organization.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct MemberResponse {
+    organization_id: Uuid,
+    user_id: Uuid,
+    role: OrganizationRole,
+    invited_by: Option<Uuid>,
+    pending: bool,
+    created_at: DateTime<Utc>,
+    accepted_at: Option<DateTime<Utc>>,
+}
+
+impl MemberResponse {
+    fn from_entity(member: OrganizationMember) -> Self {
+        Self {
+            pending: !member.is_active(),
+            organization_id: member.organization_id,
+            user_id: member.user_id,
+            role: member.role,
+            invited_by: member.invited_by,
+            created_at: member.created_at,
+            accepted_at: member.accepted_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct BalanceResponse {
+    organization_id: Uuid,
+    balances: Vec<Money>,
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/", post(create_organization))
+        .route(
+            "/:slug",
+            get(get_organization)
+                .put(update_organization)
+                .delete(delete_organization),
+        )
+        .route("/:slug/projects", get(list_projects).post(create_project))
+        .route("/:slug/members", get(list_members).post(invite_member))
+        .route(
+            "/:slug/members/:user_id",
+            put(update_member).delete(remove_member),
+        )
+        .route("/:slug/members/:user_id/accept", post(accept_invitation))
+        .route("/:slug/balance", get(get_balance))
+        .route("/:slug/deposit", post(deposit))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn create_organization(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateOrganizationPayload>,
+) -> Result<(StatusCode, Json<OrganizationResponse>), DomainError> {
+    let organization = organization_usecases(pool)
+        .create_organization(
+            &auth.actor(),
+            payload.slug,
+            payload.display_name,
+            payload.description,
+        )
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(OrganizationResponse::from_entity(organization)),
+    ))
+}
+
+async fn get_organization(
+    State(pool): State<Pool<Postgres>>,
+    Path(slug): Path<String>,
+) -> Result<Json<OrganizationResponse>, DomainError> {
+    let organization = organization_usecases(pool).get_organization(&slug).await?;
+    Ok(Json(OrganizationResponse::from_entity(organization)))
+}
+
+async fn update_organization(
+    State(pool): State<Pool<Postgres>>,
+    Path(slug): Path<String>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateOrganizationPayload>,
+) -> Result<Json<OrganizationResponse>, DomainError> {
+    let organization = organization_usecases(pool)
+        .update_organization(
+            &slug,
+            &auth.actor(),
+            payload.display_name,
+            payload.description,
+        )
+        .await?;
+    Ok(Json(OrganizationResponse::from_entity(organization)))
+}
+
+async fn delete_organization(
+    State(pool): State<Pool<Postgres>>,
+    Path(slug): Path<String>,
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    organization_usecases(pool)
+        .delete_organization(&slug, &auth.actor())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_projects(
+    State(pool): State<Pool<Postgres>>,
+    Path(slug): Path<String>,
+    auth: OptionalAuthUser,
+    Query(params): Query<ListProjectsParams>,
+) -> Result<Json<PageResponse<ProjectResponse>>, DomainError> {
+    let organization = organization_usecases(pool.clone())
+        .get_organization(&slug)
+        .await?;
+    let query = ProjectQuery {
+        owner_id: None,
+        organization_id: Some(organization.id),
+        tag: params.tag,
+        include_hidden: false,
+        sort: params.sort.unwrap_or_default(),
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+    };
+
+    let usecases = ProjectUsecases::new(ProjectRepositorySql::new(pool));
+    let page = usecases.list_projects(auth.actor().as_ref(), query).await?;
+    Ok(Json(PageResponse::from_page(
+        page,
+        ProjectResponse::from_entity,
+    )))
+}
+
+async fn create_project(
+    State(pool): State<Pool<Postgres>>,
+    Extension(repository_host): Extension<SharedRepositoryHost>,
+    Path(slug): Path<String>,
+    auth: AuthUser,
+    Json(payload): Json<CreateProjectPayload>,
+) -> Result<(StatusCode, Json<ProjectResponse>), DomainError> {
+    let organization = organization_usecases(pool.clone())
+        .get_organization(&slug)
+        .await?;
+    let usecases =
+        ProjectUsecases::new(ProjectRepositorySql::new(pool)).with_repository_host(repository_host);
+    let project = usecases
+        .create_project(
+            &auth.actor(),
+            ProjectOwner::Organization(organization.id),
+            payload.name,
+            payload.description,
+            payload.github_link,
+            payload.tags,
+        )
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ProjectResponse::from_entity(project)),
+    ))
+}
+
+async fn list_members(
+    State(pool): State<Pool<Postgres>>,
+    Path(slug): Path<String>,
+    auth: OptionalAuthUser,
+) -> Result<Json<Vec<MemberResponse>>, DomainError> {
+    let members = organization_usecases(pool)
+        .get_members(&slug, auth.actor().as_ref())
+        .await?;
+    Ok(Json(
+        members
+            .into_iter()
+            .map(MemberResponse::from_entity)
+            .collect(),
+    ))
+}
+
+async fn invite_member(
+    State(pool): State<Pool<Postgres>>,
+    Path(slug): Path<String>,
+    auth: AuthUser,
+    Json(payload): Json<InviteMemberPayload>,
+) -> Result<(StatusCode, Json<MemberResponse>), DomainError> {
+    let member = organization_usecases(pool)
+        .invite_member(&slug, &auth.actor(), payload.user_id, payload.role)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(MemberResponse::from_entity(member)),
+    ))
+}
+
+async fn update_member(
+    State(pool): State<Pool<Postgres>>,
+    Path((slug, user_id)): Path<(String, Uuid)>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateMemberPayload>,
+) -> Result<Json<MemberResponse>, DomainError> {
+    let member = organization_usecases(pool)
+        .change_member_role(&slug, &auth.actor(), user_id, payload.role)
+        .await?;
+    Ok(Json(MemberResponse::from_entity(member)))
+}
+
+async fn accept_invitation(
+    State(pool): State<Pool<Postgres>>,
+    Path((slug, user_id)): Path<(String, Uuid)>,
+    auth: AuthUser,
+) -> Result<Json<MemberResponse>, DomainError> {
+    let member = organization_usecases(pool)
+        .accept_invitation(&slug, &auth.actor(), user_id)
+        .await?;
+    Ok(Json(MemberResponse::from_entity(member)))
+}
+
+async fn remove_member(
+    State(pool): State<Pool<Postgres>>,
+    Path((slug, user_id)): Path<(String, Uuid)>,
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    organization_usecases(pool)
+        .remove_member(&slug, &auth.actor(), user_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_balance(
+    State(pool): State<Pool<Postgres>>,
+    Path(slug): Path<String>,
+    auth: AuthUser,
+) -> Result<Json<BalanceResponse>, DomainError> {
+    let usecases = organization_usecases(pool);
+    let organization = usecases.get_organization(&slug).await?;
+    let balances = usecases.get_balances(&slug, &auth.actor()).await?;
+    Ok(Json(BalanceResponse {
+        organization_id: organization.id,
+        balances,
+    }))
+}
+
+async fn deposit(
+    State(pool): State<Pool<Postgres>>,
+    Path(slug): Path<String>,
+    auth: AuthUser,
+    Json(payload): Json<DepositPayload>,
+) -> Result<(StatusCode, Json<JournalEntryResponse>), DomainError> {
+    let entry = organization_usecases(pool)
+        .deposit(&slug, &auth.actor(), &payload.amount, &payload.currency)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(JournalEntryResponse::from_entity(entry)),
+    ))
+}
+
+// ------------------------
+// Aux Functions
+
+fn organization_usecases(
+    pool: Pool<Postgres>,
+) -> OrganizationUsecases<
+    OrganizationRepositorySql,
+    LedgerRepositorySql,
+    IssueRepositorySql,
+    ProjectRepositorySql,
+> {
+    let ledger = LedgerUsecases::new(
+        LedgerRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool.clone()),
+        platform_fee_bps(),
+    );
+    OrganizationUsecases::new(OrganizationRepositorySql::new(pool), ledger)
+}
@@ -0,0 +1,26 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, Page};
+use serde::Serialize;
+
+/// Envelope returned by every list endpoint.
+#[derive(Serialize)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> PageResponse<T> {
+    pub fn from_page<E>(page: Page<E>, f: impl FnMut(E) -> T) -> Self {
+        let page = page.map(f);
+        Self {
+            items: page.items,
+            next_cursor: page.next_cursor.map(|cursor| cursor.encode()),
+        }
+    }
+}
+
+pub fn decode_cursor(raw: Option<&str>) -> Result<Option<Cursor>, DomainError> {
+    raw.filter(|raw| !raw.is_empty())
+        .map(Cursor::decode)
+        .transpose()
+}
@@ -1,11 +1,18 @@
+use super::auth::{AuthUser, OptionalAuthUser};
+use super::extract::{Json, Path, Query};
+use super::pagination::{decode_cursor, PageResponse};
 use crate::application::usecases::project_usecases::ProjectUsecases;
-use crate::domain::errors::domain_error::DomainError;
+use crate::domain::entities::project::{Project, ProjectOwner};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::gateways::repository_host::{RepositoryStats, SharedRepositoryHost};
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::project_repository::{ProjectQuery, ProjectSort};
 use crate::infra::db::project_repository_sql::ProjectRepositorySql;
 use axum::{
-    extract::{Path, State},
+    extract::State,
     http::StatusCode,
-    routing::{delete, get, post, put},
-    Json, Router,
+    routing::{get, post, put},
+    Extension, Router,
 };
 use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Postgres};
@@ -13,7 +20,6 @@ use uuid::Uuid;
 
 #[derive(Deserialize)]
 struct CreateProjectPayload {
-    owner_id: Uuid,
     name: String,
     description: Option<String>,
     github_link: Option<String>,
@@ -28,25 +34,51 @@ struct UpdateProjectPayload {
     tags: Option<Vec<String>>,
 }
 
+#[derive(Deserialize)]
+struct ListProjectsParams {
+    owner_id: Option<Uuid>,
+    organization_id: Option<Uuid>,
+    tag: Option<String>,
+    sort: Option<ProjectSort>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct VisibilityPayload {
+    hidden: bool,
+}
+
+/// Exactly one of `user_id` and `organization_id`.
+#[derive(Deserialize)]
+struct TransferPayload {
+    user_id: Option<Uuid>,
+    organization_id: Option<Uuid>,
+}
+
 #[derive(Serialize)]
-struct ProjectResponse {
+pub(super) struct ProjectResponse {
     id: Uuid,
-    owner_id: Uuid,
+    owner: ProjectOwner,
     name: String,
     description: Option<String>,
     github_link: Option<String>,
     tags: Vec<String>,
+    hidden: bool,
+    repo_stats: Option<RepositoryStats>,
 }
 
 impl ProjectResponse {
-    fn from_entity(project: crate::domain::entities::project::Project) -> Self {
+    pub(super) fn from_entity(project: Project) -> Self {
         Self {
             id: project.id,
-            owner_id: project.owner_id,
+            owner: project.owner,
             name: project.name,
             description: project.description,
             github_link: project.github_link,
             tags: project.tags,
+            hidden: project.hidden,
+            repo_stats: project.repo_stats,
         }
     }
 }
@@ -58,90 +90,134 @@ pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
             "/:id",
             get(get_project).put(update_project).delete(delete_project),
         )
+        .route("/:id/visibility", put(set_project_visibility))
+        .route("/:id/transfer", post(transfer_project))
         .with_state(pool)
 }
 
 async fn create_project(
     State(pool): State<Pool<Postgres>>,
+    Extension(repository_host): Extension<SharedRepositoryHost>,
+    auth: AuthUser,
     Json(payload): Json<CreateProjectPayload>,
-) -> Result<Json<ProjectResponse>, StatusCode> {
+) -> Result<Json<ProjectResponse>, DomainError> {
     let repo = ProjectRepositorySql::new(pool);
-    let usecases = ProjectUsecases::new(repo);
+    let usecases = ProjectUsecases::new(repo).with_repository_host(repository_host);
     let project_entity = usecases
         .create_project(
-            payload.owner_id,
+            &auth.actor(),
+            ProjectOwner::User(auth.id),
             payload.name,
             payload.description,
             payload.github_link,
             payload.tags,
         )
-        .await
-        .map_err(map_domain_error)?;
+        .await?;
     Ok(Json(ProjectResponse::from_entity(project_entity)))
 }
 
 async fn get_project(
     State(pool): State<Pool<Postgres>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<ProjectResponse>, StatusCode> {
+    auth: OptionalAuthUser,
+) -> Result<Json<ProjectResponse>, DomainError> {
     let repo = ProjectRepositorySql::new(pool);
     let usecases = ProjectUsecases::new(repo);
-    let project_entity = usecases.get_project(id).await.map_err(map_domain_error)?;
+    let project_entity = usecases.view_project(id, auth.actor().as_ref()).await?;
     Ok(Json(ProjectResponse::from_entity(project_entity)))
 }
 
 async fn update_project(
     State(pool): State<Pool<Postgres>>,
+    Extension(repository_host): Extension<SharedRepositoryHost>,
     Path(id): Path<Uuid>,
+    auth: AuthUser,
     Json(payload): Json<UpdateProjectPayload>,
-) -> Result<Json<ProjectResponse>, StatusCode> {
+) -> Result<Json<ProjectResponse>, DomainError> {
     let repo = ProjectRepositorySql::new(pool);
-    let usecases = ProjectUsecases::new(repo);
+    let usecases = ProjectUsecases::new(repo).with_repository_host(repository_host);
     let project_entity = usecases
         .update_project(
             id,
+            &auth.actor(),
             payload.name,
             payload.description,
             payload.github_link,
             payload.tags,
         )
-        .await
-        .map_err(map_domain_error)?;
+        .await?;
+    Ok(Json(ProjectResponse::from_entity(project_entity)))
+}
+
+async fn set_project_visibility(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<VisibilityPayload>,
+) -> Result<Json<ProjectResponse>, DomainError> {
+    let repo = ProjectRepositorySql::new(pool);
+    let usecases = ProjectUsecases::new(repo);
+    let project_entity = usecases
+        .set_project_hidden(id, &auth.actor(), payload.hidden)
+        .await?;
+    Ok(Json(ProjectResponse::from_entity(project_entity)))
+}
+
+async fn transfer_project(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<TransferPayload>,
+) -> Result<Json<ProjectResponse>, DomainError> {
+    let new_owner = match (payload.user_id, payload.organization_id) {
+        (Some(user_id), None) => ProjectOwner::User(user_id),
+        (None, Some(organization_id)) => ProjectOwner::Organization(organization_id),
+        _ => {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "user_id",
+                "exactly one of user_id and organization_id is required",
+            )]))
+        }
+    };
+    let repo = ProjectRepositorySql::new(pool);
+    let usecases = ProjectUsecases::new(repo);
+    let project_entity = usecases
+        .transfer_ownership(id, &auth.actor(), new_owner)
+        .await?;
     Ok(Json(ProjectResponse::from_entity(project_entity)))
 }
 
 async fn delete_project(
     State(pool): State<Pool<Postgres>>,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, StatusCode> {
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
     let repo = ProjectRepositorySql::new(pool);
     let usecases = ProjectUsecases::new(repo);
-    usecases
-        .delete_project(id)
-        .await
-        .map_err(map_domain_error)?;
+    usecases.delete_project(id, &auth.actor()).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
 async fn list_projects(
     State(pool): State<Pool<Postgres>>,
-) -> Result<Json<Vec<ProjectResponse>>, StatusCode> {
+    auth: OptionalAuthUser,
+    Query(params): Query<ListProjectsParams>,
+) -> Result<Json<PageResponse<ProjectResponse>>, DomainError> {
+    let query = ProjectQuery {
+        owner_id: params.owner_id,
+        organization_id: params.organization_id,
+        tag: params.tag,
+        include_hidden: false,
+        sort: params.sort.unwrap_or_default(),
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+    };
+
     let repo = ProjectRepositorySql::new(pool);
     let usecases = ProjectUsecases::new(repo);
-    let projects = usecases.list_projects().await.map_err(map_domain_error)?;
-    let response = projects
-        .into_iter()
-        .map(ProjectResponse::from_entity)
-        .collect();
-    Ok(Json(response))
-}
-
-fn map_domain_error(err: DomainError) -> StatusCode {
-    match err {
-        DomainError::InvalidData(_) => StatusCode::BAD_REQUEST,
-        DomainError::Conflict(_) => StatusCode::CONFLICT,
-        DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-        DomainError::Infra(_) => StatusCode::INTERNAL_SERVER_ERROR,
-    }
+    let page = usecases.list_projects(auth.actor().as_ref(), query).await?;
+    Ok(Json(PageResponse::from_page(
+        page,
+        ProjectResponse::from_entity,
+    )))
 }
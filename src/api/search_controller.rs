@@ -0,0 +1,100 @@
+use super::auth::OptionalAuthUser;
+use super::extract::{Json, Query};
+use super::pagination::{decode_cursor, PageResponse};
+use crate::application::usecases::search_usecases::SearchUsecases;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::search_repository::{
+    SearchFacets, SearchHit, SearchKind, SearchQuery,
+};
+use crate::infra::db::search_repository_sql::SearchRepositorySql;
+use axum::{extract::State, routing::get, Router};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct SearchParams {
+    q: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<SearchKind>,
+    tag: Option<String>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SearchHitResponse {
+    #[serde(rename = "type")]
+    kind: SearchKind,
+    id: Uuid,
+    project_id: Uuid,
+    title: String,
+    snippet: String,
+    tags: Vec<String>,
+    rank: f64,
+}
+
+impl SearchHitResponse {
+    fn from_entity(hit: SearchHit) -> Self {
+        Self {
+            kind: hit.kind,
+            id: hit.id,
+            project_id: hit.project_id,
+            title: hit.title,
+            snippet: hit.snippet,
+            tags: hit.tags,
+            rank: hit.rank,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct FacetsResponse {
+    project: i64,
+    issue: i64,
+}
+
+impl FacetsResponse {
+    fn from_entity(facets: SearchFacets) -> Self {
+        Self {
+            project: facets.projects,
+            issue: facets.issues,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SearchResponse {
+    #[serde(flatten)]
+    page: PageResponse<SearchHitResponse>,
+    facets: FacetsResponse,
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new().route("/", get(search)).with_state(pool)
+}
+
+async fn search(
+    State(pool): State<Pool<Postgres>>,
+    auth: OptionalAuthUser,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<SearchResponse>, DomainError> {
+    let query = SearchQuery {
+        text: params.q.unwrap_or_default(),
+        kind: params.kind,
+        tag: params.tag,
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+        // Visibility is set by the use case from the viewer.
+        ..SearchQuery::default()
+    };
+
+    let repo = SearchRepositorySql::new(pool);
+    let usecases = SearchUsecases::new(repo);
+    let results = usecases.search(auth.actor().as_ref(), query).await?;
+    Ok(Json(SearchResponse {
+        page: PageResponse::from_page(results.page, SearchHitResponse::from_entity),
+        facets: FacetsResponse::from_entity(results.facets),
+    }))
+}
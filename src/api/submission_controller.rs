@@ -0,0 +1,217 @@
+use super::auth::{AuthUser, OptionalAuthUser};
+use super::extract::{Json, Path};
+use super::issue_controller::JournalEntryResponse;
+use crate::application::usecases::ledger_usecases::LedgerUsecases;
+use crate::application::usecases::submission_usecases::{
+    ApprovedSubmission, SubmissionHistory, SubmissionUsecases,
+};
+use crate::domain::entities::submission::{
+    ReviewDecision, Submission, SubmissionReview, SubmissionStatus,
+};
+use crate::domain::errors::domain_error::DomainError;
+use crate::infra::config::platform_fee_bps;
+use crate::infra::db::issue_claim_repository_sql::IssueClaimRepositorySql;
+use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::ledger_repository_sql::LedgerRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use crate::infra::db::submission_repository_sql::SubmissionRepositorySql;
+use axum::{extract::State, http::StatusCode, routing::post, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct SubmitPayload {
+    pr_url: String,
+    notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApprovePayload {
+    comment: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RequestChangesPayload {
+    comment: String,
+}
+
+#[derive(Serialize)]
+struct ReviewResponse {
+    id: Uuid,
+    reviewer_id: Option<Uuid>,
+    decision: ReviewDecision,
+    comment: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl ReviewResponse {
+    fn from_entity(review: SubmissionReview) -> Self {
+        Self {
+            id: review.id,
+            reviewer_id: review.reviewer_id,
+            decision: review.decision,
+            comment: review.comment,
+            created_at: review.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SubmissionResponse {
+    id: Uuid,
+    issue_id: Uuid,
+    submitter_id: Uuid,
+    pr_url: String,
+    notes: Option<String>,
+    status: SubmissionStatus,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reviews: Option<Vec<ReviewResponse>>,
+}
+
+impl SubmissionResponse {
+    fn from_entity(submission: Submission) -> Self {
+        Self {
+            id: submission.id,
+            issue_id: submission.issue_id,
+            submitter_id: submission.submitter_id,
+            pr_url: submission.pr_url,
+            notes: submission.notes,
+            status: submission.status,
+            created_at: submission.created_at,
+            updated_at: submission.updated_at,
+            reviews: None,
+        }
+    }
+
+    fn from_history(history: SubmissionHistory) -> Self {
+        Self {
+            reviews: Some(
+                history
+                    .reviews
+                    .into_iter()
+                    .map(ReviewResponse::from_entity)
+                    .collect(),
+            ),
+            ..Self::from_entity(history.submission)
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ApprovalResponse {
+    submission: SubmissionResponse,
+    payout: Option<JournalEntryResponse>,
+}
+
+impl ApprovalResponse {
+    fn from_entity(approved: ApprovedSubmission) -> Self {
+        Self {
+            submission: SubmissionResponse::from_entity(approved.submission),
+            payout: approved.payout.map(JournalEntryResponse::from_entity),
+        }
+    }
+}
+
+/// Mounted under `/issues` next to the issue routes.
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/:id/submissions", post(submit_work).get(list_submissions))
+        .route(
+            "/:id/submissions/:submission_id/approve",
+            post(approve_submission),
+        )
+        .route(
+            "/:id/submissions/:submission_id/request-changes",
+            post(request_changes),
+        )
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn submit_work(
+    State(pool): State<Pool<Postgres>>,
+    Path(issue_id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<SubmitPayload>,
+) -> Result<(StatusCode, Json<SubmissionResponse>), DomainError> {
+    let submission = submission_usecases(pool)
+        .submit_work(issue_id, &auth.actor(), payload.pr_url, payload.notes)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(SubmissionResponse::from_entity(submission)),
+    ))
+}
+
+async fn list_submissions(
+    State(pool): State<Pool<Postgres>>,
+    Path(issue_id): Path<Uuid>,
+    auth: OptionalAuthUser,
+) -> Result<Json<Vec<SubmissionResponse>>, DomainError> {
+    let history = submission_usecases(pool)
+        .get_submission_history(issue_id, auth.actor().as_ref())
+        .await?;
+    Ok(Json(
+        history
+            .into_iter()
+            .map(SubmissionResponse::from_history)
+            .collect(),
+    ))
+}
+
+async fn approve_submission(
+    State(pool): State<Pool<Postgres>>,
+    Path((issue_id, submission_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    Json(payload): Json<ApprovePayload>,
+) -> Result<Json<ApprovalResponse>, DomainError> {
+    let approved = submission_usecases(pool)
+        .approve_submission(issue_id, submission_id, &auth.actor(), payload.comment)
+        .await?;
+    Ok(Json(ApprovalResponse::from_entity(approved)))
+}
+
+async fn request_changes(
+    State(pool): State<Pool<Postgres>>,
+    Path((issue_id, submission_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    Json(payload): Json<RequestChangesPayload>,
+) -> Result<Json<SubmissionResponse>, DomainError> {
+    let submission = submission_usecases(pool)
+        .request_changes(issue_id, submission_id, &auth.actor(), payload.comment)
+        .await?;
+    Ok(Json(SubmissionResponse::from_entity(submission)))
+}
+
+// ------------------------
+// Aux Functions
+
+fn submission_usecases(
+    pool: Pool<Postgres>,
+) -> SubmissionUsecases<
+    SubmissionRepositorySql,
+    IssueClaimRepositorySql,
+    IssueRepositorySql,
+    ProjectRepositorySql,
+    LedgerRepositorySql,
+> {
+    let ledger = LedgerUsecases::new(
+        LedgerRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool.clone()),
+        platform_fee_bps(),
+    );
+    SubmissionUsecases::new(
+        SubmissionRepositorySql::new(pool.clone()),
+        IssueClaimRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool),
+        ledger,
+    )
+}
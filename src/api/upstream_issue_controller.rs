@@ -0,0 +1,231 @@
+use super::auth::{AuthUser, OptionalAuthUser};
+use super::extract::{Json, Path, Query};
+use super::issue_controller::{self, BountyPayload, IssueResponse};
+use crate::application::usecases::issue_usecases::{
+    IssueUsecases, UpstreamImport, UpstreamIssueListing,
+};
+use crate::domain::entities::issue::ClaimMode;
+use crate::domain::entities::issue_pull_request::{IssuePullRequest, PullRequestState};
+use crate::domain::entities::issue_upstream_link::{IssueUpstreamLink, UpstreamIssueState};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::repository_host::{SharedRepositoryHost, UpstreamIssue};
+use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::ledger_repository_sql::LedgerRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct ListUpstreamParams {
+    page: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ImportPayload {
+    issues: Vec<ImportItemPayload>,
+}
+
+#[derive(Deserialize)]
+struct ImportItemPayload {
+    number: i32,
+    bounty: BountyPayload,
+    #[serde(default)]
+    claim_mode: ClaimMode,
+}
+
+#[derive(Serialize)]
+struct UpstreamIssueResponse {
+    number: i32,
+    title: String,
+    url: String,
+    state: UpstreamIssueState,
+    labels: Vec<String>,
+    updated_at: DateTime<Utc>,
+    imported_issue_id: Option<Uuid>,
+}
+
+impl UpstreamIssueResponse {
+    fn from_listing(listing: UpstreamIssueListing) -> Self {
+        let UpstreamIssue {
+            number,
+            title,
+            url,
+            state,
+            labels,
+            updated_at,
+            ..
+        } = listing.issue;
+        Self {
+            number,
+            title,
+            url,
+            state,
+            labels,
+            updated_at,
+            imported_issue_id: listing.imported_issue_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct UpstreamPageResponse {
+    items: Vec<UpstreamIssueResponse>,
+    next_page: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct UpstreamLinkResponse {
+    number: i32,
+    url: String,
+    state: UpstreamIssueState,
+    synced_at: DateTime<Utc>,
+}
+
+impl UpstreamLinkResponse {
+    fn from_entity(link: IssueUpstreamLink) -> Self {
+        Self {
+            number: link.number,
+            url: link.url,
+            state: link.state,
+            synced_at: link.synced_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ImportedIssueResponse {
+    issue: IssueResponse,
+    upstream: UpstreamLinkResponse,
+}
+
+#[derive(Serialize)]
+struct PullRequestResponse {
+    repository: String,
+    number: i32,
+    url: String,
+    title: String,
+    author: Option<String>,
+    state: PullRequestState,
+    draft: bool,
+    updated_at: DateTime<Utc>,
+}
+
+impl PullRequestResponse {
+    fn from_entity(pull_request: IssuePullRequest) -> Self {
+        Self {
+            repository: format!("{}/{}", pull_request.repo_owner, pull_request.repo_name),
+            number: pull_request.number,
+            url: pull_request.url,
+            title: pull_request.title,
+            author: pull_request.author,
+            state: pull_request.state,
+            draft: pull_request.draft,
+            updated_at: pull_request.updated_at,
+        }
+    }
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/:id/upstream-issues", get(list_upstream_issues))
+        .route("/:id/upstream-issues/import", post(import_upstream_issues))
+        .with_state(pool)
+}
+
+/// Mounted under `/issues` next to the issue routes.
+pub fn issue_routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/:id/pull-requests", get(list_pull_requests))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn list_upstream_issues(
+    State(pool): State<Pool<Postgres>>,
+    Extension(repository_host): Extension<SharedRepositoryHost>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Query(params): Query<ListUpstreamParams>,
+) -> Result<Json<UpstreamPageResponse>, DomainError> {
+    let page = params.page.unwrap_or(1);
+    let (listings, has_more) = issue_usecases(pool, repository_host)
+        .list_upstream_issues(id, &auth.actor(), page)
+        .await?;
+    Ok(Json(UpstreamPageResponse {
+        items: listings
+            .into_iter()
+            .map(UpstreamIssueResponse::from_listing)
+            .collect(),
+        next_page: has_more.then_some(page + 1),
+    }))
+}
+
+async fn import_upstream_issues(
+    State(pool): State<Pool<Postgres>>,
+    Extension(repository_host): Extension<SharedRepositoryHost>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ImportPayload>,
+) -> Result<(StatusCode, Json<Vec<ImportedIssueResponse>>), DomainError> {
+    let imports = payload
+        .issues
+        .into_iter()
+        .map(|item| UpstreamImport {
+            number: item.number,
+            bounty: item.bounty.into_input(),
+            claim_mode: item.claim_mode,
+        })
+        .collect();
+    let imported = issue_usecases(pool, repository_host)
+        .import_upstream_issues(id, &auth.actor(), imports)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(
+            imported
+                .into_iter()
+                .map(|(issue, link)| ImportedIssueResponse {
+                    issue: IssueResponse::from_entity(issue),
+                    upstream: UpstreamLinkResponse::from_entity(link),
+                })
+                .collect(),
+        ),
+    ))
+}
+
+async fn list_pull_requests(
+    State(pool): State<Pool<Postgres>>,
+    Extension(repository_host): Extension<SharedRepositoryHost>,
+    Path(id): Path<Uuid>,
+    auth: OptionalAuthUser,
+) -> Result<Json<Vec<PullRequestResponse>>, DomainError> {
+    let pull_requests = issue_usecases(pool, repository_host)
+        .get_issue_pull_requests(id, auth.actor().as_ref())
+        .await?;
+    Ok(Json(
+        pull_requests
+            .into_iter()
+            .map(PullRequestResponse::from_entity)
+            .collect(),
+    ))
+}
+
+// ------------------------
+// Aux Functions
+
+fn issue_usecases(
+    pool: Pool<Postgres>,
+    repository_host: SharedRepositoryHost,
+) -> IssueUsecases<IssueRepositorySql, ProjectRepositorySql, LedgerRepositorySql> {
+    issue_controller::issue_usecases(pool).with_repository_host(repository_host)
+}
@@ -1,12 +1,23 @@
+use super::auth::AuthUser;
+use super::extract::{Json, Path, Query};
+use super::issue_controller::{ledger_usecases, JournalEntryResponse};
+use super::pagination::{decode_cursor, PageResponse};
+use crate::application::policies::{self, Actor};
+use crate::application::usecases::session_usecases::SessionUsecases;
 use crate::application::usecases::user_usecases::UserUsecases;
+use crate::domain::entities::user::{Role, User};
 use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::user_repository::{UserQuery, UserSort};
+use crate::domain::value_objects::money::Money;
+use crate::infra::db::refresh_token_repository_sql::RefreshTokenRepositorySql;
 use crate::infra::db::user_repository_sql::UserRepositorySql;
-use crate::infra::jwt::{generate_jwt, Claims};
+use crate::infra::jwt::{generate_jwt, refresh_token_ttl};
 use axum::{
-    extract::{Path, State},
-    http::{HeaderMap, StatusCode},
-    routing::{get, post},
-    Json, Router,
+    extract::State,
+    http::StatusCode,
+    routing::{get, post, put},
+    Router,
 };
 use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Postgres};
@@ -29,23 +40,80 @@ struct LoginPayload {
 struct UpdatePayload {
     username: Option<String>,
     password: Option<String>,
+    /// SS58 address for on-chain bounty payouts; an empty string removes it.
+    chain_address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ListUsersParams {
+    username: Option<String>,
+    role: Option<Role>,
+    sort: Option<UserSort>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DepositPayload {
+    amount: String,
+    currency: String,
+}
+
+#[derive(Deserialize)]
+struct RolePayload {
+    role: Role,
 }
 
 #[derive(Serialize)]
 struct UserResponse {
     id: Uuid,
     username: String,
-    email: String,
+    /// Only shown to the user themselves and to admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    /// Only shown to the user themselves and to admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chain_address: Option<String>,
+    role: Role,
     token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BalanceResponse {
+    user_id: Uuid,
+    balances: Vec<Money>,
 }
 
 impl UserResponse {
-    fn from_entity(user: crate::domain::entities::user::User, token: Option<String>) -> Self {
+    fn from_entity(user: User, token: Option<String>) -> Self {
         Self {
             id: user.id,
             username: user.username,
-            email: user.email,
+            email: Some(user.email),
+            chain_address: user.chain_address,
+            role: user.role,
             token,
+            refresh_token: None,
+        }
+    }
+
+    /// Leaves the contact details out unless `viewer` may edit the user.
+    fn for_viewer(user: User, viewer: &Actor) -> Self {
+        let private = policies::can_edit_user(viewer, user.id);
+        let mut response = Self::from_entity(user, None);
+        if !private {
+            response.email = None;
+            response.chain_address = None;
+        }
+        response
+    }
+
+    fn with_session(user: User, token: String, refresh_token: String) -> Self {
+        Self {
+            refresh_token: Some(refresh_token),
+            ..Self::from_entity(user, Some(token))
         }
     }
 }
@@ -55,6 +123,9 @@ pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
         .route("/", post(register_user).get(list_users))
         .route("/login", post(login_user))
         .route("/:id", get(get_user).put(update_user).delete(delete_user))
+        .route("/:id/role", put(set_user_role))
+        .route("/:id/balance", get(get_user_balance))
+        .route("/:id/deposit", post(deposit))
         .with_state(pool)
 }
 
@@ -64,72 +135,81 @@ pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
 async fn register_user(
     State(pool): State<Pool<Postgres>>,
     Json(payload): Json<RegisterPayload>,
-) -> Result<Json<UserResponse>, StatusCode> {
-    let repo = UserRepositorySql::new(pool);
+) -> Result<Json<UserResponse>, DomainError> {
+    let repo = UserRepositorySql::new(pool.clone());
     let usecases = UserUsecases::new(repo);
     let user_entity = usecases
         .register_user(payload.username, payload.email, payload.password)
-        .await
-        .map_err(map_domain_error)?;
+        .await?;
     // Gera token JWT imediatamente após registro, se quiser
-    let token = generate_jwt(user_entity.id).ok();
-    Ok(Json(UserResponse::from_entity(user_entity, token)))
+    let (token, refresh_token) = start_session(pool, &user_entity).await?;
+    Ok(Json(UserResponse::with_session(
+        user_entity,
+        token,
+        refresh_token,
+    )))
 }
 
 async fn login_user(
     State(pool): State<Pool<Postgres>>,
     Json(payload): Json<LoginPayload>,
-) -> Result<Json<UserResponse>, StatusCode> {
-    let repo = UserRepositorySql::new(pool);
+) -> Result<Json<UserResponse>, DomainError> {
+    let repo = UserRepositorySql::new(pool.clone());
     let usecases = UserUsecases::new(repo);
-    let user_entity = usecases
-        .login_user(payload.email, payload.password)
-        .await
-        .map_err(map_domain_error)?;
-    let token = generate_jwt(user_entity.id).ok();
-    Ok(Json(UserResponse::from_entity(user_entity, token)))
+    let user_entity = usecases.login_user(payload.email, payload.password).await?;
+    let (token, refresh_token) = start_session(pool, &user_entity).await?;
+    Ok(Json(UserResponse::with_session(
+        user_entity,
+        token,
+        refresh_token,
+    )))
 }
 
 async fn get_user(
     State(pool): State<Pool<Postgres>>,
     Path(id): Path<Uuid>,
-    headers: HeaderMap,
-) -> Result<Json<UserResponse>, StatusCode> {
-    // Validar JWT
-    let claims = check_auth(&headers)?;
-
+    auth: AuthUser,
+) -> Result<Json<UserResponse>, DomainError> {
     let repo = UserRepositorySql::new(pool);
     let usecases = UserUsecases::new(repo);
-    let user_entity = usecases.get_user(id).await.map_err(map_domain_error)?;
-
-    // Verifica se quem requisita é o mesmo user ou algo do tipo (opcional)
-    if claims.sub != id {
-        // Decida se quer permitir que outra pessoa veja o user
-        // Por simplicidade, vamos permitir
-    }
+    let user_entity = usecases.get_user(id).await?;
 
-    Ok(Json(UserResponse::from_entity(user_entity, None)))
+    Ok(Json(UserResponse::for_viewer(user_entity, &auth.actor())))
 }
 
 async fn update_user(
     State(pool): State<Pool<Postgres>>,
     Path(id): Path<Uuid>,
-    headers: HeaderMap,
+    auth: AuthUser,
     Json(payload): Json<UpdatePayload>,
-) -> Result<Json<UserResponse>, StatusCode> {
-    let claims = check_auth(&headers)?;
+) -> Result<Json<UserResponse>, DomainError> {
+    // O próprio usuário ou um admin (ver policies::can_edit_user)
+    let repo = UserRepositorySql::new(pool);
+    let usecases = UserUsecases::new(repo);
+    let user_entity = usecases
+        .update_user(
+            id,
+            &auth.actor(),
+            payload.username.clone(),
+            payload.password.clone(),
+            payload.chain_address.clone(),
+        )
+        .await?;
 
-    // Checar se o id do token bate com o user que está sendo atualizado, ou se é admin, etc.
-    if claims.sub != id {
-        return Err(StatusCode::UNAUTHORIZED);
-    }
+    Ok(Json(UserResponse::from_entity(user_entity, None)))
+}
 
+async fn set_user_role(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<RolePayload>,
+) -> Result<Json<UserResponse>, DomainError> {
     let repo = UserRepositorySql::new(pool);
     let usecases = UserUsecases::new(repo);
     let user_entity = usecases
-        .update_user(id, payload.username.clone(), payload.password.clone())
-        .await
-        .map_err(map_domain_error)?;
+        .set_user_role(id, &auth.actor(), payload.role)
+        .await?;
 
     Ok(Json(UserResponse::from_entity(user_entity, None)))
 }
@@ -137,62 +217,78 @@ async fn update_user(
 async fn delete_user(
     State(pool): State<Pool<Postgres>>,
     Path(id): Path<Uuid>,
-    headers: HeaderMap,
-) -> Result<StatusCode, StatusCode> {
-    let claims = check_auth(&headers)?;
-
-    if claims.sub != id {
-        return Err(StatusCode::UNAUTHORIZED);
-    }
-
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
     let repo = UserRepositorySql::new(pool);
     let usecases = UserUsecases::new(repo);
 
-    usecases.delete_user(id).await.map_err(map_domain_error)?;
+    usecases.delete_user(id, &auth.actor()).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 async fn list_users(
     State(pool): State<Pool<Postgres>>,
-    headers: HeaderMap,
-) -> Result<Json<Vec<UserResponse>>, StatusCode> {
-    // Em tese, só usuários logados podem ver a lista
-    let _claims = check_auth(&headers)?;
+    auth: AuthUser,
+    Query(params): Query<ListUsersParams>,
+) -> Result<Json<PageResponse<UserResponse>>, DomainError> {
+    let query = UserQuery {
+        username: params.username,
+        role: params.role,
+        sort: params.sort.unwrap_or_default(),
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+    };
 
     let repo = UserRepositorySql::new(pool);
     let usecases = UserUsecases::new(repo);
 
-    let users = usecases.list_users().await.map_err(map_domain_error)?;
-    let resp = users
-        .into_iter()
-        .map(|u| UserResponse::from_entity(u, None))
-        .collect();
+    let page = usecases.list_users(query).await?;
+    let viewer = auth.actor();
+    let resp = PageResponse::from_page(page, |u| UserResponse::for_viewer(u, &viewer));
     Ok(Json(resp))
 }
 
-// ------------------------
-// Aux Functions
+async fn get_user_balance(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<BalanceResponse>, DomainError> {
+    let balances = ledger_usecases(pool)
+        .get_user_balances(id, &auth.actor())
+        .await?;
+    Ok(Json(BalanceResponse {
+        user_id: id,
+        balances,
+    }))
+}
 
-fn map_domain_error(err: DomainError) -> StatusCode {
-    match err {
-        DomainError::InvalidData(_) => StatusCode::BAD_REQUEST,
-        DomainError::Conflict(_) => StatusCode::CONFLICT,
-        DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-        DomainError::Infra(_) => StatusCode::INTERNAL_SERVER_ERROR,
-    }
+async fn deposit(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<DepositPayload>,
+) -> Result<(StatusCode, Json<JournalEntryResponse>), DomainError> {
+    let user = UserUsecases::new(UserRepositorySql::new(pool.clone()))
+        .get_user(id)
+        .await?;
+    let entry = ledger_usecases(pool)
+        .deposit_to_user(user.id, &auth.actor(), &payload.amount, &payload.currency)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(JournalEntryResponse::from_entity(entry)),
+    ))
 }
 
-fn check_auth(headers: &HeaderMap) -> Result<Claims, StatusCode> {
-    if let Some(auth_header) = headers.get("Authorization") {
-        let auth_str = auth_header.to_str().unwrap_or("");
-        if auth_str.starts_with("Bearer ") {
-            let token = auth_str.trim_start_matches("Bearer ").trim();
-            let claims =
-                crate::infra::jwt::validate_jwt(token).map_err(|_| StatusCode::UNAUTHORIZED)?;
-            return Ok(claims);
-        }
-    }
-    Err(StatusCode::UNAUTHORIZED)
+// ------------------------
+// Aux Functions
+
+/// Issues an access token plus a refresh token starting a new session family.
+async fn start_session(pool: Pool<Postgres>, user: &User) -> Result<(String, String), DomainError> {
+    let token = generate_jwt(user.id, user.role)?;
+    let session_usecases =
+        SessionUsecases::new(RefreshTokenRepositorySql::new(pool), refresh_token_ttl());
+    let refresh_token = session_usecases.issue_refresh_token(user.id).await?;
+    Ok((token, refresh_token))
 }
@@ -0,0 +1,95 @@
+use super::extract::Json;
+use super::issue_controller::issue_usecases;
+use crate::application::usecases::github_webhook_usecases::{
+    GithubWebhookUsecases, WebhookOutcome,
+};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::repository_host::SharedRepositoryHost;
+use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::ledger_repository_sql::LedgerRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use crate::infra::db::submission_repository_sql::SubmissionRepositorySql;
+use crate::infra::db::webhook_delivery_repository_sql::WebhookDeliveryRepositorySql;
+use axum::{body::Bytes, extract::State, http::HeaderMap, routing::post, Extension, Router};
+use serde::Serialize;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DeliveryStatus {
+    Processed,
+    Ignored,
+    Duplicate,
+}
+
+#[derive(Serialize)]
+struct DeliveryResponse {
+    status: DeliveryStatus,
+    issue_ids: Vec<Uuid>,
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/github", post(receive_github_webhook))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+/// Takes the raw body, since the signature is computed over the exact bytes sent.
+async fn receive_github_webhook(
+    State(pool): State<Pool<Postgres>>,
+    Extension(repository_host): Extension<SharedRepositoryHost>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<DeliveryResponse>, DomainError> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let outcome = github_webhook_usecases(pool, repository_host)
+        .handle_delivery(
+            header("X-GitHub-Delivery").unwrap_or_default(),
+            header("X-GitHub-Event").unwrap_or_default(),
+            header("X-Hub-Signature-256"),
+            &body,
+        )
+        .await?;
+    let response = match outcome {
+        WebhookOutcome::Processed { issue_ids } => DeliveryResponse {
+            status: DeliveryStatus::Processed,
+            issue_ids,
+        },
+        WebhookOutcome::Ignored => DeliveryResponse {
+            status: DeliveryStatus::Ignored,
+            issue_ids: Vec::new(),
+        },
+        WebhookOutcome::Duplicate => DeliveryResponse {
+            status: DeliveryStatus::Duplicate,
+            issue_ids: Vec::new(),
+        },
+    };
+    Ok(Json(response))
+}
+
+// ------------------------
+// Aux Functions
+
+fn github_webhook_usecases(
+    pool: Pool<Postgres>,
+    repository_host: SharedRepositoryHost,
+) -> GithubWebhookUsecases<
+    WebhookDeliveryRepositorySql,
+    SubmissionRepositorySql,
+    IssueRepositorySql,
+    ProjectRepositorySql,
+    LedgerRepositorySql,
+> {
+    let issues = issue_usecases(pool.clone());
+    GithubWebhookUsecases::new(
+        WebhookDeliveryRepositorySql::new(pool.clone()),
+        SubmissionRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool),
+        issues,
+        repository_host,
+    )
+}
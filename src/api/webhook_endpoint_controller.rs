@@ -0,0 +1,278 @@
+use super::auth::AuthUser;
+use super::extract::{Json, Path, Query};
+use super::pagination::{decode_cursor, PageResponse};
+use crate::application::usecases::webhook_usecases::{EndpointChanges, WebhookUsecases};
+use crate::domain::entities::webhook::{
+    WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint, WebhookEventType, WebhookOwner,
+};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::DEFAULT_PAGE_SIZE;
+use crate::domain::repositories::webhook_repository::DeliveryQuery;
+use crate::infra::db::organization_repository_sql::OrganizationRepositorySql;
+use crate::infra::db::webhook_repository_sql::WebhookRepositorySql;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+struct CreateEndpointPayload {
+    url: String,
+    events: Vec<String>,
+    description: Option<String>,
+    /// Slug of the organization to register the endpoint for; the caller's own
+    /// projects otherwise.
+    organization: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UpdateEndpointPayload {
+    url: Option<String>,
+    events: Option<Vec<String>>,
+    description: Option<String>,
+    active: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct ListEndpointsParams {
+    organization: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ListDeliveriesParams {
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EndpointResponse {
+    id: Uuid,
+    owner: WebhookOwner,
+    url: String,
+    events: Vec<WebhookEventType>,
+    description: Option<String>,
+    active: bool,
+    /// Only returned when the endpoint is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl EndpointResponse {
+    fn from_entity(endpoint: WebhookEndpoint) -> Self {
+        Self {
+            id: endpoint.id,
+            owner: endpoint.owner,
+            url: endpoint.url,
+            events: endpoint.events,
+            description: endpoint.description,
+            active: endpoint.active,
+            secret: None,
+            created_at: endpoint.created_at,
+            updated_at: endpoint.updated_at,
+        }
+    }
+
+    fn with_secret(endpoint: WebhookEndpoint) -> Self {
+        let secret = endpoint.secret.clone();
+        Self {
+            secret: Some(secret),
+            ..Self::from_entity(endpoint)
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DeliveryResponse {
+    id: Uuid,
+    endpoint_id: Uuid,
+    event_id: Uuid,
+    event_type: WebhookEventType,
+    status: WebhookDeliveryStatus,
+    attempts: i32,
+    next_attempt_at: Option<DateTime<Utc>>,
+    last_attempt_at: Option<DateTime<Utc>>,
+    response_status: Option<i32>,
+    last_error: Option<String>,
+    redelivery_of: Option<Uuid>,
+    payload: serde_json::Value,
+    created_at: DateTime<Utc>,
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl DeliveryResponse {
+    fn from_entity(delivery: WebhookDelivery) -> Self {
+        let payload = serde_json::from_str(&delivery.payload)
+            .unwrap_or(serde_json::Value::String(delivery.payload));
+        Self {
+            next_attempt_at: (delivery.status == WebhookDeliveryStatus::Pending)
+                .then_some(delivery.next_attempt_at),
+            id: delivery.id,
+            endpoint_id: delivery.endpoint_id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            last_attempt_at: delivery.last_attempt_at,
+            response_status: delivery.response_status,
+            last_error: delivery.last_error,
+            redelivery_of: delivery.redelivery_of,
+            payload,
+            created_at: delivery.created_at,
+            delivered_at: delivery.delivered_at,
+        }
+    }
+}
+
+pub fn routes(pool: Pool<Postgres>) -> Router<Pool<Postgres>> {
+    Router::new()
+        .route("/", get(list_endpoints).post(create_endpoint))
+        .route(
+            "/:id",
+            get(get_endpoint)
+                .put(update_endpoint)
+                .delete(delete_endpoint),
+        )
+        .route("/:id/deliveries", get(list_deliveries))
+        .route("/:id/deliveries/:delivery_id/redeliver", post(redeliver))
+        .with_state(pool)
+}
+
+// ------------------------
+// Handlers
+
+async fn create_endpoint(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateEndpointPayload>,
+) -> Result<(StatusCode, Json<EndpointResponse>), DomainError> {
+    let actor = auth.actor();
+    let usecases = webhook_usecases(pool);
+    let owner = usecases
+        .resolve_owner(&actor, payload.organization.as_deref())
+        .await?;
+    let endpoint = usecases
+        .create_endpoint(
+            &actor,
+            owner,
+            payload.url,
+            payload.events,
+            payload.description,
+        )
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(EndpointResponse::with_secret(endpoint)),
+    ))
+}
+
+async fn list_endpoints(
+    State(pool): State<Pool<Postgres>>,
+    auth: AuthUser,
+    Query(params): Query<ListEndpointsParams>,
+) -> Result<Json<Vec<EndpointResponse>>, DomainError> {
+    let actor = auth.actor();
+    let usecases = webhook_usecases(pool);
+    let owner = usecases
+        .resolve_owner(&actor, params.organization.as_deref())
+        .await?;
+    let endpoints = usecases.list_endpoints(&actor, owner).await?;
+    Ok(Json(
+        endpoints
+            .into_iter()
+            .map(EndpointResponse::from_entity)
+            .collect(),
+    ))
+}
+
+async fn get_endpoint(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<EndpointResponse>, DomainError> {
+    let endpoint = webhook_usecases(pool)
+        .get_endpoint(id, &auth.actor())
+        .await?;
+    Ok(Json(EndpointResponse::from_entity(endpoint)))
+}
+
+async fn update_endpoint(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateEndpointPayload>,
+) -> Result<Json<EndpointResponse>, DomainError> {
+    let changes = EndpointChanges {
+        url: payload.url,
+        events: payload.events,
+        description: payload.description,
+        active: payload.active,
+    };
+    let endpoint = webhook_usecases(pool)
+        .update_endpoint(id, &auth.actor(), changes)
+        .await?;
+    Ok(Json(EndpointResponse::from_entity(endpoint)))
+}
+
+async fn delete_endpoint(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, DomainError> {
+    webhook_usecases(pool)
+        .delete_endpoint(id, &auth.actor())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_deliveries(
+    State(pool): State<Pool<Postgres>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Query(params): Query<ListDeliveriesParams>,
+) -> Result<Json<PageResponse<DeliveryResponse>>, DomainError> {
+    let query = DeliveryQuery {
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        cursor: decode_cursor(params.cursor.as_deref())?,
+    };
+    let page = webhook_usecases(pool)
+        .list_deliveries(id, &auth.actor(), query)
+        .await?;
+    Ok(Json(PageResponse::from_page(
+        page,
+        DeliveryResponse::from_entity,
+    )))
+}
+
+async fn redeliver(
+    State(pool): State<Pool<Postgres>>,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+) -> Result<(StatusCode, Json<DeliveryResponse>), DomainError> {
+    let delivery = webhook_usecases(pool)
+        .redeliver(id, delivery_id, &auth.actor())
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(DeliveryResponse::from_entity(delivery)),
+    ))
+}
+
+// ------------------------
+// Aux Functions
+
+fn webhook_usecases(
+    pool: Pool<Postgres>,
+) -> WebhookUsecases<WebhookRepositorySql, OrganizationRepositorySql> {
+    WebhookUsecases::new(
+        WebhookRepositorySql::new(pool.clone()),
+        OrganizationRepositorySql::new(pool),
+    )
+}
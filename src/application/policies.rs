@@ -0,0 +1,243 @@
+//! Authorization rules shared by the use cases. Every check is a pure function of
+//! the acting user, their role in the project involved and the resource, so it can
+//! be exercised without the HTTP layer.
+
+use crate::domain::entities::organization::OrganizationRole;
+use crate::domain::entities::project_member::ProjectRole;
+use crate::domain::entities::user::Role;
+use uuid::Uuid;
+
+/// The authenticated user on whose behalf a use case runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Actor {
+    pub id: Uuid,
+    pub role: Role,
+}
+
+impl Actor {
+    pub fn new(id: Uuid, role: Role) -> Self {
+        Self { id, role }
+    }
+}
+
+pub fn can_edit_user(actor: &Actor, user_id: Uuid) -> bool {
+    actor.id == user_id || actor.role.is_admin()
+}
+
+/// A user's ledger balance is visible to themselves and to admins.
+pub fn can_view_balance(actor: &Actor, user_id: Uuid) -> bool {
+    actor.id == user_id || actor.role.is_admin()
+}
+
+/// Deposits record money received outside the platform, so only admins who
+/// confirmed the payment can make them.
+pub fn can_record_deposit(actor: &Actor) -> bool {
+    actor.role.is_admin()
+}
+
+pub fn can_change_roles(actor: &Actor) -> bool {
+    actor.role.is_admin()
+}
+
+/// Inspecting and retrying background jobs.
+pub fn can_manage_jobs(actor: &Actor) -> bool {
+    actor.role.is_admin()
+}
+
+/// `role` is the actor's role in the project being edited, if they are a member.
+pub fn can_edit_project(actor: &Actor, role: Option<ProjectRole>) -> bool {
+    role.is_some_and(|r| r.is_maintainer()) || actor.role.is_admin()
+}
+
+/// Deleting a project or handing it to someone else.
+pub fn can_delete_project(actor: &Actor, role: Option<ProjectRole>) -> bool {
+    role == Some(ProjectRole::Owner) || actor.role.is_admin()
+}
+
+/// Inviting, removing or changing the role of a member holding `target`. Members
+/// only manage roles below their own; admins manage everyone but the owner.
+pub fn can_manage_member(actor: &Actor, role: Option<ProjectRole>, target: ProjectRole) -> bool {
+    if actor.role.is_admin() {
+        return target != ProjectRole::Owner;
+    }
+    role.is_some_and(|r| r.is_maintainer() && r.outranks(target))
+}
+
+/// Creating, editing, deleting and moving issues of a project through their lifecycle.
+pub fn can_manage_issues(actor: &Actor, role: Option<ProjectRole>) -> bool {
+    can_edit_project(actor, role)
+}
+
+/// Deciding on claims and moderating the project's discussion; open to every member.
+pub fn can_triage_project(actor: &Actor, role: Option<ProjectRole>) -> bool {
+    role.is_some() || actor.role.is_admin()
+}
+
+/// Maintainers do not compete for their own project's bounties.
+pub fn can_claim_issue(role: Option<ProjectRole>) -> bool {
+    !role.is_some_and(|r| r.is_maintainer())
+}
+
+/// Releasing or refunding an issue's escrow; staff can step in after a dispute.
+pub fn can_settle_escrow(actor: &Actor, role: Option<ProjectRole>) -> bool {
+    can_manage_issues(actor, role) || actor.role.is_staff()
+}
+
+pub fn can_resolve_dispute(actor: &Actor) -> bool {
+    actor.role.is_staff()
+}
+
+/// The parties to a dispute are the contributor and the project's maintainers.
+pub fn is_dispute_party(actor: &Actor, contributor_id: Uuid, role: Option<ProjectRole>) -> bool {
+    actor.id == contributor_id || role.is_some_and(|r| r.is_maintainer())
+}
+
+/// Opening a dispute and posting evidence; the dispute log is readable by the same
+/// people plus staff.
+pub fn can_argue_dispute(actor: &Actor, contributor_id: Uuid, role: Option<ProjectRole>) -> bool {
+    actor.id == contributor_id || can_manage_issues(actor, role)
+}
+
+/// Moderators rule, unless they are a party themselves.
+pub fn can_rule_dispute(actor: &Actor, contributor_id: Uuid, role: Option<ProjectRole>) -> bool {
+    can_resolve_dispute(actor) && !is_dispute_party(actor, contributor_id, role)
+}
+
+/// Editing the organization, creating its projects and spending its funds.
+pub fn can_manage_organization(actor: &Actor, role: Option<OrganizationRole>) -> bool {
+    role.is_some_and(|r| r.is_admin()) || actor.role.is_admin()
+}
+
+pub fn can_delete_organization(actor: &Actor, role: Option<OrganizationRole>) -> bool {
+    role == Some(OrganizationRole::Owner) || actor.role.is_admin()
+}
+
+/// Owners manage every member; organization admins only plain members.
+pub fn can_manage_organization_member(
+    actor: &Actor,
+    role: Option<OrganizationRole>,
+    target: OrganizationRole,
+) -> bool {
+    match role {
+        _ if actor.role.is_admin() => true,
+        Some(OrganizationRole::Owner) => true,
+        Some(OrganizationRole::Admin) => target == OrganizationRole::Member,
+        _ => false,
+    }
+}
+
+pub fn can_hide_content(actor: &Actor) -> bool {
+    actor.role.is_staff()
+}
+
+/// Hidden content stays visible to the project's members and to staff.
+pub fn can_view_hidden(actor: Option<&Actor>, role: Option<ProjectRole>) -> bool {
+    role.is_some() || actor.is_some_and(|a| a.role.is_staff())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use OrganizationRole as O;
+    use ProjectRole as P;
+
+    fn user() -> Actor {
+        Actor::new(Uuid::new_v4(), Role::User)
+    }
+
+    fn moderator() -> Actor {
+        Actor::new(Uuid::new_v4(), Role::Moderator)
+    }
+
+    fn admin() -> Actor {
+        Actor::new(Uuid::new_v4(), Role::Admin)
+    }
+
+    #[test]
+    fn manage_member() {
+        let cases = [
+            // Admins manage everyone but the owner, even without a role in the project
+            (admin(), None, P::Owner, false),
+            (admin(), None, P::Maintainer, true),
+            (admin(), None, P::Triager, true),
+            (admin(), Some(P::Maintainer), P::Owner, false),
+            // Members only manage roles below their own
+            (user(), Some(P::Owner), P::Owner, false),
+            (user(), Some(P::Owner), P::Maintainer, true),
+            (user(), Some(P::Owner), P::Triager, true),
+            (user(), Some(P::Maintainer), P::Maintainer, false),
+            (user(), Some(P::Maintainer), P::Triager, true),
+            // Triagers are below maintainer and manage nobody
+            (user(), Some(P::Triager), P::Triager, false),
+            (user(), None, P::Triager, false),
+            (moderator(), None, P::Triager, false),
+        ];
+        for (actor, role, target, expected) in cases {
+            assert_eq!(
+                can_manage_member(&actor, role, target),
+                expected,
+                "{:?} as {:?} managing {:?}",
+                actor.role,
+                role,
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn rule_dispute() {
+        let contributor = Uuid::new_v4();
+        let moderator_contributor = Actor::new(contributor, Role::Moderator);
+        let cases = [
+            (moderator(), None, true),
+            (admin(), None, true),
+            // A triager is no party to the dispute
+            (moderator(), Some(P::Triager), true),
+            // The project's maintainers and the contributor are parties
+            (moderator(), Some(P::Maintainer), false),
+            (admin(), Some(P::Owner), false),
+            (moderator_contributor, None, false),
+            // Only staff rule
+            (user(), None, false),
+            (Actor::new(contributor, Role::User), None, false),
+        ];
+        for (actor, role, expected) in cases {
+            assert_eq!(
+                can_rule_dispute(&actor, contributor, role),
+                expected,
+                "{:?} as {:?}",
+                actor.role,
+                role
+            );
+        }
+    }
+
+    #[test]
+    fn manage_organization_member() {
+        let cases = [
+            (user(), Some(O::Owner), O::Owner, true),
+            (user(), Some(O::Owner), O::Admin, true),
+            (user(), Some(O::Owner), O::Member, true),
+            // Organization admins only manage plain members
+            (user(), Some(O::Admin), O::Owner, false),
+            (user(), Some(O::Admin), O::Admin, false),
+            (user(), Some(O::Admin), O::Member, true),
+            (user(), Some(O::Member), O::Member, false),
+            (user(), None, O::Member, false),
+            (moderator(), None, O::Member, false),
+            // Platform admins manage everyone
+            (admin(), None, O::Owner, true),
+            (admin(), Some(O::Member), O::Admin, true),
+        ];
+        for (actor, role, target, expected) in cases {
+            assert_eq!(
+                can_manage_organization_member(&actor, role, target),
+                expected,
+                "{:?} as {:?} managing {:?}",
+                actor.role,
+                role,
+                target
+            );
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use crate::application::policies::{self, Actor};
+use crate::application::usecases::project_usecases::member_role;
+use crate::domain::entities::issue::{ClaimMode, Issue, IssueStatus};
+use crate::domain::entities::issue_claim::{ClaimStatus, IssueClaim};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::DomainEvent;
+use crate::domain::repositories::issue_claim_repository::IssueClaimRepository;
+use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+const MAX_MESSAGE_LENGTH: usize = 2000;
+
+pub struct ClaimUsecases<C: IssueClaimRepository, R: IssueRepository, P: ProjectRepository> {
+    repository: C,
+    issue_repository: R,
+    project_repository: P,
+    inactivity_ttl: Duration,
+}
+
+impl<C: IssueClaimRepository, R: IssueRepository, P: ProjectRepository> ClaimUsecases<C, R, P> {
+    /// Live claims idle for longer than `inactivity_ttl` expire.
+    pub fn new(
+        repository: C,
+        issue_repository: R,
+        project_repository: P,
+        inactivity_ttl: Duration,
+    ) -> Self {
+        Self {
+            repository,
+            issue_repository,
+            project_repository,
+            inactivity_ttl,
+        }
+    }
+
+    async fn get_issue(&self, issue_id: Uuid) -> Result<Issue, DomainError> {
+        self.issue_repository
+            .get_issue_by_id(issue_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Issue not found".to_string()))
+    }
+
+    async fn get_claim(&self, issue_id: Uuid, claim_id: Uuid) -> Result<IssueClaim, DomainError> {
+        self.repository
+            .get_claim_by_id(claim_id)
+            .await?
+            .filter(|claim| claim.issue_id == issue_id)
+            .ok_or_else(|| DomainError::NotFound("Claim not found".to_string()))
+    }
+
+    /// Loads the claim's issue and checks that `actor` may decide on its claims.
+    async fn get_managed_issue(
+        &self,
+        claim: &IssueClaim,
+        actor: &Actor,
+    ) -> Result<Issue, DomainError> {
+        let issue = self.get_issue(claim.issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        if !policies::can_triage_project(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only project members or an admin can decide on claims".to_string(),
+            ));
+        }
+        Ok(issue)
+    }
+
+    /// Run periodically by the `expire_stale_claims` job rather than on requests.
+    pub async fn expire_stale_claims(&self) -> Result<u64, DomainError> {
+        self.repository
+            .expire_stale_claims(Utc::now() - self.inactivity_ttl)
+            .await
+    }
+
+    /// Applies to work on an open issue.
+    pub async fn create_claim(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+        message: String,
+        eta: Option<DateTime<Utc>>,
+    ) -> Result<IssueClaim, DomainError> {
+        validate_claim(&message, eta)?;
+
+        let issue = self.get_issue(issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        if issue.hidden && !policies::can_view_hidden(Some(actor), role) {
+            return Err(DomainError::NotFound("Issue not found".to_string()));
+        }
+        if !policies::can_claim_issue(role) {
+            return Err(DomainError::Forbidden(
+                "Project maintainers cannot claim its issues".to_string(),
+            ));
+        }
+        if issue.status != IssueStatus::Open {
+            return Err(DomainError::Conflict(format!(
+                "Cannot claim an issue that is {}",
+                issue.status
+            )));
+        }
+        if issue.claim_mode == ClaimMode::Exclusive && issue.assignee_id.is_some() {
+            return Err(DomainError::Conflict(
+                "Issue is already assigned".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let claim = IssueClaim {
+            id: Uuid::new_v4(),
+            issue_id,
+            claimant_id: actor.id,
+            message,
+            eta,
+            status: ClaimStatus::Pending,
+            created_at: now,
+            updated_at: None,
+            last_activity_at: now,
+        };
+        let events = [DomainEvent::ClaimCreated {
+            project_id: issue.project_id,
+            claim: claim.clone(),
+        }];
+        self.repository.create_claim(&claim, &events).await?;
+        Ok(claim)
+    }
+
+    /// Lets the claimant revise their claim; also counts as activity.
+    pub async fn update_claim(
+        &self,
+        issue_id: Uuid,
+        claim_id: Uuid,
+        actor: &Actor,
+        new_message: Option<String>,
+        new_eta: Option<DateTime<Utc>>,
+    ) -> Result<IssueClaim, DomainError> {
+        let mut claim = self.get_claim(issue_id, claim_id).await?;
+        if claim.claimant_id != actor.id {
+            return Err(DomainError::Forbidden(
+                "Only the claimant can edit a claim".to_string(),
+            ));
+        }
+        if !claim.status.is_active() {
+            return Err(DomainError::Conflict("Claim is no longer open".to_string()));
+        }
+
+        if let Some(message) = new_message {
+            claim.message = message;
+        }
+        if new_eta.is_some() {
+            claim.eta = new_eta;
+        }
+        validate_claim(&claim.message, new_eta)?;
+
+        let now = Utc::now();
+        claim.updated_at = Some(now);
+        claim.last_activity_at = now;
+        self.repository.update_claim(&claim).await?;
+        Ok(claim)
+    }
+
+    /// In exclusive mode this assigns the issue and rejects the other pending claims.
+    pub async fn accept_claim(
+        &self,
+        issue_id: Uuid,
+        claim_id: Uuid,
+        actor: &Actor,
+    ) -> Result<IssueClaim, DomainError> {
+        let mut claim = self.get_claim(issue_id, claim_id).await?;
+        let issue = self.get_managed_issue(&claim, actor).await?;
+        if claim.status != ClaimStatus::Pending {
+            return Err(DomainError::Conflict(
+                "Claim is no longer pending".to_string(),
+            ));
+        }
+        if !matches!(issue.status, IssueStatus::Open | IssueStatus::InReview) {
+            return Err(DomainError::Conflict(format!(
+                "Cannot accept claims on an issue that is {}",
+                issue.status
+            )));
+        }
+
+        let now = Utc::now();
+        claim.status = ClaimStatus::Accepted;
+        claim.updated_at = Some(now);
+        claim.last_activity_at = now;
+        let events = [DomainEvent::ClaimAccepted {
+            project_id: issue.project_id,
+            claim: claim.clone(),
+        }];
+        self.repository
+            .accept_claim(
+                &claim,
+                issue.claim_mode == ClaimMode::Exclusive,
+                now,
+                &events,
+            )
+            .await?;
+        Ok(claim)
+    }
+
+    /// Declines a pending claim, or revokes an accepted one (unassigning the claimant).
+    pub async fn reject_claim(
+        &self,
+        issue_id: Uuid,
+        claim_id: Uuid,
+        actor: &Actor,
+    ) -> Result<IssueClaim, DomainError> {
+        let claim = self.get_claim(issue_id, claim_id).await?;
+        let issue = self.get_managed_issue(&claim, actor).await?;
+        self.close_claim(claim, ClaimStatus::Rejected, issue.project_id)
+            .await
+    }
+
+    pub async fn withdraw_claim(
+        &self,
+        issue_id: Uuid,
+        claim_id: Uuid,
+        actor: &Actor,
+    ) -> Result<IssueClaim, DomainError> {
+        let claim = self.get_claim(issue_id, claim_id).await?;
+        if claim.claimant_id != actor.id {
+            return Err(DomainError::Forbidden(
+                "Only the claimant can withdraw a claim".to_string(),
+            ));
+        }
+        let issue = self.get_issue(claim.issue_id).await?;
+        self.close_claim(claim, ClaimStatus::Withdrawn, issue.project_id)
+            .await
+    }
+
+    /// Rejections are announced to the claimant; withdrawals need no event.
+    async fn close_claim(
+        &self,
+        mut claim: IssueClaim,
+        status: ClaimStatus,
+        project_id: Uuid,
+    ) -> Result<IssueClaim, DomainError> {
+        let now = Utc::now();
+        claim.status = status;
+        claim.updated_at = Some(now);
+        let events: Vec<DomainEvent> = (status == ClaimStatus::Rejected)
+            .then(|| DomainEvent::ClaimRejected {
+                project_id,
+                claim: claim.clone(),
+            })
+            .into_iter()
+            .collect();
+        if !self
+            .repository
+            .close_claim(claim.id, status, now, &events)
+            .await?
+        {
+            return Err(DomainError::Conflict("Claim is no longer open".to_string()));
+        }
+        Ok(claim)
+    }
+
+    /// Claims on hidden issues are only listed for project members and staff.
+    pub async fn get_issue_claims(
+        &self,
+        issue_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<Vec<IssueClaim>, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        if issue.hidden {
+            let role = member_role(&self.project_repository, issue.project_id, viewer).await?;
+            if !policies::can_view_hidden(viewer, role) {
+                return Err(DomainError::NotFound("Issue not found".to_string()));
+            }
+        }
+        self.repository.get_claims_by_issue(issue_id).await
+    }
+}
+
+fn validate_claim(message: &str, eta: Option<DateTime<Utc>>) -> Result<(), DomainError> {
+    let mut errors = Vec::new();
+    if message.trim().is_empty() {
+        errors.push(FieldError::new("message", "cannot be empty"));
+    } else if message.chars().count() > MAX_MESSAGE_LENGTH {
+        errors.push(FieldError::new(
+            "message",
+            &format!("must be at most {} characters", MAX_MESSAGE_LENGTH),
+        ));
+    }
+    if eta.is_some_and(|eta| eta <= Utc::now()) {
+        errors.push(FieldError::new("eta", "must be in the future"));
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(DomainError::Validation(errors))
+    }
+}
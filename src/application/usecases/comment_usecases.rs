@@ -0,0 +1,281 @@
+use crate::application::policies::{self, Actor};
+use crate::application::usecases::project_usecases::member_role;
+use crate::domain::entities::comment::{extract_mentions, Comment, CommentEdit, CommentTarget};
+use crate::domain::entities::project_member::ProjectRole;
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::DomainEvent;
+use crate::domain::pagination::{validate_page_size, Page};
+use crate::domain::repositories::comment_repository::{CommentQuery, CommentRepository};
+use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use chrono::Utc;
+use uuid::Uuid;
+
+const MAX_BODY_LENGTH: usize = 10_000;
+const MAX_MENTIONS: usize = 50;
+
+/// A top-level comment with all its replies, oldest first.
+pub struct CommentThread {
+    pub comment: Comment,
+    pub replies: Vec<Comment>,
+}
+
+pub struct CommentUsecases<
+    C: CommentRepository,
+    U: UserRepository,
+    R: IssueRepository,
+    P: ProjectRepository,
+> {
+    repository: C,
+    user_repository: U,
+    issue_repository: R,
+    project_repository: P,
+}
+
+impl<C: CommentRepository, U: UserRepository, R: IssueRepository, P: ProjectRepository>
+    CommentUsecases<C, U, R, P>
+{
+    pub fn new(
+        repository: C,
+        user_repository: U,
+        issue_repository: R,
+        project_repository: P,
+    ) -> Self {
+        Self {
+            repository,
+            user_repository,
+            issue_repository,
+            project_repository,
+        }
+    }
+
+    /// Checks that `viewer` can see `target` and returns its project's id and their
+    /// role in it.
+    async fn get_visible_target(
+        &self,
+        target: CommentTarget,
+        viewer: Option<&Actor>,
+    ) -> Result<(Uuid, Option<ProjectRole>), DomainError> {
+        let (project_id, hidden, what) = match target {
+            CommentTarget::Project(id) => (id, false, "Project"),
+            CommentTarget::Issue(id) => {
+                let issue = self
+                    .issue_repository
+                    .get_issue_by_id(id)
+                    .await?
+                    .ok_or_else(|| DomainError::NotFound("Issue not found".to_string()))?;
+                (issue.project_id, issue.hidden, "Issue")
+            }
+        };
+        let project = self
+            .project_repository
+            .get_project_by_id(project_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("{} not found", what)))?;
+        let role = member_role(&self.project_repository, project.id, viewer).await?;
+        if (hidden || project.hidden) && !policies::can_view_hidden(viewer, role) {
+            return Err(DomainError::NotFound(format!("{} not found", what)));
+        }
+        Ok((project.id, role))
+    }
+
+    /// Loads a comment whose target `viewer` can see, along with their role in the
+    /// target's project.
+    async fn get_visible_comment(
+        &self,
+        comment_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<(Comment, Option<ProjectRole>), DomainError> {
+        let comment = self
+            .repository
+            .get_comment_by_id(comment_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Comment not found".to_string()))?;
+        let (_, role) = self
+            .get_visible_target(comment.target, viewer)
+            .await
+            .map_err(|_| DomainError::NotFound("Comment not found".to_string()))?;
+        Ok((comment, role))
+    }
+
+    /// Ids of the existing users @mentioned in `body`; unknown names are ignored.
+    async fn resolve_mentions(&self, body: &str) -> Result<Vec<Uuid>, DomainError> {
+        let mut usernames = extract_mentions(body);
+        usernames.truncate(MAX_MENTIONS);
+        if usernames.is_empty() {
+            return Ok(Vec::new());
+        }
+        let users = self
+            .user_repository
+            .get_users_by_usernames(&usernames)
+            .await?;
+        Ok(users.into_iter().map(|u| u.id).collect())
+    }
+
+    /// Top-level comments of the target, a page at a time, each with its replies.
+    pub async fn list_comments(
+        &self,
+        target: CommentTarget,
+        viewer: Option<&Actor>,
+        query: CommentQuery,
+    ) -> Result<Page<CommentThread>, DomainError> {
+        validate_page_size(query.limit)?;
+        self.get_visible_target(target, viewer).await?;
+
+        let page = self.repository.list_threads(target, &query).await?;
+        let ids: Vec<Uuid> = page.items.iter().map(|c| c.id).collect();
+        let replies = if ids.is_empty() {
+            Vec::new()
+        } else {
+            self.repository.get_replies(&ids).await?
+        };
+        Ok(page.map(|comment| CommentThread {
+            replies: replies
+                .iter()
+                .filter(|r| r.parent_id == Some(comment.id))
+                .cloned()
+                .collect(),
+            comment,
+        }))
+    }
+
+    /// Posts a comment, or a reply when `parent_id` names a top-level comment of
+    /// the same target.
+    pub async fn create_comment(
+        &self,
+        target: CommentTarget,
+        actor: &Actor,
+        body: String,
+        parent_id: Option<Uuid>,
+    ) -> Result<Comment, DomainError> {
+        validate_body(&body)?;
+        let (project_id, _) = self.get_visible_target(target, Some(actor)).await?;
+
+        let mut parent_author_id = None;
+        if let Some(parent_id) = parent_id {
+            let parent = self
+                .repository
+                .get_comment_by_id(parent_id)
+                .await?
+                .filter(|p| p.target == target)
+                .ok_or_else(|| {
+                    DomainError::Validation(vec![FieldError::new(
+                        "parent_id",
+                        "must be a comment on the same project or issue",
+                    )])
+                })?;
+            if parent.parent_id.is_some() {
+                return Err(DomainError::Validation(vec![FieldError::new(
+                    "parent_id",
+                    "replies cannot be nested; reply to the top-level comment",
+                )]));
+            }
+            if parent.is_deleted() {
+                return Err(DomainError::Conflict(
+                    "Cannot reply to a deleted comment".to_string(),
+                ));
+            }
+            parent_author_id = parent.author_id;
+        }
+
+        let comment = Comment {
+            id: Uuid::new_v4(),
+            target,
+            parent_id,
+            author_id: Some(actor.id),
+            mentions: self.resolve_mentions(&body).await?,
+            body,
+            created_at: Utc::now(),
+            updated_at: None,
+            deleted_at: None,
+        };
+        let events = [DomainEvent::CommentCreated {
+            project_id,
+            comment: comment.clone(),
+            parent_author_id,
+        }];
+        self.repository.create_comment(&comment, &events).await?;
+        Ok(comment)
+    }
+
+    /// Replaces the body; the previous one is kept in the edit history.
+    pub async fn edit_comment(
+        &self,
+        comment_id: Uuid,
+        actor: &Actor,
+        body: String,
+    ) -> Result<Comment, DomainError> {
+        validate_body(&body)?;
+        let (mut comment, _) = self.get_visible_comment(comment_id, Some(actor)).await?;
+        if comment.author_id != Some(actor.id) {
+            return Err(DomainError::Forbidden(
+                "Only the author can edit a comment".to_string(),
+            ));
+        }
+        if comment.is_deleted() {
+            return Err(DomainError::Conflict("Comment was deleted".to_string()));
+        }
+        if comment.body == body {
+            return Ok(comment);
+        }
+
+        let now = Utc::now();
+        let edit = CommentEdit {
+            id: Uuid::new_v4(),
+            comment_id,
+            previous_body: std::mem::replace(&mut comment.body, body),
+            edited_at: now,
+        };
+        comment.mentions = self.resolve_mentions(&comment.body).await?;
+        comment.updated_at = Some(now);
+        self.repository.update_comment(&comment, &edit).await?;
+        Ok(comment)
+    }
+
+    /// Soft-deletes a comment: its replies stay, and it is shown as deleted. Allowed
+    /// for the author, project members and staff.
+    pub async fn delete_comment(&self, comment_id: Uuid, actor: &Actor) -> Result<(), DomainError> {
+        let (mut comment, role) = self.get_visible_comment(comment_id, Some(actor)).await?;
+        let allowed = comment.author_id == Some(actor.id)
+            || policies::can_triage_project(actor, role)
+            || policies::can_hide_content(actor);
+        if !allowed {
+            return Err(DomainError::Forbidden(
+                "Only the author, a project member or a moderator can delete a comment".to_string(),
+            ));
+        }
+        if comment.is_deleted() {
+            return Ok(());
+        }
+        comment.deleted_at = Some(Utc::now());
+        self.repository.soft_delete_comment(&comment).await
+    }
+
+    /// Previous bodies of a comment, oldest first. Once deleted, only staff can
+    /// still read them.
+    pub async fn get_comment_edits(
+        &self,
+        comment_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<Vec<CommentEdit>, DomainError> {
+        let (comment, _) = self.get_visible_comment(comment_id, viewer).await?;
+        if comment.is_deleted() && !viewer.is_some_and(policies::can_hide_content) {
+            return Err(DomainError::NotFound("Comment not found".to_string()));
+        }
+        self.repository.get_edits(comment_id).await
+    }
+}
+
+fn validate_body(body: &str) -> Result<(), DomainError> {
+    let message = if body.trim().is_empty() {
+        "cannot be empty".to_string()
+    } else if body.chars().count() > MAX_BODY_LENGTH {
+        format!("must be at most {} characters", MAX_BODY_LENGTH)
+    } else {
+        return Ok(());
+    };
+    Err(DomainError::Validation(vec![FieldError::new(
+        "body", &message,
+    )]))
+}
@@ -0,0 +1,398 @@
+use crate::application::policies::{self, Actor};
+use crate::application::usecases::ledger_usecases::LedgerUsecases;
+use crate::application::usecases::project_usecases::member_role;
+use crate::domain::entities::dispute::{
+    Dispute, DisputeEvent, DisputeEventKind, DisputeRuling, DisputeStatus,
+};
+use crate::domain::entities::issue::{Issue, IssueStatus};
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::{EntryKind, JournalEntry};
+use crate::domain::entities::project_member::ProjectRole;
+use crate::domain::entities::submission::SubmissionStatus;
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::DomainEvent;
+use crate::domain::repositories::dispute_repository::DisputeRepository;
+use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::repositories::ledger_repository::LedgerRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::repositories::submission_repository::SubmissionRepository;
+use crate::domain::value_objects::money::Money;
+use chrono::Utc;
+use uuid::Uuid;
+
+const MAX_TEXT_LENGTH: usize = 5000;
+const MAX_URL_LENGTH: usize = 500;
+
+/// A dispute with its log, oldest event first.
+pub struct DisputeRecord {
+    pub dispute: Dispute,
+    pub events: Vec<DisputeEvent>,
+}
+
+/// Result of a ruling: the resolved dispute and the ledger entries that settled
+/// the escrow (none when it was empty).
+pub struct DisputeResolution {
+    pub dispute: Dispute,
+    pub entries: Vec<JournalEntry>,
+}
+
+pub struct DisputeUsecases<
+    D: DisputeRepository,
+    S: SubmissionRepository,
+    R: IssueRepository,
+    P: ProjectRepository,
+    L: LedgerRepository,
+> {
+    repository: D,
+    submission_repository: S,
+    issue_repository: R,
+    project_repository: P,
+    ledger: LedgerUsecases<L, R, P>,
+}
+
+impl<
+        D: DisputeRepository,
+        S: SubmissionRepository,
+        R: IssueRepository,
+        P: ProjectRepository,
+        L: LedgerRepository,
+    > DisputeUsecases<D, S, R, P, L>
+{
+    /// `ledger` builds the entries that settle the escrow on a ruling.
+    pub fn new(
+        repository: D,
+        submission_repository: S,
+        issue_repository: R,
+        project_repository: P,
+        ledger: LedgerUsecases<L, R, P>,
+    ) -> Self {
+        Self {
+            repository,
+            submission_repository,
+            issue_repository,
+            project_repository,
+            ledger,
+        }
+    }
+
+    async fn get_issue(&self, issue_id: Uuid) -> Result<Issue, DomainError> {
+        self.issue_repository
+            .get_issue_by_id(issue_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Issue not found".to_string()))
+    }
+
+    /// Loads a dispute with its issue and the role `actor` holds in the issue's
+    /// project, provided `actor` can see it.
+    async fn get_visible_dispute(
+        &self,
+        dispute_id: Uuid,
+        actor: &Actor,
+    ) -> Result<(Dispute, Issue, Option<ProjectRole>), DomainError> {
+        let dispute = self
+            .repository
+            .get_dispute_by_id(dispute_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Dispute not found".to_string()))?;
+        let issue = self.get_issue(dispute.issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        if !can_view_dispute(actor, &dispute, role) {
+            return Err(DomainError::NotFound("Dispute not found".to_string()));
+        }
+        Ok((dispute, issue, role))
+    }
+
+    /// Either party disputes a submission under review; the issue becomes `Disputed`.
+    pub async fn open_dispute(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+        submission_id: Uuid,
+        reason: String,
+    ) -> Result<DisputeRecord, DomainError> {
+        let reason = reason.trim().to_string();
+        validate_text("reason", &reason)?;
+
+        let issue = self.get_issue(issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        let submission = self
+            .submission_repository
+            .get_submission_by_id(submission_id)
+            .await?
+            .filter(|s| s.issue_id == issue_id)
+            .ok_or_else(|| DomainError::NotFound("Submission not found".to_string()))?;
+        if !policies::can_argue_dispute(actor, submission.submitter_id, role) {
+            return Err(DomainError::Forbidden(
+                "Only the submitter or project maintainers can open a dispute".to_string(),
+            ));
+        }
+        if submission.status != SubmissionStatus::Pending || issue.status != IssueStatus::InReview {
+            return Err(DomainError::Conflict(
+                "Only work that is under review can be disputed".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let dispute = Dispute {
+            id: Uuid::new_v4(),
+            issue_id,
+            submission_id,
+            contributor_id: submission.submitter_id,
+            opened_by: Some(actor.id),
+            reason: reason.clone(),
+            status: DisputeStatus::Open,
+            ruling: None,
+            contributor_amount: None,
+            resolved_by: None,
+            created_at: now,
+            resolved_at: None,
+        };
+        let event = DisputeEvent {
+            id: Uuid::new_v4(),
+            dispute_id: dispute.id,
+            actor_id: Some(actor.id),
+            kind: DisputeEventKind::Opened,
+            body: reason,
+            url: None,
+            created_at: now,
+        };
+        let transition = IssueTransition {
+            id: Uuid::new_v4(),
+            issue_id,
+            from_status: IssueStatus::InReview,
+            to_status: IssueStatus::Disputed,
+            actor_id: Some(actor.id),
+            note: Some(format!("Dispute {} opened", dispute.id)),
+            created_at: now,
+        };
+        let events = [DomainEvent::issue_status_changed(
+            issue.project_id,
+            &transition,
+        )];
+        self.repository
+            .open_dispute(&dispute, &event, &transition, &events)
+            .await?;
+        Ok(DisputeRecord {
+            dispute,
+            events: vec![event],
+        })
+    }
+
+    /// Adds a statement, optionally with a link, to the log of an open dispute.
+    pub async fn add_evidence(
+        &self,
+        dispute_id: Uuid,
+        actor: &Actor,
+        body: String,
+        url: Option<String>,
+    ) -> Result<DisputeEvent, DomainError> {
+        let body = body.trim().to_string();
+        let url = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+        validate_text("body", &body)?;
+        if let Some(url) = &url {
+            validate_url(url)?;
+        }
+
+        let (dispute, _, _) = self.get_visible_dispute(dispute_id, actor).await?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(DomainError::Conflict(
+                "Dispute is already resolved".to_string(),
+            ));
+        }
+
+        let event = DisputeEvent {
+            id: Uuid::new_v4(),
+            dispute_id,
+            actor_id: Some(actor.id),
+            kind: DisputeEventKind::Evidence,
+            body,
+            url,
+            created_at: Utc::now(),
+        };
+        self.repository.add_event(&event).await?;
+        Ok(event)
+    }
+
+    /// A moderator settles the dispute. The escrow is paid to the contributor
+    /// (`FullPayout`), split between the contributor and the funders (`Split`, with
+    /// `contributor_amount` in the bounty currency) or refunded (`Refund`), and the
+    /// issue moves to `Approved` or `Cancelled`, all in one transaction.
+    pub async fn rule(
+        &self,
+        dispute_id: Uuid,
+        actor: &Actor,
+        ruling: DisputeRuling,
+        contributor_amount: Option<String>,
+        note: String,
+    ) -> Result<DisputeResolution, DomainError> {
+        let note = note.trim().to_string();
+        validate_text("note", &note)?;
+
+        let (mut dispute, issue, role) = self.get_visible_dispute(dispute_id, actor).await?;
+        if !policies::can_rule_dispute(actor, dispute.contributor_id, role) {
+            return Err(DomainError::Forbidden(
+                "Only a moderator who is not a party can rule on a dispute".to_string(),
+            ));
+        }
+        if dispute.status != DisputeStatus::Open || issue.status != IssueStatus::Disputed {
+            return Err(DomainError::Conflict(
+                "Dispute is already resolved".to_string(),
+            ));
+        }
+
+        let escrow = self.ledger.get_escrow_balance(&issue).await?;
+        let awarded = match ruling {
+            DisputeRuling::FullPayout => escrow.amount_minor,
+            DisputeRuling::Refund => 0,
+            DisputeRuling::Split => parse_split(contributor_amount, &escrow)?,
+        };
+
+        let mut entries = Vec::new();
+        if awarded > 0 {
+            entries.push(self.ledger.release_entry(
+                &issue,
+                Some(actor.id),
+                dispute.contributor_id,
+                awarded,
+            )?);
+        }
+        if escrow.amount_minor > awarded {
+            entries.push(
+                self.ledger
+                    .refund_entry(&issue, Some(actor.id), escrow.amount_minor - awarded)
+                    .await?,
+            );
+        }
+
+        let (to_status, submission_status) = match ruling {
+            DisputeRuling::Refund => (IssueStatus::Cancelled, SubmissionStatus::Rejected),
+            _ => (IssueStatus::Approved, SubmissionStatus::Approved),
+        };
+        let now = Utc::now();
+        dispute.status = DisputeStatus::Resolved;
+        dispute.ruling = Some(ruling);
+        dispute.contributor_amount = Some(awarded);
+        dispute.resolved_by = Some(actor.id);
+        dispute.resolved_at = Some(now);
+        let event = DisputeEvent {
+            id: Uuid::new_v4(),
+            dispute_id,
+            actor_id: Some(actor.id),
+            kind: DisputeEventKind::Ruling,
+            body: format!(
+                "{}: {} to the contributor. {}",
+                ruling.as_str(),
+                Money::new(awarded, escrow.currency),
+                note
+            ),
+            url: None,
+            created_at: now,
+        };
+        let transition = IssueTransition {
+            id: Uuid::new_v4(),
+            issue_id: issue.id,
+            from_status: IssueStatus::Disputed,
+            to_status,
+            actor_id: Some(actor.id),
+            note: Some(format!(
+                "Dispute {} resolved: {}",
+                dispute.id,
+                ruling.as_str()
+            )),
+            created_at: now,
+        };
+        let project_id = issue.project_id;
+        let mut events = vec![DomainEvent::issue_status_changed(project_id, &transition)];
+        events.extend(entries.iter().filter_map(|entry| {
+            let entry = entry.clone();
+            match entry.kind {
+                EntryKind::Release => Some(DomainEvent::BountyPaid { project_id, entry }),
+                EntryKind::Refund => Some(DomainEvent::BountyRefunded { project_id, entry }),
+                EntryKind::Fund | EntryKind::Deposit => None,
+            }
+        }));
+        self.repository
+            .resolve_dispute(
+                &dispute,
+                &event,
+                &transition,
+                submission_status,
+                &entries,
+                &events,
+            )
+            .await?;
+        Ok(DisputeResolution { dispute, entries })
+    }
+
+    pub async fn get_dispute(
+        &self,
+        dispute_id: Uuid,
+        actor: &Actor,
+    ) -> Result<DisputeRecord, DomainError> {
+        let (dispute, _, _) = self.get_visible_dispute(dispute_id, actor).await?;
+        let events = self.repository.get_events(dispute_id).await?;
+        Ok(DisputeRecord { dispute, events })
+    }
+
+    /// Disputes of the issue that `actor` is allowed to see, newest first.
+    pub async fn get_issue_disputes(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+    ) -> Result<Vec<Dispute>, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        let disputes = self.repository.get_disputes_by_issue(issue_id).await?;
+        Ok(disputes
+            .into_iter()
+            .filter(|d| can_view_dispute(actor, d, role))
+            .collect())
+    }
+}
+
+fn can_view_dispute(actor: &Actor, dispute: &Dispute, role: Option<ProjectRole>) -> bool {
+    policies::can_argue_dispute(actor, dispute.contributor_id, role)
+        || policies::can_resolve_dispute(actor)
+}
+
+/// The contributor's share of a split must leave something on both sides.
+fn parse_split(amount: Option<String>, escrow: &Money) -> Result<i64, DomainError> {
+    let invalid = |message: &str| {
+        DomainError::Validation(vec![FieldError::new("contributor_amount", message)])
+    };
+    let amount = amount.ok_or_else(|| invalid("is required for a split"))?;
+    let amount = Money::parse(&amount, escrow.currency).map_err(|message| invalid(&message))?;
+    if !amount.is_positive() || amount.amount_minor >= escrow.amount_minor {
+        return Err(invalid(&format!(
+            "must be more than zero and less than the escrow ({})",
+            escrow
+        )));
+    }
+    Ok(amount.amount_minor)
+}
+
+fn validate_text(field: &str, value: &str) -> Result<(), DomainError> {
+    let message = if value.is_empty() {
+        "cannot be empty".to_string()
+    } else if value.chars().count() > MAX_TEXT_LENGTH {
+        format!("must be at most {} characters", MAX_TEXT_LENGTH)
+    } else {
+        return Ok(());
+    };
+    Err(DomainError::Validation(vec![FieldError::new(
+        field, &message,
+    )]))
+}
+
+fn validate_url(url: &str) -> Result<(), DomainError> {
+    let valid = url.len() <= MAX_URL_LENGTH
+        && url::Url::parse(url).is_ok_and(|u| matches!(u.scheme(), "http" | "https"));
+    if valid {
+        Ok(())
+    } else {
+        Err(DomainError::Validation(vec![FieldError::new(
+            "url",
+            "must be an http(s) link",
+        )]))
+    }
+}
@@ -0,0 +1,36 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::{DomainEvent, EventSubscriber, OutboxEvent};
+use crate::domain::gateways::mailer::{EmailMessage, SharedEmailQueue};
+use async_trait::async_trait;
+
+/// Account emails sent in reaction to domain events. Emails about notifications
+/// are queued by the notification center instead, as they follow the user's
+/// channel preferences.
+pub struct EmailUsecases {
+    email_queue: SharedEmailQueue,
+}
+
+impl EmailUsecases {
+    pub fn new(email_queue: SharedEmailQueue) -> Self {
+        Self { email_queue }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for EmailUsecases {
+    fn name(&self) -> &'static str {
+        "emails"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), DomainError> {
+        match &event.event {
+            DomainEvent::UserRegistered { user_id, .. } => {
+                let dedup_key = format!("welcome:{}", event.id);
+                self.email_queue
+                    .queue_email(*user_id, EmailMessage::Welcome, &dedup_key)
+                    .await
+            }
+            _ => Ok(()),
+        }
+    }
+}
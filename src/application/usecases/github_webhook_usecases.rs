@@ -0,0 +1,246 @@
+use crate::application::usecases::issue_usecases::IssueUsecases;
+use crate::domain::entities::issue::IssueStatus;
+use crate::domain::entities::issue_pull_request::{IssuePullRequest, PullRequestState};
+use crate::domain::entities::submission::SubmissionStatus;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::repository_host::{
+    RepositoryEvent, RepositoryRef, SharedRepositoryHost, UpstreamIssue, UpstreamPullRequest,
+};
+use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::repositories::ledger_repository::LedgerRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::repositories::submission_repository::SubmissionRepository;
+use crate::domain::repositories::webhook_delivery_repository::WebhookDeliveryRepository;
+use uuid::Uuid;
+
+const MAX_DELIVERY_ID_LENGTH: usize = 100;
+
+/// What happened to a webhook delivery.
+#[derive(Debug, Clone)]
+pub enum WebhookOutcome {
+    /// The delivery was handled; `issue_ids` are the issues it touched.
+    Processed { issue_ids: Vec<Uuid> },
+    /// An event or action rust4u does not act on.
+    Ignored,
+    /// The delivery was handled before.
+    Duplicate,
+}
+
+/// Applies the code host's webhooks to issues: imported issues follow their
+/// upstream issue, and issues move into review when a pull request working on
+/// them is opened or merged.
+pub struct GithubWebhookUsecases<
+    D: WebhookDeliveryRepository,
+    S: SubmissionRepository,
+    R: IssueRepository,
+    P: ProjectRepository,
+    L: LedgerRepository,
+> {
+    deliveries: D,
+    submission_repository: S,
+    issue_repository: R,
+    issues: IssueUsecases<R, P, L>,
+    repository_host: SharedRepositoryHost,
+}
+
+impl<
+        D: WebhookDeliveryRepository,
+        S: SubmissionRepository,
+        R: IssueRepository,
+        P: ProjectRepository,
+        L: LedgerRepository,
+    > GithubWebhookUsecases<D, S, R, P, L>
+{
+    /// `issues` applies the status transitions.
+    pub fn new(
+        deliveries: D,
+        submission_repository: S,
+        issue_repository: R,
+        issues: IssueUsecases<R, P, L>,
+        repository_host: SharedRepositoryHost,
+    ) -> Self {
+        Self {
+            deliveries,
+            submission_repository,
+            issue_repository,
+            issues,
+            repository_host,
+        }
+    }
+
+    /// Verifies and handles one delivery. Each `delivery_id` is handled once; if
+    /// handling fails the delivery is forgotten, so the host's redelivery retries it.
+    pub async fn handle_delivery(
+        &self,
+        delivery_id: &str,
+        event: &str,
+        signature: Option<&str>,
+        payload: &[u8],
+    ) -> Result<WebhookOutcome, DomainError> {
+        self.repository_host.verify_webhook(payload, signature)?;
+        if delivery_id.is_empty() || delivery_id.len() > MAX_DELIVERY_ID_LENGTH {
+            return Err(DomainError::InvalidData(
+                "Missing or invalid delivery id".to_string(),
+            ));
+        }
+        let Some(repository_event) = self.repository_host.parse_webhook(event, payload)? else {
+            return Ok(WebhookOutcome::Ignored);
+        };
+        if !self.deliveries.record_delivery(delivery_id, event).await? {
+            return Ok(WebhookOutcome::Duplicate);
+        }
+
+        let result = match repository_event {
+            RepositoryEvent::Issue { repository, issue } => {
+                self.apply_issue_event(&repository, issue).await
+            }
+            RepositoryEvent::PullRequest {
+                repository,
+                pull_request,
+            } => {
+                self.apply_pull_request_event(&repository, pull_request)
+                    .await
+            }
+        };
+        match result {
+            Ok(issue_ids) => Ok(WebhookOutcome::Processed { issue_ids }),
+            Err(e) => {
+                self.deliveries.forget_delivery(delivery_id).await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn apply_issue_event(
+        &self,
+        repository: &RepositoryRef,
+        upstream: UpstreamIssue,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let links = self
+            .issue_repository
+            .get_upstream_links_by_number(&repository.owner, &repository.name, upstream.number)
+            .await?;
+        let mut issue_ids = Vec::new();
+        for link in links {
+            issue_ids.push(link.issue_id);
+            self.issues
+                .apply_upstream_issue(link, upstream.clone())
+                .await?;
+        }
+        Ok(issue_ids)
+    }
+
+    /// Records the pull request on every issue it works on: imported issues it
+    /// closes, and issues it was handed in for as a submission.
+    async fn apply_pull_request_event(
+        &self,
+        repository: &RepositoryRef,
+        upstream: UpstreamPullRequest,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let mut issue_ids = Vec::new();
+        for number in &upstream.closing_issues {
+            let links = self
+                .issue_repository
+                .get_upstream_links_by_number(&repository.owner, &repository.name, *number)
+                .await?;
+            issue_ids.extend(links.into_iter().map(|l| l.issue_id));
+        }
+        let submissions = self
+            .submission_repository
+            .get_submissions_by_pr_url(&upstream.url)
+            .await?;
+        issue_ids.extend(submissions.into_iter().map(|s| s.issue_id));
+        issue_ids.sort();
+        issue_ids.dedup();
+
+        for issue_id in &issue_ids {
+            let pull_request = IssuePullRequest {
+                issue_id: *issue_id,
+                repo_owner: repository.owner.clone(),
+                repo_name: repository.name.clone(),
+                number: upstream.number,
+                url: upstream.url.clone(),
+                title: upstream.title.clone(),
+                author: upstream.author.clone(),
+                state: upstream.state,
+                draft: upstream.draft,
+                updated_at: upstream.updated_at,
+            };
+            // An older event delivered late must not undo a newer one.
+            if self
+                .issue_repository
+                .save_pull_request(&pull_request)
+                .await?
+            {
+                self.advance_issue(&pull_request).await?;
+            }
+        }
+        Ok(issue_ids)
+    }
+
+    /// Moves an `open` issue into review once a pull request for it is ready or
+    /// merged; a merged pull request still waits for the owner's approval. A pull
+    /// request closed unmerged sends the issue back to `open` unless other work on
+    /// it is still under review.
+    async fn advance_issue(&self, pull_request: &IssuePullRequest) -> Result<(), DomainError> {
+        let issue = self.issues.get_issue(pull_request.issue_id).await?;
+        let number = pull_request.number;
+        let url = &pull_request.url;
+        match (issue.status, pull_request.state) {
+            (IssueStatus::Open, PullRequestState::Merged) => {
+                let note = format!(
+                    "Pull request #{} merged upstream ({}); awaiting owner approval",
+                    number, url
+                );
+                self.issues
+                    .apply_system_transition(issue.id, IssueStatus::InReview, Some(note))
+                    .await?;
+            }
+            (IssueStatus::Open, PullRequestState::Open) if !pull_request.draft => {
+                let note = format!("Pull request #{} opened upstream ({})", number, url);
+                self.issues
+                    .apply_system_transition(issue.id, IssueStatus::InReview, Some(note))
+                    .await?;
+            }
+            (IssueStatus::InReview, PullRequestState::Closed) => {
+                if self.has_other_work_in_review(pull_request).await? {
+                    return Ok(());
+                }
+                let note = format!(
+                    "Pull request #{} closed upstream without merging ({})",
+                    number, url
+                );
+                self.issues
+                    .apply_system_transition(issue.id, IssueStatus::Open, Some(note))
+                    .await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn has_other_work_in_review(
+        &self,
+        pull_request: &IssuePullRequest,
+    ) -> Result<bool, DomainError> {
+        let other_pull_requests = self
+            .issue_repository
+            .get_pull_requests_by_issue(pull_request.issue_id)
+            .await?;
+        if other_pull_requests.iter().any(|pr| {
+            pr.is_active()
+                && !(pr.number == pull_request.number
+                    && pr.repo_owner == pull_request.repo_owner
+                    && pr.repo_name == pull_request.repo_name)
+        }) {
+            return Ok(true);
+        }
+        let submissions = self
+            .submission_repository
+            .get_submissions_by_issue(pull_request.issue_id)
+            .await?;
+        Ok(submissions
+            .iter()
+            .any(|s| s.status == SubmissionStatus::Pending))
+    }
+}
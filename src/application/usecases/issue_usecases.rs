@@ -1,43 +1,167 @@
-use crate::domain::entities::issue::{Issue, IssueStatus};
-use crate::domain::errors::domain_error::DomainError;
-use crate::domain::repositories::issue_repository::IssueRepository;
-use chrono::Utc;
+use crate::application::policies::{self, Actor};
+use crate::application::usecases::ledger_usecases::LedgerUsecases;
+use crate::application::usecases::project_usecases::member_role;
+use crate::domain::entities::issue::{ClaimMode, Issue, IssueStatus};
+use crate::domain::entities::issue_pull_request::IssuePullRequest;
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::issue_upstream_link::{IssueUpstreamLink, UpstreamIssueState};
+use crate::domain::entities::project::Project;
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::DomainEvent;
+use crate::domain::gateways::escrow_gateway::{
+    ChainTransaction, EscrowGateway, EscrowState, EscrowStatus, SharedEscrowGateway,
+};
+use crate::domain::gateways::repository_host::{
+    RepositoryHost, RepositoryRef, SharedRepositoryHost, UpstreamIssue,
+};
+use crate::domain::pagination::{validate_page_size, Page};
+use crate::domain::repositories::issue_repository::{IssueQuery, IssueRepository, IssueSort};
+use crate::domain::repositories::ledger_repository::LedgerRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::value_objects::money::{Currency, Money};
+use chrono::{Duration, Utc};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
-pub struct IssueUsecases<R: IssueRepository> {
+/// Upstream issues listed per page by `list_upstream_issues`.
+const UPSTREAM_PAGE_SIZE: u32 = 30;
+/// Most upstream issues imported in one call.
+const MAX_IMPORT_BATCH: usize = 50;
+
+/// A bounty as received from the client, validated by `IssueUsecases`.
+#[derive(Debug, Clone)]
+pub struct BountyInput {
+    pub amount: String,
+    pub currency: String,
+}
+
+/// One upstream issue to import, with the bounty it gets on rust4u.
+#[derive(Debug, Clone)]
+pub struct UpstreamImport {
+    pub number: i32,
+    pub bounty: BountyInput,
+    pub claim_mode: ClaimMode,
+}
+
+/// An upstream issue and the rust4u issue it was imported as, if any.
+#[derive(Debug, Clone)]
+pub struct UpstreamIssueListing {
+    pub issue: UpstreamIssue,
+    pub imported_issue_id: Option<Uuid>,
+}
+
+pub struct IssueUsecases<R: IssueRepository, P: ProjectRepository, L: LedgerRepository> {
     repository: R,
+    project_repository: P,
+    ledger: LedgerUsecases<L, R, P>,
+    escrow: Option<SharedEscrowGateway>,
+    repository_host: Option<SharedRepositoryHost>,
 }
 
-impl<R: IssueRepository> IssueUsecases<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+impl<R: IssueRepository, P: ProjectRepository, L: LedgerRepository> IssueUsecases<R, P, L> {
+    /// `ledger` refunds the escrow when an issue is cancelled.
+    pub fn new(repository: R, project_repository: P, ledger: LedgerUsecases<L, R, P>) -> Self {
+        Self {
+            repository,
+            project_repository,
+            ledger,
+            escrow: None,
+            repository_host: None,
+        }
+    }
+
+    /// Enables the on-chain escrow operations and the bounty lock in `update_issue`.
+    pub fn with_escrow_gateway(mut self, escrow: SharedEscrowGateway) -> Self {
+        self.escrow = Some(escrow);
+        self
+    }
+
+    fn escrow_gateway(&self) -> Result<&dyn EscrowGateway, DomainError> {
+        self.escrow
+            .as_deref()
+            .ok_or_else(|| DomainError::Infra("Escrow gateway is not configured".to_string()))
+    }
+
+    /// Enables importing and syncing issues from the projects' code host.
+    pub fn with_repository_host(mut self, repository_host: SharedRepositoryHost) -> Self {
+        self.repository_host = Some(repository_host);
+        self
+    }
+
+    fn repository_host(&self) -> Result<&dyn RepositoryHost, DomainError> {
+        self.repository_host
+            .as_deref()
+            .ok_or_else(|| DomainError::Infra("Repository host is not configured".to_string()))
+    }
+
+    async fn get_project(&self, project_id: Uuid) -> Result<Project, DomainError> {
+        self.project_repository
+            .get_project_by_id(project_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Project not found".to_string()))
+    }
+
+    /// Loads the issue's project and checks that `actor` is allowed to manage its issues.
+    async fn get_managed_project(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+    ) -> Result<Project, DomainError> {
+        let project = self.get_project(project_id).await?;
+        let role = member_role(&self.project_repository, project.id, Some(actor)).await?;
+        if !policies::can_manage_issues(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only project maintainers or an admin can manage its issues".to_string(),
+            ));
+        }
+        Ok(project)
     }
 
     pub async fn create_issue(
         &self,
+        actor: &Actor,
         project_id: Uuid,
         title: String,
         description: Option<String>,
-        bounty_value: f64,
+        bounty: BountyInput,
+        claim_mode: ClaimMode,
     ) -> Result<Issue, DomainError> {
+        let mut errors = Vec::new();
         if title.is_empty() {
-            return Err(DomainError::InvalidData(
-                "Issue title cannot be empty".to_string(),
-            ));
+            errors.push(FieldError::new("title", "cannot be empty"));
         }
+        let bounty = match validate_bounty(&bounty) {
+            Ok(bounty) => Some(bounty),
+            Err(bounty_errors) => {
+                errors.extend(bounty_errors);
+                None
+            }
+        };
+        let bounty = match bounty {
+            Some(bounty) if errors.is_empty() => bounty,
+            _ => return Err(DomainError::Validation(errors)),
+        };
+
+        self.get_managed_project(project_id, actor).await?;
 
         let issue = Issue {
             id: Uuid::new_v4(),
             project_id,
             title,
             description,
-            bounty_value,
+            bounty,
             status: IssueStatus::Open,
+            assignee_id: None,
+            claim_mode,
+            hidden: false,
             created_at: Utc::now(),
             updated_at: None,
         };
 
-        self.repository.create_issue(&issue).await?;
+        let events = [DomainEvent::IssueCreated {
+            issue: issue.clone(),
+        }];
+        self.repository.create_issue(&issue, &events).await?;
         Ok(issue)
     }
 
@@ -49,6 +173,22 @@ impl<R: IssueRepository> IssueUsecases<R> {
         }
     }
 
+    /// Like `get_issue`, but hidden issues are only returned to project members and staff.
+    pub async fn view_issue(
+        &self,
+        issue_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<Issue, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        if issue.hidden {
+            let role = member_role(&self.project_repository, issue.project_id, viewer).await?;
+            if !policies::can_view_hidden(viewer, role) {
+                return Err(DomainError::NotFound("Issue not found".to_string()));
+            }
+        }
+        Ok(issue)
+    }
+
     pub async fn get_issues_by_project(&self, project_id: Uuid) -> Result<Vec<Issue>, DomainError> {
         self.repository.get_issues_by_project(project_id).await
     }
@@ -56,12 +196,14 @@ impl<R: IssueRepository> IssueUsecases<R> {
     pub async fn update_issue(
         &self,
         issue_id: Uuid,
+        actor: &Actor,
         new_title: Option<String>,
         new_description: Option<String>,
-        new_bounty_value: Option<f64>,
-        new_status: Option<IssueStatus>,
+        new_bounty: Option<BountyInput>,
+        new_claim_mode: Option<ClaimMode>,
     ) -> Result<Issue, DomainError> {
         let mut issue = self.get_issue(issue_id).await?;
+        self.get_managed_project(issue.project_id, actor).await?;
 
         if let Some(title) = new_title {
             if !title.is_empty() {
@@ -71,31 +213,548 @@ impl<R: IssueRepository> IssueUsecases<R> {
         if let Some(description) = new_description {
             issue.description = Some(description);
         }
-        if let Some(bounty_value) = new_bounty_value {
-            issue.bounty_value = bounty_value;
+        if let Some(bounty) = new_bounty {
+            let bounty = validate_bounty(&bounty).map_err(DomainError::Validation)?;
+            if bounty != issue.bounty {
+                self.ensure_bounty_unlocked(issue.id).await?;
+            }
+            issue.bounty = bounty;
         }
-        if let Some(status) = new_status {
-            issue.status = status;
+        if let Some(claim_mode) = new_claim_mode {
+            issue.claim_mode = claim_mode;
         }
 
         issue.updated_at = Some(Utc::now());
-        self.repository.update_issue(&issue).await?;
+        let events = [DomainEvent::IssueUpdated {
+            issue: issue.clone(),
+        }];
+        self.repository.update_issue(&issue, &events).await?;
         Ok(issue)
     }
 
-    pub async fn update_issue_status(
+    /// Moves an issue to `to_status` on behalf of `actor`, rejecting any move not
+    /// allowed by `IssueStatus::allowed_transitions`. Disputes are opened and
+    /// settled through `DisputeUsecases` only, and work is approved through
+    /// `SubmissionUsecases`, as these move money and keep a record in step with
+    /// the issue.
+    pub async fn transition_issue(
         &self,
         issue_id: Uuid,
-        status: IssueStatus,
-    ) -> Result<(), DomainError> {
-        self.repository.update_issue_status(issue_id, status).await
+        actor: &Actor,
+        to_status: IssueStatus,
+        note: Option<String>,
+    ) -> Result<Issue, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        self.get_managed_project(issue.project_id, actor).await?;
+        if issue.status == IssueStatus::Disputed || to_status == IssueStatus::Disputed {
+            return Err(DomainError::Conflict(
+                "Disputes are opened with POST /issues/:id/disputes and settled with POST /disputes/:id/ruling"
+                    .to_string(),
+            ));
+        }
+        if issue.status == IssueStatus::InReview && to_status == IssueStatus::Approved {
+            return Err(DomainError::Conflict(
+                "Issues are approved with POST /issues/:id/submissions/:submission_id/approve"
+                    .to_string(),
+            ));
+        }
+        self.apply_transition(issue, Some(actor.id), to_status, note)
+            .await
+    }
+
+    /// Same as `transition_issue`, for changes driven by the system rather than a user.
+    pub async fn apply_system_transition(
+        &self,
+        issue_id: Uuid,
+        to_status: IssueStatus,
+        note: Option<String>,
+    ) -> Result<Issue, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        self.apply_transition(issue, None, to_status, note).await
+    }
+
+    async fn apply_transition(
+        &self,
+        mut issue: Issue,
+        actor_id: Option<Uuid>,
+        to_status: IssueStatus,
+        note: Option<String>,
+    ) -> Result<Issue, DomainError> {
+        if !issue.status.can_transition_to(to_status) {
+            return Err(DomainError::Conflict(format!(
+                "Cannot transition issue from {} to {}",
+                issue.status, to_status
+            )));
+        }
+
+        let transition = IssueTransition {
+            id: Uuid::new_v4(),
+            issue_id: issue.id,
+            from_status: issue.status,
+            to_status,
+            actor_id,
+            note,
+            created_at: Utc::now(),
+        };
+
+        // The escrow of a cancelled issue goes back to its funders in the same transaction.
+        let refund = if to_status == IssueStatus::Cancelled {
+            let escrow = self.ledger.get_escrow_balance(&issue).await?;
+            if escrow.is_positive() {
+                Some(
+                    self.ledger
+                        .refund_entry(&issue, actor_id, escrow.amount_minor)
+                        .await?,
+                )
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let mut events = vec![DomainEvent::issue_status_changed(
+            issue.project_id,
+            &transition,
+        )];
+        events.extend(refund.iter().map(|entry| DomainEvent::BountyRefunded {
+            project_id: issue.project_id,
+            entry: entry.clone(),
+        }));
+        self.repository
+            .apply_transition(&transition, refund.as_ref(), &events)
+            .await?;
+
+        issue.status = to_status;
+        issue.updated_at = Some(transition.created_at);
+        Ok(issue)
+    }
+
+    pub async fn get_issue_transitions(
+        &self,
+        issue_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<Vec<IssueTransition>, DomainError> {
+        self.view_issue(issue_id, viewer).await?;
+        self.repository.get_transitions_by_issue(issue_id).await
+    }
+
+    pub async fn set_issue_hidden(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+        hidden: bool,
+    ) -> Result<Issue, DomainError> {
+        if !policies::can_hide_content(actor) {
+            return Err(DomainError::Forbidden(
+                "Only moderators can hide content".to_string(),
+            ));
+        }
+        let mut issue = self.get_issue(issue_id).await?;
+        self.repository.set_issue_hidden(issue_id, hidden).await?;
+        issue.hidden = hidden;
+        Ok(issue)
     }
 
-    pub async fn delete_issue(&self, issue_id: Uuid) -> Result<(), DomainError> {
+    pub async fn delete_issue(&self, issue_id: Uuid, actor: &Actor) -> Result<(), DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        self.get_managed_project(issue.project_id, actor).await?;
         self.repository.delete_issue(issue_id).await
     }
 
-    pub async fn list_issues(&self) -> Result<Vec<Issue>, DomainError> {
-        self.repository.list_issues().await
+    /// A bounty already funded on the ledger or sent to the chain cannot change
+    /// underneath it.
+    async fn ensure_bounty_unlocked(&self, issue_id: Uuid) -> Result<(), DomainError> {
+        if self.repository.has_funded_escrow(issue_id).await? {
+            return Err(DomainError::Conflict(
+                "Bounty cannot change while its escrow holds funds".to_string(),
+            ));
+        }
+        let Some(escrow) = &self.escrow else {
+            return Ok(());
+        };
+        let state = escrow.status(issue_id).await?.state;
+        if !matches!(state, EscrowState::Unfunded | EscrowState::Refunded) {
+            return Err(DomainError::Conflict(format!(
+                "Bounty cannot change while its escrow is {}",
+                state.as_str()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Locks the issue's bounty in on-chain escrow. Issues funded on the ledger
+    /// are paid out there instead.
+    pub async fn lock_bounty_on_chain(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+    ) -> Result<ChainTransaction, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        self.get_managed_project(issue.project_id, actor).await?;
+        if !matches!(issue.status, IssueStatus::Open | IssueStatus::InReview) {
+            return Err(DomainError::Conflict(format!(
+                "Cannot lock the bounty of an issue that is {}",
+                issue.status
+            )));
+        }
+        if self.ledger.is_funded_on_ledger(issue.id).await? {
+            return Err(DomainError::Conflict(
+                "Bounty is funded on the ledger and is paid out there".to_string(),
+            ));
+        }
+        self.escrow_gateway()?
+            .lock_funds(issue.id, issue.bounty)
+            .await
+    }
+
+    /// Pays the on-chain escrow of an approved issue to the chain address of the
+    /// approved submission's author.
+    pub async fn release_bounty_on_chain(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+    ) -> Result<ChainTransaction, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        if !policies::can_settle_escrow(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only project maintainers or staff can move escrowed funds".to_string(),
+            ));
+        }
+        if !matches!(issue.status, IssueStatus::Approved | IssueStatus::Closed) {
+            return Err(DomainError::Conflict(
+                "Escrow can only be released once the issue is approved".to_string(),
+            ));
+        }
+        if self.ledger.is_funded_on_ledger(issue.id).await? {
+            return Err(DomainError::Conflict(
+                "Bounty is funded on the ledger and was paid out there".to_string(),
+            ));
+        }
+        let payee = self.repository.get_payee(issue.id).await?.ok_or_else(|| {
+            DomainError::Conflict("Issue has no approved submission to pay".to_string())
+        })?;
+        let address = payee.chain_address.ok_or_else(|| {
+            DomainError::Conflict(format!(
+                "User {} has not registered a chain address for payouts",
+                payee.user_id
+            ))
+        })?;
+        self.escrow_gateway()?.release(issue.id, &address).await
+    }
+
+    /// Returns the on-chain escrow of a cancelled issue to whoever locked it.
+    pub async fn refund_bounty_on_chain(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+    ) -> Result<ChainTransaction, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        if !policies::can_settle_escrow(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only project maintainers or staff can move escrowed funds".to_string(),
+            ));
+        }
+        if issue.status != IssueStatus::Cancelled {
+            return Err(DomainError::Conflict(
+                "Escrow can only be refunded once the issue is cancelled".to_string(),
+            ));
+        }
+        self.escrow_gateway()?.refund(issue.id).await
+    }
+
+    pub async fn get_escrow_status(
+        &self,
+        issue_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<EscrowStatus, DomainError> {
+        let issue = self.view_issue(issue_id, viewer).await?;
+        self.escrow_gateway()?.status(issue.id).await
+    }
+
+    /// The repository the project's `github_link` points to.
+    fn upstream_repository(&self, project: &Project) -> Result<RepositoryRef, DomainError> {
+        let host = self.repository_host()?;
+        project
+            .github_link
+            .as_deref()
+            .and_then(|link| host.parse_link(link))
+            .ok_or_else(|| {
+                DomainError::Conflict("Project has no GitHub repository link".to_string())
+            })
+    }
+
+    /// Open issues of the project's upstream repository, for maintainers picking
+    /// what to import. Returns the page and whether there are more.
+    pub async fn list_upstream_issues(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+        page: u32,
+    ) -> Result<(Vec<UpstreamIssueListing>, bool), DomainError> {
+        if page == 0 {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "page",
+                "must be at least 1",
+            )]));
+        }
+        let project = self.get_managed_project(project_id, actor).await?;
+        let repository = self.upstream_repository(&project)?;
+        let upstream = self
+            .repository_host()?
+            .list_issues(&repository, page, UPSTREAM_PAGE_SIZE)
+            .await?;
+
+        let imported: HashMap<i32, Uuid> = self
+            .repository
+            .get_upstream_links_by_project(project.id)
+            .await?
+            .into_iter()
+            .filter(|l| l.repo_owner == repository.owner && l.repo_name == repository.name)
+            .map(|l| (l.number, l.issue_id))
+            .collect();
+        let listings = upstream
+            .issues
+            .into_iter()
+            .map(|issue| UpstreamIssueListing {
+                imported_issue_id: imported.get(&issue.number).copied(),
+                issue,
+            })
+            .collect();
+        Ok((listings, upstream.has_more))
+    }
+
+    /// Creates an issue for each of the upstream issues picked by a maintainer,
+    /// copying its title and body. Nothing is imported unless every pick is an
+    /// open upstream issue that was not imported before.
+    pub async fn import_upstream_issues(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+        imports: Vec<UpstreamImport>,
+    ) -> Result<Vec<(Issue, IssueUpstreamLink)>, DomainError> {
+        if imports.is_empty() || imports.len() > MAX_IMPORT_BATCH {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "issues",
+                &format!("must contain between 1 and {} issues", MAX_IMPORT_BATCH),
+            )]));
+        }
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+        let mut bounties = Vec::new();
+        for (i, import) in imports.iter().enumerate() {
+            if !seen.insert(import.number) {
+                errors.push(FieldError::new(
+                    &format!("issues[{}].number", i),
+                    "is listed more than once",
+                ));
+            }
+            match validate_bounty(&import.bounty) {
+                Ok(bounty) => bounties.push(bounty),
+                Err(bounty_errors) => {
+                    errors.extend(bounty_errors.into_iter().map(|e| {
+                        FieldError::new(&format!("issues[{}].{}", i, e.field), &e.message)
+                    }))
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(DomainError::Validation(errors));
+        }
+
+        let project = self.get_managed_project(project_id, actor).await?;
+        let repository = self.upstream_repository(&project)?;
+        let existing = self
+            .repository
+            .get_upstream_links_by_project(project.id)
+            .await?;
+        if let Some(link) = existing.iter().find(|l| {
+            l.repo_owner == repository.owner
+                && l.repo_name == repository.name
+                && seen.contains(&l.number)
+        }) {
+            return Err(DomainError::Conflict(format!(
+                "Upstream issue #{} is already imported",
+                link.number
+            )));
+        }
+
+        let host = self.repository_host()?;
+        let mut upstream_issues = Vec::new();
+        for (i, import) in imports.iter().enumerate() {
+            let field = format!("issues[{}].number", i);
+            match host.get_issue(&repository, import.number).await {
+                Ok(issue) if issue.state == UpstreamIssueState::Open => upstream_issues.push(issue),
+                Ok(_) => errors.push(FieldError::new(&field, "is closed upstream")),
+                Err(DomainError::NotFound(_)) => {
+                    errors.push(FieldError::new(&field, "is not an issue of the repository"))
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(DomainError::Validation(errors));
+        }
+
+        let now = Utc::now();
+        let mut imported = Vec::new();
+        for ((import, bounty), upstream) in imports.iter().zip(bounties).zip(upstream_issues) {
+            let issue = Issue {
+                id: Uuid::new_v4(),
+                project_id: project.id,
+                title: upstream.title,
+                description: upstream.body,
+                bounty,
+                status: IssueStatus::Open,
+                assignee_id: None,
+                claim_mode: import.claim_mode,
+                hidden: false,
+                created_at: now,
+                updated_at: None,
+            };
+            let link = IssueUpstreamLink {
+                issue_id: issue.id,
+                project_id: project.id,
+                repo_owner: repository.owner.clone(),
+                repo_name: repository.name.clone(),
+                number: upstream.number,
+                url: upstream.url,
+                state: upstream.state,
+                synced_at: now,
+            };
+            let events = [DomainEvent::IssueCreated {
+                issue: issue.clone(),
+            }];
+            self.repository
+                .create_imported_issue(&issue, &link, &events)
+                .await?;
+            imported.push((issue, link));
+        }
+        Ok(imported)
+    }
+
+    /// Pulls title and state of up to `limit` imported issues not synced within
+    /// `max_age`. An issue closed upstream is cancelled here while it is still
+    /// `open`; once work is under review it is left to the maintainers. Returns how
+    /// many issues were synced.
+    pub async fn sync_upstream_issues(
+        &self,
+        max_age: Duration,
+        limit: u32,
+    ) -> Result<usize, DomainError> {
+        let host = self.repository_host()?;
+        let links = self
+            .repository
+            .get_upstream_links_due_for_sync(Utc::now() - max_age, limit)
+            .await?;
+
+        let mut synced = 0;
+        for mut link in links {
+            let repository = RepositoryRef {
+                owner: link.repo_owner.clone(),
+                name: link.repo_name.clone(),
+            };
+            match host.get_issue(&repository, link.number).await {
+                Ok(upstream) => {
+                    self.apply_upstream_issue(link, upstream).await?;
+                    synced += 1;
+                }
+                // Deleted or transferred upstream; keep the last known state.
+                Err(DomainError::NotFound(_)) => {
+                    link.synced_at = Utc::now();
+                    self.repository.update_upstream_link(&link).await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(synced)
+    }
+
+    /// Brings an imported issue in line with its upstream issue: copies the title
+    /// and cancels the issue if it was closed upstream while still `open`.
+    pub async fn apply_upstream_issue(
+        &self,
+        mut link: IssueUpstreamLink,
+        upstream: UpstreamIssue,
+    ) -> Result<(), DomainError> {
+        let mut issue = self.get_issue(link.issue_id).await?;
+        if upstream.title != issue.title && !upstream.title.is_empty() {
+            issue.title = upstream.title;
+            issue.updated_at = Some(Utc::now());
+            let events = [DomainEvent::IssueUpdated {
+                issue: issue.clone(),
+            }];
+            self.repository.update_issue(&issue, &events).await?;
+        }
+        if upstream.state == UpstreamIssueState::Closed && issue.status == IssueStatus::Open {
+            let note = format!("Closed upstream ({})", upstream.url);
+            self.apply_transition(issue, None, IssueStatus::Cancelled, Some(note))
+                .await?;
+        }
+        link.url = upstream.url;
+        link.state = upstream.state;
+        link.synced_at = Utc::now();
+        self.repository.update_upstream_link(&link).await
+    }
+
+    /// Upstream pull requests working on the issue, most recently updated first.
+    pub async fn get_issue_pull_requests(
+        &self,
+        issue_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<Vec<IssuePullRequest>, DomainError> {
+        let issue = self.view_issue(issue_id, viewer).await?;
+        self.repository.get_pull_requests_by_issue(issue.id).await
+    }
+
+    /// Hidden issues are only listed for staff.
+    pub async fn list_issues(
+        &self,
+        viewer: Option<&Actor>,
+        mut query: IssueQuery,
+    ) -> Result<Page<Issue>, DomainError> {
+        validate_page_size(query.limit)?;
+        // Amounts are in minor units, which differ between currencies.
+        if matches!(query.sort, IssueSort::BountyDesc | IssueSort::BountyAsc)
+            && query.currency.is_none()
+        {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "currency",
+                "is required when sorting by bounty",
+            )]));
+        }
+        query.include_hidden = viewer.is_some_and(|a| a.role.is_staff());
+        self.repository.list_issues(&query).await
+    }
+}
+
+/// Checks the currency allow-list, the amount format, that the amount is positive
+/// and that it does not exceed the currency's maximum bounty.
+fn validate_bounty(bounty: &BountyInput) -> Result<Money, Vec<FieldError>> {
+    let currency = Currency::from_code(&bounty.currency).ok_or_else(|| {
+        let codes: Vec<&str> = Currency::ALL.iter().map(|c| c.code()).collect();
+        vec![FieldError::new(
+            "bounty.currency",
+            &format!("must be one of {}", codes.join(", ")),
+        )]
+    })?;
+    let money = Money::parse(&bounty.amount, currency)
+        .map_err(|message| vec![FieldError::new("bounty.amount", &message)])?;
+
+    if !money.is_positive() {
+        return Err(vec![FieldError::new(
+            "bounty.amount",
+            "must be greater than zero",
+        )]);
+    }
+    let max = Money::new(currency.max_bounty(), currency);
+    if money.amount_minor > max.amount_minor {
+        return Err(vec![FieldError::new(
+            "bounty.amount",
+            &format!("must be at most {}", max),
+        )]);
     }
+    Ok(money)
 }
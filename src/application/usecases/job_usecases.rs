@@ -0,0 +1,67 @@
+use crate::application::policies::{self, Actor};
+use crate::domain::entities::job::{Job, JobStatus};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{validate_page_size, Page};
+use crate::domain::repositories::job_repository::{JobQuery, JobRepository};
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Admin view of the background job queue.
+pub struct JobUsecases<J: JobRepository> {
+    repository: J,
+}
+
+impl<J: JobRepository> JobUsecases<J> {
+    pub fn new(repository: J) -> Self {
+        Self { repository }
+    }
+
+    fn check_can_manage(actor: &Actor) -> Result<(), DomainError> {
+        if !policies::can_manage_jobs(actor) {
+            return Err(DomainError::Forbidden(
+                "Only admins can manage background jobs".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn list_jobs(
+        &self,
+        actor: &Actor,
+        query: JobQuery,
+    ) -> Result<Page<Job>, DomainError> {
+        Self::check_can_manage(actor)?;
+        validate_page_size(query.limit)?;
+        self.repository.list_jobs(&query).await
+    }
+
+    pub async fn get_job(&self, job_id: Uuid, actor: &Actor) -> Result<Job, DomainError> {
+        Self::check_can_manage(actor)?;
+        self.repository
+            .get_job_by_id(job_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Job not found".to_string()))
+    }
+
+    /// Queues a dead job again with a fresh set of attempts. Its last error is kept
+    /// for reference.
+    pub async fn retry_job(&self, job_id: Uuid, actor: &Actor) -> Result<Job, DomainError> {
+        let mut job = self.get_job(job_id, actor).await?;
+        if job.status != JobStatus::Dead {
+            return Err(DomainError::Conflict(
+                "Only dead jobs can be retried".to_string(),
+            ));
+        }
+        let seen_attempts = job.attempts;
+        job.status = JobStatus::Pending;
+        job.attempts = 0;
+        job.run_at = Utc::now();
+        job.finished_at = None;
+        if !self.repository.update_job(&job, seen_attempts).await? {
+            return Err(DomainError::Conflict(
+                "Job was retried concurrently".to_string(),
+            ));
+        }
+        Ok(job)
+    }
+}
@@ -0,0 +1,587 @@
+use crate::application::policies::{self, Actor};
+use crate::application::usecases::project_usecases::member_role;
+use crate::domain::entities::issue::{Issue, IssueStatus};
+use crate::domain::entities::ledger::{AccountKind, AccountRef, EntryKind, JournalEntry, Posting};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::DomainEvent;
+use crate::domain::gateways::escrow_gateway::{EscrowState, SharedEscrowGateway};
+use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::repositories::ledger_repository::LedgerRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::value_objects::money::{Currency, Money};
+use uuid::Uuid;
+
+/// Escrow balance of an issue plus every journal entry that touched it.
+pub struct IssueLedger {
+    pub escrow: Money,
+    pub entries: Vec<JournalEntry>,
+}
+
+pub struct LedgerUsecases<L: LedgerRepository, R: IssueRepository, P: ProjectRepository> {
+    repository: L,
+    issue_repository: R,
+    project_repository: P,
+    fee_bps: u32,
+    escrow: Option<SharedEscrowGateway>,
+}
+
+impl<L: LedgerRepository, R: IssueRepository, P: ProjectRepository> LedgerUsecases<L, R, P> {
+    /// `fee_bps` is the platform fee taken from each payout, in basis points.
+    pub fn new(repository: L, issue_repository: R, project_repository: P, fee_bps: u32) -> Self {
+        Self {
+            repository,
+            issue_repository,
+            project_repository,
+            fee_bps,
+            escrow: None,
+        }
+    }
+
+    /// Refuses ledger funding for issues whose bounty is escrowed on chain, so
+    /// each bounty has a single payout path.
+    pub fn with_escrow_gateway(mut self, escrow: SharedEscrowGateway) -> Self {
+        self.escrow = Some(escrow);
+        self
+    }
+
+    async fn get_issue(&self, issue_id: Uuid) -> Result<Issue, DomainError> {
+        self.issue_repository
+            .get_issue_by_id(issue_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Issue not found".to_string()))
+    }
+
+    /// Loads the issue and checks that `actor` may release or refund its escrow.
+    async fn get_settleable_issue(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+    ) -> Result<Issue, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        if !policies::can_settle_escrow(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only project maintainers or staff can move escrowed funds".to_string(),
+            ));
+        }
+        Ok(issue)
+    }
+
+    /// Current escrow balance of `issue`, in its bounty currency.
+    pub async fn get_escrow_balance(&self, issue: &Issue) -> Result<Money, DomainError> {
+        self.repository
+            .get_balance(&AccountRef::escrow(issue.id, issue.bounty.currency))
+            .await
+    }
+
+    /// Whether the issue was ever funded on the ledger. Its bounty is then paid
+    /// out there, and cannot also go through the on-chain escrow.
+    pub async fn is_funded_on_ledger(&self, issue_id: Uuid) -> Result<bool, DomainError> {
+        let entries = self.repository.get_entries_by_issue(issue_id).await?;
+        Ok(entries.iter().any(|entry| entry.kind == EntryKind::Fund))
+    }
+
+    /// Moves `amount` (a decimal string in the issue's bounty currency) from
+    /// `actor`'s own account into the issue's escrow.
+    pub async fn fund_issue(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+        amount: &str,
+        currency: &str,
+    ) -> Result<JournalEntry, DomainError> {
+        self.fund_issue_from(issue_id, actor, None, amount, currency)
+            .await
+    }
+
+    /// Same as `fund_issue`, drawing the money from the organization's account. The
+    /// caller checks that `actor` may spend the organization's funds.
+    pub async fn fund_issue_from_organization(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+        organization_id: Uuid,
+        amount: &str,
+        currency: &str,
+    ) -> Result<JournalEntry, DomainError> {
+        self.fund_issue_from(issue_id, actor, Some(organization_id), amount, currency)
+            .await
+    }
+
+    async fn fund_issue_from(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+        organization_id: Option<Uuid>,
+        amount: &str,
+        currency: &str,
+    ) -> Result<JournalEntry, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        if !matches!(issue.status, IssueStatus::Open | IssueStatus::InReview) {
+            return Err(DomainError::Conflict(format!(
+                "Cannot fund an issue that is {}",
+                issue.status
+            )));
+        }
+        if let Some(escrow) = &self.escrow {
+            let state = escrow.status(issue.id).await?.state;
+            if !matches!(state, EscrowState::Unfunded | EscrowState::Refunded) {
+                return Err(DomainError::Conflict(format!(
+                    "Bounty is settled on chain and its escrow is {}",
+                    state.as_str()
+                )));
+            }
+        }
+
+        let currency = Currency::from_code(currency)
+            .filter(|c| *c == issue.bounty.currency)
+            .ok_or_else(|| {
+                DomainError::Validation(vec![FieldError::new(
+                    "currency",
+                    &format!("must match the bounty currency ({})", issue.bounty.currency),
+                )])
+            })?;
+        let amount = parse_amount(amount, currency)?;
+        let source = match organization_id {
+            Some(id) => AccountRef::organization(id, currency),
+            None => AccountRef::user(actor.id, currency),
+        };
+
+        let entry = JournalEntry::new(
+            EntryKind::Fund,
+            Some(issue.id),
+            Some(actor.id),
+            Some(format!("Funding for issue {}", issue.id)),
+            vec![
+                Posting::new(source, -amount.amount_minor),
+                Posting::new(AccountRef::escrow(issue.id, currency), amount.amount_minor),
+            ],
+        )?;
+        let events = [DomainEvent::BountyFunded {
+            project_id: issue.project_id,
+            entry: entry.clone(),
+            amount,
+            organization_id,
+        }];
+        self.repository
+            .post_funding(&issue, &entry, &events)
+            .await?;
+        Ok(entry)
+    }
+
+    /// Records a payment of `amount` received outside the platform into the
+    /// organization's account.
+    pub async fn deposit_to_organization(
+        &self,
+        organization_id: Uuid,
+        actor: &Actor,
+        amount: &str,
+        currency: &str,
+    ) -> Result<JournalEntry, DomainError> {
+        let account = |currency| AccountRef::organization(organization_id, currency);
+        let memo = format!("Deposit for organization {}", organization_id);
+        self.deposit(account, memo, actor, amount, currency).await
+    }
+
+    /// Records a payment of `amount` received outside the platform into the
+    /// user's account, from which they can fund issues.
+    pub async fn deposit_to_user(
+        &self,
+        user_id: Uuid,
+        actor: &Actor,
+        amount: &str,
+        currency: &str,
+    ) -> Result<JournalEntry, DomainError> {
+        let account = |currency| AccountRef::user(user_id, currency);
+        let memo = format!("Deposit for user {}", user_id);
+        self.deposit(account, memo, actor, amount, currency).await
+    }
+
+    async fn deposit(
+        &self,
+        account: impl Fn(Currency) -> AccountRef,
+        memo: String,
+        actor: &Actor,
+        amount: &str,
+        currency: &str,
+    ) -> Result<JournalEntry, DomainError> {
+        if !policies::can_record_deposit(actor) {
+            return Err(DomainError::Forbidden(
+                "Only admins can record deposits".to_string(),
+            ));
+        }
+        let currency = Currency::from_code(currency).ok_or_else(|| {
+            let codes: Vec<&str> = Currency::ALL.iter().map(|c| c.code()).collect();
+            DomainError::Validation(vec![FieldError::new(
+                "currency",
+                &format!("must be one of {}", codes.join(", ")),
+            )])
+        })?;
+        let amount = parse_amount(amount, currency)?;
+
+        let entry = JournalEntry::new(
+            EntryKind::Deposit,
+            None,
+            Some(actor.id),
+            Some(memo),
+            vec![
+                Posting::new(AccountRef::external(currency), -amount.amount_minor),
+                Posting::new(account(currency), amount.amount_minor),
+            ],
+        )?;
+        self.repository.post_entry(&entry, &[]).await?;
+        Ok(entry)
+    }
+
+    /// Balances of the organization's account, per currency. The caller checks that
+    /// the viewer may see them.
+    pub async fn get_organization_balances(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<Money>, DomainError> {
+        self.repository
+            .get_balances(AccountKind::Organization, organization_id)
+            .await
+    }
+
+    /// Pays the whole escrow of an approved issue to `solver_id`, minus the platform fee.
+    pub async fn release_escrow(
+        &self,
+        issue_id: Uuid,
+        actor: Option<&Actor>,
+        solver_id: Uuid,
+    ) -> Result<JournalEntry, DomainError> {
+        let issue = match actor {
+            Some(actor) => self.get_settleable_issue(issue_id, actor).await?,
+            None => self.get_issue(issue_id).await?,
+        };
+        if !matches!(issue.status, IssueStatus::Approved | IssueStatus::Closed) {
+            return Err(DomainError::Conflict(
+                "Escrow can only be released once the issue is approved".to_string(),
+            ));
+        }
+
+        let currency = issue.bounty.currency;
+        let escrow = self
+            .repository
+            .get_balance(&AccountRef::escrow(issue.id, currency))
+            .await?;
+        if !escrow.is_positive() {
+            return Err(DomainError::Conflict("Escrow is empty".to_string()));
+        }
+
+        let entry =
+            self.release_entry(&issue, actor.map(|a| a.id), solver_id, escrow.amount_minor)?;
+        let events = [DomainEvent::BountyPaid {
+            project_id: issue.project_id,
+            entry: entry.clone(),
+        }];
+        self.repository.post_entry(&entry, &events).await?;
+        Ok(entry)
+    }
+
+    /// Returns the escrow of a cancelled issue to the users who funded it.
+    pub async fn refund_escrow(
+        &self,
+        issue_id: Uuid,
+        actor: Option<&Actor>,
+    ) -> Result<JournalEntry, DomainError> {
+        let issue = match actor {
+            Some(actor) => self.get_settleable_issue(issue_id, actor).await?,
+            None => self.get_issue(issue_id).await?,
+        };
+        if issue.status != IssueStatus::Cancelled {
+            return Err(DomainError::Conflict(
+                "Escrow can only be refunded once the issue is cancelled".to_string(),
+            ));
+        }
+
+        let currency = issue.bounty.currency;
+        let escrow = self
+            .repository
+            .get_balance(&AccountRef::escrow(issue.id, currency))
+            .await?;
+        if !escrow.is_positive() {
+            return Err(DomainError::Conflict(
+                "Escrow has nothing left to refund".to_string(),
+            ));
+        }
+        let entry = self
+            .refund_entry(&issue, actor.map(|a| a.id), escrow.amount_minor)
+            .await?;
+        let events = [DomainEvent::BountyRefunded {
+            project_id: issue.project_id,
+            entry: entry.clone(),
+        }];
+        self.repository.post_entry(&entry, &events).await?;
+        Ok(entry)
+    }
+
+    /// Builds (without posting) the entry paying `amount_minor` of the issue's escrow
+    /// to `solver_id`, minus the platform fee.
+    pub fn release_entry(
+        &self,
+        issue: &Issue,
+        actor_id: Option<Uuid>,
+        solver_id: Uuid,
+        amount_minor: i64,
+    ) -> Result<JournalEntry, DomainError> {
+        if amount_minor <= 0 {
+            return Err(DomainError::InvalidData(
+                "Payout must be greater than zero".to_string(),
+            ));
+        }
+        JournalEntry::new(
+            EntryKind::Release,
+            Some(issue.id),
+            actor_id,
+            Some(format!("Bounty payout for issue {}", issue.id)),
+            payout_postings(
+                AccountRef::escrow(issue.id, issue.bounty.currency),
+                AccountRef::user(solver_id, issue.bounty.currency),
+                amount_minor,
+                self.fee_bps,
+            ),
+        )
+    }
+
+    /// Builds (without posting) the entry returning `amount_minor` of the issue's
+    /// escrow to its funders, pro rata to what each put in. Only possible while
+    /// nothing has been released from the escrow yet.
+    pub async fn refund_entry(
+        &self,
+        issue: &Issue,
+        actor_id: Option<Uuid>,
+        amount_minor: i64,
+    ) -> Result<JournalEntry, DomainError> {
+        let currency = issue.bounty.currency;
+        let escrow = self
+            .repository
+            .get_balance(&AccountRef::escrow(issue.id, currency))
+            .await?;
+        let funders: Vec<(AccountRef, i64)> = self
+            .repository
+            .get_escrow_funders(issue.id, currency)
+            .await?
+            .into_iter()
+            .filter(|(_, amount)| amount.is_positive())
+            .map(|(account, amount)| (account, amount.amount_minor))
+            .collect();
+        let funded: i64 = funders.iter().map(|(_, amount)| amount).sum();
+        if funded != escrow.amount_minor || amount_minor > funded {
+            return Err(DomainError::Conflict(
+                "Escrow has nothing left to refund".to_string(),
+            ));
+        }
+
+        let mut postings = vec![Posting::new(
+            AccountRef::escrow(issue.id, currency),
+            -amount_minor,
+        )];
+        postings.extend(
+            pro_rata(amount_minor, &funders)
+                .into_iter()
+                .filter(|(_, share)| *share > 0)
+                .map(|(account, share)| Posting::new(account, share)),
+        );
+
+        JournalEntry::new(
+            EntryKind::Refund,
+            Some(issue.id),
+            actor_id,
+            Some(format!("Refund for issue {}", issue.id)),
+            postings,
+        )
+    }
+
+    pub async fn get_user_balances(
+        &self,
+        user_id: Uuid,
+        actor: &Actor,
+    ) -> Result<Vec<Money>, DomainError> {
+        if !policies::can_view_balance(actor, user_id) {
+            return Err(DomainError::Forbidden(
+                "You can only view your own balance".to_string(),
+            ));
+        }
+        self.repository
+            .get_balances(AccountKind::User, user_id)
+            .await
+    }
+
+    /// Hidden issues keep their ledger private to project members and staff.
+    pub async fn get_issue_ledger(
+        &self,
+        issue_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<IssueLedger, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        if issue.hidden {
+            let role = member_role(&self.project_repository, issue.project_id, viewer).await?;
+            if !policies::can_view_hidden(viewer, role) {
+                return Err(DomainError::NotFound("Issue not found".to_string()));
+            }
+        }
+
+        let escrow = self
+            .repository
+            .get_balance(&AccountRef::escrow(issue.id, issue.bounty.currency))
+            .await?;
+        let entries = self.repository.get_entries_by_issue(issue.id).await?;
+        Ok(IssueLedger { escrow, entries })
+    }
+}
+
+/// Parses a positive amount in `currency`, reporting problems on the `amount` field.
+fn parse_amount(amount: &str, currency: Currency) -> Result<Money, DomainError> {
+    let amount = Money::parse(amount, currency)
+        .map_err(|message| DomainError::Validation(vec![FieldError::new("amount", &message)]))?;
+    if !amount.is_positive() {
+        return Err(DomainError::Validation(vec![FieldError::new(
+            "amount",
+            "must be greater than zero",
+        )]));
+    }
+    Ok(amount)
+}
+
+/// Moves `amount_minor` out of `escrow`: the platform fee to the platform, the
+/// rest to `solver`. A fee that rounds down to nothing gets no posting.
+fn payout_postings(
+    escrow: AccountRef,
+    solver: AccountRef,
+    amount_minor: i64,
+    fee_bps: u32,
+) -> Vec<Posting> {
+    let fee = platform_fee(amount_minor, fee_bps);
+    let mut postings = vec![
+        Posting::new(escrow, -amount_minor),
+        Posting::new(solver, amount_minor - fee),
+    ];
+    if fee > 0 {
+        postings.push(Posting::new(AccountRef::platform_fee(escrow.currency), fee));
+    }
+    postings
+}
+
+/// Fee in minor units, rounded down so the solver never receives less than their share.
+fn platform_fee(amount_minor: i64, fee_bps: u32) -> i64 {
+    (i128::from(amount_minor) * i128::from(fee_bps) / 10_000) as i64
+}
+
+/// Splits `total` across `weights` in proportion to each weight, handing the units
+/// lost to rounding to the largest remainders so the shares add up to `total`.
+fn pro_rata<K: Copy>(total: i64, weights: &[(K, i64)]) -> Vec<(K, i64)> {
+    let sum: i128 = weights.iter().map(|(_, w)| i128::from(*w)).sum();
+    if sum == 0 {
+        return Vec::new();
+    }
+    let mut shares: Vec<(K, i64, i128)> = weights
+        .iter()
+        .map(|(id, w)| {
+            let exact = i128::from(total) * i128::from(*w);
+            (*id, (exact / sum) as i64, exact % sum)
+        })
+        .collect();
+    let mut leftover = total - shares.iter().map(|(_, share, _)| share).sum::<i64>();
+    let mut order: Vec<usize> = (0..shares.len()).collect();
+    order.sort_by(|a, b| shares[*b].2.cmp(&shares[*a].2));
+    for i in order {
+        if leftover == 0 {
+            break;
+        }
+        shares[i].1 += 1;
+        leftover -= 1;
+    }
+    shares
+        .into_iter()
+        .map(|(id, share, _)| (id, share))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amounts(postings: &[Posting]) -> Vec<(AccountKind, i64)> {
+        postings
+            .iter()
+            .map(|p| (p.account.kind, p.amount.amount_minor))
+            .collect()
+    }
+
+    #[test]
+    fn platform_fee_rounds_down() {
+        assert_eq!(platform_fee(10_000, 500), 500);
+        assert_eq!(platform_fee(999, 500), 49);
+        assert_eq!(platform_fee(19, 500), 0);
+        assert_eq!(platform_fee(10_000, 0), 0);
+        assert_eq!(platform_fee(10_000, 10_000), 10_000);
+        // No overflow on the largest amounts
+        assert_eq!(platform_fee(i64::MAX, 10_000), i64::MAX);
+    }
+
+    #[test]
+    fn payout_splits_the_fee_off() {
+        let escrow = AccountRef::escrow(Uuid::new_v4(), Currency::Usd);
+        let solver = AccountRef::user(Uuid::new_v4(), Currency::Usd);
+
+        let postings = payout_postings(escrow, solver, 999, 500);
+        assert_eq!(
+            amounts(&postings),
+            [
+                (AccountKind::Escrow, -999),
+                (AccountKind::User, 950),
+                (AccountKind::PlatformFee, 49),
+            ]
+        );
+        assert!(postings.iter().all(|p| p.amount.currency == Currency::Usd));
+        assert!(JournalEntry::new(EntryKind::Release, None, None, None, postings).is_ok());
+
+        // A fee rounding to zero is left out rather than posted as zero
+        for fee_bps in [0, 500] {
+            let postings = payout_postings(escrow, solver, 19, fee_bps);
+            assert_eq!(
+                amounts(&postings),
+                [(AccountKind::Escrow, -19), (AccountKind::User, 19)]
+            );
+            assert!(JournalEntry::new(EntryKind::Release, None, None, None, postings).is_ok());
+        }
+    }
+
+    #[test]
+    fn pro_rata_shares_add_up() {
+        let shares = pro_rata(1_000, &[('a', 1), ('b', 1), ('c', 1)]);
+        // The leftover unit goes to the first of the equal remainders
+        assert_eq!(shares, [('a', 334), ('b', 333), ('c', 333)]);
+
+        let shares = pro_rata(100, &[('a', 1), ('b', 2), ('c', 3)]);
+        // 16.67, 33.33, 50: the leftover unit goes to the largest remainder
+        assert_eq!(shares, [('a', 17), ('b', 33), ('c', 50)]);
+
+        let shares = pro_rata(10, &[('a', 3), ('b', 3), ('c', 3), ('d', 3)]);
+        assert_eq!(shares, [('a', 3), ('b', 3), ('c', 2), ('d', 2)]);
+
+        let shares = pro_rata(50, &[('a', 100), ('b', 0)]);
+        assert_eq!(shares, [('a', 50), ('b', 0)]);
+
+        // Partial refunds of large escrows do not overflow
+        let shares = pro_rata(i64::MAX, &[('a', i64::MAX), ('b', i64::MAX)]);
+        assert_eq!(
+            shares.iter().map(|(_, s)| i128::from(*s)).sum::<i128>(),
+            i128::from(i64::MAX)
+        );
+
+        assert!(pro_rata(10, &[('a', 0)]).is_empty());
+    }
+
+    #[test]
+    fn pro_rata_is_deterministic() {
+        let weights = [('a', 7), ('b', 7), ('c', 7)];
+        let first = pro_rata(100, &weights);
+        for _ in 0..10 {
+            assert_eq!(pro_rata(100, &weights), first);
+        }
+        assert_eq!(first.iter().map(|(_, s)| s).sum::<i64>(), 100);
+    }
+}
@@ -3,3 +3,31 @@ pub mod user_usecases;
 pub mod project_usecases;
 
 pub mod issue_usecases;
+
+pub mod session_usecases;
+
+pub mod search_usecases;
+
+pub mod ledger_usecases;
+
+pub mod claim_usecases;
+
+pub mod submission_usecases;
+
+pub mod dispute_usecases;
+
+pub mod comment_usecases;
+
+pub mod organization_usecases;
+
+pub mod github_webhook_usecases;
+
+pub mod webhook_usecases;
+
+pub mod outbox_usecases;
+
+pub mod job_usecases;
+
+pub mod notification_usecases;
+
+pub mod email_usecases;
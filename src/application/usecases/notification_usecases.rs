@@ -0,0 +1,352 @@
+use crate::application::policies::Actor;
+use crate::domain::entities::comment::CommentTarget;
+use crate::domain::entities::ledger::{AccountKind, Posting};
+use crate::domain::entities::notification::{
+    Notification, NotificationChannel, NotificationPreference, NotificationType,
+};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::{DomainEvent, EventSubscriber, OutboxEvent};
+use crate::domain::gateways::mailer::{EmailMessage, SharedEmailQueue};
+use crate::domain::pagination::{validate_page_size, Page};
+use crate::domain::repositories::notification_repository::{
+    NotificationQuery, NotificationRepository,
+};
+use crate::domain::repositories::project_repository::ProjectRepository;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// What the notifications created for one event point at.
+struct Subject {
+    actor_id: Option<Uuid>,
+    project_id: Uuid,
+    issue_id: Option<Uuid>,
+    subject_id: Uuid,
+}
+
+/// The notification center: notifications created from domain events for the
+/// users they concern, and each user's choice of channel per notification type.
+pub struct NotificationUsecases<N: NotificationRepository, P: ProjectRepository> {
+    repository: N,
+    project_repository: P,
+    email_queue: Option<SharedEmailQueue>,
+}
+
+impl<N: NotificationRepository, P: ProjectRepository> NotificationUsecases<N, P> {
+    pub fn new(repository: N, project_repository: P) -> Self {
+        Self {
+            repository,
+            project_repository,
+            email_queue: None,
+        }
+    }
+
+    /// Emails the notifications of users who chose the `email` channel. Without
+    /// it they only show up in the notification center.
+    pub fn with_email_queue(mut self, email_queue: SharedEmailQueue) -> Self {
+        self.email_queue = Some(email_queue);
+        self
+    }
+
+    pub async fn list_notifications(
+        &self,
+        actor: &Actor,
+        query: NotificationQuery,
+    ) -> Result<Page<Notification>, DomainError> {
+        validate_page_size(query.limit)?;
+        self.repository.list_notifications(actor.id, &query).await
+    }
+
+    pub async fn count_unread(&self, actor: &Actor) -> Result<i64, DomainError> {
+        self.repository.count_unread(actor.id).await
+    }
+
+    pub async fn mark_read(&self, notification_id: Uuid, actor: &Actor) -> Result<(), DomainError> {
+        if !self
+            .repository
+            .mark_read(actor.id, notification_id, Utc::now())
+            .await?
+        {
+            return Err(DomainError::NotFound("Notification not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Returns how many notifications were unread.
+    pub async fn mark_all_read(&self, actor: &Actor) -> Result<u64, DomainError> {
+        self.repository.mark_all_read(actor.id, Utc::now()).await
+    }
+
+    /// The channel of every notification type, defaults included.
+    pub async fn get_preferences(
+        &self,
+        actor: &Actor,
+    ) -> Result<Vec<NotificationPreference>, DomainError> {
+        let chosen = self.repository.get_preferences(actor.id).await?;
+        Ok(NotificationType::ALL
+            .into_iter()
+            .map(|notification_type| NotificationPreference {
+                notification_type,
+                channel: channel_of(&chosen, notification_type),
+            })
+            .collect())
+    }
+
+    /// Sets the channel of the listed types; the others keep theirs.
+    pub async fn update_preferences(
+        &self,
+        actor: &Actor,
+        preferences: Vec<NotificationPreference>,
+    ) -> Result<Vec<NotificationPreference>, DomainError> {
+        self.repository
+            .save_preferences(actor.id, &preferences)
+            .await?;
+        self.get_preferences(actor).await
+    }
+
+    async fn maintainers(
+        &self,
+        project_id: Uuid,
+        notification_type: NotificationType,
+    ) -> Result<Vec<(Uuid, NotificationType)>, DomainError> {
+        let ids = self
+            .project_repository
+            .get_maintainer_ids(project_id)
+            .await?;
+        Ok(ids.into_iter().map(|id| (id, notification_type)).collect())
+    }
+
+    /// Who `event` concerns and how, most specific first: a user listed twice only
+    /// counts with their first type.
+    async fn recipients(
+        &self,
+        event: &DomainEvent,
+    ) -> Result<Option<(Subject, Vec<(Uuid, NotificationType)>)>, DomainError> {
+        let found = match event {
+            DomainEvent::ClaimCreated { project_id, claim } => (
+                Subject {
+                    actor_id: Some(claim.claimant_id),
+                    project_id: *project_id,
+                    issue_id: Some(claim.issue_id),
+                    subject_id: claim.id,
+                },
+                self.maintainers(*project_id, NotificationType::ClaimCreated)
+                    .await?,
+            ),
+            DomainEvent::ClaimAccepted { project_id, claim }
+            | DomainEvent::ClaimRejected { project_id, claim } => {
+                let notification_type = match event {
+                    DomainEvent::ClaimAccepted { .. } => NotificationType::ClaimAccepted,
+                    _ => NotificationType::ClaimRejected,
+                };
+                (
+                    Subject {
+                        actor_id: None,
+                        project_id: *project_id,
+                        issue_id: Some(claim.issue_id),
+                        subject_id: claim.id,
+                    },
+                    vec![(claim.claimant_id, notification_type)],
+                )
+            }
+            DomainEvent::SubmissionCreated {
+                project_id,
+                submission,
+            } => (
+                Subject {
+                    actor_id: Some(submission.submitter_id),
+                    project_id: *project_id,
+                    issue_id: Some(submission.issue_id),
+                    subject_id: submission.id,
+                },
+                self.maintainers(*project_id, NotificationType::SubmissionCreated)
+                    .await?,
+            ),
+            DomainEvent::SubmissionApproved {
+                project_id,
+                submission,
+                review,
+            }
+            | DomainEvent::ChangesRequested {
+                project_id,
+                submission,
+                review,
+            } => {
+                let notification_type = match event {
+                    DomainEvent::SubmissionApproved { .. } => NotificationType::SubmissionApproved,
+                    _ => NotificationType::ChangesRequested,
+                };
+                (
+                    Subject {
+                        actor_id: review.reviewer_id,
+                        project_id: *project_id,
+                        issue_id: Some(submission.issue_id),
+                        subject_id: submission.id,
+                    },
+                    vec![(submission.submitter_id, notification_type)],
+                )
+            }
+            DomainEvent::CommentCreated {
+                project_id,
+                comment,
+                parent_author_id,
+            } => {
+                let mut recipients: Vec<_> = parent_author_id
+                    .map(|id| (id, NotificationType::CommentReply))
+                    .into_iter()
+                    .chain(
+                        comment
+                            .mentions
+                            .iter()
+                            .map(|id| (*id, NotificationType::Mentioned)),
+                    )
+                    .collect();
+                if comment.parent_id.is_none() {
+                    recipients.extend(
+                        self.maintainers(*project_id, NotificationType::CommentCreated)
+                            .await?,
+                    );
+                }
+                let issue_id = match comment.target {
+                    CommentTarget::Issue(id) => Some(id),
+                    CommentTarget::Project(_) => None,
+                };
+                (
+                    Subject {
+                        actor_id: comment.author_id,
+                        project_id: *project_id,
+                        issue_id,
+                        subject_id: comment.id,
+                    },
+                    recipients,
+                )
+            }
+            DomainEvent::BountyPaid { project_id, entry } => {
+                let payees = entry
+                    .postings
+                    .iter()
+                    .filter_map(payee)
+                    .map(|id| (id, NotificationType::BountyPaid))
+                    .collect();
+                (
+                    Subject {
+                        actor_id: entry.actor_id,
+                        project_id: *project_id,
+                        issue_id: entry.issue_id,
+                        subject_id: entry.id,
+                    },
+                    payees,
+                )
+            }
+            DomainEvent::UserRegistered { .. }
+            | DomainEvent::ProjectCreated { .. }
+            | DomainEvent::ProjectUpdated { .. }
+            | DomainEvent::IssueCreated { .. }
+            | DomainEvent::IssueUpdated { .. }
+            | DomainEvent::IssueStatusChanged { .. }
+            | DomainEvent::BountyFunded { .. }
+            | DomainEvent::BountyRefunded { .. } => return Ok(None),
+        };
+        Ok(Some(found))
+    }
+}
+
+#[async_trait]
+impl<N, P> EventSubscriber for NotificationUsecases<N, P>
+where
+    N: NotificationRepository + Send + Sync,
+    P: ProjectRepository + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    /// Users are not notified of their own actions, nor of types they turned off.
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), DomainError> {
+        let Some((subject, recipients)) = self.recipients(&event.event).await? else {
+            return Ok(());
+        };
+        let mut seen = HashSet::new();
+        let mut notifications = Vec::new();
+        let mut emails = Vec::new();
+        for (user_id, notification_type) in recipients {
+            if Some(user_id) == subject.actor_id || !seen.insert(user_id) {
+                continue;
+            }
+            let chosen = self.repository.get_preferences(user_id).await?;
+            match channel_of(&chosen, notification_type) {
+                NotificationChannel::Off => continue,
+                NotificationChannel::Email => emails.push((
+                    user_id,
+                    email_message(&event.event, user_id, notification_type),
+                )),
+                NotificationChannel::InApp => {}
+            }
+            notifications.push(Notification {
+                id: Uuid::new_v4(),
+                user_id,
+                notification_type,
+                event_id: event.id,
+                actor_id: subject.actor_id,
+                project_id: Some(subject.project_id),
+                issue_id: subject.issue_id,
+                subject_id: Some(subject.subject_id),
+                created_at: event.created_at,
+                read_at: None,
+            });
+        }
+        if notifications.is_empty() {
+            return Ok(());
+        }
+        self.repository.create_notifications(&notifications).await?;
+        // Keyed by event and user, so a retry of this event skips emails already queued
+        if let Some(email_queue) = &self.email_queue {
+            for (user_id, message) in emails {
+                let dedup_key = format!("notification:{}:{}", event.id, user_id);
+                email_queue
+                    .queue_email(user_id, message, &dedup_key)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The user paid by a posting, if it credits a user account.
+fn payee(posting: &Posting) -> Option<Uuid> {
+    if posting.account.kind == AccountKind::User && posting.amount.is_positive() {
+        posting.account.owner_id
+    } else {
+        None
+    }
+}
+
+/// The email sent for a notification: payouts say how much was paid, the other
+/// types only point at the notification center.
+fn email_message(
+    event: &DomainEvent,
+    user_id: Uuid,
+    notification_type: NotificationType,
+) -> EmailMessage {
+    if let DomainEvent::BountyPaid { entry, .. } = event {
+        let paid = entry.postings.iter().find(|p| payee(p) == Some(user_id));
+        if let (Some(issue_id), Some(posting)) = (entry.issue_id, paid) {
+            return EmailMessage::BountyPaid {
+                issue_id,
+                amount: posting.amount,
+            };
+        }
+    }
+    EmailMessage::Notification { notification_type }
+}
+
+fn channel_of(
+    chosen: &[NotificationPreference],
+    notification_type: NotificationType,
+) -> NotificationChannel {
+    chosen
+        .iter()
+        .find(|p| p.notification_type == notification_type)
+        .map_or(notification_type.default_channel(), |p| p.channel)
+}
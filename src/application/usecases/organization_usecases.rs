@@ -0,0 +1,376 @@
+use crate::application::policies::{self, Actor};
+use crate::application::usecases::ledger_usecases::LedgerUsecases;
+use crate::domain::entities::ledger::JournalEntry;
+use crate::domain::entities::organization::{Organization, OrganizationMember, OrganizationRole};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::repositories::ledger_repository::LedgerRepository;
+use crate::domain::repositories::organization_repository::OrganizationRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::value_objects::money::Money;
+use chrono::Utc;
+use uuid::Uuid;
+
+const MAX_SLUG_LENGTH: usize = 39;
+const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+
+pub struct OrganizationUsecases<
+    O: OrganizationRepository,
+    L: LedgerRepository,
+    R: IssueRepository,
+    P: ProjectRepository,
+> {
+    repository: O,
+    ledger: LedgerUsecases<L, R, P>,
+}
+
+impl<O: OrganizationRepository, L: LedgerRepository, R: IssueRepository, P: ProjectRepository>
+    OrganizationUsecases<O, L, R, P>
+{
+    /// `ledger` holds the organization's account and funds bounties from it.
+    pub fn new(repository: O, ledger: LedgerUsecases<L, R, P>) -> Self {
+        Self { repository, ledger }
+    }
+
+    /// Creates the organization with `actor` as its owner.
+    pub async fn create_organization(
+        &self,
+        actor: &Actor,
+        slug: String,
+        display_name: String,
+        description: Option<String>,
+    ) -> Result<Organization, DomainError> {
+        let slug = slug.trim().to_lowercase();
+        let display_name = display_name.trim().to_string();
+        let mut errors = Vec::new();
+        if let Err(message) = validate_slug(&slug) {
+            errors.push(FieldError::new("slug", message));
+        }
+        if let Err(message) = validate_display_name(&display_name) {
+            errors.push(FieldError::new("display_name", &message));
+        }
+        if !errors.is_empty() {
+            return Err(DomainError::Validation(errors));
+        }
+
+        let now = Utc::now();
+        let organization = Organization {
+            id: Uuid::new_v4(),
+            slug,
+            display_name,
+            description,
+            created_at: now,
+            updated_at: None,
+        };
+        let owner = OrganizationMember {
+            organization_id: organization.id,
+            user_id: actor.id,
+            role: OrganizationRole::Owner,
+            invited_by: None,
+            created_at: now,
+            accepted_at: Some(now),
+        };
+        self.repository
+            .create_organization(&organization, &owner)
+            .await?;
+        Ok(organization)
+    }
+
+    pub async fn get_organization(&self, slug: &str) -> Result<Organization, DomainError> {
+        self.repository
+            .get_organization_by_slug(&slug.to_lowercase())
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Organization not found".to_string()))
+    }
+
+    /// The role `actor` holds in the organization, if they are an accepted member.
+    async fn get_role(
+        &self,
+        organization_id: Uuid,
+        actor: &Actor,
+    ) -> Result<Option<OrganizationRole>, DomainError> {
+        Ok(self
+            .repository
+            .get_member(organization_id, actor.id)
+            .await?
+            .filter(OrganizationMember::is_active)
+            .map(|m| m.role))
+    }
+
+    /// Loads the organization and checks that `actor` may manage it.
+    async fn get_managed_organization(
+        &self,
+        slug: &str,
+        actor: &Actor,
+    ) -> Result<Organization, DomainError> {
+        let organization = self.get_organization(slug).await?;
+        let role = self.get_role(organization.id, actor).await?;
+        if !policies::can_manage_organization(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only organization owners and admins can do this".to_string(),
+            ));
+        }
+        Ok(organization)
+    }
+
+    pub async fn update_organization(
+        &self,
+        slug: &str,
+        actor: &Actor,
+        new_display_name: Option<String>,
+        new_description: Option<String>,
+    ) -> Result<Organization, DomainError> {
+        let mut organization = self.get_managed_organization(slug, actor).await?;
+        if let Some(display_name) = new_display_name {
+            let display_name = display_name.trim().to_string();
+            validate_display_name(&display_name).map_err(|message| {
+                DomainError::Validation(vec![FieldError::new("display_name", &message)])
+            })?;
+            organization.display_name = display_name;
+        }
+        if let Some(description) = new_description {
+            organization.description = Some(description);
+        }
+        organization.updated_at = Some(Utc::now());
+        self.repository.update_organization(&organization).await?;
+        Ok(organization)
+    }
+
+    /// Only possible once the organization owns no projects and its account is empty.
+    pub async fn delete_organization(&self, slug: &str, actor: &Actor) -> Result<(), DomainError> {
+        let organization = self.get_organization(slug).await?;
+        let role = self.get_role(organization.id, actor).await?;
+        if !policies::can_delete_organization(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only organization owners can delete it".to_string(),
+            ));
+        }
+        let balances = self
+            .ledger
+            .get_organization_balances(organization.id)
+            .await?;
+        if balances.iter().any(Money::is_positive) {
+            return Err(DomainError::Conflict(
+                "Organization still holds funds".to_string(),
+            ));
+        }
+        self.repository.delete_organization(organization.id).await
+    }
+
+    /// Pending invitations are only listed for those who can manage them and for
+    /// the invitee.
+    pub async fn get_members(
+        &self,
+        slug: &str,
+        viewer: Option<&Actor>,
+    ) -> Result<Vec<OrganizationMember>, DomainError> {
+        let organization = self.get_organization(slug).await?;
+        let role = match viewer {
+            Some(actor) => self.get_role(organization.id, actor).await?,
+            None => None,
+        };
+        let members = self.repository.get_members(organization.id).await?;
+        Ok(members
+            .into_iter()
+            .filter(|m| {
+                m.is_active()
+                    || viewer.is_some_and(|actor| {
+                        actor.id == m.user_id
+                            || policies::can_manage_organization_member(actor, role, m.role)
+                    })
+            })
+            .collect())
+    }
+
+    async fn get_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<OrganizationMember, DomainError> {
+        self.repository
+            .get_member(organization_id, user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Member not found".to_string()))
+    }
+
+    /// Invites `user_id` as `role`; the role is only granted once accepted.
+    pub async fn invite_member(
+        &self,
+        slug: &str,
+        actor: &Actor,
+        user_id: Uuid,
+        role: OrganizationRole,
+    ) -> Result<OrganizationMember, DomainError> {
+        let organization = self.get_organization(slug).await?;
+        let actor_role = self.get_role(organization.id, actor).await?;
+        if !policies::can_manage_organization_member(actor, actor_role, role) {
+            return Err(DomainError::Forbidden(format!(
+                "You cannot invite an organization {}",
+                role.as_str()
+            )));
+        }
+
+        let member = OrganizationMember {
+            organization_id: organization.id,
+            user_id,
+            role,
+            invited_by: Some(actor.id),
+            created_at: Utc::now(),
+            accepted_at: None,
+        };
+        self.repository.add_member(&member).await?;
+        Ok(member)
+    }
+
+    pub async fn accept_invitation(
+        &self,
+        slug: &str,
+        actor: &Actor,
+        user_id: Uuid,
+    ) -> Result<OrganizationMember, DomainError> {
+        if actor.id != user_id {
+            return Err(DomainError::Forbidden(
+                "Only the invitee can accept an invitation".to_string(),
+            ));
+        }
+        let organization = self.get_organization(slug).await?;
+        let mut member = self.get_member(organization.id, user_id).await?;
+        if member.is_active() {
+            return Err(DomainError::Conflict(
+                "Invitation was already accepted".to_string(),
+            ));
+        }
+        member.accepted_at = Some(Utc::now());
+        self.repository.update_member(&member).await?;
+        Ok(member)
+    }
+
+    pub async fn change_member_role(
+        &self,
+        slug: &str,
+        actor: &Actor,
+        user_id: Uuid,
+        role: OrganizationRole,
+    ) -> Result<OrganizationMember, DomainError> {
+        let organization = self.get_organization(slug).await?;
+        let mut member = self.get_member(organization.id, user_id).await?;
+        let actor_role = self.get_role(organization.id, actor).await?;
+        if !policies::can_manage_organization_member(actor, actor_role, member.role)
+            || !policies::can_manage_organization_member(actor, actor_role, role)
+        {
+            return Err(DomainError::Forbidden(
+                "You cannot change this member's role".to_string(),
+            ));
+        }
+        if member.role == OrganizationRole::Owner && role != OrganizationRole::Owner {
+            self.ensure_other_owner(&member).await?;
+        }
+        member.role = role;
+        self.repository.update_member(&member).await?;
+        Ok(member)
+    }
+
+    /// Removes a member or withdraws an invitation. Members can also remove
+    /// themselves, which is how an invitation is declined.
+    pub async fn remove_member(
+        &self,
+        slug: &str,
+        actor: &Actor,
+        user_id: Uuid,
+    ) -> Result<(), DomainError> {
+        let organization = self.get_organization(slug).await?;
+        let member = self.get_member(organization.id, user_id).await?;
+        if actor.id != user_id {
+            let actor_role = self.get_role(organization.id, actor).await?;
+            if !policies::can_manage_organization_member(actor, actor_role, member.role) {
+                return Err(DomainError::Forbidden(
+                    "You cannot remove this member".to_string(),
+                ));
+            }
+        }
+        if member.role == OrganizationRole::Owner {
+            self.ensure_other_owner(&member).await?;
+        }
+        self.repository
+            .remove_member(organization.id, user_id)
+            .await
+    }
+
+    /// An organization always keeps at least one accepted owner.
+    async fn ensure_other_owner(&self, owner: &OrganizationMember) -> Result<(), DomainError> {
+        let members = self.repository.get_members(owner.organization_id).await?;
+        let has_other = members.iter().any(|m| {
+            m.user_id != owner.user_id && m.role == OrganizationRole::Owner && m.is_active()
+        });
+        if !has_other {
+            return Err(DomainError::Conflict(
+                "The organization must keep at least one owner".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Balances of the organization's account, visible to its owners and admins.
+    pub async fn get_balances(&self, slug: &str, actor: &Actor) -> Result<Vec<Money>, DomainError> {
+        let organization = self.get_managed_organization(slug, actor).await?;
+        self.ledger.get_organization_balances(organization.id).await
+    }
+
+    /// Records a payment into the organization's account received outside the
+    /// platform. Only admins can do this.
+    pub async fn deposit(
+        &self,
+        slug: &str,
+        actor: &Actor,
+        amount: &str,
+        currency: &str,
+    ) -> Result<JournalEntry, DomainError> {
+        let organization = self.get_organization(slug).await?;
+        self.ledger
+            .deposit_to_organization(organization.id, actor, amount, currency)
+            .await
+    }
+
+    /// Funds an issue's escrow from the organization's account. Refunds of that
+    /// escrow go back to the organization.
+    pub async fn fund_issue(
+        &self,
+        slug: &str,
+        issue_id: Uuid,
+        actor: &Actor,
+        amount: &str,
+        currency: &str,
+    ) -> Result<JournalEntry, DomainError> {
+        let organization = self.get_managed_organization(slug, actor).await?;
+        self.ledger
+            .fund_issue_from_organization(issue_id, actor, organization.id, amount, currency)
+            .await
+    }
+}
+
+/// Lowercase letters, digits and hyphens, neither starting nor ending with a hyphen.
+fn validate_slug(slug: &str) -> Result<(), &'static str> {
+    if slug.is_empty() || slug.len() > MAX_SLUG_LENGTH {
+        return Err("must be between 1 and 39 characters");
+    }
+    let valid_chars = slug
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_chars || slug.starts_with('-') || slug.ends_with('-') {
+        return Err("may only contain letters, digits and inner hyphens");
+    }
+    Ok(())
+}
+
+fn validate_display_name(display_name: &str) -> Result<(), String> {
+    if display_name.is_empty() {
+        return Err("cannot be empty".to_string());
+    }
+    if display_name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+        return Err(format!(
+            "must be at most {} characters",
+            MAX_DISPLAY_NAME_LENGTH
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::{OutboxEvent, SharedEventSubscriber};
+use crate::domain::repositories::outbox_repository::OutboxRepository;
+use chrono::{Duration, Utc};
+
+/// How long a claimed event is held back from other dispatchers while it is handled.
+const DISPATCH_LEASE_MINUTES: i64 = 5;
+const RETRY_BASE_SECONDS: i64 = 5;
+const RETRY_MAX_SECONDS: i64 = 60 * 60;
+
+/// Hands the events recorded in the outbox to the in-process subscribers.
+pub struct OutboxUsecases<O: OutboxRepository> {
+    repository: O,
+    subscribers: Vec<SharedEventSubscriber>,
+}
+
+impl<O: OutboxRepository> OutboxUsecases<O> {
+    pub fn new(repository: O) -> Self {
+        Self {
+            repository,
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Adds a subscriber receiving every event; it is told apart by its name.
+    pub fn with_subscriber(mut self, subscriber: SharedEventSubscriber) -> Self {
+        self.subscribers.push(subscriber);
+        self
+    }
+
+    /// Hands up to `limit` due events, oldest first, to the subscribers that have
+    /// not handled them yet. An event is done once every subscriber succeeded;
+    /// otherwise the ones that failed get it again after an exponential backoff,
+    /// with no limit on the attempts. Returns how many events were claimed.
+    pub async fn dispatch_due(&self, limit: u32) -> Result<usize, DomainError> {
+        let now = Utc::now();
+        let claimed = self
+            .repository
+            .claim_due_events(now, now + Duration::minutes(DISPATCH_LEASE_MINUTES), limit)
+            .await?;
+
+        let dispatched = claimed.len();
+        for mut event in claimed {
+            let mut errors = Vec::new();
+            for subscriber in &self.subscribers {
+                let name = subscriber.name();
+                if event.handled_by.iter().any(|handled| handled == name) {
+                    continue;
+                }
+                match subscriber.handle(&event).await {
+                    Ok(()) => event.handled_by.push(name.to_string()),
+                    Err(e) => errors.push(format!("{}: {}", name, e)),
+                }
+            }
+
+            if errors.is_empty() {
+                self.repository
+                    .mark_dispatched(event.id, Utc::now())
+                    .await?;
+                continue;
+            }
+            let error = errors.join("; ");
+            tracing::warn!(
+                "Event {} ({}) failed for a subscriber: {}",
+                event.id,
+                event.event.name(),
+                error
+            );
+            event.attempts += 1;
+            let next_attempt_at = Utc::now() + retry_delay(&event);
+            self.repository
+                .record_failure(&event, next_attempt_at, &error)
+                .await?;
+        }
+        Ok(dispatched)
+    }
+
+    /// Deletes events dispatched more than `max_age` ago. Returns how many were deleted.
+    pub async fn purge_dispatched(&self, max_age: Duration) -> Result<u64, DomainError> {
+        self.repository.purge_dispatched(Utc::now() - max_age).await
+    }
+}
+
+/// Waits 5s after the first failure, doubling up to an hour.
+fn retry_delay(event: &OutboxEvent) -> Duration {
+    let exponent = (event.attempts - 1).clamp(0, 20) as u32;
+    Duration::seconds((RETRY_BASE_SECONDS << exponent).min(RETRY_MAX_SECONDS))
+}
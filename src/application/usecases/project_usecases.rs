@@ -1,47 +1,130 @@
-use crate::domain::entities::project::Project;
-use crate::domain::errors::domain_error::DomainError;
-use crate::domain::repositories::project_repository::ProjectRepository;
-use chrono::Utc;
+use crate::application::policies::{self, Actor};
+use crate::domain::entities::project::{Project, ProjectOwner};
+use crate::domain::entities::project_member::{ProjectMember, ProjectRole};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::DomainEvent;
+use crate::domain::gateways::repository_host::{RepositoryStats, SharedRepositoryHost};
+use crate::domain::pagination::{validate_page_size, Page};
+use crate::domain::repositories::project_repository::{ProjectQuery, ProjectRepository};
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 
 pub struct ProjectUsecases<R: ProjectRepository> {
     repository: R,
+    repository_host: Option<SharedRepositoryHost>,
 }
 
 impl<R: ProjectRepository> ProjectUsecases<R> {
     pub fn new(repository: R) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            repository_host: None,
+        }
+    }
+
+    /// Enables `github_link` validation on create/update and `sync_repo_stats`.
+    pub fn with_repository_host(mut self, repository_host: SharedRepositoryHost) -> Self {
+        self.repository_host = Some(repository_host);
+        self
     }
 
+    /// Creates a project owned by `owner`: either `actor` themselves or an
+    /// organization `actor` manages.
     pub async fn create_project(
         &self,
-        owner_id: Uuid,
+        actor: &Actor,
+        owner: ProjectOwner,
         name: String,
         description: Option<String>,
         github_link: Option<String>,
         tags: Vec<String>,
     ) -> Result<Project, DomainError> {
         if name.is_empty() {
-            return Err(DomainError::InvalidData(
-                "Project name cannot be empty".to_string(),
-            ));
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "name",
+                "cannot be empty",
+            )]));
         }
+        self.check_can_own(actor, owner).await?;
+        let (github_link, repo_stats) = match github_link {
+            Some(link) => {
+                let (link, stats) = self.check_repository_link(&link).await?;
+                (Some(link), stats)
+            }
+            None => (None, None),
+        };
 
         let project = Project {
             id: Uuid::new_v4(),
-            owner_id,
+            owner,
             name,
             description,
             github_link,
             tags,
+            hidden: false,
             created_at: Utc::now(),
             updated_at: None,
+            repo_stats,
         };
 
-        self.repository.create_project(&project).await?;
+        let events = [DomainEvent::ProjectCreated {
+            project: project.clone(),
+        }];
+        self.repository.create_project(&project, &events).await?;
+        if let Some(stats) = &project.repo_stats {
+            self.repository.save_repo_stats(project.id, stats).await?;
+        }
         Ok(project)
     }
 
+    /// Checks that `link` points to an existing repository on the configured host
+    /// and returns its canonical form. Stats are returned when the host could be
+    /// reached; otherwise the next sync fills them in.
+    async fn check_repository_link(
+        &self,
+        link: &str,
+    ) -> Result<(String, Option<RepositoryStats>), DomainError> {
+        let Some(host) = &self.repository_host else {
+            return Ok((link.to_string(), None));
+        };
+        let invalid =
+            |message: &str| DomainError::Validation(vec![FieldError::new("github_link", message)]);
+        let repository = host.parse_link(link).ok_or_else(|| {
+            invalid("must link to a GitHub repository (https://github.com/owner/repo)")
+        })?;
+        match host.fetch_stats(&repository).await {
+            Ok(stats) => Ok((host.link(&repository), Some(stats))),
+            Err(DomainError::NotFound(_)) => {
+                Err(invalid("repository does not exist or is not public"))
+            }
+            Err(e) => {
+                tracing::warn!("Could not check repository {}: {}", link, e);
+                Ok((host.link(&repository), None))
+            }
+        }
+    }
+
+    /// Users can only create projects for themselves; organizations need an owner or
+    /// admin of theirs.
+    async fn check_can_own(&self, actor: &Actor, owner: ProjectOwner) -> Result<(), DomainError> {
+        let allowed = match owner {
+            ProjectOwner::User(user_id) => user_id == actor.id,
+            ProjectOwner::Organization(organization_id) => {
+                let role = self
+                    .repository
+                    .get_organization_role(organization_id, actor.id)
+                    .await?;
+                policies::can_manage_organization(actor, role)
+            }
+        };
+        if !allowed {
+            return Err(DomainError::Forbidden(
+                "Only organization owners and admins can give it projects".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     pub async fn get_project(&self, project_id: Uuid) -> Result<Project, DomainError> {
         if let Some(project) = self.repository.get_project_by_id(project_id).await? {
             Ok(project)
@@ -50,19 +133,66 @@ impl<R: ProjectRepository> ProjectUsecases<R> {
         }
     }
 
+    /// Like `get_project`, but hidden projects are only returned to their members and staff.
+    pub async fn view_project(
+        &self,
+        project_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<Project, DomainError> {
+        let project = self.get_project(project_id).await?;
+        let role = member_role(&self.repository, project.id, viewer).await?;
+        if project.hidden && !policies::can_view_hidden(viewer, role) {
+            return Err(DomainError::NotFound("Project not found".to_string()));
+        }
+        Ok(project)
+    }
+
     pub async fn get_projects_by_owner(&self, owner_id: Uuid) -> Result<Vec<Project>, DomainError> {
         self.repository.get_projects_by_owner(owner_id).await
     }
 
+    /// Loads the project and checks that `actor` is allowed to modify it.
+    async fn get_editable_project(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+    ) -> Result<Project, DomainError> {
+        let project = self.get_project(project_id).await?;
+        let role = member_role(&self.repository, project.id, Some(actor)).await?;
+        if !policies::can_edit_project(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only project maintainers or an admin can modify this project".to_string(),
+            ));
+        }
+        Ok(project)
+    }
+
+    /// Loads the project and checks that `actor` may delete or transfer it.
+    async fn get_owned_project(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+    ) -> Result<Project, DomainError> {
+        let project = self.get_project(project_id).await?;
+        let role = member_role(&self.repository, project.id, Some(actor)).await?;
+        if !policies::can_delete_project(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only the project owner or an admin can do this".to_string(),
+            ));
+        }
+        Ok(project)
+    }
+
     pub async fn update_project(
         &self,
         project_id: Uuid,
+        actor: &Actor,
         new_name: Option<String>,
         new_description: Option<String>,
         new_github_link: Option<String>,
         new_tags: Option<Vec<String>>,
     ) -> Result<Project, DomainError> {
-        let mut project = self.get_project(project_id).await?;
+        let mut project = self.get_editable_project(project_id, actor).await?;
 
         if let Some(name) = new_name {
             if !name.is_empty() {
@@ -72,23 +202,319 @@ impl<R: ProjectRepository> ProjectUsecases<R> {
         if let Some(description) = new_description {
             project.description = Some(description);
         }
+        let mut new_repo_stats = None;
         if let Some(github_link) = new_github_link {
-            project.github_link = Some(github_link);
+            let github_link = if github_link.trim().is_empty() {
+                None
+            } else {
+                let (link, stats) = self.check_repository_link(&github_link).await?;
+                new_repo_stats = Some(stats);
+                Some(link)
+            };
+            if github_link != project.github_link {
+                project.github_link = github_link;
+                project.repo_stats = None;
+            }
         }
         if let Some(tags) = new_tags {
             project.tags = tags;
         }
 
         project.updated_at = Some(Utc::now());
-        self.repository.update_project(&project).await?;
+        if project.repo_stats.is_none() {
+            self.repository.delete_repo_stats(project.id).await?;
+        }
+        if let Some(Some(stats)) = new_repo_stats {
+            self.repository.save_repo_stats(project.id, &stats).await?;
+            project.repo_stats = Some(stats);
+        }
+        let events = [DomainEvent::ProjectUpdated {
+            project: project.clone(),
+        }];
+        self.repository.update_project(&project, &events).await?;
+        Ok(project)
+    }
+
+    /// Refreshes the repository stats of up to `limit` projects that were not
+    /// checked within `max_age`. Failures are recorded on the project and retried
+    /// once `max_age` has passed again. Returns how many projects were synced.
+    pub async fn sync_repo_stats(
+        &self,
+        max_age: Duration,
+        limit: u32,
+    ) -> Result<usize, DomainError> {
+        let Some(host) = &self.repository_host else {
+            return Err(DomainError::Infra(
+                "Repository host is not configured".to_string(),
+            ));
+        };
+        let projects = self
+            .repository
+            .get_projects_due_for_sync(Utc::now() - max_age, limit)
+            .await?;
+
+        let mut synced = 0;
+        for project in projects {
+            let Some(link) = &project.github_link else {
+                continue;
+            };
+            let result = match host.parse_link(link) {
+                Some(repository) => host.fetch_stats(&repository).await,
+                None => Err(DomainError::InvalidData(
+                    "Not a GitHub repository link".to_string(),
+                )),
+            };
+            match result {
+                Ok(stats) => {
+                    self.repository.save_repo_stats(project.id, &stats).await?;
+                    synced += 1;
+                }
+                Err(e) => {
+                    self.repository
+                        .record_repo_sync_error(project.id, Utc::now(), &e.to_string())
+                        .await?;
+                }
+            }
+        }
+        Ok(synced)
+    }
+
+    pub async fn set_project_hidden(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+        hidden: bool,
+    ) -> Result<Project, DomainError> {
+        if !policies::can_hide_content(actor) {
+            return Err(DomainError::Forbidden(
+                "Only moderators can hide content".to_string(),
+            ));
+        }
+        let mut project = self.get_project(project_id).await?;
+        self.repository
+            .set_project_hidden(project_id, hidden)
+            .await?;
+        project.hidden = hidden;
         Ok(project)
     }
 
-    pub async fn delete_project(&self, project_id: Uuid) -> Result<(), DomainError> {
+    pub async fn delete_project(&self, project_id: Uuid, actor: &Actor) -> Result<(), DomainError> {
+        self.get_owned_project(project_id, actor).await?;
         self.repository.delete_project(project_id).await
     }
 
-    pub async fn list_projects(&self) -> Result<Vec<Project>, DomainError> {
-        self.repository.list_projects().await
+    /// Hidden projects are only listed for staff.
+    pub async fn list_projects(
+        &self,
+        viewer: Option<&Actor>,
+        mut query: ProjectQuery,
+    ) -> Result<Page<Project>, DomainError> {
+        validate_page_size(query.limit)?;
+        query.include_hidden = viewer.is_some_and(|a| a.role.is_staff());
+        self.repository.list_projects(&query).await
+    }
+
+    /// Pending invitations are only listed for those who can manage them and for
+    /// the invitee.
+    pub async fn get_members(
+        &self,
+        project_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<Vec<ProjectMember>, DomainError> {
+        let project = self.view_project(project_id, viewer).await?;
+        let role = member_role(&self.repository, project.id, viewer).await?;
+        let members = self.repository.get_members(project.id).await?;
+        Ok(members
+            .into_iter()
+            .filter(|m| {
+                m.is_active()
+                    || viewer.is_some_and(|actor| {
+                        actor.id == m.user_id || policies::can_manage_member(actor, role, m.role)
+                    })
+            })
+            .collect())
+    }
+
+    /// Loads the member `user_id` of `project_id`, pending or not.
+    async fn get_member(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<ProjectMember, DomainError> {
+        self.repository
+            .get_member(project_id, user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Member not found".to_string()))
+    }
+
+    /// Invites `user_id` to the project as `role`; the role is only granted once the
+    /// invitation is accepted.
+    pub async fn invite_member(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+        user_id: Uuid,
+        role: ProjectRole,
+    ) -> Result<ProjectMember, DomainError> {
+        if role == ProjectRole::Owner {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "role",
+                "must be maintainer or triager; use an ownership transfer instead",
+            )]));
+        }
+        let project = self.get_project(project_id).await?;
+        let actor_role = member_role(&self.repository, project.id, Some(actor)).await?;
+        if !policies::can_manage_member(actor, actor_role, role) {
+            return Err(DomainError::Forbidden(format!(
+                "You cannot invite a {}",
+                role.as_str()
+            )));
+        }
+
+        let member = ProjectMember {
+            project_id: project.id,
+            user_id,
+            role,
+            invited_by: Some(actor.id),
+            created_at: Utc::now(),
+            accepted_at: None,
+        };
+        self.repository.add_member(&member).await?;
+        Ok(member)
+    }
+
+    /// Accepts the invitation `actor` received to the project.
+    pub async fn accept_invitation(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+        user_id: Uuid,
+    ) -> Result<ProjectMember, DomainError> {
+        if actor.id != user_id {
+            return Err(DomainError::Forbidden(
+                "Only the invitee can accept an invitation".to_string(),
+            ));
+        }
+        let mut member = self.get_member(project_id, user_id).await?;
+        if member.is_active() {
+            return Err(DomainError::Conflict(
+                "Invitation was already accepted".to_string(),
+            ));
+        }
+        member.accepted_at = Some(Utc::now());
+        self.repository.update_member(&member).await?;
+        Ok(member)
+    }
+
+    /// Moves a member (or a pending invitation) to another role below the owner.
+    pub async fn change_member_role(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+        user_id: Uuid,
+        role: ProjectRole,
+    ) -> Result<ProjectMember, DomainError> {
+        if role == ProjectRole::Owner {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "role",
+                "must be maintainer or triager; use an ownership transfer instead",
+            )]));
+        }
+        let mut member = self.get_member(project_id, user_id).await?;
+        let actor_role = member_role(&self.repository, project_id, Some(actor)).await?;
+        if !policies::can_manage_member(actor, actor_role, member.role)
+            || !policies::can_manage_member(actor, actor_role, role)
+        {
+            return Err(DomainError::Forbidden(
+                "You cannot change this member's role".to_string(),
+            ));
+        }
+        member.role = role;
+        self.repository.update_member(&member).await?;
+        Ok(member)
     }
+
+    /// Removes a member or withdraws an invitation. Members can also remove
+    /// themselves, which is how an invitation is declined. The owner has to
+    /// transfer the project first.
+    pub async fn remove_member(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+        user_id: Uuid,
+    ) -> Result<(), DomainError> {
+        let member = self.get_member(project_id, user_id).await?;
+        if member.role == ProjectRole::Owner {
+            return Err(DomainError::Conflict(
+                "The owner cannot leave the project; transfer it first".to_string(),
+            ));
+        }
+        if actor.id != user_id {
+            let actor_role = member_role(&self.repository, project_id, Some(actor)).await?;
+            if !policies::can_manage_member(actor, actor_role, member.role) {
+                return Err(DomainError::Forbidden(
+                    "You cannot remove this member".to_string(),
+                ));
+            }
+        }
+        self.repository.remove_member(project_id, user_id).await
+    }
+
+    /// Hands the project to a user who is already one of its members, or to an
+    /// organization `actor` manages. A previous owning user stays on as a maintainer.
+    pub async fn transfer_ownership(
+        &self,
+        project_id: Uuid,
+        actor: &Actor,
+        new_owner: ProjectOwner,
+    ) -> Result<Project, DomainError> {
+        let project = self.get_owned_project(project_id, actor).await?;
+        if project.owner == new_owner {
+            return Err(DomainError::Conflict(
+                "The project already has this owner".to_string(),
+            ));
+        }
+        match new_owner {
+            ProjectOwner::User(user_id) => {
+                let member = self.get_member(project.id, user_id).await?;
+                if !member.is_active() {
+                    return Err(DomainError::Conflict(
+                        "The new owner must accept their invitation first".to_string(),
+                    ));
+                }
+            }
+            ProjectOwner::Organization(_) => self.check_can_own(actor, new_owner).await?,
+        }
+        self.repository
+            .transfer_ownership(project.id, new_owner)
+            .await?;
+        self.get_project(project.id).await
+    }
+}
+
+/// The role `actor` holds in the project, either as a member or through the
+/// organization that owns it, whichever is higher; `None` for anonymous viewers,
+/// outsiders and pending invitations.
+pub async fn member_role<P: ProjectRepository>(
+    repository: &P,
+    project_id: Uuid,
+    actor: Option<&Actor>,
+) -> Result<Option<ProjectRole>, DomainError> {
+    let Some(actor) = actor else {
+        return Ok(None);
+    };
+    let member = repository
+        .get_member(project_id, actor.id)
+        .await?
+        .filter(ProjectMember::is_active)
+        .map(|m| m.role);
+    let inherited = repository
+        .get_owner_organization_role(project_id, actor.id)
+        .await?
+        .map(|r| r.project_role());
+    Ok(match (member, inherited) {
+        (Some(a), Some(b)) if b.outranks(a) => Some(b),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    })
 }
@@ -0,0 +1,45 @@
+use crate::application::policies::Actor;
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::pagination::validate_page_size;
+use crate::domain::repositories::search_repository::{
+    SearchQuery, SearchRepository, SearchResults,
+};
+
+const MAX_QUERY_LENGTH: usize = 200;
+
+pub struct SearchUsecases<R: SearchRepository> {
+    repository: R,
+}
+
+impl<R: SearchRepository> SearchUsecases<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Hidden projects and issues are only searched on behalf of staff and of
+    /// the members of their project.
+    pub async fn search(
+        &self,
+        viewer: Option<&Actor>,
+        mut query: SearchQuery,
+    ) -> Result<SearchResults, DomainError> {
+        query.text = query.text.trim().to_string();
+        if query.text.is_empty() {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "q",
+                "cannot be empty",
+            )]));
+        }
+        if query.text.chars().count() > MAX_QUERY_LENGTH {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "q",
+                &format!("must be at most {} characters", MAX_QUERY_LENGTH),
+            )]));
+        }
+        validate_page_size(query.limit)?;
+
+        query.include_hidden = viewer.is_some_and(|a| a.role.is_staff());
+        query.viewer_id = viewer.map(|a| a.id);
+        self.repository.search(&query).await
+    }
+}
@@ -0,0 +1,254 @@
+use crate::domain::entities::refresh_token::RefreshToken;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+pub struct SessionUsecases<R: RefreshTokenRepository> {
+    repository: R,
+    refresh_token_ttl: Duration,
+}
+
+impl<R: RefreshTokenRepository> SessionUsecases<R> {
+    pub fn new(repository: R, refresh_token_ttl: Duration) -> Self {
+        Self {
+            repository,
+            refresh_token_ttl,
+        }
+    }
+
+    /// Starts a new session (token family) for `user_id` and returns the raw refresh token.
+    pub async fn issue_refresh_token(&self, user_id: Uuid) -> Result<String, DomainError> {
+        let (raw_token, token) = self.new_token(user_id, Uuid::new_v4());
+        self.repository.create_token(&token).await?;
+        Ok(raw_token)
+    }
+
+    /// Exchanges a refresh token for a new one. Presenting a token that was already
+    /// rotated or revoked revokes its whole family.
+    pub async fn rotate_refresh_token(
+        &self,
+        raw_token: &str,
+    ) -> Result<(Uuid, String), DomainError> {
+        let current = self
+            .repository
+            .get_token_by_hash(&hash_token(raw_token))
+            .await?
+            .ok_or_else(|| DomainError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        if current.revoked_at.is_some() {
+            if current.replaced_by.is_none() {
+                return Err(DomainError::Unauthorized(
+                    "Refresh token revoked".to_string(),
+                ));
+            }
+            // Already rotated once: someone is replaying a stolen token.
+            self.repository.revoke_family(current.family_id).await?;
+            return Err(DomainError::Unauthorized(
+                "Refresh token reuse detected".to_string(),
+            ));
+        }
+        if current.expires_at <= Utc::now() {
+            return Err(DomainError::Unauthorized(
+                "Refresh token expired".to_string(),
+            ));
+        }
+
+        let (raw_token, next) = self.new_token(current.user_id, current.family_id);
+        if !self.repository.rotate_token(current.id, &next).await? {
+            // Lost a race against another use of the same token.
+            self.repository.revoke_family(current.family_id).await?;
+            return Err(DomainError::Unauthorized(
+                "Refresh token reuse detected".to_string(),
+            ));
+        }
+        Ok((current.user_id, raw_token))
+    }
+
+    /// Ends the session the refresh token belongs to. Unknown tokens are ignored.
+    pub async fn revoke_refresh_token(&self, raw_token: &str) -> Result<(), DomainError> {
+        if let Some(token) = self
+            .repository
+            .get_token_by_hash(&hash_token(raw_token))
+            .await?
+        {
+            self.repository.revoke_family(token.family_id).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn revoke_all_sessions(&self, user_id: Uuid) -> Result<(), DomainError> {
+        self.repository.revoke_all_for_user(user_id).await
+    }
+
+    fn new_token(&self, user_id: Uuid, family_id: Uuid) -> (String, RefreshToken) {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let raw_token = hex::encode(bytes);
+
+        let now = Utc::now();
+        let token = RefreshToken {
+            id: Uuid::new_v4(),
+            user_id,
+            family_id,
+            token_hash: hash_token(&raw_token),
+            expires_at: now + self.refresh_token_ttl,
+            created_at: now,
+            revoked_at: None,
+            replaced_by: None,
+        };
+        (raw_token, token)
+    }
+}
+
+fn hash_token(raw_token: &str) -> String {
+    hex::encode(Sha256::digest(raw_token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use axum::http::StatusCode;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockRefreshTokenRepository {
+        tokens: Mutex<Vec<RefreshToken>>,
+    }
+
+    impl MockRefreshTokenRepository {
+        fn family(&self, family_id: Uuid) -> Vec<RefreshToken> {
+            let tokens = self.tokens.lock().unwrap();
+            tokens
+                .iter()
+                .filter(|t| t.family_id == family_id)
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl RefreshTokenRepository for MockRefreshTokenRepository {
+        async fn create_token(&self, token: &RefreshToken) -> Result<(), DomainError> {
+            self.tokens.lock().unwrap().push(token.clone());
+            Ok(())
+        }
+
+        async fn get_token_by_hash(
+            &self,
+            token_hash: &str,
+        ) -> Result<Option<RefreshToken>, DomainError> {
+            let tokens = self.tokens.lock().unwrap();
+            Ok(tokens.iter().find(|t| t.token_hash == token_hash).cloned())
+        }
+
+        async fn rotate_token(
+            &self,
+            old_token_id: Uuid,
+            new_token: &RefreshToken,
+        ) -> Result<bool, DomainError> {
+            let mut tokens = self.tokens.lock().unwrap();
+            let old = tokens
+                .iter_mut()
+                .find(|t| t.id == old_token_id && t.revoked_at.is_none());
+            match old {
+                Some(old) => {
+                    old.revoked_at = Some(Utc::now());
+                    old.replaced_by = Some(new_token.id);
+                    tokens.push(new_token.clone());
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn revoke_family(&self, family_id: Uuid) -> Result<(), DomainError> {
+            let mut tokens = self.tokens.lock().unwrap();
+            for token in tokens.iter_mut().filter(|t| t.family_id == family_id) {
+                token.revoked_at.get_or_insert_with(Utc::now);
+            }
+            Ok(())
+        }
+
+        async fn revoke_all_for_user(&self, user_id: Uuid) -> Result<(), DomainError> {
+            let mut tokens = self.tokens.lock().unwrap();
+            for token in tokens.iter_mut().filter(|t| t.user_id == user_id) {
+                token.revoked_at.get_or_insert_with(Utc::now);
+            }
+            Ok(())
+        }
+    }
+
+    fn sessions() -> SessionUsecases<MockRefreshTokenRepository> {
+        SessionUsecases::new(MockRefreshTokenRepository::default(), Duration::days(30))
+    }
+
+    fn assert_unauthorized(result: Result<(Uuid, String), DomainError>) {
+        match result {
+            Err(err @ DomainError::Unauthorized(_)) => {
+                assert_eq!(err.status_code(), StatusCode::UNAUTHORIZED)
+            }
+            other => panic!("expected a 401, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn rotation_replaces_the_token() {
+        let sessions = sessions();
+        let user_id = Uuid::new_v4();
+        let first = sessions.issue_refresh_token(user_id).await.unwrap();
+
+        let (rotated_for, second) = sessions.rotate_refresh_token(&first).await.unwrap();
+        assert_eq!(rotated_for, user_id);
+        assert_ne!(first, second);
+
+        let (_, third) = sessions.rotate_refresh_token(&second).await.unwrap();
+        assert_ne!(second, third);
+    }
+
+    #[tokio::test]
+    async fn replaying_a_rotated_token_revokes_the_whole_family() {
+        let sessions = sessions();
+        let user_id = Uuid::new_v4();
+        let stolen = sessions.issue_refresh_token(user_id).await.unwrap();
+        let (_, second) = sessions.rotate_refresh_token(&stolen).await.unwrap();
+        let (_, current) = sessions.rotate_refresh_token(&second).await.unwrap();
+        // Another session of the same user is not part of the family.
+        let other_session = sessions.issue_refresh_token(user_id).await.unwrap();
+
+        assert_unauthorized(sessions.rotate_refresh_token(&stolen).await);
+
+        let family_id = sessions
+            .repository
+            .get_token_by_hash(&hash_token(&stolen))
+            .await
+            .unwrap()
+            .unwrap()
+            .family_id;
+        let family = sessions.repository.family(family_id);
+        assert_eq!(family.len(), 3);
+        assert!(family.iter().all(|t| t.revoked_at.is_some()));
+
+        // The legitimate holder is logged out too.
+        assert_unauthorized(sessions.rotate_refresh_token(&current).await);
+        assert!(sessions.rotate_refresh_token(&other_session).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn unknown_logged_out_and_expired_tokens_are_refused() {
+        let sessions = sessions();
+        let user_id = Uuid::new_v4();
+        assert_unauthorized(sessions.rotate_refresh_token("not-a-token").await);
+
+        let logged_out = sessions.issue_refresh_token(user_id).await.unwrap();
+        sessions.revoke_refresh_token(&logged_out).await.unwrap();
+        assert_unauthorized(sessions.rotate_refresh_token(&logged_out).await);
+
+        let expired = SessionUsecases::new(MockRefreshTokenRepository::default(), Duration::zero());
+        let token = expired.issue_refresh_token(user_id).await.unwrap();
+        assert_unauthorized(expired.rotate_refresh_token(&token).await);
+    }
+}
@@ -0,0 +1,395 @@
+use crate::application::policies::{self, Actor};
+use crate::application::usecases::ledger_usecases::LedgerUsecases;
+use crate::application::usecases::project_usecases::member_role;
+use crate::domain::entities::issue::{Issue, IssueStatus};
+use crate::domain::entities::issue_claim::ClaimStatus;
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::JournalEntry;
+use crate::domain::entities::submission::{
+    ReviewDecision, Submission, SubmissionReview, SubmissionStatus,
+};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::DomainEvent;
+use crate::domain::repositories::issue_claim_repository::IssueClaimRepository;
+use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::repositories::ledger_repository::LedgerRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::repositories::submission_repository::SubmissionRepository;
+use chrono::Utc;
+use url::Url;
+use uuid::Uuid;
+
+const MAX_URL_LENGTH: usize = 500;
+const MAX_TEXT_LENGTH: usize = 5000;
+
+/// A submission together with the reviews it received, oldest first.
+pub struct SubmissionHistory {
+    pub submission: Submission,
+    pub reviews: Vec<SubmissionReview>,
+}
+
+/// Result of an approval. `payout` is the ledger entry paying the submitter, or
+/// `None` when the issue had nothing in escrow.
+pub struct ApprovedSubmission {
+    pub submission: Submission,
+    pub payout: Option<JournalEntry>,
+}
+
+pub struct SubmissionUsecases<
+    S: SubmissionRepository,
+    C: IssueClaimRepository,
+    R: IssueRepository,
+    P: ProjectRepository,
+    L: LedgerRepository,
+> {
+    repository: S,
+    claim_repository: C,
+    issue_repository: R,
+    project_repository: P,
+    ledger: LedgerUsecases<L, R, P>,
+}
+
+impl<
+        S: SubmissionRepository,
+        C: IssueClaimRepository,
+        R: IssueRepository,
+        P: ProjectRepository,
+        L: LedgerRepository,
+    > SubmissionUsecases<S, C, R, P, L>
+{
+    /// `ledger` pays the bounty out when a submission is approved.
+    pub fn new(
+        repository: S,
+        claim_repository: C,
+        issue_repository: R,
+        project_repository: P,
+        ledger: LedgerUsecases<L, R, P>,
+    ) -> Self {
+        Self {
+            repository,
+            claim_repository,
+            issue_repository,
+            project_repository,
+            ledger,
+        }
+    }
+
+    async fn get_issue(&self, issue_id: Uuid) -> Result<Issue, DomainError> {
+        self.issue_repository
+            .get_issue_by_id(issue_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Issue not found".to_string()))
+    }
+
+    /// Loads a pending submission of `issue_id` and checks that `actor` may review it.
+    async fn get_reviewable_submission(
+        &self,
+        issue_id: Uuid,
+        submission_id: Uuid,
+        actor: &Actor,
+    ) -> Result<(Issue, Submission), DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        let role = member_role(&self.project_repository, issue.project_id, Some(actor)).await?;
+        if !policies::can_manage_issues(actor, role) {
+            return Err(DomainError::Forbidden(
+                "Only project maintainers or an admin can review submissions".to_string(),
+            ));
+        }
+        let submission = self
+            .repository
+            .get_submission_by_id(submission_id)
+            .await?
+            .filter(|s| s.issue_id == issue_id)
+            .ok_or_else(|| DomainError::NotFound("Submission not found".to_string()))?;
+        if submission.status != SubmissionStatus::Pending {
+            return Err(DomainError::Conflict(
+                "Submission was already reviewed".to_string(),
+            ));
+        }
+        Ok((issue, submission))
+    }
+
+    /// Hands in work for an issue. Only contributors with an accepted claim can
+    /// submit; the first submission moves the issue into review.
+    pub async fn submit_work(
+        &self,
+        issue_id: Uuid,
+        actor: &Actor,
+        pr_url: String,
+        notes: Option<String>,
+    ) -> Result<Submission, DomainError> {
+        let pr_url = pr_url.trim().to_string();
+        let notes = notes
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+        validate_submission(&pr_url, notes.as_deref())?;
+
+        let issue = self.get_issue(issue_id).await?;
+        if !matches!(issue.status, IssueStatus::Open | IssueStatus::InReview) {
+            return Err(DomainError::Conflict(format!(
+                "Cannot submit work for an issue that is {}",
+                issue.status
+            )));
+        }
+        let claimed = self
+            .claim_repository
+            .get_claims_by_issue(issue_id)
+            .await?
+            .iter()
+            .any(|c| c.claimant_id == actor.id && c.status == ClaimStatus::Accepted);
+        if !claimed {
+            return Err(DomainError::Forbidden(
+                "Only contributors with an accepted claim can submit work".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let submission = Submission {
+            id: Uuid::new_v4(),
+            issue_id,
+            submitter_id: actor.id,
+            pr_url,
+            notes,
+            status: SubmissionStatus::Pending,
+            created_at: now,
+            updated_at: None,
+        };
+        let transition = (issue.status == IssueStatus::Open).then(|| IssueTransition {
+            id: Uuid::new_v4(),
+            issue_id,
+            from_status: IssueStatus::Open,
+            to_status: IssueStatus::InReview,
+            actor_id: Some(actor.id),
+            note: Some(format!("Work submitted: {}", submission.pr_url)),
+            created_at: now,
+        });
+        let mut events = vec![DomainEvent::SubmissionCreated {
+            project_id: issue.project_id,
+            submission: submission.clone(),
+        }];
+        events.extend(
+            transition
+                .iter()
+                .map(|t| DomainEvent::issue_status_changed(issue.project_id, t)),
+        );
+        self.repository
+            .create_submission(&submission, transition.as_ref(), &events)
+            .await?;
+        Ok(submission)
+    }
+
+    /// Approves a pending submission: the issue becomes `Approved`, the submitter
+    /// its assignee, and whatever sits in escrow is paid out to them.
+    pub async fn approve_submission(
+        &self,
+        issue_id: Uuid,
+        submission_id: Uuid,
+        actor: &Actor,
+        comment: Option<String>,
+    ) -> Result<ApprovedSubmission, DomainError> {
+        let comment = comment
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty());
+        validate_comment(comment.as_deref(), false)?;
+
+        let (issue, mut submission) = self
+            .get_reviewable_submission(issue_id, submission_id, actor)
+            .await?;
+        if issue.status != IssueStatus::InReview {
+            return Err(DomainError::Conflict(format!(
+                "Cannot approve work for an issue that is {}",
+                issue.status
+            )));
+        }
+
+        let now = Utc::now();
+        let review = SubmissionReview {
+            id: Uuid::new_v4(),
+            submission_id,
+            reviewer_id: Some(actor.id),
+            decision: ReviewDecision::Approved,
+            comment,
+            created_at: now,
+        };
+        let transition = IssueTransition {
+            id: Uuid::new_v4(),
+            issue_id,
+            from_status: IssueStatus::InReview,
+            to_status: IssueStatus::Approved,
+            actor_id: Some(actor.id),
+            note: Some(format!("Submission {} approved", submission.id)),
+            created_at: now,
+        };
+        submission.status = SubmissionStatus::Approved;
+        submission.updated_at = Some(now);
+
+        // The payout is posted with the approval, so either both happen or neither.
+        let escrow = self.ledger.get_escrow_balance(&issue).await?;
+        let payout = if escrow.is_positive() {
+            Some(self.ledger.release_entry(
+                &issue,
+                Some(actor.id),
+                submission.submitter_id,
+                escrow.amount_minor,
+            )?)
+        } else {
+            None
+        };
+
+        let mut events = vec![
+            DomainEvent::issue_status_changed(issue.project_id, &transition),
+            DomainEvent::SubmissionApproved {
+                project_id: issue.project_id,
+                submission: submission.clone(),
+                review: review.clone(),
+            },
+        ];
+        events.extend(payout.iter().map(|entry| DomainEvent::BountyPaid {
+            project_id: issue.project_id,
+            entry: entry.clone(),
+        }));
+        self.repository
+            .approve_submission(&submission, &review, &transition, payout.as_ref(), &events)
+            .await?;
+
+        Ok(ApprovedSubmission { submission, payout })
+    }
+
+    /// Sends a pending submission back with a comment. The issue reopens once no
+    /// submission is waiting for review anymore.
+    pub async fn request_changes(
+        &self,
+        issue_id: Uuid,
+        submission_id: Uuid,
+        actor: &Actor,
+        comment: String,
+    ) -> Result<Submission, DomainError> {
+        let comment = comment.trim().to_string();
+        validate_comment(Some(&comment), true)?;
+
+        let (issue, mut submission) = self
+            .get_reviewable_submission(issue_id, submission_id, actor)
+            .await?;
+
+        let now = Utc::now();
+        let review = SubmissionReview {
+            id: Uuid::new_v4(),
+            submission_id,
+            reviewer_id: Some(actor.id),
+            decision: ReviewDecision::ChangesRequested,
+            comment: Some(comment),
+            created_at: now,
+        };
+        let reopen = IssueTransition {
+            id: Uuid::new_v4(),
+            issue_id: issue.id,
+            from_status: IssueStatus::InReview,
+            to_status: IssueStatus::Open,
+            actor_id: Some(actor.id),
+            note: Some(format!("Changes requested on submission {}", submission.id)),
+            created_at: now,
+        };
+        submission.status = SubmissionStatus::ChangesRequested;
+        submission.updated_at = Some(now);
+        let events = [DomainEvent::ChangesRequested {
+            project_id: issue.project_id,
+            submission: submission.clone(),
+            review: review.clone(),
+        }];
+        let reopen_events = [DomainEvent::issue_status_changed(issue.project_id, &reopen)];
+        self.repository
+            .request_changes(&submission, &review, &reopen, &events, &reopen_events)
+            .await?;
+        Ok(submission)
+    }
+
+    /// Every submission of the issue with its reviews, oldest first.
+    pub async fn get_submission_history(
+        &self,
+        issue_id: Uuid,
+        viewer: Option<&Actor>,
+    ) -> Result<Vec<SubmissionHistory>, DomainError> {
+        let issue = self.get_issue(issue_id).await?;
+        if issue.hidden {
+            let role = member_role(&self.project_repository, issue.project_id, viewer).await?;
+            if !policies::can_view_hidden(viewer, role) {
+                return Err(DomainError::NotFound("Issue not found".to_string()));
+            }
+        }
+
+        let submissions = self.repository.get_submissions_by_issue(issue_id).await?;
+        let reviews = self.repository.get_reviews_by_issue(issue_id).await?;
+        let history = submissions
+            .into_iter()
+            .map(|submission| SubmissionHistory {
+                reviews: reviews
+                    .iter()
+                    .filter(|r| r.submission_id == submission.id)
+                    .cloned()
+                    .collect(),
+                submission,
+            })
+            .collect();
+        Ok(history)
+    }
+}
+
+/// The PR link must be an https URL to a pull or merge request
+/// (`.../pull/42`, `.../pulls/42` or `.../merge_requests/42`).
+fn validate_submission(pr_url: &str, notes: Option<&str>) -> Result<(), DomainError> {
+    let mut errors = Vec::new();
+    if pr_url.is_empty() {
+        errors.push(FieldError::new("pr_url", "cannot be empty"));
+    } else if pr_url.len() > MAX_URL_LENGTH {
+        errors.push(FieldError::new(
+            "pr_url",
+            &format!("must be at most {} characters", MAX_URL_LENGTH),
+        ));
+    } else if !is_pull_request_url(pr_url) {
+        errors.push(FieldError::new(
+            "pr_url",
+            "must be an https link to a pull request",
+        ));
+    }
+    if notes.is_some_and(|n| n.chars().count() > MAX_TEXT_LENGTH) {
+        errors.push(FieldError::new(
+            "notes",
+            &format!("must be at most {} characters", MAX_TEXT_LENGTH),
+        ));
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(DomainError::Validation(errors))
+    }
+}
+
+fn validate_comment(comment: Option<&str>, required: bool) -> Result<(), DomainError> {
+    let error = match comment {
+        None | Some("") if required => Some(FieldError::new("comment", "cannot be empty")),
+        Some(c) if c.chars().count() > MAX_TEXT_LENGTH => Some(FieldError::new(
+            "comment",
+            &format!("must be at most {} characters", MAX_TEXT_LENGTH),
+        )),
+        _ => None,
+    };
+    match error {
+        Some(error) => Err(DomainError::Validation(vec![error])),
+        None => Ok(()),
+    }
+}
+
+fn is_pull_request_url(raw: &str) -> bool {
+    let Ok(url) = Url::parse(raw) else {
+        return false;
+    };
+    if url.scheme() != "https" || url.host_str().is_none() {
+        return false;
+    }
+    let segments: Vec<&str> = url.path_segments().map(|s| s.collect()).unwrap_or_default();
+    segments.windows(2).any(|pair| {
+        matches!(pair[0], "pull" | "pulls" | "merge_requests")
+            && !pair[1].is_empty()
+            && pair[1].bytes().all(|b| b.is_ascii_digit())
+    })
+}
@@ -1,6 +1,10 @@
-use crate::domain::entities::user::User;
-use crate::domain::errors::domain_error::DomainError;
-use crate::domain::repositories::user_repository::UserRepository;
+use crate::application::policies::{self, Actor};
+use crate::domain::entities::user::{Role, User};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::DomainEvent;
+use crate::domain::gateways::escrow_gateway::is_ss58_address;
+use crate::domain::pagination::{validate_page_size, Page};
+use crate::domain::repositories::user_repository::{UserQuery, UserRepository};
 use bcrypt::{hash, verify, DEFAULT_COST};
 use chrono::Utc;
 use uuid::Uuid;
@@ -20,14 +24,22 @@ impl<R: UserRepository> UserUsecases<R> {
         password: String,
     ) -> Result<User, DomainError> {
         // Basic validations
-        if username.is_empty() || email.is_empty() || password.is_empty() {
-            return Err(DomainError::InvalidData(
-                "Fields cannot be empty".to_string(),
-            ));
+        let mut errors = Vec::new();
+        if username.is_empty() {
+            errors.push(FieldError::new("username", "cannot be empty"));
+        }
+        if email.is_empty() {
+            errors.push(FieldError::new("email", "cannot be empty"));
+        }
+        if password.is_empty() {
+            errors.push(FieldError::new("password", "cannot be empty"));
+        }
+        if !errors.is_empty() {
+            return Err(DomainError::Validation(errors));
         }
 
         // Check if email is already taken
-        if let Some(_) = self.repository.get_user_by_email(&email).await? {
+        if self.repository.get_user_by_email(&email).await?.is_some() {
             return Err(DomainError::Conflict("Email already in use".to_string()));
         }
 
@@ -39,11 +51,15 @@ impl<R: UserRepository> UserUsecases<R> {
             username,
             email,
             password_hash: hashed_password,
+            role: Role::User,
+            chain_address: None,
             created_at: Utc::now(),
             updated_at: None,
         };
 
-        self.repository.create_user(&user).await?;
+        self.repository
+            .create_user(&user, &[DomainEvent::user_registered(&user)])
+            .await?;
         Ok(user)
     }
 
@@ -72,9 +88,16 @@ impl<R: UserRepository> UserUsecases<R> {
     pub async fn update_user(
         &self,
         user_id: Uuid,
+        actor: &Actor,
         new_username: Option<String>,
         new_password: Option<String>,
+        new_chain_address: Option<String>,
     ) -> Result<User, DomainError> {
+        if !policies::can_edit_user(actor, user_id) {
+            return Err(DomainError::Forbidden(
+                "Cannot modify another user".to_string(),
+            ));
+        }
         let mut user = self.get_user(user_id).await?;
         if let Some(u) = new_username {
             if !u.is_empty() {
@@ -87,16 +110,51 @@ impl<R: UserRepository> UserUsecases<R> {
                     .map_err(|e| DomainError::Infra(format!("Error hashing password: {:?}", e)))?;
             }
         }
+        if let Some(address) = new_chain_address {
+            // An empty address unregisters it
+            let address = address.trim().to_string();
+            if !address.is_empty() && !is_ss58_address(&address) {
+                return Err(DomainError::Validation(vec![FieldError::new(
+                    "chain_address",
+                    "is not a valid SS58 address",
+                )]));
+            }
+            user.chain_address = Some(address).filter(|a| !a.is_empty());
+        }
         user.updated_at = Some(Utc::now());
         self.repository.update_user(&user).await?;
         Ok(user)
     }
 
-    pub async fn delete_user(&self, user_id: Uuid) -> Result<(), DomainError> {
+    pub async fn set_user_role(
+        &self,
+        user_id: Uuid,
+        actor: &Actor,
+        role: Role,
+    ) -> Result<User, DomainError> {
+        if !policies::can_change_roles(actor) {
+            return Err(DomainError::Forbidden(
+                "Only admins can change roles".to_string(),
+            ));
+        }
+        let mut user = self.get_user(user_id).await?;
+        user.role = role;
+        user.updated_at = Some(Utc::now());
+        self.repository.update_user(&user).await?;
+        Ok(user)
+    }
+
+    pub async fn delete_user(&self, user_id: Uuid, actor: &Actor) -> Result<(), DomainError> {
+        if !policies::can_edit_user(actor, user_id) {
+            return Err(DomainError::Forbidden(
+                "Cannot modify another user".to_string(),
+            ));
+        }
         self.repository.delete_user(user_id).await
     }
 
-    pub async fn list_users(&self) -> Result<Vec<User>, DomainError> {
-        self.repository.list_users().await
+    pub async fn list_users(&self, query: UserQuery) -> Result<Page<User>, DomainError> {
+        validate_page_size(query.limit)?;
+        self.repository.list_users(&query).await
     }
 }
@@ -0,0 +1,455 @@
+use crate::application::policies::{self, Actor};
+use crate::domain::entities::organization::OrganizationMember;
+use crate::domain::entities::webhook::{
+    is_public_address, WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint, WebhookEvent,
+    WebhookEventType, WebhookOwner,
+};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::events::{EventSubscriber, OutboxEvent};
+use crate::domain::gateways::webhook_sender::{SharedWebhookSender, WebhookSender};
+use crate::domain::pagination::{validate_page_size, Page};
+use crate::domain::repositories::organization_repository::OrganizationRepository;
+use crate::domain::repositories::webhook_repository::{DeliveryQuery, WebhookRepository};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use std::net::IpAddr;
+use url::{Host, Url};
+use uuid::Uuid;
+
+const MAX_URL_LENGTH: usize = 500;
+const MAX_DESCRIPTION_LENGTH: usize = 200;
+const MAX_ENDPOINTS_PER_OWNER: usize = 10;
+/// Attempts before a delivery is given up as `Failed`.
+const MAX_ATTEMPTS: i32 = 10;
+/// How long a claimed delivery is held back from other workers while it is sent.
+const DELIVERY_LEASE_MINUTES: i64 = 5;
+const RETRY_BASE_SECONDS: i64 = 30;
+const RETRY_MAX_SECONDS: i64 = 6 * 60 * 60;
+
+/// Changes to an endpoint; `None` leaves a field as it is.
+#[derive(Debug, Clone, Default)]
+pub struct EndpointChanges {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+}
+
+/// Webhook endpoints registered by users and organizations, and the delivery of
+/// the events queued for them.
+pub struct WebhookUsecases<W: WebhookRepository, O: OrganizationRepository> {
+    repository: W,
+    organization_repository: O,
+    sender: Option<SharedWebhookSender>,
+}
+
+impl<W: WebhookRepository, O: OrganizationRepository> WebhookUsecases<W, O> {
+    pub fn new(repository: W, organization_repository: O) -> Self {
+        Self {
+            repository,
+            organization_repository,
+            sender: None,
+        }
+    }
+
+    /// Enables `deliver_due`.
+    pub fn with_webhook_sender(mut self, sender: SharedWebhookSender) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    fn webhook_sender(&self) -> Result<&dyn WebhookSender, DomainError> {
+        self.sender
+            .as_deref()
+            .ok_or_else(|| DomainError::Infra("Webhook sender is not configured".to_string()))
+    }
+
+    /// The owner `actor` registers endpoints for: themselves, or the organization
+    /// `organization_slug` if they manage it.
+    pub async fn resolve_owner(
+        &self,
+        actor: &Actor,
+        organization_slug: Option<&str>,
+    ) -> Result<WebhookOwner, DomainError> {
+        let Some(slug) = organization_slug else {
+            return Ok(WebhookOwner::User(actor.id));
+        };
+        let organization = self
+            .organization_repository
+            .get_organization_by_slug(&slug.to_lowercase())
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Organization not found".to_string()))?;
+        let owner = WebhookOwner::Organization(organization.id);
+        self.check_can_manage(actor, owner).await?;
+        Ok(owner)
+    }
+
+    async fn check_can_manage(
+        &self,
+        actor: &Actor,
+        owner: WebhookOwner,
+    ) -> Result<(), DomainError> {
+        let allowed = match owner {
+            WebhookOwner::User(user_id) => policies::can_edit_user(actor, user_id),
+            WebhookOwner::Organization(organization_id) => {
+                let role = self
+                    .organization_repository
+                    .get_member(organization_id, actor.id)
+                    .await?
+                    .filter(OrganizationMember::is_active)
+                    .map(|m| m.role);
+                policies::can_manage_organization(actor, role)
+            }
+        };
+        if !allowed {
+            return Err(DomainError::Forbidden(
+                "You cannot manage these webhook endpoints".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Loads an endpoint `actor` may manage. Other endpoints are reported as
+    /// missing, so their ids are not confirmed to exist.
+    pub async fn get_endpoint(
+        &self,
+        endpoint_id: Uuid,
+        actor: &Actor,
+    ) -> Result<WebhookEndpoint, DomainError> {
+        let not_found = || DomainError::NotFound("Webhook endpoint not found".to_string());
+        let endpoint = self
+            .repository
+            .get_endpoint_by_id(endpoint_id)
+            .await?
+            .ok_or_else(not_found)?;
+        self.check_can_manage(actor, endpoint.owner)
+            .await
+            .map_err(|_| not_found())?;
+        Ok(endpoint)
+    }
+
+    /// Registers an endpoint receiving `events` of the projects `owner` owns. The
+    /// returned endpoint carries the signing secret; it is not shown again.
+    pub async fn create_endpoint(
+        &self,
+        actor: &Actor,
+        owner: WebhookOwner,
+        url: String,
+        events: Vec<String>,
+        description: Option<String>,
+    ) -> Result<WebhookEndpoint, DomainError> {
+        let url = url.trim().to_string();
+        let description = description
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty());
+        let mut errors = Vec::new();
+        if let Err(message) = validate_url(&url).await {
+            errors.push(FieldError::new("url", &message));
+        }
+        let events = match parse_events(&events) {
+            Ok(events) => events,
+            Err(event_errors) => {
+                errors.extend(event_errors);
+                Vec::new()
+            }
+        };
+        if let Err(message) = validate_description(description.as_deref()) {
+            errors.push(FieldError::new("description", &message));
+        }
+        if !errors.is_empty() {
+            return Err(DomainError::Validation(errors));
+        }
+
+        self.check_can_manage(actor, owner).await?;
+        let existing = self.repository.get_endpoints_by_owner(owner).await?;
+        if existing.len() >= MAX_ENDPOINTS_PER_OWNER {
+            return Err(DomainError::Conflict(format!(
+                "At most {} webhook endpoints can be registered",
+                MAX_ENDPOINTS_PER_OWNER
+            )));
+        }
+
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let endpoint = WebhookEndpoint {
+            id: Uuid::new_v4(),
+            owner,
+            url,
+            secret: hex::encode(secret),
+            events,
+            description,
+            active: true,
+            created_at: Utc::now(),
+            updated_at: None,
+        };
+        self.repository.create_endpoint(&endpoint).await?;
+        Ok(endpoint)
+    }
+
+    pub async fn list_endpoints(
+        &self,
+        actor: &Actor,
+        owner: WebhookOwner,
+    ) -> Result<Vec<WebhookEndpoint>, DomainError> {
+        self.check_can_manage(actor, owner).await?;
+        self.repository.get_endpoints_by_owner(owner).await
+    }
+
+    pub async fn update_endpoint(
+        &self,
+        endpoint_id: Uuid,
+        actor: &Actor,
+        changes: EndpointChanges,
+    ) -> Result<WebhookEndpoint, DomainError> {
+        let mut endpoint = self.get_endpoint(endpoint_id, actor).await?;
+        let mut errors = Vec::new();
+        if let Some(url) = changes.url {
+            let url = url.trim().to_string();
+            match validate_url(&url).await {
+                Ok(()) => endpoint.url = url,
+                Err(message) => errors.push(FieldError::new("url", &message)),
+            }
+        }
+        if let Some(events) = changes.events {
+            match parse_events(&events) {
+                Ok(events) => endpoint.events = events,
+                Err(event_errors) => errors.extend(event_errors),
+            }
+        }
+        if let Some(description) = changes.description {
+            let description = Some(description.trim().to_string()).filter(|d| !d.is_empty());
+            match validate_description(description.as_deref()) {
+                Ok(()) => endpoint.description = description,
+                Err(message) => errors.push(FieldError::new("description", &message)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(DomainError::Validation(errors));
+        }
+        if let Some(active) = changes.active {
+            endpoint.active = active;
+        }
+        endpoint.updated_at = Some(Utc::now());
+        self.repository.update_endpoint(&endpoint).await?;
+        Ok(endpoint)
+    }
+
+    /// Deletes the endpoint together with its delivery log.
+    pub async fn delete_endpoint(
+        &self,
+        endpoint_id: Uuid,
+        actor: &Actor,
+    ) -> Result<(), DomainError> {
+        let endpoint = self.get_endpoint(endpoint_id, actor).await?;
+        self.repository.delete_endpoint(endpoint.id).await
+    }
+
+    /// Deliveries to the endpoint, newest first.
+    pub async fn list_deliveries(
+        &self,
+        endpoint_id: Uuid,
+        actor: &Actor,
+        query: DeliveryQuery,
+    ) -> Result<Page<WebhookDelivery>, DomainError> {
+        validate_page_size(query.limit)?;
+        let endpoint = self.get_endpoint(endpoint_id, actor).await?;
+        self.repository.list_deliveries(endpoint.id, &query).await
+    }
+
+    /// Queues the payload of an earlier delivery again, as a new delivery.
+    pub async fn redeliver(
+        &self,
+        endpoint_id: Uuid,
+        delivery_id: Uuid,
+        actor: &Actor,
+    ) -> Result<WebhookDelivery, DomainError> {
+        let endpoint = self.get_endpoint(endpoint_id, actor).await?;
+        let original = self
+            .repository
+            .get_delivery_by_id(delivery_id)
+            .await?
+            .filter(|d| d.endpoint_id == endpoint.id)
+            .ok_or_else(|| DomainError::NotFound("Webhook delivery not found".to_string()))?;
+        if original.status == WebhookDeliveryStatus::Pending {
+            return Err(DomainError::Conflict(
+                "Delivery is still being attempted".to_string(),
+            ));
+        }
+        if !endpoint.active {
+            return Err(DomainError::Conflict(
+                "Webhook endpoint is inactive".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let delivery = WebhookDelivery {
+            id: Uuid::new_v4(),
+            endpoint_id: endpoint.id,
+            event_id: original.event_id,
+            event_type: original.event_type,
+            payload: original.payload,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_attempt_at: None,
+            response_status: None,
+            last_error: None,
+            redelivery_of: Some(original.id),
+            created_at: now,
+            delivered_at: None,
+        };
+        self.repository.create_delivery(&delivery).await?;
+        Ok(delivery)
+    }
+
+    /// Attempts up to `limit` deliveries that are due. A 2xx response marks a
+    /// delivery `Delivered`; anything else schedules a retry with exponential
+    /// backoff until `MAX_ATTEMPTS` is reached. Returns how many were attempted.
+    pub async fn deliver_due(&self, limit: u32) -> Result<usize, DomainError> {
+        let sender = self.webhook_sender()?;
+        let now = Utc::now();
+        let claimed = self
+            .repository
+            .claim_due_deliveries(now, now + Duration::minutes(DELIVERY_LEASE_MINUTES), limit)
+            .await?;
+
+        let attempted = claimed.len();
+        for (mut delivery, endpoint) in claimed {
+            let result = sender.send(&endpoint, &delivery).await;
+            let now = Utc::now();
+            delivery.attempts += 1;
+            delivery.last_attempt_at = Some(now);
+            match result {
+                Ok(response) if (200..300).contains(&response.status) => {
+                    delivery.status = WebhookDeliveryStatus::Delivered;
+                    delivery.response_status = Some(i32::from(response.status));
+                    delivery.last_error = None;
+                    delivery.delivered_at = Some(now);
+                }
+                Ok(response) => {
+                    delivery.response_status = Some(i32::from(response.status));
+                    delivery.last_error = None;
+                    schedule_retry(&mut delivery);
+                }
+                Err(e) => {
+                    delivery.response_status = None;
+                    delivery.last_error = Some(match e {
+                        DomainError::Infra(message) => message,
+                        other => other.to_string(),
+                    });
+                    schedule_retry(&mut delivery);
+                }
+            }
+            self.repository.record_attempt(&delivery).await?;
+        }
+        Ok(attempted)
+    }
+}
+
+/// Queues the webhook deliveries of outbox events. Queuing an event twice is a
+/// no-op, as each endpoint gets one delivery per event id.
+#[async_trait]
+impl<W, O> EventSubscriber for WebhookUsecases<W, O>
+where
+    W: WebhookRepository + Send + Sync,
+    O: OrganizationRepository + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), DomainError> {
+        let Some(event) = WebhookEvent::from_outbox(event) else {
+            return Ok(());
+        };
+        let queued = self.repository.enqueue_event(&event).await?;
+        if queued > 0 {
+            tracing::debug!(
+                "Queued {} for {} webhook endpoints",
+                event.event_type,
+                queued
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Waits 30s after the first failure, doubling up to 6h; gives up after
+/// `MAX_ATTEMPTS` attempts.
+fn schedule_retry(delivery: &mut WebhookDelivery) {
+    if delivery.attempts >= MAX_ATTEMPTS {
+        delivery.status = WebhookDeliveryStatus::Failed;
+        return;
+    }
+    let exponent = (delivery.attempts - 1).clamp(0, 20) as u32;
+    let delay = (RETRY_BASE_SECONDS << exponent).min(RETRY_MAX_SECONDS);
+    delivery.next_attempt_at = Utc::now() + Duration::seconds(delay);
+}
+
+/// Also resolves the host, which must only point to public addresses. The sender
+/// checks again when it connects, as DNS can change in between.
+async fn validate_url(url: &str) -> Result<(), String> {
+    if url.is_empty() {
+        return Err("cannot be empty".to_string());
+    }
+    if url.len() > MAX_URL_LENGTH {
+        return Err(format!("must be at most {} characters", MAX_URL_LENGTH));
+    }
+    let parsed = match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => parsed,
+        _ => return Err("must be an http or https URL".to_string()),
+    };
+    let addresses: Vec<IpAddr> = match parsed.host() {
+        Some(Host::Ipv4(ip)) => vec![IpAddr::V4(ip)],
+        Some(Host::Ipv6(ip)) => vec![IpAddr::V6(ip)],
+        Some(Host::Domain(domain)) => match tokio::net::lookup_host((domain, 0)).await {
+            Ok(resolved) => resolved.map(|address| address.ip()).collect(),
+            Err(_) => Vec::new(),
+        },
+        None => return Err("must be an http or https URL".to_string()),
+    };
+    if addresses.is_empty() {
+        return Err("host cannot be resolved".to_string());
+    }
+    if !addresses.into_iter().all(is_public_address) {
+        return Err("must point to a public address".to_string());
+    }
+    Ok(())
+}
+
+fn validate_description(description: Option<&str>) -> Result<(), String> {
+    if description.is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_LENGTH) {
+        return Err(format!(
+            "must be at most {} characters",
+            MAX_DESCRIPTION_LENGTH
+        ));
+    }
+    Ok(())
+}
+
+/// Event names to event types, without duplicates.
+fn parse_events(names: &[String]) -> Result<Vec<WebhookEventType>, Vec<FieldError>> {
+    if names.is_empty() {
+        return Err(vec![FieldError::new(
+            "events",
+            "must list at least one event",
+        )]);
+    }
+    let mut events = Vec::new();
+    let mut errors = Vec::new();
+    for (i, name) in names.iter().enumerate() {
+        match WebhookEventType::from_name(name.trim()) {
+            Some(event) if !events.contains(&event) => events.push(event),
+            Some(_) => {}
+            None => errors.push(FieldError::new(
+                &format!("events[{}]", i),
+                "is not a known event",
+            )),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(events)
+}
@@ -0,0 +1,25 @@
+use dotenv::dotenv;
+use rust4u_backend::infra::db::create_db_pool;
+use rust4u_backend::infra::mailer::mailer_from_env;
+use rust4u_backend::infra::repository_host::repository_host_from_env;
+use rust4u_backend::infra::webhooks::webhook_sender_from_env;
+use rust4u_backend::worker;
+use std::env;
+
+/// Runs the background worker without the API, so it can be scaled on its own.
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv().ok();
+    tracing_subscriber::fmt::init();
+
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env");
+    let pool = create_db_pool(&database_url).await?;
+    let repository_host = repository_host_from_env()?;
+    let webhook_sender = webhook_sender_from_env()?;
+    let mailer = mailer_from_env()?;
+
+    tracing::info!("Worker started");
+    worker::run(pool, repository_host, webhook_sender, mailer).await;
+
+    Ok(())
+}
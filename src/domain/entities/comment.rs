@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+use uuid::Uuid;
+
+/// What a comment is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum CommentTarget {
+    Project(Uuid),
+    Issue(Uuid),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: Uuid,
+    pub target: CommentTarget,
+    /// Set on replies; always a top-level comment of the same target.
+    pub parent_id: Option<Uuid>,
+    /// `None` once the author's account is gone.
+    pub author_id: Option<Uuid>,
+    /// Markdown source.
+    pub body: String,
+    /// Users @mentioned in the current body.
+    pub mentions: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Soft delete: the row stays so replies keep their thread.
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl Comment {
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+/// The target is stored as two nullable columns (`project_id`, `issue_id`) and the
+/// mentions come aggregated in a `mentions` array column.
+impl<'r> FromRow<'r, PgRow> for Comment {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let project_id: Option<Uuid> = row.try_get("project_id")?;
+        let issue_id: Option<Uuid> = row.try_get("issue_id")?;
+        let target = match (project_id, issue_id) {
+            (Some(id), None) => CommentTarget::Project(id),
+            (None, Some(id)) => CommentTarget::Issue(id),
+            _ => {
+                return Err(sqlx::Error::ColumnDecode {
+                    index: "project_id".to_string(),
+                    source: "comment must have exactly one target".into(),
+                })
+            }
+        };
+        Ok(Self {
+            id: row.try_get("id")?,
+            target,
+            parent_id: row.try_get("parent_id")?,
+            author_id: row.try_get("author_id")?,
+            body: row.try_get("body")?,
+            mentions: row.try_get("mentions")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            deleted_at: row.try_get("deleted_at")?,
+        })
+    }
+}
+
+/// A previous body of an edited comment.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CommentEdit {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub previous_body: String,
+    pub edited_at: DateTime<Utc>,
+}
+
+/// Usernames @mentioned in `body`, lowercased and without duplicates, in order of
+/// appearance. A mention starts at an `@` that does not follow a word character (so
+/// e-mail addresses are skipped) and runs over letters, digits, `_`, `-` and `.`;
+/// trailing dots belong to the sentence, not the name. Code spans and blocks are
+/// not excluded.
+pub fn extract_mentions(body: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let starts_mention = c == '@' && !prev.is_some_and(|p| p.is_alphanumeric() || p == '_');
+        prev = Some(c);
+        if !starts_mention {
+            continue;
+        }
+        let start = i + 1;
+        let mut end = start;
+        while let Some(&(j, n)) = chars.peek() {
+            if n.is_alphanumeric() || matches!(n, '_' | '-' | '.') {
+                end = j + n.len_utf8();
+                prev = Some(n);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        let name = body[start..end].trim_end_matches('.').to_lowercase();
+        if !name.is_empty() && !mentions.contains(&name) {
+            mentions.push(name);
+        }
+    }
+    mentions
+}
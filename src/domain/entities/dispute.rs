@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A disagreement between a project and a contributor over a submission. While it
+/// is open the issue stays `Disputed`; a moderator's ruling settles the escrow.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Dispute {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub submission_id: Uuid,
+    pub contributor_id: Uuid,
+    pub opened_by: Option<Uuid>,
+    pub reason: String,
+    pub status: DisputeStatus,
+    pub ruling: Option<DisputeRuling>,
+    /// Gross amount awarded to the contributor, in minor units of the bounty currency.
+    pub contributor_amount: Option<i64>,
+    pub resolved_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "dispute_status", rename_all = "snake_case")]
+pub enum DisputeStatus {
+    Open,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "dispute_ruling", rename_all = "snake_case")]
+pub enum DisputeRuling {
+    /// The contributor gets the whole escrow.
+    FullPayout,
+    /// The contributor gets part of the escrow; the rest goes back to the funders.
+    Split,
+    /// The funders get the whole escrow back.
+    Refund,
+}
+
+impl DisputeRuling {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeRuling::FullPayout => "full_payout",
+            DisputeRuling::Split => "split",
+            DisputeRuling::Refund => "refund",
+        }
+    }
+}
+
+/// One line of the append-only dispute log.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DisputeEvent {
+    pub id: Uuid,
+    pub dispute_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub kind: DisputeEventKind,
+    pub body: String,
+    pub url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "dispute_event_kind", rename_all = "snake_case")]
+pub enum DisputeEventKind {
+    Opened,
+    Evidence,
+    Ruling,
+}
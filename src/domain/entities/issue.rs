@@ -1,25 +1,162 @@
+use crate::domain::value_objects::money::{Currency, Money};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+use std::fmt;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Issue {
     pub id: Uuid,
     pub project_id: Uuid,
     pub title: String,
     pub description: Option<String>,
-    pub bounty_value: f64,
+    pub bounty: Money,
     pub status: IssueStatus,
+    pub assignee_id: Option<Uuid>,
+    pub claim_mode: ClaimMode,
+    pub hidden: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+/// The bounty is stored as `bounty_amount` (minor units) + `bounty_currency` (code).
+impl<'r> FromRow<'r, PgRow> for Issue {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let code: String = row.try_get("bounty_currency")?;
+        let currency = Currency::from_code(&code).ok_or_else(|| sqlx::Error::ColumnDecode {
+            index: "bounty_currency".to_string(),
+            source: format!("unsupported currency {:?}", code).into(),
+        })?;
+        Ok(Self {
+            id: row.try_get("id")?,
+            project_id: row.try_get("project_id")?,
+            title: row.try_get("title")?,
+            description: row.try_get("description")?,
+            bounty: Money::new(row.try_get("bounty_amount")?, currency),
+            status: row.try_get("status")?,
+            assignee_id: row.try_get("assignee_id")?,
+            claim_mode: row.try_get("claim_mode")?,
+            hidden: row.try_get("hidden")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// How claims on an issue are handled, see `ClaimUsecases`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "claim_mode", rename_all = "snake_case")]
+pub enum ClaimMode {
+    /// Accepting a claim assigns the issue and rejects the other pending claims.
+    #[default]
+    Exclusive,
+    /// Several claims can be accepted and compete; nobody is assigned up front.
+    Competitive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
 #[sqlx(type_name = "issue_status", rename_all = "snake_case")]
 pub enum IssueStatus {
     Open,
     InReview,
     Approved,
     Disputed,
+    Closed,
+    Cancelled,
+}
+
+impl IssueStatus {
+    /// Statuses reachable from `self` in a single transition.
+    pub fn allowed_transitions(&self) -> &'static [IssueStatus] {
+        match self {
+            IssueStatus::Open => &[IssueStatus::InReview, IssueStatus::Cancelled],
+            IssueStatus::InReview => &[
+                IssueStatus::Open,
+                IssueStatus::Approved,
+                IssueStatus::Disputed,
+            ],
+            IssueStatus::Disputed => &[
+                IssueStatus::Open,
+                IssueStatus::Approved,
+                IssueStatus::Cancelled,
+            ],
+            IssueStatus::Approved => &[IssueStatus::Closed],
+            IssueStatus::Closed | IssueStatus::Cancelled => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: IssueStatus) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.allowed_transitions().is_empty()
+    }
+
+    /// Same value stored in the `issue_status` Postgres enum.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueStatus::Open => "open",
+            IssueStatus::InReview => "in_review",
+            IssueStatus::Approved => "approved",
+            IssueStatus::Disputed => "disputed",
+            IssueStatus::Closed => "closed",
+            IssueStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for IssueStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IssueStatus::{self, *};
+
+    const ALL: [IssueStatus; 6] = [Open, InReview, Approved, Disputed, Closed, Cancelled];
+
+    #[test]
+    fn transitions() {
+        let allowed = [
+            (Open, InReview),
+            (Open, Cancelled),
+            (InReview, Open),
+            (InReview, Approved),
+            (InReview, Disputed),
+            (Disputed, Open),
+            (Disputed, Approved),
+            (Disputed, Cancelled),
+            (Approved, Closed),
+        ];
+        for from in ALL {
+            for to in ALL {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    allowed.contains(&(from, to)),
+                    "{} -> {}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn terminal_statuses() {
+        for status in ALL {
+            assert_eq!(
+                status.is_terminal(),
+                matches!(status, Closed | Cancelled),
+                "{}",
+                status
+            );
+        }
+    }
 }
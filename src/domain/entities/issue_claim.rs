@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IssueClaim {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub claimant_id: Uuid,
+    pub message: String,
+    /// When the claimant expects to deliver.
+    pub eta: Option<DateTime<Utc>>,
+    pub status: ClaimStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Bumped whenever the claimant touches the claim; live claims idle for longer
+    /// than the inactivity window expire.
+    pub last_activity_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "claim_status", rename_all = "snake_case")]
+pub enum ClaimStatus {
+    Pending,
+    Accepted,
+    Rejected,
+    Withdrawn,
+    Expired,
+}
+
+impl ClaimStatus {
+    /// Pending and accepted claims are live; the rest are final.
+    pub fn is_active(&self) -> bool {
+        matches!(self, ClaimStatus::Pending | ClaimStatus::Accepted)
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "pull_request_state", rename_all = "snake_case")]
+pub enum PullRequestState {
+    Open,
+    /// Closed without being merged.
+    Closed,
+    Merged,
+}
+
+/// An upstream pull request working on an issue, as last reported by a webhook.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IssuePullRequest {
+    pub issue_id: Uuid,
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub number: i32,
+    pub url: String,
+    pub title: String,
+    /// Login of the pull request's author on the code host.
+    pub author: Option<String>,
+    pub state: PullRequestState,
+    pub draft: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IssuePullRequest {
+    /// Open and ready for review, or already merged.
+    pub fn is_active(&self) -> bool {
+        match self.state {
+            PullRequestState::Open => !self.draft,
+            PullRequestState::Merged => true,
+            PullRequestState::Closed => false,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::domain::entities::issue::IssueStatus;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IssueTransition {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub from_status: IssueStatus,
+    pub to_status: IssueStatus,
+    /// `None` when the transition was triggered by the system rather than a user.
+    pub actor_id: Option<Uuid>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "upstream_issue_state", rename_all = "snake_case")]
+pub enum UpstreamIssueState {
+    Open,
+    Closed,
+}
+
+/// Ties an issue imported from the code host to the upstream issue it mirrors.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IssueUpstreamLink {
+    pub issue_id: Uuid,
+    pub project_id: Uuid,
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub number: i32,
+    pub url: String,
+    /// Upstream state as of `synced_at`.
+    pub state: UpstreamIssueState,
+    pub synced_at: DateTime<Utc>,
+}
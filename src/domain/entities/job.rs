@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "job_status", rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting until `run_at`, for its first attempt or a retry.
+    Pending,
+    /// Claimed by a worker until `run_at`.
+    Running,
+    Completed,
+    /// Every attempt failed; kept for inspection until an admin retries it.
+    Dead,
+}
+
+/// A unit of background work as stored in the queue. `kind` selects the handler
+/// that runs it, and `payload` is the handler's input as JSON.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Job {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: String,
+    /// A job is only queued once per key; see `JobQueue::enqueue_once`.
+    pub dedup_key: Option<String>,
+    pub status: JobStatus,
+    /// Attempts started so far, including a running one.
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    /// Error of the last failed attempt.
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl Job {
+    /// A pending job due at `run_at`.
+    pub fn new(kind: &str, payload: String, max_attempts: i32, run_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind: kind.to_string(),
+            payload,
+            dedup_key: None,
+            status: JobStatus::Pending,
+            attempts: 0,
+            max_attempts,
+            run_at,
+            last_error: None,
+            created_at: Utc::now(),
+            finished_at: None,
+        }
+    }
+}
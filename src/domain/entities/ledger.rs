@@ -0,0 +1,246 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::value_objects::money::{Currency, Money};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "ledger_account_kind", rename_all = "snake_case")]
+pub enum AccountKind {
+    /// Money entering or leaving the platform (deposits, withdrawals). Its balance
+    /// is the negative of everything held on the platform.
+    External,
+    /// Per user: payouts and refunds owed to them.
+    User,
+    /// Per issue: funds locked until the bounty is released or refunded.
+    Escrow,
+    /// Fees retained by the platform on every payout.
+    PlatformFee,
+    /// Per organization: deposited funds it can put into its bounties.
+    Organization,
+}
+
+impl AccountKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountKind::External => "external",
+            AccountKind::User => "user",
+            AccountKind::Escrow => "escrow",
+            AccountKind::PlatformFee => "platform_fee",
+            AccountKind::Organization => "organization",
+        }
+    }
+
+    /// Every account except `External` must never go below zero.
+    pub fn allows_negative_balance(&self) -> bool {
+        matches!(self, AccountKind::External)
+    }
+}
+
+/// Identifies a ledger account by what it belongs to; accounts are created on first use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AccountRef {
+    pub kind: AccountKind,
+    /// User id for `User`, issue id for `Escrow`, organization id for `Organization`,
+    /// `None` for platform-wide accounts.
+    pub owner_id: Option<Uuid>,
+    pub currency: Currency,
+}
+
+impl AccountRef {
+    pub fn external(currency: Currency) -> Self {
+        Self {
+            kind: AccountKind::External,
+            owner_id: None,
+            currency,
+        }
+    }
+
+    pub fn user(user_id: Uuid, currency: Currency) -> Self {
+        Self {
+            kind: AccountKind::User,
+            owner_id: Some(user_id),
+            currency,
+        }
+    }
+
+    pub fn escrow(issue_id: Uuid, currency: Currency) -> Self {
+        Self {
+            kind: AccountKind::Escrow,
+            owner_id: Some(issue_id),
+            currency,
+        }
+    }
+
+    pub fn organization(organization_id: Uuid, currency: Currency) -> Self {
+        Self {
+            kind: AccountKind::Organization,
+            owner_id: Some(organization_id),
+            currency,
+        }
+    }
+
+    pub fn platform_fee(currency: Currency) -> Self {
+        Self {
+            kind: AccountKind::PlatformFee,
+            owner_id: None,
+            currency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "journal_entry_kind", rename_all = "snake_case")]
+pub enum EntryKind {
+    Fund,
+    Release,
+    Refund,
+    /// Money paid into an organization's account.
+    Deposit,
+}
+
+/// One side of a journal entry: a signed amount added to an account's balance.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Posting {
+    pub account: AccountRef,
+    pub amount: Money,
+}
+
+impl Posting {
+    pub fn new(account: AccountRef, amount_minor: i64) -> Self {
+        Self {
+            account,
+            amount: Money::new(amount_minor, account.currency),
+        }
+    }
+}
+
+/// An immutable, balanced set of postings: amounts always sum to zero, so money is
+/// only ever moved between accounts, never created or destroyed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: Uuid,
+    pub kind: EntryKind,
+    pub issue_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub memo: Option<String>,
+    pub postings: Vec<Posting>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl JournalEntry {
+    pub fn new(
+        kind: EntryKind,
+        issue_id: Option<Uuid>,
+        actor_id: Option<Uuid>,
+        memo: Option<String>,
+        postings: Vec<Posting>,
+    ) -> Result<Self, DomainError> {
+        let entry = Self {
+            id: Uuid::new_v4(),
+            kind,
+            issue_id,
+            actor_id,
+            memo,
+            postings,
+            created_at: Utc::now(),
+        };
+        entry.validate()?;
+        Ok(entry)
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        let unbalanced = || DomainError::InvalidData("Journal entry does not balance".to_string());
+        let currency = match self.postings.first() {
+            Some(posting) if self.postings.len() >= 2 => posting.amount.currency,
+            _ => {
+                return Err(DomainError::InvalidData(
+                    "Journal entry needs at least two postings".to_string(),
+                ))
+            }
+        };
+
+        let mut total = Money::zero(currency);
+        for posting in &self.postings {
+            if posting.amount.amount_minor == 0 {
+                return Err(DomainError::InvalidData(
+                    "Journal entry postings cannot be zero".to_string(),
+                ));
+            }
+            total = total.checked_add(posting.amount).ok_or_else(unbalanced)?;
+        }
+        if total.amount_minor != 0 {
+            return Err(unbalanced());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(postings: Vec<Posting>) -> Result<JournalEntry, DomainError> {
+        JournalEntry::new(EntryKind::Fund, None, None, None, postings)
+    }
+
+    #[test]
+    fn balanced_entries_are_accepted() {
+        let user = AccountRef::user(Uuid::new_v4(), Currency::Dot);
+        let escrow = AccountRef::escrow(Uuid::new_v4(), Currency::Dot);
+        assert!(entry(vec![Posting::new(user, -500), Posting::new(escrow, 500)]).is_ok());
+        assert!(entry(vec![
+            Posting::new(escrow, -500),
+            Posting::new(user, 475),
+            Posting::new(AccountRef::platform_fee(Currency::Dot), 25),
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn unbalanced_entries_are_rejected() {
+        let user = AccountRef::user(Uuid::new_v4(), Currency::Usd);
+        let escrow = AccountRef::escrow(Uuid::new_v4(), Currency::Usd);
+        let cases = [
+            vec![],
+            vec![Posting::new(user, 100)],
+            vec![Posting::new(user, -100), Posting::new(escrow, 99)],
+            vec![Posting::new(user, 100), Posting::new(escrow, 100)],
+            // Zero postings move nothing, even when the entry balances
+            vec![
+                Posting::new(user, -100),
+                Posting::new(escrow, 100),
+                Posting::new(AccountRef::platform_fee(Currency::Usd), 0),
+            ],
+            // Overflowing the running total does not wrap around to a balance
+            vec![
+                Posting::new(user, i64::MAX),
+                Posting::new(escrow, i64::MAX),
+                Posting::new(AccountRef::external(Currency::Usd), 2),
+            ],
+        ];
+        for postings in cases {
+            assert!(entry(postings.clone()).is_err(), "{:?}", postings);
+        }
+    }
+
+    #[test]
+    fn entries_balance_within_one_currency() {
+        // 1.00 USD against 100 planck of DOT sums to zero in minor units only
+        let usd = AccountRef::user(Uuid::new_v4(), Currency::Usd);
+        let dot = AccountRef::escrow(Uuid::new_v4(), Currency::Dot);
+        assert!(entry(vec![Posting::new(usd, -100), Posting::new(dot, 100)]).is_err());
+
+        let usd_escrow = AccountRef::escrow(Uuid::new_v4(), Currency::Usd);
+        let dot_user = AccountRef::user(Uuid::new_v4(), Currency::Dot);
+        assert!(entry(vec![
+            Posting::new(usd, -100),
+            Posting::new(usd_escrow, 100),
+            Posting::new(dot_user, -100),
+            Posting::new(dot, 100),
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// What a notification tells its user about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "notification_type", rename_all = "snake_case")]
+pub enum NotificationType {
+    /// Someone claimed an issue of a project the user maintains.
+    ClaimCreated,
+    ClaimAccepted,
+    ClaimRejected,
+    /// Work was submitted for an issue of a project the user maintains.
+    SubmissionCreated,
+    SubmissionApproved,
+    ChangesRequested,
+    /// A new top-level comment on a project the user maintains, or on one of its issues.
+    CommentCreated,
+    /// A reply to the user's comment.
+    CommentReply,
+    Mentioned,
+    /// A bounty was paid out to the user.
+    BountyPaid,
+}
+
+impl NotificationType {
+    pub const ALL: [NotificationType; 10] = [
+        NotificationType::ClaimCreated,
+        NotificationType::ClaimAccepted,
+        NotificationType::ClaimRejected,
+        NotificationType::SubmissionCreated,
+        NotificationType::SubmissionApproved,
+        NotificationType::ChangesRequested,
+        NotificationType::CommentCreated,
+        NotificationType::CommentReply,
+        NotificationType::Mentioned,
+        NotificationType::BountyPaid,
+    ];
+
+    /// Channel used until the user picks one. Payouts are also emailed.
+    pub fn default_channel(&self) -> NotificationChannel {
+        match self {
+            NotificationType::BountyPaid => NotificationChannel::Email,
+            _ => NotificationChannel::InApp,
+        }
+    }
+}
+
+/// How a user wants to hear about one type of notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "notification_channel", rename_all = "snake_case")]
+pub enum NotificationChannel {
+    /// In the notification center.
+    InApp,
+    /// In the notification center and by email.
+    Email,
+    Off,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub notification_type: NotificationType,
+    /// The domain event it was created from.
+    pub event_id: Uuid,
+    /// Who caused it, when known.
+    pub actor_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub issue_id: Option<Uuid>,
+    /// The claim, submission, comment or journal entry it is about.
+    pub subject_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct NotificationPreference {
+    pub notification_type: NotificationType,
+    pub channel: NotificationChannel,
+}
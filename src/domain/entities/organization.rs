@@ -0,0 +1,69 @@
+use super::project_member::ProjectRole;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A shared identity that can own projects and hold funds, run by its members.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Organization {
+    pub id: Uuid,
+    /// URL name: lowercase letters, digits and inner hyphens.
+    pub slug: String,
+    pub display_name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationMember {
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub role: OrganizationRole,
+    pub invited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// `None` while the invitation is pending.
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+impl OrganizationMember {
+    pub fn is_active(&self) -> bool {
+        self.accepted_at.is_some()
+    }
+}
+
+/// Owners run the organization and can delete it, admins manage its members,
+/// projects and funds, and members help out on its projects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "organization_role", rename_all = "snake_case")]
+pub enum OrganizationRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+impl OrganizationRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrganizationRole::Owner => "owner",
+            OrganizationRole::Admin => "admin",
+            OrganizationRole::Member => "member",
+        }
+    }
+
+    /// Owners and admins.
+    pub fn is_admin(&self) -> bool {
+        matches!(self, OrganizationRole::Owner | OrganizationRole::Admin)
+    }
+
+    /// The role this grants in every project the organization owns.
+    pub fn project_role(&self) -> ProjectRole {
+        match self {
+            OrganizationRole::Owner => ProjectRole::Owner,
+            OrganizationRole::Admin => ProjectRole::Maintainer,
+            OrganizationRole::Member => ProjectRole::Triager,
+        }
+    }
+}
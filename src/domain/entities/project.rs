@@ -1,16 +1,95 @@
+use crate::domain::gateways::repository_host::RepositoryStats;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+/// Who a project belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum ProjectOwner {
+    User(Uuid),
+    Organization(Uuid),
+}
+
+impl ProjectOwner {
+    pub fn user_id(&self) -> Option<Uuid> {
+        match self {
+            ProjectOwner::User(id) => Some(*id),
+            ProjectOwner::Organization(_) => None,
+        }
+    }
+
+    pub fn organization_id(&self) -> Option<Uuid> {
+        match self {
+            ProjectOwner::User(_) => None,
+            ProjectOwner::Organization(id) => Some(*id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
     pub id: Uuid,
-    pub owner_id: Uuid,
+    pub owner: ProjectOwner,
     pub name: String,
     pub description: Option<String>,
     pub github_link: Option<String>,
     pub tags: Vec<String>,
+    pub hidden: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// Metadata of the `github_link` repository, once it has been synced.
+    pub repo_stats: Option<RepositoryStats>,
+}
+
+/// The owner is stored as two nullable columns (`owner_id`, `organization_id`).
+/// Repository stats come from a LEFT JOIN on `project_repo_stats` and are only
+/// set once a sync has succeeded.
+impl<'r> FromRow<'r, PgRow> for Project {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let owner_id: Option<Uuid> = row.try_get("owner_id")?;
+        let organization_id: Option<Uuid> = row.try_get("organization_id")?;
+        let owner = match (owner_id, organization_id) {
+            (Some(id), None) => ProjectOwner::User(id),
+            (None, Some(id)) => ProjectOwner::Organization(id),
+            _ => {
+                return Err(sqlx::Error::ColumnDecode {
+                    index: "owner_id".to_string(),
+                    source: "project must have exactly one owner".into(),
+                })
+            }
+        };
+        let synced_at: Option<DateTime<Utc>> = row.try_get("synced_at")?;
+        let repo_stats = match synced_at {
+            Some(synced_at) => Some(RepositoryStats {
+                stars: row.try_get::<Option<i32>, _>("stars")?.unwrap_or_default(),
+                forks: row.try_get::<Option<i32>, _>("forks")?.unwrap_or_default(),
+                open_issues: row
+                    .try_get::<Option<i32>, _>("open_issues")?
+                    .unwrap_or_default(),
+                primary_language: row.try_get("primary_language")?,
+                license: row.try_get("license")?,
+                topics: row
+                    .try_get::<Option<Vec<String>>, _>("topics")?
+                    .unwrap_or_default(),
+                pushed_at: row.try_get("pushed_at")?,
+                synced_at,
+            }),
+            None => None,
+        };
+        Ok(Self {
+            id: row.try_get("id")?,
+            owner,
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            github_link: row.try_get("github_link")?,
+            tags: row.try_get("tags")?,
+            hidden: row.try_get("hidden")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            repo_stats,
+        })
+    }
 }
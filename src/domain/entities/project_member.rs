@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProjectMember {
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub role: ProjectRole,
+    pub invited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// `None` while the invitation is pending.
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+impl ProjectMember {
+    /// Only accepted members hold their role; pending invitations grant nothing.
+    pub fn is_active(&self) -> bool {
+        self.accepted_at.is_some()
+    }
+}
+
+/// What a member may do in a project. Each role includes everything below it:
+/// triagers handle claims and discussion, maintainers run the project and its
+/// bounties, and the single owner can also delete or transfer it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "project_role", rename_all = "snake_case")]
+pub enum ProjectRole {
+    Owner,
+    Maintainer,
+    Triager,
+}
+
+impl ProjectRole {
+    fn rank(&self) -> u8 {
+        match self {
+            ProjectRole::Owner => 2,
+            ProjectRole::Maintainer => 1,
+            ProjectRole::Triager => 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectRole::Owner => "owner",
+            ProjectRole::Maintainer => "maintainer",
+            ProjectRole::Triager => "triager",
+        }
+    }
+
+    pub fn outranks(&self, other: ProjectRole) -> bool {
+        self.rank() > other.rank()
+    }
+
+    /// Owners and maintainers.
+    pub fn is_maintainer(&self) -> bool {
+        matches!(self, ProjectRole::Owner | ProjectRole::Maintainer)
+    }
+}
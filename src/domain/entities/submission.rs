@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Work handed in for an issue, pointing at the pull request that solves it.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Submission {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub submitter_id: Uuid,
+    pub pr_url: String,
+    pub notes: Option<String>,
+    pub status: SubmissionStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "submission_status", rename_all = "snake_case")]
+pub enum SubmissionStatus {
+    /// Waiting for a maintainer's review.
+    Pending,
+    /// Sent back; the submitter answers with a new submission.
+    ChangesRequested,
+    Approved,
+    /// Still pending when another submission for the issue was approved.
+    Superseded,
+    /// Lost a dispute.
+    Rejected,
+}
+
+/// One decision of a project maintainer on a submission. Reviews are never edited,
+/// so together they form the review history of the issue.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SubmissionReview {
+    pub id: Uuid,
+    pub submission_id: Uuid,
+    pub reviewer_id: Option<Uuid>,
+    pub decision: ReviewDecision,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "review_decision", rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+}
@@ -9,6 +9,29 @@ pub struct User {
     pub username: String,
     pub email: String,
     pub password_hash: String,
+    pub role: Role,
+    /// SS58 address on-chain bounties are paid to.
+    pub chain_address: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "user_role", rename_all = "snake_case")]
+pub enum Role {
+    User,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    pub fn is_admin(&self) -> bool {
+        *self == Role::Admin
+    }
+
+    /// Moderators and admins.
+    pub fn is_staff(&self) -> bool {
+        matches!(self, Role::Moderator | Role::Admin)
+    }
+}
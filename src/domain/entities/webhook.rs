@@ -0,0 +1,437 @@
+use crate::domain::entities::issue::Issue;
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::{AccountKind, JournalEntry};
+use crate::domain::entities::project::Project;
+use crate::domain::events::{DomainEvent, OutboxEvent};
+use crate::domain::value_objects::money::Money;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use uuid::Uuid;
+
+/// Events integrators can subscribe a webhook endpoint to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "issue.created")]
+    IssueCreated,
+    #[serde(rename = "issue.status_changed")]
+    IssueStatusChanged,
+    #[serde(rename = "bounty.funded")]
+    BountyFunded,
+    #[serde(rename = "bounty.paid")]
+    BountyPaid,
+    #[serde(rename = "project.updated")]
+    ProjectUpdated,
+}
+
+impl WebhookEventType {
+    pub const ALL: [WebhookEventType; 5] = [
+        WebhookEventType::IssueCreated,
+        WebhookEventType::IssueStatusChanged,
+        WebhookEventType::BountyFunded,
+        WebhookEventType::BountyPaid,
+        WebhookEventType::ProjectUpdated,
+    ];
+
+    /// Name used in payloads, headers and the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::IssueCreated => "issue.created",
+            WebhookEventType::IssueStatusChanged => "issue.status_changed",
+            WebhookEventType::BountyFunded => "bounty.funded",
+            WebhookEventType::BountyPaid => "bounty.paid",
+            WebhookEventType::ProjectUpdated => "project.updated",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<WebhookEventType> {
+        Self::ALL.into_iter().find(|t| t.as_str() == name)
+    }
+}
+
+impl fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Who registered a webhook endpoint. It receives the events of the projects
+/// this user or organization owns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum WebhookOwner {
+    User(Uuid),
+    Organization(Uuid),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    pub owner: WebhookOwner,
+    pub url: String,
+    /// Key of the HMAC-SHA256 signature sent with every delivery.
+    pub secret: String,
+    pub events: Vec<WebhookEventType>,
+    pub description: Option<String>,
+    /// Inactive endpoints get no new deliveries.
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// The owner is stored as two nullable columns (`owner_id`, `organization_id`),
+/// the events as a `TEXT[]` of event names.
+impl<'r> FromRow<'r, PgRow> for WebhookEndpoint {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let owner_id: Option<Uuid> = row.try_get("owner_id")?;
+        let organization_id: Option<Uuid> = row.try_get("organization_id")?;
+        let owner = match (owner_id, organization_id) {
+            (Some(id), None) => WebhookOwner::User(id),
+            (None, Some(id)) => WebhookOwner::Organization(id),
+            _ => {
+                return Err(sqlx::Error::ColumnDecode {
+                    index: "owner_id".to_string(),
+                    source: "webhook endpoint must have exactly one owner".into(),
+                })
+            }
+        };
+        let names: Vec<String> = row.try_get("events")?;
+        // Event types that were dropped since are skipped rather than failing the row.
+        let events = names
+            .iter()
+            .filter_map(|name| WebhookEventType::from_name(name))
+            .collect();
+        Ok(Self {
+            id: row.try_get("id")?,
+            owner,
+            url: row.try_get("url")?,
+            secret: row.try_get("secret")?,
+            events,
+            description: row.try_get("description")?,
+            active: row.try_get("active")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// Something that happened on a project, to be sent to the webhook endpoints of
+/// the project's owner.
+#[derive(Debug, Clone)]
+pub struct WebhookEvent {
+    pub id: Uuid,
+    pub event_type: WebhookEventType,
+    pub project_id: Uuid,
+    pub data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookEvent {
+    pub fn new(event_type: WebhookEventType, project_id: Uuid, data: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type,
+            project_id,
+            data,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn issue_created(issue: &Issue) -> Self {
+        Self::new(
+            WebhookEventType::IssueCreated,
+            issue.project_id,
+            json!({ "issue": issue }),
+        )
+    }
+
+    pub fn issue_status_changed(project_id: Uuid, transition: &IssueTransition) -> Self {
+        Self::new(
+            WebhookEventType::IssueStatusChanged,
+            project_id,
+            json!({
+                "issue_id": transition.issue_id,
+                "project_id": project_id,
+                "from": transition.from_status,
+                "to": transition.to_status,
+                "actor_id": transition.actor_id,
+                "note": transition.note,
+                "changed_at": transition.created_at,
+            }),
+        )
+    }
+
+    /// `amount` is what went into the escrow; `organization_id` is set when an
+    /// organization paid for it.
+    pub fn bounty_funded(
+        project_id: Uuid,
+        entry: &JournalEntry,
+        amount: Money,
+        organization_id: Option<Uuid>,
+    ) -> Self {
+        Self::new(
+            WebhookEventType::BountyFunded,
+            project_id,
+            json!({
+                "issue_id": entry.issue_id,
+                "project_id": project_id,
+                "journal_entry_id": entry.id,
+                "amount": amount,
+                "funded_by": entry.actor_id,
+                "organization_id": organization_id,
+            }),
+        )
+    }
+
+    /// `entry` is a release entry; the payee and the fee are read from its postings.
+    pub fn bounty_paid(project_id: Uuid, entry: &JournalEntry) -> Self {
+        let payout = entry
+            .postings
+            .iter()
+            .find(|p| p.account.kind == AccountKind::User);
+        let fee = entry
+            .postings
+            .iter()
+            .find(|p| p.account.kind == AccountKind::PlatformFee)
+            .map(|p| p.amount);
+        Self::new(
+            WebhookEventType::BountyPaid,
+            project_id,
+            json!({
+                "issue_id": entry.issue_id,
+                "project_id": project_id,
+                "journal_entry_id": entry.id,
+                "payee_id": payout.and_then(|p| p.account.owner_id),
+                "amount": payout.map(|p| p.amount),
+                "fee": fee,
+            }),
+        )
+    }
+
+    pub fn project_updated(project: &Project) -> Self {
+        Self::new(
+            WebhookEventType::ProjectUpdated,
+            project.id,
+            json!({ "project": project }),
+        )
+    }
+
+    /// The webhook event for an outbox event, if endpoints can subscribe to its
+    /// type. It keeps the outbox event's id, so handling the event again does not
+    /// queue a second delivery.
+    pub fn from_outbox(event: &OutboxEvent) -> Option<Self> {
+        let webhook_event = match &event.event {
+            DomainEvent::IssueCreated { issue } => Self::issue_created(issue),
+            DomainEvent::IssueStatusChanged {
+                project_id,
+                transition,
+            } => Self::issue_status_changed(*project_id, transition),
+            DomainEvent::BountyFunded {
+                project_id,
+                entry,
+                amount,
+                organization_id,
+            } => Self::bounty_funded(*project_id, entry, *amount, *organization_id),
+            DomainEvent::BountyPaid { project_id, entry } => Self::bounty_paid(*project_id, entry),
+            DomainEvent::ProjectUpdated { project } => Self::project_updated(project),
+            DomainEvent::UserRegistered { .. }
+            | DomainEvent::ProjectCreated { .. }
+            | DomainEvent::IssueUpdated { .. }
+            | DomainEvent::BountyRefunded { .. }
+            | DomainEvent::ClaimCreated { .. }
+            | DomainEvent::ClaimAccepted { .. }
+            | DomainEvent::ClaimRejected { .. }
+            | DomainEvent::SubmissionCreated { .. }
+            | DomainEvent::SubmissionApproved { .. }
+            | DomainEvent::ChangesRequested { .. }
+            | DomainEvent::CommentCreated { .. } => return None,
+        };
+        Some(Self {
+            id: event.id,
+            created_at: event.created_at,
+            ..webhook_event
+        })
+    }
+
+    /// Body sent to the endpoints.
+    pub fn payload(&self) -> String {
+        json!({
+            "id": self.id,
+            "type": self.event_type,
+            "created_at": self.created_at,
+            "data": self.data,
+        })
+        .to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first attempt or a retry.
+    Pending,
+    /// The endpoint answered with a 2xx status.
+    Delivered,
+    /// Every attempt failed; only a redelivery sends it again.
+    Failed,
+}
+
+/// One event queued for one endpoint, with the outcome of its last attempt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: WebhookEventType,
+    /// The exact body sent, so a redelivery is byte-for-byte the same.
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub response_status: Option<i32>,
+    /// Transport error of the last failed attempt; an HTTP error only leaves
+    /// `response_status`.
+    pub last_error: Option<String>,
+    /// The delivery this one was redelivered from.
+    pub redelivery_of: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// `event_type` is stored as the event name.
+impl<'r> FromRow<'r, PgRow> for WebhookDelivery {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let name: String = row.try_get("event_type")?;
+        let event_type =
+            WebhookEventType::from_name(&name).ok_or_else(|| sqlx::Error::ColumnDecode {
+                index: "event_type".to_string(),
+                source: format!("unknown webhook event type {:?}", name).into(),
+            })?;
+        Ok(Self {
+            id: row.try_get("id")?,
+            endpoint_id: row.try_get("endpoint_id")?,
+            event_id: row.try_get("event_id")?,
+            event_type,
+            payload: row.try_get("payload")?,
+            status: row.try_get("status")?,
+            attempts: row.try_get("attempts")?,
+            next_attempt_at: row.try_get("next_attempt_at")?,
+            last_attempt_at: row.try_get("last_attempt_at")?,
+            response_status: row.try_get("response_status")?,
+            last_error: row.try_get("last_error")?,
+            redelivery_of: row.try_get("redelivery_of")?,
+            created_at: row.try_get("created_at")?,
+            delivered_at: row.try_get("delivered_at")?,
+        })
+    }
+}
+
+/// Whether webhooks may be sent to `ip`. Loopback, private, link-local (cloud
+/// metadata included), shared, reserved and multicast addresses are refused, so
+/// endpoints cannot reach the platform's own network.
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_v4(mapped),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // "This network", shared address space (carrier-grade NAT), IETF
+        // protocol assignments, benchmarking and the reserved 240/4
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b))
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // IPv4-compatible (::/96), unique local (fc00::/7), link-local (fe80::/10),
+        // documentation (2001:db8::/32) and NAT64 (64:ff9b::/96). IPv4-compatible
+        // and NAT64 addresses can embed any IPv4 address
+        || ip.segments()[..6] == [0; 6]
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8)
+        || (first == 0x0064 && ip.segments()[1] == 0xff9b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_public_addresses_are_allowed() {
+        let cases = [
+            // Public
+            ("93.184.216.34", true),
+            ("8.8.8.8", true),
+            ("100.63.255.255", true),
+            ("100.128.0.0", true),
+            ("172.32.0.1", true),
+            ("2606:4700:4700::1111", true),
+            ("::ffff:93.184.216.34", true),
+            // Loopback and unspecified
+            ("127.0.0.1", false),
+            ("127.255.255.254", false),
+            ("0.0.0.0", false),
+            ("::1", false),
+            ("::", false),
+            // RFC 1918
+            ("10.0.0.1", false),
+            ("172.16.0.1", false),
+            ("172.31.255.255", false),
+            ("192.168.1.1", false),
+            // Link-local, cloud metadata included
+            ("169.254.169.254", false),
+            ("169.254.0.1", false),
+            ("fe80::1", false),
+            // Shared address space (carrier-grade NAT)
+            ("100.64.0.1", false),
+            ("100.127.255.255", false),
+            // Other special-purpose ranges
+            ("192.0.0.8", false),
+            ("192.0.2.1", false),
+            ("198.18.0.1", false),
+            ("224.0.0.1", false),
+            ("240.0.0.1", false),
+            ("255.255.255.255", false),
+            ("2001:db8::1", false),
+            ("ff02::1", false),
+            // Unique local (fc00::/7)
+            ("fc00::1", false),
+            ("fd12:3456:789a::1", false),
+            // IPv4 addresses embedded in IPv6
+            ("::ffff:127.0.0.1", false),
+            ("::ffff:10.0.0.1", false),
+            ("::ffff:169.254.169.254", false),
+            ("::127.0.0.1", false),
+            ("64:ff9b::a9fe:a9fe", false),
+        ];
+        for (ip, public) in cases {
+            assert_eq!(is_public_address(ip.parse().unwrap()), public, "{}", ip);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -5,6 +6,9 @@ pub enum DomainError {
     #[error("Invalid data: {0}")]
     InvalidData(String),
 
+    #[error("Validation failed: {}", format_field_errors(.0))]
+    Validation(Vec<FieldError>),
+
     #[error("Not found: {0}")]
     NotFound(String),
 
@@ -16,4 +20,31 @@ pub enum DomainError {
 
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+}
+
+/// A single invalid input field, reported back to the client as-is.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+fn format_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
@@ -0,0 +1,163 @@
+//! Domain events: facts about state changes, emitted by the use cases. Each event is
+//! written to the outbox in the same transaction as the change it describes, and a
+//! background dispatcher hands it to the `EventSubscriber`s afterwards.
+
+use crate::domain::entities::comment::Comment;
+use crate::domain::entities::issue::Issue;
+use crate::domain::entities::issue_claim::IssueClaim;
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::JournalEntry;
+use crate::domain::entities::project::Project;
+use crate::domain::entities::submission::{Submission, SubmissionReview};
+use crate::domain::entities::user::User;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::value_objects::money::Money;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Events carry a snapshot of the state they describe, so subscribers do not need
+/// to read it back (it may have changed again by the time they run).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum DomainEvent {
+    UserRegistered {
+        user_id: Uuid,
+        username: String,
+        email: String,
+    },
+    ProjectCreated {
+        project: Project,
+    },
+    ProjectUpdated {
+        project: Project,
+    },
+    IssueCreated {
+        issue: Issue,
+    },
+    IssueUpdated {
+        issue: Issue,
+    },
+    IssueStatusChanged {
+        project_id: Uuid,
+        transition: IssueTransition,
+    },
+    /// `amount` went into the escrow; `organization_id` is set when an organization
+    /// paid for it.
+    BountyFunded {
+        project_id: Uuid,
+        entry: JournalEntry,
+        amount: Money,
+        organization_id: Option<Uuid>,
+    },
+    /// `entry` is the release entry paying a contributor.
+    BountyPaid {
+        project_id: Uuid,
+        entry: JournalEntry,
+    },
+    /// `entry` is the refund entry returning escrow to its funders.
+    BountyRefunded {
+        project_id: Uuid,
+        entry: JournalEntry,
+    },
+    ClaimCreated {
+        project_id: Uuid,
+        claim: IssueClaim,
+    },
+    ClaimAccepted {
+        project_id: Uuid,
+        claim: IssueClaim,
+    },
+    /// A maintainer declined or revoked the claim.
+    ClaimRejected {
+        project_id: Uuid,
+        claim: IssueClaim,
+    },
+    SubmissionCreated {
+        project_id: Uuid,
+        submission: Submission,
+    },
+    SubmissionApproved {
+        project_id: Uuid,
+        submission: Submission,
+        review: SubmissionReview,
+    },
+    ChangesRequested {
+        project_id: Uuid,
+        submission: Submission,
+        review: SubmissionReview,
+    },
+    /// `parent_author_id` is the author of the comment replied to, if any.
+    CommentCreated {
+        project_id: Uuid,
+        comment: Comment,
+        parent_author_id: Option<Uuid>,
+    },
+}
+
+impl DomainEvent {
+    pub fn user_registered(user: &User) -> Self {
+        DomainEvent::UserRegistered {
+            user_id: user.id,
+            username: user.username.clone(),
+            email: user.email.clone(),
+        }
+    }
+
+    pub fn issue_status_changed(project_id: Uuid, transition: &IssueTransition) -> Self {
+        DomainEvent::IssueStatusChanged {
+            project_id,
+            transition: transition.clone(),
+        }
+    }
+
+    /// Name stored alongside the event and used in logs.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::UserRegistered { .. } => "user_registered",
+            DomainEvent::ProjectCreated { .. } => "project_created",
+            DomainEvent::ProjectUpdated { .. } => "project_updated",
+            DomainEvent::IssueCreated { .. } => "issue_created",
+            DomainEvent::IssueUpdated { .. } => "issue_updated",
+            DomainEvent::IssueStatusChanged { .. } => "issue_status_changed",
+            DomainEvent::BountyFunded { .. } => "bounty_funded",
+            DomainEvent::BountyPaid { .. } => "bounty_paid",
+            DomainEvent::BountyRefunded { .. } => "bounty_refunded",
+            DomainEvent::ClaimCreated { .. } => "claim_created",
+            DomainEvent::ClaimAccepted { .. } => "claim_accepted",
+            DomainEvent::ClaimRejected { .. } => "claim_rejected",
+            DomainEvent::SubmissionCreated { .. } => "submission_created",
+            DomainEvent::SubmissionApproved { .. } => "submission_approved",
+            DomainEvent::ChangesRequested { .. } => "changes_requested",
+            DomainEvent::CommentCreated { .. } => "comment_created",
+        }
+    }
+}
+
+/// An event as recorded in the outbox.
+#[derive(Debug, Clone)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub event: DomainEvent,
+    pub created_at: DateTime<Utc>,
+    /// Failed dispatch attempts so far.
+    pub attempts: i32,
+    /// Subscribers that already handled the event in an earlier attempt.
+    pub handled_by: Vec<String>,
+}
+
+/// Reacts to domain events in-process.
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// Unique name, used to remember which subscribers handled an event.
+    fn name(&self) -> &'static str;
+
+    /// Events are delivered at least once: after a crash or a failed attempt the
+    /// same event can arrive again, so handling it twice must be harmless. An error
+    /// makes the dispatcher retry the event for this subscriber later.
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), DomainError>;
+}
+
+pub type SharedEventSubscriber = Arc<dyn EventSubscriber>;
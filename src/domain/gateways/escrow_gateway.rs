@@ -0,0 +1,105 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::value_objects::money::Money;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Where an issue's on-chain escrow stands. The `-ing` states mean a transaction
+/// has been submitted but is not confirmed yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowState {
+    Unfunded,
+    Locking,
+    Locked,
+    Releasing,
+    Released,
+    Refunding,
+    Refunded,
+}
+
+impl EscrowState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EscrowState::Unfunded => "unfunded",
+            EscrowState::Locking => "locking",
+            EscrowState::Locked => "locked",
+            EscrowState::Releasing => "releasing",
+            EscrowState::Released => "released",
+            EscrowState::Refunding => "refunding",
+            EscrowState::Refunded => "refunded",
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self,
+            EscrowState::Locking | EscrowState::Releasing | EscrowState::Refunding
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    /// Submitted, or included in a block that is not final yet.
+    Pending,
+    Confirmed,
+    /// Included but reverted; the escrow stays in its previous state.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainTransaction {
+    pub hash: String,
+    pub block_number: Option<u64>,
+    pub confirmations: u64,
+    pub status: TransactionStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscrowStatus {
+    pub state: EscrowState,
+    pub amount: Option<Money>,
+    pub payee: Option<String>,
+    pub last_transaction: Option<ChainTransaction>,
+}
+
+impl EscrowStatus {
+    pub fn unfunded() -> Self {
+        Self {
+            state: EscrowState::Unfunded,
+            amount: None,
+            payee: None,
+            last_transaction: None,
+        }
+    }
+}
+
+/// Loose SS58 check: base58 alphabet and the usual length of a Substrate address.
+/// Gateways that send funds decode the address and verify its checksum.
+pub fn is_ss58_address(address: &str) -> bool {
+    const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    (46..=48).contains(&address.len()) && address.chars().all(|c| BASE58.contains(c))
+}
+
+/// Settles bounties on a blockchain. Each call submits a transaction and returns
+/// right away; `status` reports when it is confirmed or if it failed.
+#[async_trait]
+pub trait EscrowGateway: Send + Sync {
+    async fn lock_funds(&self, issue_id: Uuid, amount: Money)
+        -> Result<ChainTransaction, DomainError>;
+    /// Pays the whole escrow to `payee_address` (an SS58 account on Substrate chains).
+    async fn release(
+        &self,
+        issue_id: Uuid,
+        payee_address: &str,
+    ) -> Result<ChainTransaction, DomainError>;
+    /// Returns the escrow to the account that locked it.
+    async fn refund(&self, issue_id: Uuid) -> Result<ChainTransaction, DomainError>;
+    async fn status(&self, issue_id: Uuid) -> Result<EscrowStatus, DomainError>;
+}
+
+pub type SharedEscrowGateway = Arc<dyn EscrowGateway>;
@@ -0,0 +1,64 @@
+use crate::domain::entities::notification::NotificationType;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::value_objects::money::Money;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The emails the platform sends. Each has an HTML and a plain-text template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EmailMessage {
+    Welcome,
+    /// `link` carries the token confirming the address.
+    VerifyEmail {
+        link: String,
+    },
+    /// `link` carries the token allowing a new password to be set.
+    ResetPassword {
+        link: String,
+    },
+    BountyPaid {
+        issue_id: Uuid,
+        amount: Money,
+    },
+    /// Any other notification the user chose to get by email.
+    Notification {
+        notification_type: NotificationType,
+    },
+}
+
+/// A rendered email, ready to be sent.
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+/// Hands rendered emails to a mail server, or wherever the backend puts them.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: &Email) -> Result<(), DomainError>;
+}
+
+pub type SharedMailer = Arc<dyn Mailer>;
+
+/// Sends emails in the background, so requests do not wait for the mail server.
+#[async_trait]
+pub trait EmailQueue: Send + Sync {
+    /// Queues `message` for `user_id`. It is rendered and sent to the user's
+    /// address when a worker picks it up; users gone by then are skipped. An email
+    /// queued again under the same `dedup_key` is dropped, so callers handling an
+    /// event more than once send it once.
+    async fn queue_email(
+        &self,
+        user_id: Uuid,
+        message: EmailMessage,
+        dedup_key: &str,
+    ) -> Result<(), DomainError>;
+}
+
+pub type SharedEmailQueue = Arc<dyn EmailQueue>;
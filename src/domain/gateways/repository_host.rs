@@ -0,0 +1,117 @@
+use crate::domain::entities::issue_pull_request::PullRequestState;
+use crate::domain::entities::issue_upstream_link::UpstreamIssueState;
+use crate::domain::errors::domain_error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A repository on a code host, e.g. `rust-lang/rust` on GitHub.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryRef {
+    pub owner: String,
+    pub name: String,
+}
+
+/// Repository metadata as last reported by the code host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryStats {
+    pub stars: i32,
+    pub forks: i32,
+    /// On GitHub this includes open pull requests.
+    pub open_issues: i32,
+    pub primary_language: Option<String>,
+    /// SPDX identifier where the host knows it.
+    pub license: Option<String>,
+    pub topics: Vec<String>,
+    pub pushed_at: Option<DateTime<Utc>>,
+    pub synced_at: DateTime<Utc>,
+}
+
+/// An issue as reported by the code host. Pull requests are never returned as issues.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpstreamIssue {
+    pub number: i32,
+    pub title: String,
+    pub body: Option<String>,
+    pub url: String,
+    pub state: UpstreamIssueState,
+    pub labels: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpstreamIssuePage {
+    pub issues: Vec<UpstreamIssue>,
+    pub has_more: bool,
+}
+
+/// A pull request as reported by the code host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpstreamPullRequest {
+    pub number: i32,
+    pub title: String,
+    pub body: Option<String>,
+    pub url: String,
+    pub author: Option<String>,
+    pub state: PullRequestState,
+    pub draft: bool,
+    pub updated_at: DateTime<Utc>,
+    /// Issues of the same repository the pull request closes when merged
+    /// ("Fixes #12" in its description).
+    pub closing_issues: Vec<i32>,
+}
+
+/// A webhook event of the code host that rust4u acts on.
+#[derive(Debug, Clone)]
+pub enum RepositoryEvent {
+    /// An issue was edited, closed or reopened.
+    Issue {
+        repository: RepositoryRef,
+        issue: UpstreamIssue,
+    },
+    /// A pull request was opened, edited, closed (or merged) or changed its
+    /// draft state.
+    PullRequest {
+        repository: RepositoryRef,
+        pull_request: UpstreamPullRequest,
+    },
+}
+
+/// Reads public repository metadata from a code host, and the webhooks it sends.
+#[async_trait]
+pub trait RepositoryHost: Send + Sync {
+    /// Recognizes a link to one of the host's repositories; `None` for anything else.
+    fn parse_link(&self, link: &str) -> Option<RepositoryRef>;
+    /// The canonical link of the repository, as stored on projects.
+    fn link(&self, repository: &RepositoryRef) -> String;
+    /// Fails with `NotFound` if the repository does not exist or is private.
+    async fn fetch_stats(&self, repository: &RepositoryRef)
+        -> Result<RepositoryStats, DomainError>;
+    /// Open issues, most recently created first. `page` starts at 1.
+    async fn list_issues(
+        &self,
+        repository: &RepositoryRef,
+        page: u32,
+        per_page: u32,
+    ) -> Result<UpstreamIssuePage, DomainError>;
+    /// Fails with `NotFound` if there is no such issue, including when `number`
+    /// is a pull request.
+    async fn get_issue(
+        &self,
+        repository: &RepositoryRef,
+        number: i32,
+    ) -> Result<UpstreamIssue, DomainError>;
+    /// Checks the webhook `signature` header against `payload`. Fails with
+    /// `Unauthorized` if it is missing or does not match.
+    fn verify_webhook(&self, payload: &[u8], signature: Option<&str>) -> Result<(), DomainError>;
+    /// Reads a verified webhook payload of type `event`. Returns `None` for events
+    /// and actions rust4u does not act on.
+    fn parse_webhook(
+        &self,
+        event: &str,
+        payload: &[u8],
+    ) -> Result<Option<RepositoryEvent>, DomainError>;
+}
+
+pub type SharedRepositoryHost = Arc<dyn RepositoryHost>;
@@ -0,0 +1,25 @@
+use crate::domain::entities::webhook::{WebhookDelivery, WebhookEndpoint};
+use crate::domain::errors::domain_error::DomainError;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// What an endpoint answered to a delivery attempt. The body is not read: an
+/// endpoint's answer is not shown to whoever registered it.
+#[derive(Debug, Clone)]
+pub struct WebhookResponse {
+    pub status: u16,
+}
+
+/// Sends webhook deliveries over the network.
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// POSTs the delivery's payload to the endpoint, signed with its secret.
+    /// Fails with `Infra` if no response was received (connection error, timeout).
+    async fn send(
+        &self,
+        endpoint: &WebhookEndpoint,
+        delivery: &WebhookDelivery,
+    ) -> Result<WebhookResponse, DomainError>;
+}
+
+pub type SharedWebhookSender = Arc<dyn WebhookSender>;
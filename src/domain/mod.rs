@@ -1,13 +1,51 @@
 pub mod entities {
+    pub mod comment;
+    pub mod dispute;
     pub mod issue;
+    pub mod issue_claim;
+    pub mod issue_pull_request;
+    pub mod issue_transition;
+    pub mod issue_upstream_link;
+    pub mod job;
+    pub mod ledger;
+    pub mod notification;
+    pub mod organization;
     pub mod project;
+    pub mod project_member;
+    pub mod refresh_token;
+    pub mod submission;
     pub mod user;
+    pub mod webhook;
 }
 pub mod repositories {
+    pub mod comment_repository;
+    pub mod dispute_repository;
+    pub mod issue_claim_repository;
     pub mod issue_repository;
+    pub mod job_repository;
+    pub mod ledger_repository;
+    pub mod notification_repository;
+    pub mod organization_repository;
+    pub mod outbox_repository;
     pub mod project_repository;
+    pub mod refresh_token_repository;
+    pub mod search_repository;
+    pub mod submission_repository;
     pub mod user_repository;
+    pub mod webhook_delivery_repository;
+    pub mod webhook_repository;
+}
+pub mod gateways {
+    pub mod escrow_gateway;
+    pub mod mailer;
+    pub mod repository_host;
+    pub mod webhook_sender;
+}
+pub mod value_objects {
+    pub mod money;
 }
 pub mod errors {
     pub mod domain_error;
 }
+pub mod events;
+pub mod pagination;
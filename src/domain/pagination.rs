@@ -0,0 +1,203 @@
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Value of the sort column for the last row of a page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "k", content = "v", rename_all = "snake_case")]
+pub enum CursorValue {
+    Timestamp(DateTime<Utc>),
+    Number(f64),
+    Integer(i64),
+    Text(String),
+}
+
+/// Keyset position: the sort value plus the row id as a tie-breaker.
+/// Clients only ever see it through `encode`/`decode` as an opaque string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cursor {
+    pub value: CursorValue,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(value: CursorValue, id: Uuid) -> Self {
+        Self { value, id }
+    }
+
+    pub fn encode(&self) -> String {
+        // Serializing a plain struct of owned values cannot fail.
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(raw: &str) -> Result<Self, DomainError> {
+        URL_SAFE_NO_PAD
+            .decode(raw)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| DomainError::Validation(vec![FieldError::new("cursor", "is invalid")]))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from up to `limit + 1` rows; the extra row only signals that
+    /// another page exists.
+    pub fn from_rows(mut rows: Vec<T>, limit: u32, cursor_of: impl Fn(&T) -> Cursor) -> Self {
+        let has_more = rows.len() > limit as usize;
+        rows.truncate(limit as usize);
+        let next_cursor = if has_more {
+            rows.last().map(cursor_of)
+        } else {
+            None
+        };
+        Self {
+            items: rows,
+            next_cursor,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+pub fn validate_page_size(limit: u32) -> Result<(), DomainError> {
+    if limit == 0 || limit > MAX_PAGE_SIZE {
+        return Err(DomainError::Validation(vec![FieldError::new(
+            "limit",
+            &format!("must be between 1 and {}", MAX_PAGE_SIZE),
+        )]));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn id() -> Uuid {
+        Uuid::parse_str("6f1c1f5e-8a4b-4c61-9d2e-0b7e4a3c2d10").unwrap()
+    }
+
+    fn is_cursor_error(result: Result<Cursor, DomainError>) -> bool {
+        matches!(result, Err(DomainError::Validation(errors)) if errors[0].field == "cursor")
+    }
+
+    #[test]
+    fn cursors_round_trip() {
+        let values = [
+            CursorValue::Timestamp(Utc.with_ymd_and_hms(2023, 2, 6, 10, 0, 0).unwrap()),
+            CursorValue::Number(0.0607927),
+            CursorValue::Integer(-42),
+            CursorValue::Integer(i64::MAX),
+            CursorValue::Text("Zürich & co/+=".to_string()),
+            CursorValue::Text(String::new()),
+        ];
+        for value in values {
+            let encoded = Cursor::new(value.clone(), id()).encode();
+            assert!(
+                encoded
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+                "{} is not URL safe",
+                encoded
+            );
+            let decoded = Cursor::decode(&encoded).unwrap();
+            assert_eq!(decoded.id, id());
+            assert_eq!(format!("{:?}", decoded.value), format!("{:?}", value));
+        }
+    }
+
+    #[test]
+    fn tampered_cursors_are_rejected() {
+        let encoded = Cursor::new(CursorValue::Integer(7), id()).encode();
+        let mut flipped = encoded.clone().into_bytes();
+        flipped[3] = if flipped[3] == b'A' { b'B' } else { b'A' };
+        let flipped = String::from_utf8(flipped).unwrap();
+
+        let not_json = URL_SAFE_NO_PAD.encode("not json");
+        let unknown_kind = URL_SAFE_NO_PAD.encode(format!(
+            r#"{{"value":{{"k":"date","v":1}},"id":"{}"}}"#,
+            id()
+        ));
+        let wrong_value = URL_SAFE_NO_PAD.encode(format!(
+            r#"{{"value":{{"k":"integer","v":"7"}},"id":"{}"}}"#,
+            id()
+        ));
+        let bad_id = URL_SAFE_NO_PAD.encode(r#"{"value":{"k":"integer","v":7},"id":"nope"}"#);
+        let padded = format!("{}=", encoded);
+        let truncated = &encoded[..encoded.len() - 2];
+
+        for raw in [
+            "",
+            "%%%",
+            "not a cursor",
+            flipped.as_str(),
+            not_json.as_str(),
+            unknown_kind.as_str(),
+            wrong_value.as_str(),
+            bad_id.as_str(),
+            padded.as_str(),
+            truncated,
+        ] {
+            assert!(
+                is_cursor_error(Cursor::decode(raw)),
+                "{:?} was accepted",
+                raw
+            );
+        }
+    }
+
+    #[test]
+    fn pages_stop_at_the_limit() {
+        let cursor_of = |n: &i64| Cursor::new(CursorValue::Integer(*n), id());
+        let cases: [(usize, u32, usize, Option<i64>); 5] = [
+            (0, 3, 0, None),
+            (2, 3, 2, None),
+            (3, 3, 3, None),
+            // The extra row only says another page exists; the cursor is the last kept row.
+            (4, 3, 3, Some(2)),
+            (2, 1, 1, Some(0)),
+        ];
+        for (rows, limit, expected_len, expected_next) in cases {
+            let page = Page::from_rows((0..rows as i64).collect(), limit, cursor_of);
+            assert_eq!(
+                page.items.len(),
+                expected_len,
+                "{} rows, limit {}",
+                rows,
+                limit
+            );
+            let next = page.next_cursor.map(|c| match c.value {
+                CursorValue::Integer(n) => n,
+                other => panic!("unexpected cursor value {:?}", other),
+            });
+            assert_eq!(next, expected_next, "{} rows, limit {}", rows, limit);
+        }
+    }
+
+    #[test]
+    fn page_size_is_bounded() {
+        assert!(validate_page_size(0).is_err());
+        assert!(validate_page_size(1).is_ok());
+        assert!(validate_page_size(MAX_PAGE_SIZE).is_ok());
+        assert!(validate_page_size(MAX_PAGE_SIZE + 1).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::domain::entities::comment::{Comment, CommentEdit, CommentTarget};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::pagination::{Cursor, Page};
+use async_trait::async_trait;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentSort {
+    #[default]
+    Oldest,
+    Newest,
+}
+
+/// Page position for `CommentRepository::list_threads`.
+#[derive(Debug, Clone, Default)]
+pub struct CommentQuery {
+    pub sort: CommentSort,
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
+#[async_trait]
+pub trait CommentRepository {
+    /// Stores the comment together with its mentions.
+    async fn create_comment(
+        &self,
+        comment: &Comment,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    async fn get_comment_by_id(&self, comment_id: Uuid) -> Result<Option<Comment>, DomainError>;
+    /// Top-level comments of `target`, deleted ones included.
+    async fn list_threads(
+        &self,
+        target: CommentTarget,
+        query: &CommentQuery,
+    ) -> Result<Page<Comment>, DomainError>;
+    /// Replies to any of `parent_ids`, oldest first.
+    async fn get_replies(&self, parent_ids: &[Uuid]) -> Result<Vec<Comment>, DomainError>;
+    /// Saves the new body and mentions of `comment`, keeping `edit` (the previous
+    /// body) in the history, in one transaction. Fails with `Conflict` if the
+    /// comment was deleted meanwhile.
+    async fn update_comment(
+        &self,
+        comment: &Comment,
+        edit: &CommentEdit,
+    ) -> Result<(), DomainError>;
+    /// Oldest first.
+    async fn get_edits(&self, comment_id: Uuid) -> Result<Vec<CommentEdit>, DomainError>;
+    async fn soft_delete_comment(&self, comment: &Comment) -> Result<(), DomainError>;
+}
@@ -0,0 +1,44 @@
+use crate::domain::entities::dispute::{Dispute, DisputeEvent};
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::JournalEntry;
+use crate::domain::entities::submission::SubmissionStatus;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait DisputeRepository {
+    /// Stores the dispute with its `opened` log event and applies `transition` (the
+    /// move to `Disputed`) in one transaction, recording `events` along with it. Fails with `Conflict` if the issue
+    /// already has an open dispute or its status changed concurrently.
+    async fn open_dispute(
+        &self,
+        dispute: &Dispute,
+        event: &DisputeEvent,
+        transition: &IssueTransition,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    async fn get_dispute_by_id(&self, dispute_id: Uuid) -> Result<Option<Dispute>, DomainError>;
+    /// Newest first.
+    async fn get_disputes_by_issue(&self, issue_id: Uuid) -> Result<Vec<Dispute>, DomainError>;
+    /// Appends to the log of an open dispute; fails with `Conflict` once it is resolved.
+    async fn add_event(&self, event: &DisputeEvent) -> Result<(), DomainError>;
+    /// Oldest first.
+    async fn get_events(&self, dispute_id: Uuid) -> Result<Vec<DisputeEvent>, DomainError>;
+    /// Applies a ruling in one transaction: stores the resolved `dispute` and its
+    /// `ruling` log event, posts `entries` to the ledger, applies `transition`, moves
+    /// the disputed submission to `submission_status` and supersedes the issue's
+    /// other pending submissions, recording `events` as well. When the submission is approved the contributor
+    /// also becomes the assignee. Fails with `Conflict` if the dispute was already
+    /// resolved.
+    async fn resolve_dispute(
+        &self,
+        dispute: &Dispute,
+        event: &DisputeEvent,
+        transition: &IssueTransition,
+        submission_status: SubmissionStatus,
+        entries: &[JournalEntry],
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+}
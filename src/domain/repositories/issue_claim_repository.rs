@@ -0,0 +1,44 @@
+use crate::domain::entities::issue_claim::{ClaimStatus, IssueClaim};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait IssueClaimRepository {
+    /// Fails with `Conflict` if the claimant already has a live claim on the issue.
+    async fn create_claim(
+        &self,
+        claim: &IssueClaim,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    async fn get_claim_by_id(&self, claim_id: Uuid) -> Result<Option<IssueClaim>, DomainError>;
+    /// Oldest first.
+    async fn get_claims_by_issue(&self, issue_id: Uuid) -> Result<Vec<IssueClaim>, DomainError>;
+    /// Saves message, ETA and activity time of a live claim.
+    async fn update_claim(&self, claim: &IssueClaim) -> Result<(), DomainError>;
+    /// Accepts a pending claim. With `exclusive`, also assigns the issue to the
+    /// claimant (failing with `Conflict` if someone else is assigned) and rejects
+    /// the other pending claims, all in one transaction.
+    async fn accept_claim(
+        &self,
+        claim: &IssueClaim,
+        exclusive: bool,
+        at: DateTime<Utc>,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    /// Moves a live claim to a final status and unassigns the claimant if they
+    /// were the assignee. Returns `false` if the claim was no longer live.
+    async fn close_claim(
+        &self,
+        claim_id: Uuid,
+        status: ClaimStatus,
+        at: DateTime<Utc>,
+        events: &[DomainEvent],
+    ) -> Result<bool, DomainError>;
+    /// Expires every live claim idle since before `cutoff` on issues that are still
+    /// open or in review, unassigning their claimants. Claims whose claimant has a
+    /// submission waiting for review are kept. Returns how many claims expired.
+    async fn expire_stale_claims(&self, cutoff: DateTime<Utc>) -> Result<u64, DomainError>;
+}
@@ -1,19 +1,128 @@
 use crate::domain::entities::issue::{Issue, IssueStatus};
+use crate::domain::entities::issue_pull_request::IssuePullRequest;
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::issue_upstream_link::IssueUpstreamLink;
+use crate::domain::entities::ledger::JournalEntry;
 use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::pagination::{Cursor, Page};
+use crate::domain::value_objects::money::{Currency, Money};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSort {
+    #[default]
+    Newest,
+    Oldest,
+    /// Bounties are only comparable within a currency, so these require
+    /// `IssueQuery::currency`.
+    BountyDesc,
+    BountyAsc,
+}
+
+/// Author of an issue's approved submission, who its on-chain bounty is paid to.
+#[derive(Debug, Clone)]
+pub struct Payee {
+    pub user_id: Uuid,
+    /// `None` until the author registers an address.
+    pub chain_address: Option<String>,
+}
+
+/// Filters, ordering and page position for `IssueRepository::list_issues`.
+/// `tag` and `owner_id` refer to the issue's project; `min_bounty` also restricts
+/// results to its currency.
+#[derive(Debug, Clone, Default)]
+pub struct IssueQuery {
+    pub status: Option<IssueStatus>,
+    pub project_id: Option<Uuid>,
+    pub currency: Option<Currency>,
+    pub min_bounty: Option<Money>,
+    pub assignee_id: Option<Uuid>,
+    pub tag: Option<String>,
+    pub owner_id: Option<Uuid>,
+    pub include_hidden: bool,
+    pub sort: IssueSort,
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
 #[async_trait]
 pub trait IssueRepository {
-    async fn create_issue(&self, issue: &Issue) -> Result<(), DomainError>;
+    /// Issue writes record `events` in the same transaction as the change.
+    async fn create_issue(&self, issue: &Issue, events: &[DomainEvent]) -> Result<(), DomainError>;
     async fn get_issue_by_id(&self, issue_id: Uuid) -> Result<Option<Issue>, DomainError>;
     async fn get_issues_by_project(&self, project_id: Uuid) -> Result<Vec<Issue>, DomainError>;
-    async fn update_issue(&self, issue: &Issue) -> Result<(), DomainError>;
-    async fn update_issue_status(
+    /// Fails with `Conflict` if the bounty changes while the issue's ledger escrow
+    /// holds funds; the issue row is locked while this is checked.
+    async fn update_issue(&self, issue: &Issue, events: &[DomainEvent]) -> Result<(), DomainError>;
+    /// Moves the issue from `transition.from_status` to `transition.to_status` and
+    /// records the transition atomically, posting `refund` with it. Fails with
+    /// `Conflict` if the stored status no longer matches `from_status`, or if
+    /// `refund` no longer empties the escrow.
+    async fn apply_transition(
         &self,
-        issue_id: Uuid,
-        status: IssueStatus,
+        transition: &IssueTransition,
+        refund: Option<&JournalEntry>,
+        events: &[DomainEvent],
     ) -> Result<(), DomainError>;
+    async fn get_transitions_by_issue(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueTransition>, DomainError>;
+    async fn set_issue_hidden(&self, issue_id: Uuid, hidden: bool) -> Result<(), DomainError>;
+    /// Fails with `Conflict` while the issue's ledger escrow holds funds, as they
+    /// could then neither be released nor refunded.
     async fn delete_issue(&self, issue_id: Uuid) -> Result<(), DomainError>;
-    async fn list_issues(&self) -> Result<Vec<Issue>, DomainError>;
+    /// Whether the issue's ledger escrow holds funds, in any currency.
+    async fn has_funded_escrow(&self, issue_id: Uuid) -> Result<bool, DomainError>;
+    /// `None` while no submission of the issue is approved.
+    async fn get_payee(&self, issue_id: Uuid) -> Result<Option<Payee>, DomainError>;
+    async fn list_issues(&self, query: &IssueQuery) -> Result<Page<Issue>, DomainError>;
+
+    /// Creates the issue and its upstream link atomically. Fails with `Conflict` if
+    /// the upstream issue is already imported into the project.
+    async fn create_imported_issue(
+        &self,
+        issue: &Issue,
+        link: &IssueUpstreamLink,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    async fn get_upstream_link(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Option<IssueUpstreamLink>, DomainError>;
+    async fn get_upstream_links_by_project(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<IssueUpstreamLink>, DomainError>;
+    /// Links last synced before `synced_before`, least recently synced first.
+    async fn get_upstream_links_due_for_sync(
+        &self,
+        synced_before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<IssueUpstreamLink>, DomainError>;
+    /// Stores the link's `url`, `state` and `synced_at`.
+    async fn update_upstream_link(&self, link: &IssueUpstreamLink) -> Result<(), DomainError>;
+    /// Links to upstream issue `number` of the repository, one per project that
+    /// imported it.
+    async fn get_upstream_links_by_number(
+        &self,
+        repo_owner: &str,
+        repo_name: &str,
+        number: i32,
+    ) -> Result<Vec<IssueUpstreamLink>, DomainError>;
+
+    /// Inserts or updates the pull request of the issue. An update older than the
+    /// stored `updated_at` is dropped; returns whether the pull request was stored.
+    async fn save_pull_request(&self, pull_request: &IssuePullRequest)
+        -> Result<bool, DomainError>;
+    /// Most recently updated first.
+    async fn get_pull_requests_by_issue(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssuePullRequest>, DomainError>;
 }
@@ -0,0 +1,40 @@
+use crate::domain::entities::job::{Job, JobStatus};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, Page};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Filters and page position for `JobRepository::list_jobs`, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct JobQuery {
+    pub status: Option<JobStatus>,
+    pub kind: Option<String>,
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
+#[async_trait]
+pub trait JobRepository {
+    /// Returns `false`, storing nothing, if a job with the same `dedup_key` exists.
+    async fn create_job(&self, job: &Job) -> Result<bool, DomainError>;
+    async fn get_job_by_id(&self, job_id: Uuid) -> Result<Option<Job>, DomainError>;
+    async fn list_jobs(&self, query: &JobQuery) -> Result<Page<Job>, DomainError>;
+    /// Takes up to `limit` due jobs of the given `kinds`, oldest due first, marks
+    /// them `Running` until `lease_until` and counts the attempt. A running job whose
+    /// lease ran out (its worker stopped) is due again.
+    async fn claim_due_jobs(
+        &self,
+        kinds: &[String],
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<Job>, DomainError>;
+    /// Stores the job's `status`, `attempts`, `run_at`, `last_error` and
+    /// `finished_at`, unless its stored `attempts` is no longer `seen_attempts`:
+    /// another worker claimed it since, e.g. after the lease ran out. Returns
+    /// whether it was stored.
+    async fn update_job(&self, job: &Job, seen_attempts: i32) -> Result<bool, DomainError>;
+    /// Deletes jobs completed before `before`. Returns how many were deleted.
+    async fn purge_completed(&self, before: DateTime<Utc>) -> Result<u64, DomainError>;
+}
@@ -0,0 +1,45 @@
+use crate::domain::entities::issue::Issue;
+use crate::domain::entities::ledger::{AccountKind, AccountRef, JournalEntry};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::value_objects::money::{Currency, Money};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait LedgerRepository {
+    /// Records the entry and its postings in one transaction, creating missing
+    /// accounts, and `events` along with it. Fails with `Conflict` if an account that
+    /// cannot go negative would.
+    async fn post_entry(
+        &self,
+        entry: &JournalEntry,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    /// Same as `post_entry` for an entry funding `issue`'s escrow. The issue row is
+    /// locked first, and the entry fails with `Conflict` if the issue's status or
+    /// bounty currency changed since `issue` was read.
+    async fn post_funding(
+        &self,
+        issue: &Issue,
+        entry: &JournalEntry,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    async fn get_balance(&self, account: &AccountRef) -> Result<Money, DomainError>;
+    /// Balance of every account of `kind` belonging to `owner_id`, one per currency.
+    async fn get_balances(
+        &self,
+        kind: AccountKind,
+        owner_id: Uuid,
+    ) -> Result<Vec<Money>, DomainError>;
+    /// Net amount put into the issue's escrow through `Fund` entries, per account a
+    /// refund should go back to: the account the money was drawn from, or the
+    /// funding user's account for money that came straight from outside.
+    async fn get_escrow_funders(
+        &self,
+        issue_id: Uuid,
+        currency: Currency,
+    ) -> Result<Vec<(AccountRef, Money)>, DomainError>;
+    /// Oldest first.
+    async fn get_entries_by_issue(&self, issue_id: Uuid) -> Result<Vec<JournalEntry>, DomainError>;
+}
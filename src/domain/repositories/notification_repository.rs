@@ -0,0 +1,50 @@
+use crate::domain::entities::notification::{Notification, NotificationPreference};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, Page};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Filters and page position for `NotificationRepository::list_notifications`,
+/// newest first.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationQuery {
+    pub unread_only: bool,
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
+#[async_trait]
+pub trait NotificationRepository {
+    /// Skips notifications whose user already has one for the same event, and
+    /// those of users that no longer exist.
+    async fn create_notifications(&self, notifications: &[Notification])
+        -> Result<(), DomainError>;
+    async fn list_notifications(
+        &self,
+        user_id: Uuid,
+        query: &NotificationQuery,
+    ) -> Result<Page<Notification>, DomainError>;
+    async fn count_unread(&self, user_id: Uuid) -> Result<i64, DomainError>;
+    /// Marks one of the user's notifications read, unless it already is. Returns
+    /// `false` if the user has no such notification.
+    async fn mark_read(
+        &self,
+        user_id: Uuid,
+        notification_id: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<bool, DomainError>;
+    /// Returns how many notifications were unread.
+    async fn mark_all_read(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<u64, DomainError>;
+    /// The channels the user picked; types missing from the list use their default.
+    async fn get_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<NotificationPreference>, DomainError>;
+    /// Replaces the user's channel for each type in `preferences`.
+    async fn save_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: &[NotificationPreference],
+    ) -> Result<(), DomainError>;
+}
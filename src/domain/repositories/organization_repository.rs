@@ -0,0 +1,38 @@
+use crate::domain::entities::organization::{Organization, OrganizationMember};
+use crate::domain::errors::domain_error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait OrganizationRepository {
+    /// Creates the organization with `owner` as its first member, in one transaction.
+    /// Fails with `Conflict` if the slug is taken.
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+        owner: &OrganizationMember,
+    ) -> Result<(), DomainError>;
+    async fn get_organization_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Organization>, DomainError>;
+    async fn update_organization(&self, organization: &Organization) -> Result<(), DomainError>;
+    /// Fails with `Conflict` while the organization still owns projects.
+    async fn delete_organization(&self, organization_id: Uuid) -> Result<(), DomainError>;
+
+    /// Members and pending invitations, owners first.
+    async fn get_members(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<OrganizationMember>, DomainError>;
+    async fn get_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationMember>, DomainError>;
+    /// Fails with `Conflict` if the user is already a member or invited.
+    async fn add_member(&self, member: &OrganizationMember) -> Result<(), DomainError>;
+    /// Stores the member's role and `accepted_at`.
+    async fn update_member(&self, member: &OrganizationMember) -> Result<(), DomainError>;
+    async fn remove_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<(), DomainError>;
+}
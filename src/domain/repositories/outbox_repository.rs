@@ -0,0 +1,34 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::OutboxEvent;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Events are written to the outbox by the repositories making the change they
+/// describe; this repository only serves the dispatcher.
+#[async_trait]
+pub trait OutboxRepository {
+    /// Takes up to `limit` undispatched events due at `now`, oldest first, and
+    /// postpones them to `lease_until` so no other dispatcher picks them up meanwhile.
+    async fn claim_due_events(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<OutboxEvent>, DomainError>;
+    async fn mark_dispatched(
+        &self,
+        event_id: Uuid,
+        dispatched_at: DateTime<Utc>,
+    ) -> Result<(), DomainError>;
+    /// Stores a failed attempt: `attempts`, `handled_by` and the error, and schedules
+    /// the next attempt.
+    async fn record_failure(
+        &self,
+        event: &OutboxEvent,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+    ) -> Result<(), DomainError>;
+    /// Deletes events dispatched before `before`. Returns how many were deleted.
+    async fn purge_dispatched(&self, before: DateTime<Utc>) -> Result<u64, DomainError>;
+}
@@ -1,14 +1,114 @@
-use crate::domain::entities::project::Project;
+use crate::domain::entities::organization::OrganizationRole;
+use crate::domain::entities::project::{Project, ProjectOwner};
+use crate::domain::entities::project_member::ProjectMember;
 use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::gateways::repository_host::RepositoryStats;
+use crate::domain::pagination::{Cursor, Page};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectSort {
+    #[default]
+    Newest,
+    Oldest,
+    Name,
+}
+
+/// Filters, ordering and page position for `ProjectRepository::list_projects`.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectQuery {
+    /// Projects owned by this user.
+    pub owner_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
+    pub tag: Option<String>,
+    pub include_hidden: bool,
+    pub sort: ProjectSort,
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
 #[async_trait]
 pub trait ProjectRepository {
-    async fn create_project(&self, project: &Project) -> Result<(), DomainError>;
+    /// A project owned by a user also gets that user as its owner member. `events`
+    /// are recorded in the same transaction.
+    async fn create_project(
+        &self,
+        project: &Project,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
     async fn get_project_by_id(&self, project_id: Uuid) -> Result<Option<Project>, DomainError>;
     async fn get_projects_by_owner(&self, owner_id: Uuid) -> Result<Vec<Project>, DomainError>;
-    async fn update_project(&self, project: &Project) -> Result<(), DomainError>;
+    async fn update_project(
+        &self,
+        project: &Project,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    async fn set_project_hidden(&self, project_id: Uuid, hidden: bool) -> Result<(), DomainError>;
+    /// Deletes the project with its issues. Fails with `Conflict` while the ledger
+    /// escrow of any of its issues holds funds.
     async fn delete_project(&self, project_id: Uuid) -> Result<(), DomainError>;
-    async fn list_projects(&self) -> Result<Vec<Project>, DomainError>;
+    async fn list_projects(&self, query: &ProjectQuery) -> Result<Page<Project>, DomainError>;
+
+    /// Members and pending invitations, owner first.
+    async fn get_members(&self, project_id: Uuid) -> Result<Vec<ProjectMember>, DomainError>;
+    async fn get_member(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ProjectMember>, DomainError>;
+    /// Fails with `Conflict` if the user is already a member or invited.
+    async fn add_member(&self, member: &ProjectMember) -> Result<(), DomainError>;
+    /// Stores the member's role and `accepted_at`.
+    async fn update_member(&self, member: &ProjectMember) -> Result<(), DomainError>;
+    async fn remove_member(&self, project_id: Uuid, user_id: Uuid) -> Result<(), DomainError>;
+    /// Hands the project to `new_owner` in one transaction. A previous owning user
+    /// stays on as a maintainer; a new owning user must be an accepted member.
+    async fn transfer_ownership(
+        &self,
+        project_id: Uuid,
+        new_owner: ProjectOwner,
+    ) -> Result<(), DomainError>;
+
+    /// Role of `user_id` in the organization, if they are an accepted member.
+    async fn get_organization_role(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationRole>, DomainError>;
+    /// Role of `user_id` in the organization that owns the project, if any.
+    async fn get_owner_organization_role(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationRole>, DomainError>;
+    /// Users holding a maintainer role in the project, directly or through the
+    /// organization that owns it.
+    async fn get_maintainer_ids(&self, project_id: Uuid) -> Result<Vec<Uuid>, DomainError>;
+
+    /// Stores a successful sync of the project's repository metadata.
+    async fn save_repo_stats(
+        &self,
+        project_id: Uuid,
+        stats: &RepositoryStats,
+    ) -> Result<(), DomainError>;
+    /// Records a failed sync attempt; previously synced stats are kept.
+    async fn record_repo_sync_error(
+        &self,
+        project_id: Uuid,
+        checked_at: DateTime<Utc>,
+        error: &str,
+    ) -> Result<(), DomainError>;
+    async fn delete_repo_stats(&self, project_id: Uuid) -> Result<(), DomainError>;
+    /// Projects with a `github_link` that were never synced or last checked before
+    /// `checked_before`, least recently checked first.
+    async fn get_projects_due_for_sync(
+        &self,
+        checked_before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<Project>, DomainError>;
 }
@@ -0,0 +1,22 @@
+use crate::domain::entities::refresh_token::RefreshToken;
+use crate::domain::errors::domain_error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait RefreshTokenRepository {
+    async fn create_token(&self, token: &RefreshToken) -> Result<(), DomainError>;
+    async fn get_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, DomainError>;
+    /// Revokes `old_token_id` and stores `new_token` in its place. Returns `false`
+    /// without storing anything if the old token had already been revoked.
+    async fn rotate_token(
+        &self,
+        old_token_id: Uuid,
+        new_token: &RefreshToken,
+    ) -> Result<bool, DomainError>;
+    async fn revoke_family(&self, family_id: Uuid) -> Result<(), DomainError>;
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> Result<(), DomainError>;
+}
@@ -0,0 +1,68 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, Page};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Project,
+    Issue,
+}
+
+impl SearchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKind::Project => "project",
+            SearchKind::Issue => "issue",
+        }
+    }
+}
+
+/// Full-text query plus filters for `SearchRepository::search`.
+/// `tag` matches the project's tags (an issue's own project for issue hits).
+/// Hidden items are searched when `include_hidden` is set, or when they belong
+/// to a project `viewer_id` is a member of.
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    pub text: String,
+    pub kind: Option<SearchKind>,
+    pub tag: Option<String>,
+    pub include_hidden: bool,
+    pub viewer_id: Option<Uuid>,
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
+/// A single ranked match. `snippet` is an HTML excerpt of the description (or
+/// the title when there is none): the text is escaped and matched terms are
+/// wrapped in `<mark>`.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub snippet: String,
+    pub tags: Vec<String>,
+    pub rank: f64,
+}
+
+/// Number of matches per kind, ignoring the `kind` filter and pagination.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFacets {
+    pub projects: i64,
+    pub issues: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResults {
+    pub page: Page<SearchHit>,
+    pub facets: SearchFacets,
+}
+
+#[async_trait]
+pub trait SearchRepository {
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResults, DomainError>;
+}
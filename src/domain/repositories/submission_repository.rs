@@ -0,0 +1,62 @@
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::JournalEntry;
+use crate::domain::entities::submission::{Submission, SubmissionReview};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait SubmissionRepository {
+    /// Stores a new submission, counts it as activity on the submitter's accepted
+    /// claim and, when given, applies `transition` (the move of the issue into
+    /// review) in the same transaction, recording `events` along with it. Fails with
+    /// `Conflict` if the submitter already has a pending submission on the issue, the
+    /// claim is no longer accepted or the issue status changed concurrently.
+    async fn create_submission(
+        &self,
+        submission: &Submission,
+        transition: Option<&IssueTransition>,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    async fn get_submission_by_id(
+        &self,
+        submission_id: Uuid,
+    ) -> Result<Option<Submission>, DomainError>;
+    /// Oldest first.
+    async fn get_submissions_by_issue(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Vec<Submission>, DomainError>;
+    /// Submissions of any issue pointing at the pull request `pr_url`.
+    async fn get_submissions_by_pr_url(&self, pr_url: &str)
+        -> Result<Vec<Submission>, DomainError>;
+    /// Reviews of every submission of the issue, oldest first.
+    async fn get_reviews_by_issue(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Vec<SubmissionReview>, DomainError>;
+    /// Approves a pending submission in one transaction: records the review,
+    /// supersedes the other pending submissions, assigns the issue to the submitter,
+    /// applies `transition` (the move to `Approved`) and posts `payout`, if any.
+    async fn approve_submission(
+        &self,
+        submission: &Submission,
+        review: &SubmissionReview,
+        transition: &IssueTransition,
+        payout: Option<&JournalEntry>,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError>;
+    /// Sends a pending submission back with `review`. If no other submission of
+    /// the issue is pending anymore, `reopen` (the move back to `Open`) is applied
+    /// in the same transaction, together with `reopen_events`. Returns whether the
+    /// issue was reopened.
+    async fn request_changes(
+        &self,
+        submission: &Submission,
+        review: &SubmissionReview,
+        reopen: &IssueTransition,
+        events: &[DomainEvent],
+        reopen_events: &[DomainEvent],
+    ) -> Result<bool, DomainError>;
+}
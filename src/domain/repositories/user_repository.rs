@@ -1,14 +1,40 @@
 use async_trait::async_trait;
+use serde::Deserialize;
 use uuid::Uuid;
-use crate::domain::entities::user::User;
+use crate::domain::entities::user::{Role, User};
 use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::pagination::{Cursor, Page};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSort {
+    #[default]
+    Newest,
+    Oldest,
+    Username,
+}
+
+/// Filters, ordering and page position for `UserRepository::list_users`.
+#[derive(Debug, Clone, Default)]
+pub struct UserQuery {
+    /// Case-insensitive username prefix.
+    pub username: Option<String>,
+    pub role: Option<Role>,
+    pub sort: UserSort,
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
 
 #[async_trait]
 pub trait UserRepository {
-    async fn create_user(&self, user: &User) -> Result<(), DomainError>;
+    /// Records `events` in the same transaction as the user.
+    async fn create_user(&self, user: &User, events: &[DomainEvent]) -> Result<(), DomainError>;
     async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<User>, DomainError>;
     async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, DomainError>;
+    /// Users whose username matches one of `usernames`, ignoring case.
+    async fn get_users_by_usernames(&self, usernames: &[String]) -> Result<Vec<User>, DomainError>;
     async fn update_user(&self, user: &User) -> Result<(), DomainError>;
     async fn delete_user(&self, user_id: Uuid) -> Result<(), DomainError>;
-    async fn list_users(&self) -> Result<Vec<User>, DomainError>;
+    async fn list_users(&self, query: &UserQuery) -> Result<Page<User>, DomainError>;
 }
@@ -0,0 +1,12 @@
+use crate::domain::errors::domain_error::DomainError;
+use async_trait::async_trait;
+
+/// Deliveries of incoming code host webhooks, so each one is handled only once.
+#[async_trait]
+pub trait WebhookDeliveryRepository {
+    /// Records the delivery. Returns `false` if it was recorded before.
+    async fn record_delivery(&self, delivery_id: &str, event: &str) -> Result<bool, DomainError>;
+    /// Drops the record of a delivery that could not be handled, so a redelivery
+    /// is handled again.
+    async fn forget_delivery(&self, delivery_id: &str) -> Result<(), DomainError>;
+}
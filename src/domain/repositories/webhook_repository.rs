@@ -0,0 +1,60 @@
+use crate::domain::entities::webhook::{
+    WebhookDelivery, WebhookEndpoint, WebhookEvent, WebhookOwner,
+};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, Page};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Page position for `WebhookRepository::list_deliveries`, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct DeliveryQuery {
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
+#[async_trait]
+pub trait WebhookRepository {
+    async fn create_endpoint(&self, endpoint: &WebhookEndpoint) -> Result<(), DomainError>;
+    async fn get_endpoint_by_id(
+        &self,
+        endpoint_id: Uuid,
+    ) -> Result<Option<WebhookEndpoint>, DomainError>;
+    /// Oldest first.
+    async fn get_endpoints_by_owner(
+        &self,
+        owner: WebhookOwner,
+    ) -> Result<Vec<WebhookEndpoint>, DomainError>;
+    /// Stores the endpoint's `url`, `events`, `description`, `active` and `updated_at`.
+    async fn update_endpoint(&self, endpoint: &WebhookEndpoint) -> Result<(), DomainError>;
+    /// Deletes the endpoint together with its delivery log.
+    async fn delete_endpoint(&self, endpoint_id: Uuid) -> Result<(), DomainError>;
+
+    /// Queues a delivery of `event` to every active endpoint of the project's owner
+    /// that is subscribed to its type, skipping endpoints that already have one.
+    /// Returns how many were queued.
+    async fn enqueue_event(&self, event: &WebhookEvent) -> Result<u64, DomainError>;
+    async fn create_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DomainError>;
+    async fn get_delivery_by_id(
+        &self,
+        delivery_id: Uuid,
+    ) -> Result<Option<WebhookDelivery>, DomainError>;
+    async fn list_deliveries(
+        &self,
+        endpoint_id: Uuid,
+        query: &DeliveryQuery,
+    ) -> Result<Page<WebhookDelivery>, DomainError>;
+    /// Takes up to `limit` pending deliveries due at `now`, together with their
+    /// endpoints, and postpones them to `lease_until` so no other worker picks them
+    /// up meanwhile. Deliveries to inactive endpoints are left alone.
+    async fn claim_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<(WebhookDelivery, WebhookEndpoint)>, DomainError>;
+    /// Stores the outcome of an attempt: `status`, `attempts`, `next_attempt_at`,
+    /// `last_attempt_at`, `response_status`, `last_error` and `delivered_at`.
+    async fn record_attempt(&self, delivery: &WebhookDelivery) -> Result<(), DomainError>;
+}
@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Currencies a bounty can be denominated in, each with its number of minor-unit
+/// decimals (cents for fiat, planck for Substrate tokens).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    Usd,
+    Eur,
+    Brl,
+    Dot,
+    Ksm,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 5] = [
+        Currency::Usd,
+        Currency::Eur,
+        Currency::Brl,
+        Currency::Dot,
+        Currency::Ksm,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Brl => "BRL",
+            Currency::Dot => "DOT",
+            Currency::Ksm => "KSM",
+        }
+    }
+
+    /// Case-insensitive lookup; `None` for anything outside the allow-list.
+    pub fn from_code(code: &str) -> Option<Currency> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.code().eq_ignore_ascii_case(code.trim()))
+    }
+
+    pub fn decimals(&self) -> u32 {
+        match self {
+            Currency::Usd | Currency::Eur | Currency::Brl => 2,
+            Currency::Dot => 10,
+            Currency::Ksm => 12,
+        }
+    }
+
+    /// Largest bounty accepted, in minor units.
+    pub fn max_bounty(&self) -> i64 {
+        let major: i64 = match self {
+            Currency::Usd | Currency::Eur | Currency::Brl => 1_000_000,
+            Currency::Dot => 100_000,
+            Currency::Ksm => 10_000,
+        };
+        major * 10_i64.pow(self.decimals())
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// An exact amount of money: an integer number of minor units plus its currency.
+///
+/// In JSON the amount is a decimal *string* in major units
+/// (`{"amount": "12.50", "currency": "USD"}`) so no client ever routes it through
+/// a float.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "MoneyRepr", into = "MoneyRepr")]
+pub struct Money {
+    pub amount_minor: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount_minor: i64, currency: Currency) -> Self {
+        Self {
+            amount_minor,
+            currency,
+        }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Parses a decimal string in major units (`"12.5"`, `"-3"`, `"0.0000000001"`).
+    /// Exponents and more fractional digits than the currency supports are
+    /// rejected rather than rounded.
+    pub fn parse(amount: &str, currency: Currency) -> Result<Self, String> {
+        let amount = amount.trim();
+        let (negative, digits) = match amount.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, amount),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err("must be a decimal number such as \"12.50\"".to_string());
+        }
+        let decimals = currency.decimals();
+        if fraction.len() > decimals as usize {
+            return Err(format!(
+                "cannot have more than {} decimal places for {}",
+                decimals, currency
+            ));
+        }
+
+        let too_large = || "is too large".to_string();
+        let scale = 10_i64.pow(decimals);
+        let whole: i64 = whole.parse().map_err(|_| too_large())?;
+        let fraction: i64 = if fraction.is_empty() {
+            0
+        } else {
+            let padded = format!("{:0<width$}", fraction, width = decimals as usize);
+            padded.parse().map_err(|_| too_large())?
+        };
+        let minor = whole
+            .checked_mul(scale)
+            .and_then(|m| m.checked_add(fraction))
+            .ok_or_else(too_large)?;
+
+        Ok(Self::new(if negative { -minor } else { minor }, currency))
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.amount_minor > 0
+    }
+
+    /// `None` on overflow or when the currencies differ.
+    pub fn checked_add(&self, other: Money) -> Option<Money> {
+        if self.currency != other.currency {
+            return None;
+        }
+        self.amount_minor
+            .checked_add(other.amount_minor)
+            .map(|amount| Money::new(amount, self.currency))
+    }
+
+    /// `None` on overflow or when the currencies differ.
+    pub fn checked_sub(&self, other: Money) -> Option<Money> {
+        if self.currency != other.currency {
+            return None;
+        }
+        self.amount_minor
+            .checked_sub(other.amount_minor)
+            .map(|amount| Money::new(amount, self.currency))
+    }
+
+    /// The amount in major units, with exactly `currency.decimals()` fractional digits.
+    pub fn to_decimal_string(&self) -> String {
+        let decimals = self.currency.decimals();
+        let scale = 10_u64.pow(decimals);
+        let abs = self.amount_minor.unsigned_abs();
+        let sign = if self.amount_minor < 0 { "-" } else { "" };
+        format!(
+            "{}{}.{:0width$}",
+            sign,
+            abs / scale,
+            abs % scale,
+            width = decimals as usize
+        )
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.to_decimal_string(), self.currency)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct MoneyRepr {
+    amount: String,
+    currency: Currency,
+}
+
+impl TryFrom<MoneyRepr> for Money {
+    type Error = String;
+
+    fn try_from(repr: MoneyRepr) -> Result<Self, Self::Error> {
+        Money::parse(&repr.amount, repr.currency)
+    }
+}
+
+impl From<Money> for MoneyRepr {
+    fn from(money: Money) -> Self {
+        Self {
+            amount: money.to_decimal_string(),
+            currency: money.currency,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scales_to_the_currency_decimals() {
+        let cases = [
+            ("12.5", Currency::Usd, 1_250),
+            ("12.50", Currency::Eur, 1_250),
+            ("0.01", Currency::Brl, 1),
+            ("7", Currency::Usd, 700),
+            ("1.", Currency::Usd, 100),
+            (" 3.25 ", Currency::Usd, 325),
+            ("1", Currency::Dot, 10_000_000_000),
+            ("0.0000000001", Currency::Dot, 1),
+            ("1", Currency::Ksm, 1_000_000_000_000),
+            ("0.000000000001", Currency::Ksm, 1),
+            ("-3", Currency::Usd, -300),
+            ("-0.5", Currency::Dot, -5_000_000_000),
+            ("0", Currency::Ksm, 0),
+        ];
+        for (input, currency, minor) in cases {
+            assert_eq!(
+                Money::parse(input, currency),
+                Ok(Money::new(minor, currency)),
+                "{:?} {}",
+                input,
+                currency
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_malformed_amounts() {
+        for input in [
+            "", "-", ".5", "abc", "1,50", "1.2.3", "+1", "--1", "1e3", "1 000", "0x10", "1.-5",
+        ] {
+            assert!(
+                Money::parse(input, Currency::Usd).is_err(),
+                "{:?} was accepted",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_extra_decimal_places() {
+        assert!(Money::parse("0.001", Currency::Usd).is_err());
+        assert!(Money::parse("0.00000000001", Currency::Dot).is_err());
+        assert!(Money::parse("0.0000000000001", Currency::Ksm).is_err());
+        // Trailing zeros count too: nothing is rounded away
+        assert!(Money::parse("1.500", Currency::Usd).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_overflow() {
+        assert_eq!(
+            Money::parse("92233720368547758.07", Currency::Usd),
+            Ok(Money::new(i64::MAX, Currency::Usd))
+        );
+        assert_eq!(
+            Money::parse("9223372.036854775807", Currency::Ksm),
+            Ok(Money::new(i64::MAX, Currency::Ksm))
+        );
+        for (input, currency) in [
+            ("92233720368547758.08", Currency::Usd),
+            ("9223372.036854775808", Currency::Ksm),
+            ("10000000", Currency::Ksm),
+            ("99999999999999999999", Currency::Usd),
+        ] {
+            assert_eq!(
+                Money::parse(input, currency),
+                Err("is too large".to_string()),
+                "{:?} {}",
+                input,
+                currency
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_string_round_trips() {
+        let cases = [
+            (Money::new(1_250, Currency::Usd), "12.50"),
+            (Money::new(5, Currency::Eur), "0.05"),
+            (Money::new(-300, Currency::Brl), "-3.00"),
+            (Money::new(-1, Currency::Usd), "-0.01"),
+            (Money::new(1, Currency::Dot), "0.0000000001"),
+            (Money::new(15_000_000_000, Currency::Dot), "1.5000000000"),
+            (
+                Money::new(1_000_000_000_000, Currency::Ksm),
+                "1.000000000000",
+            ),
+            (Money::new(i64::MAX, Currency::Usd), "92233720368547758.07"),
+            (Money::zero(Currency::Ksm), "0.000000000000"),
+        ];
+        for (money, text) in cases {
+            assert_eq!(money.to_decimal_string(), text);
+            assert_eq!(Money::parse(text, money.currency), Ok(money));
+        }
+    }
+
+    #[test]
+    fn json_uses_a_decimal_string() {
+        let money = Money::new(1_250, Currency::Usd);
+        let json = serde_json::to_string(&money).unwrap();
+        assert_eq!(json, r#"{"amount":"12.50","currency":"USD"}"#);
+        assert_eq!(serde_json::from_str::<Money>(&json).unwrap(), money);
+        assert!(serde_json::from_str::<Money>(r#"{"amount":"0.001","currency":"USD"}"#).is_err());
+        assert!(serde_json::from_str::<Money>(r#"{"amount":"1","currency":"XYZ"}"#).is_err());
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let usd = |minor| Money::new(minor, Currency::Usd);
+        assert_eq!(usd(150).checked_add(usd(50)), Some(usd(200)));
+        assert_eq!(usd(150).checked_sub(usd(200)), Some(usd(-50)));
+        assert_eq!(usd(i64::MAX).checked_add(usd(1)), None);
+        assert_eq!(usd(i64::MIN).checked_sub(usd(1)), None);
+        let dot = Money::new(1, Currency::Dot);
+        assert_eq!(usd(1).checked_add(dot), None);
+        assert_eq!(usd(1).checked_sub(dot), None);
+    }
+
+    #[test]
+    fn max_bounty_fits_in_minor_units() {
+        for currency in Currency::ALL {
+            let max = Money::new(currency.max_bounty(), currency);
+            assert_eq!(
+                Money::parse(&max.to_decimal_string(), currency),
+                Ok(max),
+                "{}",
+                currency
+            );
+        }
+    }
+}
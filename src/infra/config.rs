@@ -0,0 +1,45 @@
+use chrono::Duration;
+use std::env;
+
+/// Fee retained by the platform on every bounty payout, in basis points (1/100 of a percent).
+pub fn platform_fee_bps() -> u32 {
+    env::var("PLATFORM_FEE_BPS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|bps| *bps <= 10_000)
+        .unwrap_or(500)
+}
+
+/// Live issue claims with no activity for this long expire.
+pub fn claim_inactivity_ttl() -> Duration {
+    let days: i64 = env::var("CLAIM_INACTIVITY_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(14);
+    Duration::days(days)
+}
+
+/// Project repository stats older than this are pulled again from the code host.
+pub fn repo_sync_interval() -> Duration {
+    let minutes: i64 = env::var("REPO_SYNC_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(360);
+    Duration::minutes(minutes)
+}
+
+/// Whether the API server also runs the background worker. Turn it off when the
+/// `rust4u-worker` binary runs it instead.
+pub fn run_worker() -> bool {
+    env::var("RUN_WORKER")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(true)
+}
+
+/// Base URL of the web app, for the links in emails.
+pub fn app_url() -> String {
+    env::var("APP_URL").unwrap_or_else(|_| "http://localhost:3000".to_string())
+}
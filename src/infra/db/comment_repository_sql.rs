@@ -0,0 +1,240 @@
+use crate::domain::entities::comment::{Comment, CommentEdit, CommentTarget};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::pagination::{Cursor, CursorValue, Page};
+use crate::domain::repositories::comment_repository::{
+    CommentQuery, CommentRepository, CommentSort,
+};
+use crate::infra::db::outbox_repository_sql::record_events_in;
+use crate::infra::db::pagination::{SortColumn, ValueKind};
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres, QueryBuilder, Transaction};
+use uuid::Uuid;
+
+pub struct CommentRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl CommentRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Columns read by `Comment::from_row`, for a query over `comments c`.
+const COMMENT_COLUMNS: &str = r#"
+    c.id, c.project_id, c.issue_id, c.parent_id, c.author_id, c.body,
+    ARRAY(SELECT m.user_id FROM comment_mentions m WHERE m.comment_id = c.id) AS mentions,
+    c.created_at, c.updated_at, c.deleted_at
+"#;
+
+fn target_columns(target: CommentTarget) -> (Option<Uuid>, Option<Uuid>) {
+    match target {
+        CommentTarget::Project(id) => (Some(id), None),
+        CommentTarget::Issue(id) => (None, Some(id)),
+    }
+}
+
+/// Replaces the stored mentions of `comment` with `comment.mentions`.
+async fn save_mentions_in(
+    tx: &mut Transaction<'_, Postgres>,
+    comment: &Comment,
+) -> Result<(), DomainError> {
+    sqlx::query("DELETE FROM comment_mentions WHERE comment_id = $1")
+        .bind(comment.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+    let query = r#"
+        INSERT INTO comment_mentions (comment_id, user_id)
+        SELECT $1, UNNEST($2::UUID[])
+        ON CONFLICT DO NOTHING
+    "#;
+    sqlx::query(query)
+        .bind(comment.id)
+        .bind(&comment.mentions)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    Ok(())
+}
+
+#[async_trait]
+impl CommentRepository for CommentRepositorySql {
+    async fn create_comment(
+        &self,
+        comment: &Comment,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let (project_id, issue_id) = target_columns(comment.target);
+        let query = r#"
+            INSERT INTO comments (id, project_id, issue_id, parent_id, author_id, body, created_at, updated_at, deleted_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#;
+        sqlx::query(query)
+            .bind(comment.id)
+            .bind(project_id)
+            .bind(issue_id)
+            .bind(comment.parent_id)
+            .bind(comment.author_id)
+            .bind(&comment.body)
+            .bind(comment.created_at)
+            .bind(comment.updated_at)
+            .bind(comment.deleted_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        save_mentions_in(&mut tx, comment).await?;
+        record_events_in(&mut tx, events).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_comment_by_id(&self, comment_id: Uuid) -> Result<Option<Comment>, DomainError> {
+        let query = format!("SELECT {} FROM comments c WHERE c.id = $1", COMMENT_COLUMNS);
+        let row = sqlx::query_as::<_, Comment>(&query)
+            .bind(comment_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn list_threads(
+        &self,
+        target: CommentTarget,
+        query: &CommentQuery,
+    ) -> Result<Page<Comment>, DomainError> {
+        let sort = match query.sort {
+            CommentSort::Oldest => {
+                SortColumn::new("c.created_at", "c.id", false, ValueKind::Timestamp)
+            }
+            CommentSort::Newest => {
+                SortColumn::new("c.created_at", "c.id", true, ValueKind::Timestamp)
+            }
+        };
+
+        let mut builder = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {} FROM comments c WHERE c.parent_id IS NULL",
+            COMMENT_COLUMNS
+        ));
+        match target {
+            CommentTarget::Project(id) => builder.push(" AND c.project_id = ").push_bind(id),
+            CommentTarget::Issue(id) => builder.push(" AND c.issue_id = ").push_bind(id),
+        };
+        if let Some(cursor) = &query.cursor {
+            sort.push_keyset(&mut builder, cursor)?;
+        }
+        sort.push_order_and_limit(&mut builder, query.limit);
+
+        let rows = builder
+            .build_query_as::<Comment>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(Page::from_rows(rows, query.limit, |comment| {
+            Cursor::new(CursorValue::Timestamp(comment.created_at), comment.id)
+        }))
+    }
+
+    async fn get_replies(&self, parent_ids: &[Uuid]) -> Result<Vec<Comment>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM comments c WHERE c.parent_id = ANY($1) ORDER BY c.created_at ASC, c.id ASC",
+            COMMENT_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, Comment>(&query)
+            .bind(parent_ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn update_comment(
+        &self,
+        comment: &Comment,
+        edit: &CommentEdit,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            UPDATE comments
+            SET body = $1,
+                updated_at = $2
+            WHERE id = $3 AND deleted_at IS NULL
+        "#;
+        let result = sqlx::query(query)
+            .bind(&comment.body)
+            .bind(comment.updated_at)
+            .bind(comment.id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::Conflict("Comment was deleted".to_string()));
+        }
+
+        let query = r#"
+            INSERT INTO comment_edits (id, comment_id, previous_body, edited_at)
+            VALUES ($1, $2, $3, $4)
+        "#;
+        sqlx::query(query)
+            .bind(edit.id)
+            .bind(edit.comment_id)
+            .bind(&edit.previous_body)
+            .bind(edit.edited_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        save_mentions_in(&mut tx, comment).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_edits(&self, comment_id: Uuid) -> Result<Vec<CommentEdit>, DomainError> {
+        let query = r#"
+            SELECT id, comment_id, previous_body, edited_at
+            FROM comment_edits
+            WHERE comment_id = $1
+            ORDER BY edited_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, CommentEdit>(query)
+            .bind(comment_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn soft_delete_comment(&self, comment: &Comment) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE comments
+            SET deleted_at = $1
+            WHERE id = $2 AND deleted_at IS NULL
+        "#;
+        sqlx::query(query)
+            .bind(comment.deleted_at)
+            .bind(comment.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use crate::domain::entities::dispute::{Dispute, DisputeEvent};
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::JournalEntry;
+use crate::domain::entities::submission::SubmissionStatus;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::repositories::dispute_repository::DisputeRepository;
+use crate::infra::db::issue_repository_sql::apply_transition_in;
+use crate::infra::db::ledger_repository_sql::post_entry_in;
+use crate::infra::db::outbox_repository_sql::record_events_in;
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres, Transaction};
+use uuid::Uuid;
+
+pub struct DisputeRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl DisputeRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+async fn insert_event_in(
+    tx: &mut Transaction<'_, Postgres>,
+    event: &DisputeEvent,
+) -> Result<(), DomainError> {
+    let query = r#"
+        INSERT INTO dispute_events (id, dispute_id, actor_id, kind, body, url, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+    "#;
+    sqlx::query(query)
+        .bind(event.id)
+        .bind(event.dispute_id)
+        .bind(event.actor_id)
+        .bind(event.kind)
+        .bind(&event.body)
+        .bind(&event.url)
+        .bind(event.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    Ok(())
+}
+
+#[async_trait]
+impl DisputeRepository for DisputeRepositorySql {
+    async fn open_dispute(
+        &self,
+        dispute: &Dispute,
+        event: &DisputeEvent,
+        transition: &IssueTransition,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            INSERT INTO disputes (id, issue_id, submission_id, contributor_id, opened_by, reason, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#;
+        sqlx::query(query)
+            .bind(dispute.id)
+            .bind(dispute.issue_id)
+            .bind(dispute.submission_id)
+            .bind(dispute.contributor_id)
+            .bind(dispute.opened_by)
+            .bind(&dispute.reason)
+            .bind(dispute.status)
+            .bind(dispute.created_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "23505" => DomainError::Conflict(
+                    "Issue already has an open dispute".to_string(),
+                ),
+                _ => DomainError::Infra(format!("DB error: {:?}", e)),
+            })?;
+
+        apply_transition_in(&mut tx, transition).await?;
+        insert_event_in(&mut tx, event).await?;
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_dispute_by_id(&self, dispute_id: Uuid) -> Result<Option<Dispute>, DomainError> {
+        let query = r#"
+            SELECT id, issue_id, submission_id, contributor_id, opened_by, reason, status,
+                   ruling, contributor_amount, resolved_by, created_at, resolved_at
+            FROM disputes
+            WHERE id = $1
+        "#;
+        let row = sqlx::query_as::<_, Dispute>(query)
+            .bind(dispute_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn get_disputes_by_issue(&self, issue_id: Uuid) -> Result<Vec<Dispute>, DomainError> {
+        let query = r#"
+            SELECT id, issue_id, submission_id, contributor_id, opened_by, reason, status,
+                   ruling, contributor_amount, resolved_by, created_at, resolved_at
+            FROM disputes
+            WHERE issue_id = $1
+            ORDER BY created_at DESC
+        "#;
+        let rows = sqlx::query_as::<_, Dispute>(query)
+            .bind(issue_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn add_event(&self, event: &DisputeEvent) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        // Holding the dispute row keeps a ruling from slipping in before the event.
+        let query = "SELECT status = 'open' FROM disputes WHERE id = $1 FOR SHARE";
+        let open: Option<(bool,)> = sqlx::query_as(query)
+            .bind(event.dispute_id)
+            .fetch_optional(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if !matches!(open, Some((true,))) {
+            return Err(DomainError::Conflict("Dispute is already resolved".to_string()));
+        }
+        insert_event_in(&mut tx, event).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_events(&self, dispute_id: Uuid) -> Result<Vec<DisputeEvent>, DomainError> {
+        let query = r#"
+            SELECT id, dispute_id, actor_id, kind, body, url, created_at
+            FROM dispute_events
+            WHERE dispute_id = $1
+            ORDER BY created_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, DisputeEvent>(query)
+            .bind(dispute_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn resolve_dispute(
+        &self,
+        dispute: &Dispute,
+        event: &DisputeEvent,
+        transition: &IssueTransition,
+        submission_status: SubmissionStatus,
+        entries: &[JournalEntry],
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            UPDATE disputes
+            SET status = $1,
+                ruling = $2,
+                contributor_amount = $3,
+                resolved_by = $4,
+                resolved_at = $5
+            WHERE id = $6 AND status = 'open'
+        "#;
+        let result = sqlx::query(query)
+            .bind(dispute.status)
+            .bind(dispute.ruling)
+            .bind(dispute.contributor_amount)
+            .bind(dispute.resolved_by)
+            .bind(dispute.resolved_at)
+            .bind(dispute.id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::Conflict("Dispute is already resolved".to_string()));
+        }
+
+        for entry in entries {
+            post_entry_in(&mut tx, entry).await?;
+        }
+        apply_transition_in(&mut tx, transition).await?;
+
+        let query = r#"
+            UPDATE submissions
+            SET status = CASE WHEN id = $1 THEN $2 ELSE 'superseded' END,
+                updated_at = $3
+            WHERE id = $1 OR (issue_id = $4 AND status = 'pending')
+        "#;
+        sqlx::query(query)
+            .bind(dispute.submission_id)
+            .bind(submission_status)
+            .bind(dispute.resolved_at)
+            .bind(dispute.issue_id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        if submission_status == SubmissionStatus::Approved {
+            let query = "UPDATE issues SET assignee_id = $1 WHERE id = $2";
+            sqlx::query(query)
+                .bind(dispute.contributor_id)
+                .bind(dispute.issue_id)
+                .execute(&mut tx)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        }
+
+        insert_event_in(&mut tx, event).await?;
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+}
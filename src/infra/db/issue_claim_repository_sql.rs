@@ -0,0 +1,264 @@
+use crate::domain::entities::issue_claim::{ClaimStatus, IssueClaim};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::repositories::issue_claim_repository::IssueClaimRepository;
+use crate::infra::db::outbox_repository_sql::record_events_in;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct IssueClaimRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl IssueClaimRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IssueClaimRepository for IssueClaimRepositorySql {
+    async fn create_claim(
+        &self,
+        claim: &IssueClaim,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        let query = r#"
+            INSERT INTO issue_claims (id, issue_id, claimant_id, message, eta, status, created_at, updated_at, last_activity_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#;
+        sqlx::query(query)
+            .bind(claim.id)
+            .bind(claim.issue_id)
+            .bind(claim.claimant_id)
+            .bind(&claim.message)
+            .bind(claim.eta)
+            .bind(claim.status)
+            .bind(claim.created_at)
+            .bind(claim.updated_at)
+            .bind(claim.last_activity_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "23505" => DomainError::Conflict(
+                    "You already have an open claim on this issue".to_string(),
+                ),
+                _ => DomainError::Infra(format!("DB error: {:?}", e)),
+            })?;
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_claim_by_id(&self, claim_id: Uuid) -> Result<Option<IssueClaim>, DomainError> {
+        let query = r#"
+            SELECT id, issue_id, claimant_id, message, eta, status, created_at, updated_at, last_activity_at
+            FROM issue_claims
+            WHERE id = $1
+        "#;
+        let row = sqlx::query_as::<_, IssueClaim>(query)
+            .bind(claim_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn get_claims_by_issue(&self, issue_id: Uuid) -> Result<Vec<IssueClaim>, DomainError> {
+        let query = r#"
+            SELECT id, issue_id, claimant_id, message, eta, status, created_at, updated_at, last_activity_at
+            FROM issue_claims
+            WHERE issue_id = $1
+            ORDER BY created_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, IssueClaim>(query)
+            .bind(issue_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn update_claim(&self, claim: &IssueClaim) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE issue_claims
+            SET message = $1,
+                eta = $2,
+                updated_at = $3,
+                last_activity_at = $4
+            WHERE id = $5 AND status IN ('pending', 'accepted')
+        "#;
+        let result = sqlx::query(query)
+            .bind(&claim.message)
+            .bind(claim.eta)
+            .bind(claim.updated_at)
+            .bind(claim.last_activity_at)
+            .bind(claim.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::Conflict("Claim is no longer open".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn accept_claim(
+        &self,
+        claim: &IssueClaim,
+        exclusive: bool,
+        at: DateTime<Utc>,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            UPDATE issue_claims
+            SET status = 'accepted',
+                updated_at = $1,
+                last_activity_at = $1
+            WHERE id = $2 AND status = 'pending'
+        "#;
+        let result = sqlx::query(query)
+            .bind(at)
+            .bind(claim.id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::Conflict("Claim is no longer pending".to_string()));
+        }
+
+        if exclusive {
+            let query = r#"
+                UPDATE issues
+                SET assignee_id = $1,
+                    updated_at = $2
+                WHERE id = $3 AND assignee_id IS NULL
+            "#;
+            let result = sqlx::query(query)
+                .bind(claim.claimant_id)
+                .bind(at)
+                .bind(claim.issue_id)
+                .execute(&mut tx)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+            if result.rows_affected() == 0 {
+                return Err(DomainError::Conflict(
+                    "Issue is already assigned".to_string(),
+                ));
+            }
+
+            let query = r#"
+                UPDATE issue_claims
+                SET status = 'rejected',
+                    updated_at = $1
+                WHERE issue_id = $2 AND id <> $3 AND status = 'pending'
+            "#;
+            sqlx::query(query)
+                .bind(at)
+                .bind(claim.issue_id)
+                .bind(claim.id)
+                .execute(&mut tx)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        }
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn close_claim(
+        &self,
+        claim_id: Uuid,
+        status: ClaimStatus,
+        at: DateTime<Utc>,
+        events: &[DomainEvent],
+    ) -> Result<bool, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        let query = r#"
+            WITH closed AS (
+                UPDATE issue_claims
+                SET status = $1,
+                    updated_at = $2
+                WHERE id = $3 AND status IN ('pending', 'accepted')
+                RETURNING issue_id, claimant_id
+            ), unassigned AS (
+                UPDATE issues i
+                SET assignee_id = NULL,
+                    updated_at = $2
+                FROM closed c
+                WHERE i.id = c.issue_id AND i.assignee_id = c.claimant_id
+            )
+            SELECT COUNT(*) FROM closed
+        "#;
+        let (closed,): (i64,) = sqlx::query_as(query)
+            .bind(status)
+            .bind(at)
+            .bind(claim_id)
+            .fetch_one(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if closed == 0 {
+            return Ok(false);
+        }
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(true)
+    }
+
+    async fn expire_stale_claims(&self, cutoff: DateTime<Utc>) -> Result<u64, DomainError> {
+        let query = r#"
+            WITH expired AS (
+                UPDATE issue_claims
+                SET status = 'expired',
+                    updated_at = NOW()
+                WHERE status IN ('pending', 'accepted')
+                  AND last_activity_at < $1
+                  AND issue_id IN (SELECT id FROM issues WHERE status IN ('open', 'in_review'))
+                  AND NOT EXISTS (
+                      SELECT 1 FROM submissions s
+                      WHERE s.issue_id = issue_claims.issue_id
+                        AND s.submitter_id = issue_claims.claimant_id
+                        AND s.status = 'pending'
+                  )
+                RETURNING issue_id, claimant_id
+            ), unassigned AS (
+                UPDATE issues i
+                SET assignee_id = NULL,
+                    updated_at = NOW()
+                FROM expired e
+                WHERE i.id = e.issue_id AND i.assignee_id = e.claimant_id
+            )
+            SELECT COUNT(*) FROM expired
+        "#;
+        let (expired,): (i64,) = sqlx::query_as(query)
+            .bind(cutoff)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(expired as u64)
+    }
+}
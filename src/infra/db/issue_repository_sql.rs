@@ -1,17 +1,22 @@
-use crate::domain::entities::issue::{Issue, IssueStatus};
+use crate::domain::entities::issue::Issue;
+use crate::domain::entities::issue_pull_request::IssuePullRequest;
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::issue_upstream_link::IssueUpstreamLink;
+use crate::domain::entities::ledger::JournalEntry;
 use crate::domain::errors::domain_error::DomainError;
-use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::events::DomainEvent;
+use crate::domain::pagination::{Cursor, CursorValue, Page};
+use crate::domain::repositories::issue_repository::{
+    IssueQuery, IssueRepository, IssueSort, Payee,
+};
+use crate::infra::db::ledger_repository_sql::post_entry_in;
+use crate::infra::db::outbox_repository_sql::record_events_in;
+use crate::infra::db::pagination::{SortColumn, ValueKind};
 use async_trait::async_trait;
-use sqlx::{Pool, Postgres};
-use std::fmt;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, QueryBuilder, Transaction};
 use uuid::Uuid;
 
-impl fmt::Display for IssueStatus {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
 pub struct IssueRepositorySql {
     pub pool: Pool<Postgres>,
 }
@@ -24,21 +29,34 @@ impl IssueRepositorySql {
 
 #[async_trait]
 impl IssueRepository for IssueRepositorySql {
-    async fn create_issue(&self, issue: &Issue) -> Result<(), DomainError> {
+    async fn create_issue(&self, issue: &Issue, events: &[DomainEvent]) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         let query = r#"
-            INSERT INTO issues (id, project_id, title, description, bounty_value, status, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO issues (id, project_id, title, description, bounty_amount, bounty_currency, status, assignee_id, claim_mode, hidden, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
         "#;
         sqlx::query(query)
             .bind(issue.id)
             .bind(issue.project_id)
             .bind(&issue.title)
             .bind(&issue.description)
-            .bind(issue.bounty_value)
-            .bind(issue.status.to_string())
+            .bind(issue.bounty.amount_minor)
+            .bind(issue.bounty.currency.code())
+            .bind(issue.status)
+            .bind(issue.assignee_id)
+            .bind(issue.claim_mode)
+            .bind(issue.hidden)
             .bind(issue.created_at)
             .bind(issue.updated_at)
-            .execute(&self.pool)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         Ok(())
@@ -46,7 +64,7 @@ impl IssueRepository for IssueRepositorySql {
 
     async fn get_issue_by_id(&self, issue_id: Uuid) -> Result<Option<Issue>, DomainError> {
         let query = r#"
-            SELECT id, project_id, title, description, bounty_value, status, created_at, updated_at
+            SELECT id, project_id, title, description, bounty_amount, bounty_currency, status, assignee_id, claim_mode, hidden, created_at, updated_at
             FROM issues
             WHERE id = $1
         "#;
@@ -60,9 +78,9 @@ impl IssueRepository for IssueRepositorySql {
 
     async fn get_issues_by_project(&self, project_id: Uuid) -> Result<Vec<Issue>, DomainError> {
         let query = r#"
-            SELECT id, project_id, title, description, bounty_value, status, created_at, updated_at
+            SELECT id, project_id, title, description, bounty_amount, bounty_currency, status, assignee_id, claim_mode, hidden, created_at, updated_at
             FROM issues
-            WHERE project_id = $1
+            WHERE project_id = $1 AND NOT hidden
             ORDER BY created_at DESC
         "#;
         let rows = sqlx::query_as::<_, Issue>(query)
@@ -73,37 +91,117 @@ impl IssueRepository for IssueRepositorySql {
         Ok(rows)
     }
 
-    async fn update_issue(&self, issue: &Issue) -> Result<(), DomainError> {
+    async fn update_issue(&self, issue: &Issue, events: &[DomainEvent]) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        // Locking the row holds off funding until the new bounty is committed.
+        let query = r#"
+            SELECT bounty_amount, bounty_currency
+            FROM issues
+            WHERE id = $1
+            FOR UPDATE
+        "#;
+        let current: Option<(i64, String)> = sqlx::query_as(query)
+            .bind(issue.id)
+            .fetch_optional(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        let Some((amount, currency)) = current else {
+            return Err(DomainError::NotFound("Issue not found".to_string()));
+        };
+        let bounty_changed =
+            amount != issue.bounty.amount_minor || currency != issue.bounty.currency.code();
+        if bounty_changed && escrow_funded_in(&mut tx, issue.id).await? {
+            return Err(DomainError::Conflict(
+                "Bounty cannot change while its escrow holds funds".to_string(),
+            ));
+        }
+
         let query = r#"
             UPDATE issues
             SET title = $1,
                 description = $2,
-                bounty_value = $3,
-                status = $4,
-                updated_at = $5
-            WHERE id = $6
+                bounty_amount = $3,
+                bounty_currency = $4,
+                claim_mode = $5,
+                updated_at = $6
+            WHERE id = $7
         "#;
         sqlx::query(query)
             .bind(&issue.title)
             .bind(&issue.description)
-            .bind(issue.bounty_value)
-            .bind(issue.status.to_string())
+            .bind(issue.bounty.amount_minor)
+            .bind(issue.bounty.currency.code())
+            .bind(issue.claim_mode)
             .bind(issue.updated_at)
             .bind(issue.id)
-            .execute(&self.pool)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         Ok(())
     }
 
-    async fn update_issue_status(
+    async fn apply_transition(
         &self,
-        issue_id: Uuid,
-        status: IssueStatus,
+        transition: &IssueTransition,
+        refund: Option<&JournalEntry>,
+        events: &[DomainEvent],
     ) -> Result<(), DomainError> {
-        let query = "UPDATE issues SET status = $1 WHERE id = $2";
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        apply_transition_in(&mut tx, transition).await?;
+
+        if let Some(entry) = refund {
+            post_entry_in(&mut tx, entry).await?;
+            // Funding that landed after the refund was built would be left behind.
+            if escrow_funded_in(&mut tx, transition.issue_id).await? {
+                return Err(DomainError::Conflict(
+                    "Escrow was funded concurrently".to_string(),
+                ));
+            }
+        }
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_transitions_by_issue(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueTransition>, DomainError> {
+        let query = r#"
+            SELECT id, issue_id, from_status, to_status, actor_id, note, created_at
+            FROM issue_transitions
+            WHERE issue_id = $1
+            ORDER BY created_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, IssueTransition>(query)
+            .bind(issue_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn set_issue_hidden(&self, issue_id: Uuid, hidden: bool) -> Result<(), DomainError> {
+        let query = "UPDATE issues SET hidden = $1 WHERE id = $2";
         sqlx::query(query)
-            .bind(status.to_string())
+            .bind(hidden)
             .bind(issue_id)
             .execute(&self.pool)
             .await
@@ -112,25 +210,421 @@ impl IssueRepository for IssueRepositorySql {
     }
 
     async fn delete_issue(&self, issue_id: Uuid) -> Result<(), DomainError> {
-        let query = "DELETE FROM issues WHERE id = $1";
+        let query = r#"
+            DELETE FROM issues
+            WHERE id = $1
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM ledger_accounts la
+                  JOIN journal_postings jp ON jp.account_id = la.id
+                  WHERE la.kind = 'escrow' AND la.owner_id = $1
+                  GROUP BY la.id
+                  HAVING SUM(jp.amount) > 0
+              )
+        "#;
+        let result = sqlx::query(query)
+            .bind(issue_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::Conflict(
+                "Issue cannot be deleted while its escrow holds funds; refund or release them first"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn has_funded_escrow(&self, issue_id: Uuid) -> Result<bool, DomainError> {
+        let (funded,): (bool,) = sqlx::query_as(ESCROW_FUNDED_QUERY)
+            .bind(issue_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(funded)
+    }
+
+    async fn get_payee(&self, issue_id: Uuid) -> Result<Option<Payee>, DomainError> {
+        let query = r#"
+            SELECT s.submitter_id, u.chain_address
+            FROM submissions s
+            JOIN users u ON u.id = s.submitter_id
+            WHERE s.issue_id = $1 AND s.status = 'approved'
+        "#;
+        let row: Option<(Uuid, Option<String>)> = sqlx::query_as(query)
+            .bind(issue_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row.map(|(user_id, chain_address)| Payee {
+            user_id,
+            chain_address,
+        }))
+    }
+
+    async fn list_issues(&self, query: &IssueQuery) -> Result<Page<Issue>, DomainError> {
+        let sort = match query.sort {
+            IssueSort::Newest => {
+                SortColumn::new("i.created_at", "i.id", true, ValueKind::Timestamp)
+            }
+            IssueSort::Oldest => {
+                SortColumn::new("i.created_at", "i.id", false, ValueKind::Timestamp)
+            }
+            IssueSort::BountyDesc => {
+                SortColumn::new("i.bounty_amount", "i.id", true, ValueKind::Integer)
+            }
+            IssueSort::BountyAsc => {
+                SortColumn::new("i.bounty_amount", "i.id", false, ValueKind::Integer)
+            }
+        };
+
+        let mut builder = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT i.id, i.project_id, i.title, i.description, i.bounty_amount, i.bounty_currency, i.status, i.assignee_id, i.claim_mode, i.hidden, i.created_at, i.updated_at
+            FROM issues i
+            JOIN projects p ON p.id = i.project_id
+            WHERE TRUE
+            "#,
+        );
+        if !query.include_hidden {
+            builder.push(" AND NOT i.hidden AND NOT p.hidden");
+        }
+        if let Some(status) = query.status {
+            builder.push(" AND i.status = ").push_bind(status);
+        }
+        if let Some(project_id) = query.project_id {
+            builder.push(" AND i.project_id = ").push_bind(project_id);
+        }
+        if let Some(currency) = query.currency {
+            builder
+                .push(" AND i.bounty_currency = ")
+                .push_bind(currency.code());
+        }
+        if let Some(min_bounty) = query.min_bounty {
+            builder
+                .push(" AND i.bounty_currency = ")
+                .push_bind(min_bounty.currency.code())
+                .push(" AND i.bounty_amount >= ")
+                .push_bind(min_bounty.amount_minor);
+        }
+        if let Some(assignee_id) = query.assignee_id {
+            builder.push(" AND i.assignee_id = ").push_bind(assignee_id);
+        }
+        if let Some(tag) = &query.tag {
+            builder
+                .push(" AND ")
+                .push_bind(tag.clone())
+                .push(" = ANY(p.tags)");
+        }
+        if let Some(owner_id) = query.owner_id {
+            builder.push(" AND p.owner_id = ").push_bind(owner_id);
+        }
+        if let Some(cursor) = &query.cursor {
+            sort.push_keyset(&mut builder, cursor)?;
+        }
+        sort.push_order_and_limit(&mut builder, query.limit);
+
+        let rows = builder
+            .build_query_as::<Issue>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(Page::from_rows(rows, query.limit, |issue| {
+            let value = match query.sort {
+                IssueSort::Newest | IssueSort::Oldest => CursorValue::Timestamp(issue.created_at),
+                IssueSort::BountyDesc | IssueSort::BountyAsc => {
+                    CursorValue::Integer(issue.bounty.amount_minor)
+                }
+            };
+            Cursor::new(value, issue.id)
+        }))
+    }
+
+    async fn create_imported_issue(
+        &self,
+        issue: &Issue,
+        link: &IssueUpstreamLink,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            INSERT INTO issues (id, project_id, title, description, bounty_amount, bounty_currency, status, assignee_id, claim_mode, hidden, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        "#;
         sqlx::query(query)
+            .bind(issue.id)
+            .bind(issue.project_id)
+            .bind(&issue.title)
+            .bind(&issue.description)
+            .bind(issue.bounty.amount_minor)
+            .bind(issue.bounty.currency.code())
+            .bind(issue.status)
+            .bind(issue.assignee_id)
+            .bind(issue.claim_mode)
+            .bind(issue.hidden)
+            .bind(issue.created_at)
+            .bind(issue.updated_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            INSERT INTO issue_upstream_links (issue_id, project_id, repo_owner, repo_name, number, url, state, synced_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#;
+        sqlx::query(query)
+            .bind(link.issue_id)
+            .bind(link.project_id)
+            .bind(&link.repo_owner)
+            .bind(&link.repo_name)
+            .bind(link.number)
+            .bind(&link.url)
+            .bind(link.state)
+            .bind(link.synced_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "23505" => DomainError::Conflict(format!(
+                    "Upstream issue #{} is already imported",
+                    link.number
+                )),
+                _ => DomainError::Infra(format!("DB error: {:?}", e)),
+            })?;
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_upstream_link(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Option<IssueUpstreamLink>, DomainError> {
+        let query = r#"
+            SELECT issue_id, project_id, repo_owner, repo_name, number, url, state, synced_at
+            FROM issue_upstream_links
+            WHERE issue_id = $1
+        "#;
+        let row = sqlx::query_as::<_, IssueUpstreamLink>(query)
             .bind(issue_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn get_upstream_links_by_project(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<IssueUpstreamLink>, DomainError> {
+        let query = r#"
+            SELECT issue_id, project_id, repo_owner, repo_name, number, url, state, synced_at
+            FROM issue_upstream_links
+            WHERE project_id = $1
+            ORDER BY number
+        "#;
+        let rows = sqlx::query_as::<_, IssueUpstreamLink>(query)
+            .bind(project_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn get_upstream_links_due_for_sync(
+        &self,
+        synced_before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<IssueUpstreamLink>, DomainError> {
+        let query = r#"
+            SELECT issue_id, project_id, repo_owner, repo_name, number, url, state, synced_at
+            FROM issue_upstream_links
+            WHERE synced_at < $1
+            ORDER BY synced_at, issue_id
+            LIMIT $2
+        "#;
+        let rows = sqlx::query_as::<_, IssueUpstreamLink>(query)
+            .bind(synced_before)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn update_upstream_link(&self, link: &IssueUpstreamLink) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE issue_upstream_links
+            SET url = $1,
+                state = $2,
+                synced_at = $3
+            WHERE issue_id = $4
+        "#;
+        sqlx::query(query)
+            .bind(&link.url)
+            .bind(link.state)
+            .bind(link.synced_at)
+            .bind(link.issue_id)
             .execute(&self.pool)
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         Ok(())
     }
 
-    async fn list_issues(&self) -> Result<Vec<Issue>, DomainError> {
+    async fn get_upstream_links_by_number(
+        &self,
+        repo_owner: &str,
+        repo_name: &str,
+        number: i32,
+    ) -> Result<Vec<IssueUpstreamLink>, DomainError> {
         let query = r#"
-            SELECT id, project_id, title, description, bounty_value, status, created_at, updated_at
-            FROM issues
-            ORDER BY created_at DESC
+            SELECT issue_id, project_id, repo_owner, repo_name, number, url, state, synced_at
+            FROM issue_upstream_links
+            WHERE lower(repo_owner) = lower($1) AND lower(repo_name) = lower($2) AND number = $3
         "#;
-        let rows = sqlx::query_as::<_, Issue>(query)
+        let rows = sqlx::query_as::<_, IssueUpstreamLink>(query)
+            .bind(repo_owner)
+            .bind(repo_name)
+            .bind(number)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         Ok(rows)
     }
+
+    async fn save_pull_request(
+        &self,
+        pull_request: &IssuePullRequest,
+    ) -> Result<bool, DomainError> {
+        let query = r#"
+            INSERT INTO issue_pull_requests (issue_id, repo_owner, repo_name, number, url, title, author, state, draft, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (issue_id, repo_owner, repo_name, number) DO UPDATE
+            SET url = EXCLUDED.url,
+                title = EXCLUDED.title,
+                author = EXCLUDED.author,
+                state = EXCLUDED.state,
+                draft = EXCLUDED.draft,
+                updated_at = EXCLUDED.updated_at
+            WHERE issue_pull_requests.updated_at <= EXCLUDED.updated_at
+        "#;
+        let result = sqlx::query(query)
+            .bind(pull_request.issue_id)
+            .bind(&pull_request.repo_owner)
+            .bind(&pull_request.repo_name)
+            .bind(pull_request.number)
+            .bind(&pull_request.url)
+            .bind(&pull_request.title)
+            .bind(&pull_request.author)
+            .bind(pull_request.state)
+            .bind(pull_request.draft)
+            .bind(pull_request.updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_pull_requests_by_issue(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssuePullRequest>, DomainError> {
+        let query = r#"
+            SELECT issue_id, repo_owner, repo_name, number, url, title, author, state, draft, updated_at
+            FROM issue_pull_requests
+            WHERE issue_id = $1
+            ORDER BY updated_at DESC
+        "#;
+        let rows = sqlx::query_as::<_, IssuePullRequest>(query)
+            .bind(issue_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+}
+
+/// Whether any escrow account of issue `$1` holds funds.
+const ESCROW_FUNDED_QUERY: &str = r#"
+    SELECT EXISTS (
+        SELECT 1
+        FROM ledger_accounts la
+        JOIN journal_postings jp ON jp.account_id = la.id
+        WHERE la.kind = 'escrow' AND la.owner_id = $1
+        GROUP BY la.id
+        HAVING SUM(jp.amount) > 0
+    )
+"#;
+
+async fn escrow_funded_in(
+    tx: &mut Transaction<'_, Postgres>,
+    issue_id: Uuid,
+) -> Result<bool, DomainError> {
+    let (funded,): (bool,) = sqlx::query_as(ESCROW_FUNDED_QUERY)
+        .bind(issue_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    Ok(funded)
+}
+
+/// Moves the issue from `transition.from_status` to `transition.to_status` inside
+/// `tx` and records the transition. Fails with `Conflict` if the stored status no
+/// longer matches `from_status`.
+pub(crate) async fn apply_transition_in(
+    tx: &mut Transaction<'_, Postgres>,
+    transition: &IssueTransition,
+) -> Result<(), DomainError> {
+    let query = r#"
+        UPDATE issues
+        SET status = $1,
+            updated_at = $2
+        WHERE id = $3 AND status = $4
+    "#;
+    let result = sqlx::query(query)
+        .bind(transition.to_status)
+        .bind(transition.created_at)
+        .bind(transition.issue_id)
+        .bind(transition.from_status)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    if result.rows_affected() == 0 {
+        return Err(DomainError::Conflict(
+            "Issue status was changed concurrently".to_string(),
+        ));
+    }
+    record_transition_in(tx, transition).await
+}
+
+/// Appends `transition` to the issue history without touching the issue itself,
+/// for callers that already moved the issue with a more specific update.
+pub(crate) async fn record_transition_in(
+    tx: &mut Transaction<'_, Postgres>,
+    transition: &IssueTransition,
+) -> Result<(), DomainError> {
+    let query = r#"
+        INSERT INTO issue_transitions (id, issue_id, from_status, to_status, actor_id, note, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+    "#;
+    sqlx::query(query)
+        .bind(transition.id)
+        .bind(transition.issue_id)
+        .bind(transition.from_status)
+        .bind(transition.to_status)
+        .bind(transition.actor_id)
+        .bind(&transition.note)
+        .bind(transition.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    Ok(())
 }
@@ -0,0 +1,153 @@
+use crate::domain::entities::job::Job;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, CursorValue, Page};
+use crate::domain::repositories::job_repository::{JobQuery, JobRepository};
+use crate::infra::db::pagination::{SortColumn, ValueKind};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+const JOB_COLUMNS: &str = "id, kind, payload, dedup_key, status, attempts, max_attempts, run_at, last_error, created_at, finished_at";
+
+pub struct JobRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl JobRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl JobRepository for JobRepositorySql {
+    async fn create_job(&self, job: &Job) -> Result<bool, DomainError> {
+        let query = format!(
+            "INSERT INTO jobs ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) ON CONFLICT (dedup_key) DO NOTHING",
+            JOB_COLUMNS
+        );
+        let result = sqlx::query(&query)
+            .bind(job.id)
+            .bind(&job.kind)
+            .bind(&job.payload)
+            .bind(&job.dedup_key)
+            .bind(job.status)
+            .bind(job.attempts)
+            .bind(job.max_attempts)
+            .bind(job.run_at)
+            .bind(&job.last_error)
+            .bind(job.created_at)
+            .bind(job.finished_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_job_by_id(&self, job_id: Uuid) -> Result<Option<Job>, DomainError> {
+        let query = format!("SELECT {} FROM jobs WHERE id = $1", JOB_COLUMNS);
+        let row = sqlx::query_as::<_, Job>(&query)
+            .bind(job_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn list_jobs(&self, query: &JobQuery) -> Result<Page<Job>, DomainError> {
+        let sort = SortColumn::new("created_at", "id", true, ValueKind::Timestamp);
+        let mut builder =
+            QueryBuilder::<Postgres>::new(format!("SELECT {} FROM jobs WHERE TRUE", JOB_COLUMNS));
+        if let Some(status) = query.status {
+            builder.push(" AND status = ").push_bind(status);
+        }
+        if let Some(kind) = &query.kind {
+            builder.push(" AND kind = ").push_bind(kind.clone());
+        }
+        if let Some(cursor) = &query.cursor {
+            sort.push_keyset(&mut builder, cursor)?;
+        }
+        sort.push_order_and_limit(&mut builder, query.limit);
+
+        let rows = builder
+            .build_query_as::<Job>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(Page::from_rows(rows, query.limit, |job| {
+            Cursor::new(CursorValue::Timestamp(job.created_at), job.id)
+        }))
+    }
+
+    async fn claim_due_jobs(
+        &self,
+        kinds: &[String],
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<Job>, DomainError> {
+        // SKIP LOCKED lets several workers claim disjoint batches concurrently.
+        let query = format!(
+            r#"
+            WITH due AS (
+                SELECT id AS due_id
+                FROM jobs
+                WHERE status IN ('pending', 'running') AND run_at <= $1 AND kind = ANY($4)
+                ORDER BY run_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE jobs
+            SET status = 'running', attempts = attempts + 1, run_at = $2
+            FROM due
+            WHERE id = due_id
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        );
+        let jobs = sqlx::query_as::<_, Job>(&query)
+            .bind(now)
+            .bind(lease_until)
+            .bind(i64::from(limit))
+            .bind(kinds)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(jobs)
+    }
+
+    async fn update_job(&self, job: &Job, seen_attempts: i32) -> Result<bool, DomainError> {
+        let query = r#"
+            UPDATE jobs
+            SET status = $1,
+                attempts = $2,
+                run_at = $3,
+                last_error = $4,
+                finished_at = $5
+            WHERE id = $6 AND attempts = $7
+        "#;
+        let result = sqlx::query(query)
+            .bind(job.status)
+            .bind(job.attempts)
+            .bind(job.run_at)
+            .bind(&job.last_error)
+            .bind(job.finished_at)
+            .bind(job.id)
+            .bind(seen_attempts)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn purge_completed(&self, before: DateTime<Utc>) -> Result<u64, DomainError> {
+        let result =
+            sqlx::query("DELETE FROM jobs WHERE status = 'completed' AND finished_at < $1")
+                .bind(before)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,366 @@
+use crate::domain::entities::issue::{Issue, IssueStatus};
+use crate::domain::entities::ledger::{AccountKind, AccountRef, EntryKind, JournalEntry, Posting};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::repositories::ledger_repository::LedgerRepository;
+use crate::domain::value_objects::money::{Currency, Money};
+use crate::infra::db::outbox_repository_sql::record_events_in;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, Pool, Postgres, Transaction};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct LedgerRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl LedgerRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct EntryRow {
+    id: Uuid,
+    kind: EntryKind,
+    issue_id: Option<Uuid>,
+    actor_id: Option<Uuid>,
+    memo: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(FromRow)]
+struct PostingRow {
+    entry_id: Uuid,
+    kind: AccountKind,
+    owner_id: Option<Uuid>,
+    currency: String,
+    amount: i64,
+}
+
+#[derive(FromRow)]
+struct BalanceRow {
+    currency: String,
+    balance: i64,
+}
+
+fn decode_currency(code: &str) -> Result<Currency, DomainError> {
+    Currency::from_code(code)
+        .ok_or_else(|| DomainError::Infra(format!("Unsupported currency in ledger: {}", code)))
+}
+
+/// Creates the account if needed and returns its id.
+async fn ensure_account(
+    tx: &mut Transaction<'_, Postgres>,
+    account: &AccountRef,
+) -> Result<Uuid, DomainError> {
+    let query = r#"
+        INSERT INTO ledger_accounts (kind, owner_id, currency)
+        VALUES ($1, $2, $3)
+        ON CONFLICT DO NOTHING
+    "#;
+    sqlx::query(query)
+        .bind(account.kind)
+        .bind(account.owner_id)
+        .bind(account.currency.code())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+    let query = r#"
+        SELECT id FROM ledger_accounts
+        WHERE kind = $1 AND owner_id IS NOT DISTINCT FROM $2 AND currency = $3
+    "#;
+    let (id,): (Uuid,) = sqlx::query_as(query)
+        .bind(account.kind)
+        .bind(account.owner_id)
+        .bind(account.currency.code())
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    Ok(id)
+}
+
+/// Records `entry` and its postings inside `tx`, creating missing accounts. Fails
+/// with `Conflict` if an account that cannot go negative would.
+pub(crate) async fn post_entry_in(
+    tx: &mut Transaction<'_, Postgres>,
+    entry: &JournalEntry,
+) -> Result<(), DomainError> {
+    let mut account_ids = HashMap::new();
+    for posting in &entry.postings {
+        if let Entry::Vacant(slot) = account_ids.entry(posting.account) {
+            slot.insert(ensure_account(tx, &posting.account).await?);
+        }
+    }
+
+    // Lock accounts being debited that must stay non-negative (in a fixed order
+    // to avoid deadlocks), so concurrent entries cannot overdraw them.
+    let mut guarded: Vec<(Uuid, AccountKind)> = entry
+        .postings
+        .iter()
+        .filter(|p| p.amount.amount_minor < 0 && !p.account.kind.allows_negative_balance())
+        .map(|p| (account_ids[&p.account], p.account.kind))
+        .collect();
+    guarded.sort_by_key(|(id, _)| *id);
+    guarded.dedup();
+    for (id, _) in &guarded {
+        sqlx::query("SELECT id FROM ledger_accounts WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    }
+
+    let query = r#"
+        INSERT INTO journal_entries (id, kind, issue_id, actor_id, memo, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+    "#;
+    sqlx::query(query)
+        .bind(entry.id)
+        .bind(entry.kind)
+        .bind(entry.issue_id)
+        .bind(entry.actor_id)
+        .bind(&entry.memo)
+        .bind(entry.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+    for posting in &entry.postings {
+        let query = r#"
+            INSERT INTO journal_postings (entry_id, account_id, amount)
+            VALUES ($1, $2, $3)
+        "#;
+        sqlx::query(query)
+            .bind(entry.id)
+            .bind(account_ids[&posting.account])
+            .bind(posting.amount.amount_minor)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    }
+
+    for (id, kind) in &guarded {
+        let query = "SELECT COALESCE(SUM(amount), 0)::BIGINT FROM journal_postings WHERE account_id = $1";
+        let (balance,): (i64,) = sqlx::query_as(query)
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if balance < 0 {
+            // The caller drops `tx`, rolling the entry back.
+            return Err(DomainError::Conflict(format!(
+                "Insufficient funds in {} account",
+                kind.as_str()
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl LedgerRepository for LedgerRepositorySql {
+    async fn post_entry(
+        &self,
+        entry: &JournalEntry,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        post_entry_in(&mut tx, entry).await?;
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn post_funding(
+        &self,
+        issue: &Issue,
+        entry: &JournalEntry,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        // Holds off transitions and bounty changes until the funding is committed.
+        let query = r#"
+            SELECT status, bounty_currency
+            FROM issues
+            WHERE id = $1
+            FOR UPDATE
+        "#;
+        let current: Option<(IssueStatus, String)> = sqlx::query_as(query)
+            .bind(issue.id)
+            .fetch_optional(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        match current {
+            None => return Err(DomainError::NotFound("Issue not found".to_string())),
+            Some((status, currency))
+                if status != issue.status || currency != issue.bounty.currency.code() =>
+            {
+                return Err(DomainError::Conflict(
+                    "Issue was changed concurrently".to_string(),
+                ));
+            }
+            Some(_) => {}
+        }
+
+        post_entry_in(&mut tx, entry).await?;
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_balance(&self, account: &AccountRef) -> Result<Money, DomainError> {
+        let query = r#"
+            SELECT COALESCE(SUM(jp.amount), 0)::BIGINT
+            FROM ledger_accounts la
+            JOIN journal_postings jp ON jp.account_id = la.id
+            WHERE la.kind = $1 AND la.owner_id IS NOT DISTINCT FROM $2 AND la.currency = $3
+        "#;
+        let (balance,): (i64,) = sqlx::query_as(query)
+            .bind(account.kind)
+            .bind(account.owner_id)
+            .bind(account.currency.code())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(Money::new(balance, account.currency))
+    }
+
+    async fn get_balances(
+        &self,
+        kind: AccountKind,
+        owner_id: Uuid,
+    ) -> Result<Vec<Money>, DomainError> {
+        let query = r#"
+            SELECT la.currency, COALESCE(SUM(jp.amount), 0)::BIGINT AS balance
+            FROM ledger_accounts la
+            LEFT JOIN journal_postings jp ON jp.account_id = la.id
+            WHERE la.kind = $1 AND la.owner_id = $2
+            GROUP BY la.currency
+            ORDER BY la.currency
+        "#;
+        let rows = sqlx::query_as::<_, BalanceRow>(query)
+            .bind(kind)
+            .bind(owner_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        rows.into_iter()
+            .map(|row| Ok(Money::new(row.balance, decode_currency(&row.currency)?)))
+            .collect()
+    }
+
+    async fn get_escrow_funders(
+        &self,
+        issue_id: Uuid,
+        currency: Currency,
+    ) -> Result<Vec<(AccountRef, Money)>, DomainError> {
+        // Every fund entry has a single debited (source) posting.
+        let query = r#"
+            SELECT CASE WHEN src.kind = 'external' THEN 'user' ELSE src.kind END::ledger_account_kind AS kind,
+                   CASE WHEN src.kind = 'external' THEN je.actor_id ELSE src.owner_id END AS owner_id,
+                   SUM(jp.amount)::BIGINT AS amount
+            FROM journal_entries je
+            JOIN journal_postings jp ON jp.entry_id = je.id
+            JOIN ledger_accounts la ON la.id = jp.account_id
+            JOIN journal_postings sp ON sp.entry_id = je.id AND sp.amount < 0
+            JOIN ledger_accounts src ON src.id = sp.account_id
+            WHERE je.kind = 'fund'
+              AND la.kind = 'escrow' AND la.owner_id = $1 AND la.currency = $2
+              AND (src.kind <> 'external' OR je.actor_id IS NOT NULL)
+            GROUP BY 1, 2
+            ORDER BY 1, 2
+        "#;
+        let rows: Vec<(AccountKind, Uuid, i64)> = sqlx::query_as(query)
+            .bind(issue_id)
+            .bind(currency.code())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows
+            .into_iter()
+            .map(|(kind, owner_id, amount)| {
+                let account = AccountRef {
+                    kind,
+                    owner_id: Some(owner_id),
+                    currency,
+                };
+                (account, Money::new(amount, currency))
+            })
+            .collect())
+    }
+
+    async fn get_entries_by_issue(&self, issue_id: Uuid) -> Result<Vec<JournalEntry>, DomainError> {
+        let query = r#"
+            SELECT id, kind, issue_id, actor_id, memo, created_at
+            FROM journal_entries
+            WHERE issue_id = $1
+            ORDER BY created_at ASC, id ASC
+        "#;
+        let entries = sqlx::query_as::<_, EntryRow>(query)
+            .bind(issue_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            SELECT jp.entry_id, la.kind, la.owner_id, la.currency, jp.amount
+            FROM journal_postings jp
+            JOIN journal_entries je ON je.id = jp.entry_id
+            JOIN ledger_accounts la ON la.id = jp.account_id
+            WHERE je.issue_id = $1
+            ORDER BY jp.id ASC
+        "#;
+        let posting_rows = sqlx::query_as::<_, PostingRow>(query)
+            .bind(issue_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let mut postings: HashMap<Uuid, Vec<Posting>> = HashMap::new();
+        for row in posting_rows {
+            let account = AccountRef {
+                kind: row.kind,
+                owner_id: row.owner_id,
+                currency: decode_currency(&row.currency)?,
+            };
+            postings
+                .entry(row.entry_id)
+                .or_default()
+                .push(Posting::new(account, row.amount));
+        }
+
+        Ok(entries
+            .into_iter()
+            .map(|row| JournalEntry {
+                postings: postings.remove(&row.id).unwrap_or_default(),
+                id: row.id,
+                kind: row.kind,
+                issue_id: row.issue_id,
+                actor_id: row.actor_id,
+                memo: row.memo,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+}
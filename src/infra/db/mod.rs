@@ -6,6 +6,20 @@ pub async fn create_db_pool(database_url: &str) -> Result<Pool<Postgres>> {
     Ok(pool)
 }
 
+pub mod comment_repository_sql;
+pub mod dispute_repository_sql;
+pub mod issue_claim_repository_sql;
 pub mod issue_repository_sql;
+pub mod job_repository_sql;
+pub mod ledger_repository_sql;
+pub mod notification_repository_sql;
+pub mod organization_repository_sql;
+pub mod outbox_repository_sql;
+pub mod pagination;
 pub mod project_repository_sql;
+pub mod refresh_token_repository_sql;
+pub mod search_repository_sql;
+pub mod submission_repository_sql;
 pub mod user_repository_sql;
+pub mod webhook_delivery_repository_sql;
+pub mod webhook_repository_sql;
@@ -0,0 +1,196 @@
+use crate::domain::entities::notification::{Notification, NotificationPreference};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, CursorValue, Page};
+use crate::domain::repositories::notification_repository::{
+    NotificationQuery, NotificationRepository,
+};
+use crate::infra::db::pagination::{SortColumn, ValueKind};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+const NOTIFICATION_COLUMNS: &str = "id, user_id, notification_type, event_id, actor_id, project_id, issue_id, subject_id, created_at, read_at";
+
+pub struct NotificationRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl NotificationRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for NotificationRepositorySql {
+    async fn create_notifications(
+        &self,
+        notifications: &[Notification],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        let query = format!(
+            r#"
+            INSERT INTO notifications ({})
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+            WHERE EXISTS (SELECT 1 FROM users WHERE id = $2)
+            ON CONFLICT (event_id, user_id) DO NOTHING
+            "#,
+            NOTIFICATION_COLUMNS
+        );
+        for notification in notifications {
+            sqlx::query(&query)
+                .bind(notification.id)
+                .bind(notification.user_id)
+                .bind(notification.notification_type)
+                .bind(notification.event_id)
+                .bind(notification.actor_id)
+                .bind(notification.project_id)
+                .bind(notification.issue_id)
+                .bind(notification.subject_id)
+                .bind(notification.created_at)
+                .bind(notification.read_at)
+                .execute(&mut tx)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn list_notifications(
+        &self,
+        user_id: Uuid,
+        query: &NotificationQuery,
+    ) -> Result<Page<Notification>, DomainError> {
+        let sort = SortColumn::new("created_at", "id", true, ValueKind::Timestamp);
+        let mut builder = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {} FROM notifications WHERE user_id = ",
+            NOTIFICATION_COLUMNS
+        ));
+        builder.push_bind(user_id);
+        if query.unread_only {
+            builder.push(" AND read_at IS NULL");
+        }
+        if let Some(cursor) = &query.cursor {
+            sort.push_keyset(&mut builder, cursor)?;
+        }
+        sort.push_order_and_limit(&mut builder, query.limit);
+
+        let rows = builder
+            .build_query_as::<Notification>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(Page::from_rows(rows, query.limit, |notification| {
+            Cursor::new(
+                CursorValue::Timestamp(notification.created_at),
+                notification.id,
+            )
+        }))
+    }
+
+    async fn count_unread(&self, user_id: Uuid) -> Result<i64, DomainError> {
+        let query = r#"
+            SELECT COUNT(*)
+            FROM notifications
+            WHERE user_id = $1 AND read_at IS NULL
+        "#;
+        let count = sqlx::query_scalar::<_, i64>(query)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(count)
+    }
+
+    async fn mark_read(
+        &self,
+        user_id: Uuid,
+        notification_id: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        let query = r#"
+            UPDATE notifications
+            SET read_at = COALESCE(read_at, $1)
+            WHERE id = $2 AND user_id = $3
+        "#;
+        let result = sqlx::query(query)
+            .bind(at)
+            .bind(notification_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn mark_all_read(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<u64, DomainError> {
+        let query = r#"
+            UPDATE notifications
+            SET read_at = $1
+            WHERE user_id = $2 AND read_at IS NULL
+        "#;
+        let result = sqlx::query(query)
+            .bind(at)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn get_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<NotificationPreference>, DomainError> {
+        let query = r#"
+            SELECT notification_type, channel
+            FROM notification_preferences
+            WHERE user_id = $1
+        "#;
+        let rows = sqlx::query_as::<_, NotificationPreference>(query)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn save_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: &[NotificationPreference],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        let query = r#"
+            INSERT INTO notification_preferences (user_id, notification_type, channel)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, notification_type) DO UPDATE
+            SET channel = EXCLUDED.channel
+        "#;
+        for preference in preferences {
+            sqlx::query(query)
+                .bind(user_id)
+                .bind(preference.notification_type)
+                .bind(preference.channel)
+                .execute(&mut tx)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+}
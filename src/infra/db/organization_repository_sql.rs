@@ -0,0 +1,214 @@
+use crate::domain::entities::organization::{Organization, OrganizationMember};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::repositories::organization_repository::OrganizationRepository;
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct OrganizationRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl OrganizationRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for OrganizationRepositorySql {
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+        owner: &OrganizationMember,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            INSERT INTO organizations (id, slug, display_name, description, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+        "#;
+        sqlx::query(query)
+            .bind(organization.id)
+            .bind(&organization.slug)
+            .bind(&organization.display_name)
+            .bind(&organization.description)
+            .bind(organization.created_at)
+            .bind(organization.updated_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "23505" => {
+                    DomainError::Conflict("Organization slug is already taken".to_string())
+                }
+                _ => DomainError::Infra(format!("DB error: {:?}", e)),
+            })?;
+
+        let query = r#"
+            INSERT INTO organization_members (organization_id, user_id, role, invited_by, created_at, accepted_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+        "#;
+        sqlx::query(query)
+            .bind(owner.organization_id)
+            .bind(owner.user_id)
+            .bind(owner.role)
+            .bind(owner.invited_by)
+            .bind(owner.created_at)
+            .bind(owner.accepted_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_organization_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Organization>, DomainError> {
+        let query = r#"
+            SELECT id, slug, display_name, description, created_at, updated_at
+            FROM organizations
+            WHERE slug = $1
+        "#;
+        let row = sqlx::query_as::<_, Organization>(query)
+            .bind(slug)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn update_organization(&self, organization: &Organization) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE organizations
+            SET display_name = $1,
+                description = $2,
+                updated_at = $3
+            WHERE id = $4
+        "#;
+        sqlx::query(query)
+            .bind(&organization.display_name)
+            .bind(&organization.description)
+            .bind(organization.updated_at)
+            .bind(organization.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn delete_organization(&self, organization_id: Uuid) -> Result<(), DomainError> {
+        let query = "DELETE FROM organizations WHERE id = $1";
+        sqlx::query(query)
+            .bind(organization_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "23503" => DomainError::Conflict(
+                    "Organization still owns projects; transfer or delete them first".to_string(),
+                ),
+                _ => DomainError::Infra(format!("DB error: {:?}", e)),
+            })?;
+        Ok(())
+    }
+
+    async fn get_members(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<OrganizationMember>, DomainError> {
+        let query = r#"
+            SELECT organization_id, user_id, role, invited_by, created_at, accepted_at
+            FROM organization_members
+            WHERE organization_id = $1
+            ORDER BY role ASC, created_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, OrganizationMember>(query)
+            .bind(organization_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn get_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationMember>, DomainError> {
+        let query = r#"
+            SELECT organization_id, user_id, role, invited_by, created_at, accepted_at
+            FROM organization_members
+            WHERE organization_id = $1 AND user_id = $2
+        "#;
+        let row = sqlx::query_as::<_, OrganizationMember>(query)
+            .bind(organization_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn add_member(&self, member: &OrganizationMember) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO organization_members (organization_id, user_id, role, invited_by, created_at, accepted_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+        "#;
+        sqlx::query(query)
+            .bind(member.organization_id)
+            .bind(member.user_id)
+            .bind(member.role)
+            .bind(member.invited_by)
+            .bind(member.created_at)
+            .bind(member.accepted_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "23505" => DomainError::Conflict(
+                    "User is already a member of this organization or invited to it".to_string(),
+                ),
+                Some(code) if code == "23503" => {
+                    DomainError::NotFound("User not found".to_string())
+                }
+                _ => DomainError::Infra(format!("DB error: {:?}", e)),
+            })?;
+        Ok(())
+    }
+
+    async fn update_member(&self, member: &OrganizationMember) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE organization_members
+            SET role = $1,
+                accepted_at = $2
+            WHERE organization_id = $3 AND user_id = $4
+        "#;
+        sqlx::query(query)
+            .bind(member.role)
+            .bind(member.accepted_at)
+            .bind(member.organization_id)
+            .bind(member.user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn remove_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<(), DomainError> {
+        let query = "DELETE FROM organization_members WHERE organization_id = $1 AND user_id = $2";
+        sqlx::query(query)
+            .bind(organization_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::{DomainEvent, OutboxEvent};
+use crate::domain::repositories::outbox_repository::OutboxRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::{Pool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+pub struct OutboxRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl OutboxRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn outbox_event_from_row(row: &PgRow) -> Result<OutboxEvent, DomainError> {
+    let decode = |e: sqlx::Error| DomainError::Infra(format!("DB error: {:?}", e));
+    let payload: String = row.try_get("payload").map_err(decode)?;
+    let event: DomainEvent = serde_json::from_str(&payload)
+        .map_err(|e| DomainError::Infra(format!("Malformed outbox event: {}", e)))?;
+    Ok(OutboxEvent {
+        id: row.try_get("id").map_err(decode)?,
+        event,
+        created_at: row.try_get("created_at").map_err(decode)?,
+        attempts: row.try_get("attempts").map_err(decode)?,
+        handled_by: row.try_get("handled_by").map_err(decode)?,
+    })
+}
+
+#[async_trait]
+impl OutboxRepository for OutboxRepositorySql {
+    async fn claim_due_events(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<OutboxEvent>, DomainError> {
+        // SKIP LOCKED lets several dispatchers claim disjoint batches concurrently.
+        let query = r#"
+            WITH due AS (
+                SELECT id AS due_id
+                FROM outbox
+                WHERE dispatched_at IS NULL AND next_attempt_at <= $1
+                ORDER BY sequence
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE outbox
+            SET next_attempt_at = $2
+            FROM due
+            WHERE id = due_id
+            RETURNING id, sequence, payload, created_at, attempts, handled_by
+        "#;
+        let mut rows = sqlx::query(query)
+            .bind(now)
+            .bind(lease_until)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        // RETURNING does not keep the CTE's order.
+        rows.sort_by_key(|row| row.get::<i64, _>("sequence"));
+        rows.iter().map(outbox_event_from_row).collect()
+    }
+
+    async fn mark_dispatched(
+        &self,
+        event_id: Uuid,
+        dispatched_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        sqlx::query("UPDATE outbox SET dispatched_at = $1 WHERE id = $2")
+            .bind(dispatched_at)
+            .bind(event_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        event: &OutboxEvent,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+    ) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE outbox
+            SET attempts = $1,
+                handled_by = $2,
+                next_attempt_at = $3,
+                last_error = $4
+            WHERE id = $5
+        "#;
+        sqlx::query(query)
+            .bind(event.attempts)
+            .bind(&event.handled_by)
+            .bind(next_attempt_at)
+            .bind(error)
+            .bind(event.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn purge_dispatched(&self, before: DateTime<Utc>) -> Result<u64, DomainError> {
+        let result = sqlx::query("DELETE FROM outbox WHERE dispatched_at < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Writes `events` to the outbox inside `tx`, so they are recorded if and only if
+/// the change they describe is committed.
+pub(crate) async fn record_events_in(
+    tx: &mut Transaction<'_, Postgres>,
+    events: &[DomainEvent],
+) -> Result<(), DomainError> {
+    let query = r#"
+        INSERT INTO outbox (id, event_type, payload, created_at, next_attempt_at)
+        VALUES ($1, $2, $3, $4, $4)
+    "#;
+    let now = Utc::now();
+    for event in events {
+        let payload = serde_json::to_string(event)
+            .map_err(|e| DomainError::Infra(format!("Event serialization error: {}", e)))?;
+        sqlx::query(query)
+            .bind(Uuid::new_v4())
+            .bind(event.name())
+            .bind(payload)
+            .bind(now)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    }
+    Ok(())
+}
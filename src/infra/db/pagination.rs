@@ -0,0 +1,135 @@
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::pagination::{Cursor, CursorValue};
+use sqlx::{Postgres, QueryBuilder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Timestamp,
+    Number,
+    Integer,
+    Text,
+}
+
+/// Column a listing is ordered by, used to build keyset (`cursor`) conditions.
+pub struct SortColumn {
+    pub column: &'static str,
+    pub id_column: &'static str,
+    pub descending: bool,
+    pub kind: ValueKind,
+}
+
+impl SortColumn {
+    pub fn new(
+        column: &'static str,
+        id_column: &'static str,
+        descending: bool,
+        kind: ValueKind,
+    ) -> Self {
+        Self {
+            column,
+            id_column,
+            descending,
+            kind,
+        }
+    }
+
+    /// Appends `AND (column, id) < (cursor)` (or `>` when ascending).
+    pub fn push_keyset(
+        &self,
+        builder: &mut QueryBuilder<'_, Postgres>,
+        cursor: &Cursor,
+    ) -> Result<(), DomainError> {
+        let kind = match cursor.value {
+            CursorValue::Timestamp(_) => ValueKind::Timestamp,
+            CursorValue::Number(_) => ValueKind::Number,
+            CursorValue::Integer(_) => ValueKind::Integer,
+            CursorValue::Text(_) => ValueKind::Text,
+        };
+        if kind != self.kind {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "cursor",
+                "does not match the requested sort order",
+            )]));
+        }
+
+        let op = if self.descending { "<" } else { ">" };
+        builder.push(format!(
+            " AND ({}, {}) {} (",
+            self.column, self.id_column, op
+        ));
+        match &cursor.value {
+            CursorValue::Timestamp(value) => builder.push_bind(*value),
+            CursorValue::Number(value) => builder.push_bind(*value),
+            CursorValue::Integer(value) => builder.push_bind(*value),
+            CursorValue::Text(value) => builder.push_bind(value.clone()),
+        };
+        builder.push(", ").push_bind(cursor.id).push(")");
+        Ok(())
+    }
+
+    /// Appends `ORDER BY ... LIMIT limit + 1`; the extra row tells whether a next page exists.
+    pub fn push_order_and_limit(&self, builder: &mut QueryBuilder<'_, Postgres>, limit: u32) {
+        let direction = if self.descending { "DESC" } else { "ASC" };
+        builder.push(format!(
+            " ORDER BY {} {}, {} {} LIMIT ",
+            self.column, direction, self.id_column, direction
+        ));
+        builder.push_bind(i64::from(limit) + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn keyset_sql(sort: &SortColumn, cursor: &Cursor) -> Result<String, DomainError> {
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT id FROM t WHERE TRUE");
+        sort.push_keyset(&mut builder, cursor)?;
+        sort.push_order_and_limit(&mut builder, 20);
+        Ok(builder.sql().to_string())
+    }
+
+    #[test]
+    fn keyset_follows_the_sort_direction() {
+        let cursor = Cursor::new(CursorValue::Timestamp(Utc::now()), Uuid::new_v4());
+
+        let newest = SortColumn::new("created_at", "id", true, ValueKind::Timestamp);
+        assert_eq!(
+            keyset_sql(&newest, &cursor).unwrap(),
+            "SELECT id FROM t WHERE TRUE AND (created_at, id) < ($1, $2) \
+             ORDER BY created_at DESC, id DESC LIMIT $3"
+        );
+
+        let oldest = SortColumn::new("created_at", "id", false, ValueKind::Timestamp);
+        assert_eq!(
+            keyset_sql(&oldest, &cursor).unwrap(),
+            "SELECT id FROM t WHERE TRUE AND (created_at, id) > ($1, $2) \
+             ORDER BY created_at ASC, id ASC LIMIT $3"
+        );
+    }
+
+    #[test]
+    fn cursors_of_another_sort_are_rejected() {
+        let by_name = SortColumn::new("name", "id", false, ValueKind::Text);
+        let cases = [
+            CursorValue::Timestamp(Utc::now()),
+            CursorValue::Number(1.5),
+            CursorValue::Integer(1),
+        ];
+        for value in cases {
+            let cursor = Cursor::new(value, Uuid::new_v4());
+            assert!(
+                matches!(
+                    keyset_sql(&by_name, &cursor),
+                    Err(DomainError::Validation(errors)) if errors[0].field == "cursor"
+                ),
+                "{:?}",
+                cursor.value
+            );
+        }
+        let cursor = Cursor::new(CursorValue::Text("m".to_string()), Uuid::new_v4());
+        assert!(keyset_sql(&by_name, &cursor).is_ok());
+    }
+}
@@ -1,8 +1,18 @@
-use crate::domain::entities::project::Project;
+use crate::domain::entities::organization::OrganizationRole;
+use crate::domain::entities::project::{Project, ProjectOwner};
+use crate::domain::entities::project_member::{ProjectMember, ProjectRole};
 use crate::domain::errors::domain_error::DomainError;
-use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::events::DomainEvent;
+use crate::domain::gateways::repository_host::RepositoryStats;
+use crate::domain::pagination::{Cursor, CursorValue, Page};
+use crate::domain::repositories::project_repository::{
+    ProjectQuery, ProjectRepository, ProjectSort,
+};
+use crate::infra::db::outbox_repository_sql::record_events_in;
+use crate::infra::db::pagination::{SortColumn, ValueKind};
 use async_trait::async_trait;
-use sqlx::{Pool, Postgres};
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 pub struct ProjectRepositorySql {
@@ -17,21 +27,53 @@ impl ProjectRepositorySql {
 
 #[async_trait]
 impl ProjectRepository for ProjectRepositorySql {
-    async fn create_project(&self, project: &Project) -> Result<(), DomainError> {
+    async fn create_project(
+        &self,
+        project: &Project,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
         let query = r#"
-            INSERT INTO projects (id, owner_id, name, description, github_link, tags, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO projects (id, owner_id, organization_id, name, description, github_link, tags, hidden, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         "#;
         sqlx::query(query)
             .bind(project.id)
-            .bind(project.owner_id)
+            .bind(project.owner.user_id())
+            .bind(project.owner.organization_id())
             .bind(&project.name)
             .bind(&project.description)
             .bind(&project.github_link)
             .bind(&project.tags)
+            .bind(project.hidden)
             .bind(project.created_at)
             .bind(project.updated_at)
-            .execute(&self.pool)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        if let ProjectOwner::User(owner_id) = project.owner {
+            let query = r#"
+                INSERT INTO project_members (project_id, user_id, role, created_at, accepted_at)
+                VALUES ($1, $2, $3, $4, $4)
+            "#;
+            sqlx::query(query)
+                .bind(project.id)
+                .bind(owner_id)
+                .bind(ProjectRole::Owner)
+                .bind(project.created_at)
+                .execute(&mut tx)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        }
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         Ok(())
@@ -39,8 +81,10 @@ impl ProjectRepository for ProjectRepositorySql {
 
     async fn get_project_by_id(&self, project_id: Uuid) -> Result<Option<Project>, DomainError> {
         let query = r#"
-            SELECT id, owner_id, name, description, github_link, tags, created_at, updated_at
+            SELECT id, owner_id, organization_id, name, description, github_link, tags, hidden, created_at, updated_at,
+                   stars, forks, open_issues, primary_language, license, topics, pushed_at, synced_at
             FROM projects
+            LEFT JOIN project_repo_stats ON project_id = id
             WHERE id = $1
         "#;
         let row = sqlx::query_as::<_, Project>(query)
@@ -53,9 +97,11 @@ impl ProjectRepository for ProjectRepositorySql {
 
     async fn get_projects_by_owner(&self, owner_id: Uuid) -> Result<Vec<Project>, DomainError> {
         let query = r#"
-            SELECT id, owner_id, name, description, github_link, tags, created_at, updated_at
+            SELECT id, owner_id, organization_id, name, description, github_link, tags, hidden, created_at, updated_at,
+                   stars, forks, open_issues, primary_language, license, topics, pushed_at, synced_at
             FROM projects
-            WHERE owner_id = $1
+            LEFT JOIN project_repo_stats ON project_id = id
+            WHERE owner_id = $1 AND NOT hidden
             ORDER BY created_at DESC
         "#;
         let rows = sqlx::query_as::<_, Project>(query)
@@ -66,7 +112,16 @@ impl ProjectRepository for ProjectRepositorySql {
         Ok(rows)
     }
 
-    async fn update_project(&self, project: &Project) -> Result<(), DomainError> {
+    async fn update_project(
+        &self,
+        project: &Project,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         let query = r#"
             UPDATE projects
             SET name = $1,
@@ -83,6 +138,21 @@ impl ProjectRepository for ProjectRepositorySql {
             .bind(&project.tags)
             .bind(project.updated_at)
             .bind(project.id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn set_project_hidden(&self, project_id: Uuid, hidden: bool) -> Result<(), DomainError> {
+        let query = "UPDATE projects SET hidden = $1 WHERE id = $2";
+        sqlx::query(query)
+            .bind(hidden)
+            .bind(project_id)
             .execute(&self.pool)
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
@@ -90,22 +160,401 @@ impl ProjectRepository for ProjectRepositorySql {
     }
 
     async fn delete_project(&self, project_id: Uuid) -> Result<(), DomainError> {
-        let query = "DELETE FROM projects WHERE id = $1";
+        let query = r#"
+            DELETE FROM projects
+            WHERE id = $1
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM issues i
+                  JOIN ledger_accounts la ON la.kind = 'escrow' AND la.owner_id = i.id
+                  JOIN journal_postings jp ON jp.account_id = la.id
+                  WHERE i.project_id = $1
+                  GROUP BY la.id
+                  HAVING SUM(jp.amount) > 0
+              )
+        "#;
+        let result = sqlx::query(query)
+            .bind(project_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::Conflict(
+                "Project cannot be deleted while the escrow of one of its issues holds funds"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn list_projects(&self, query: &ProjectQuery) -> Result<Page<Project>, DomainError> {
+        let sort = match query.sort {
+            ProjectSort::Newest => SortColumn::new("created_at", "id", true, ValueKind::Timestamp),
+            ProjectSort::Oldest => SortColumn::new("created_at", "id", false, ValueKind::Timestamp),
+            ProjectSort::Name => SortColumn::new("name", "id", false, ValueKind::Text),
+        };
+
+        let mut builder = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT id, owner_id, organization_id, name, description, github_link, tags, hidden, created_at, updated_at,
+                   stars, forks, open_issues, primary_language, license, topics, pushed_at, synced_at
+            FROM projects
+            LEFT JOIN project_repo_stats ON project_id = id
+            WHERE TRUE
+            "#,
+        );
+        if !query.include_hidden {
+            builder.push(" AND NOT hidden");
+        }
+        if let Some(owner_id) = query.owner_id {
+            builder.push(" AND owner_id = ").push_bind(owner_id);
+        }
+        if let Some(organization_id) = query.organization_id {
+            builder
+                .push(" AND organization_id = ")
+                .push_bind(organization_id);
+        }
+        if let Some(tag) = &query.tag {
+            builder
+                .push(" AND ")
+                .push_bind(tag.clone())
+                .push(" = ANY(tags)");
+        }
+        if let Some(cursor) = &query.cursor {
+            sort.push_keyset(&mut builder, cursor)?;
+        }
+        sort.push_order_and_limit(&mut builder, query.limit);
+
+        let rows = builder
+            .build_query_as::<Project>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(Page::from_rows(rows, query.limit, |project| {
+            let value = match query.sort {
+                ProjectSort::Newest | ProjectSort::Oldest => {
+                    CursorValue::Timestamp(project.created_at)
+                }
+                ProjectSort::Name => CursorValue::Text(project.name.clone()),
+            };
+            Cursor::new(value, project.id)
+        }))
+    }
+
+    async fn get_members(&self, project_id: Uuid) -> Result<Vec<ProjectMember>, DomainError> {
+        let query = r#"
+            SELECT project_id, user_id, role, invited_by, created_at, accepted_at
+            FROM project_members
+            WHERE project_id = $1
+            ORDER BY role ASC, created_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, ProjectMember>(query)
+            .bind(project_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn get_member(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ProjectMember>, DomainError> {
+        let query = r#"
+            SELECT project_id, user_id, role, invited_by, created_at, accepted_at
+            FROM project_members
+            WHERE project_id = $1 AND user_id = $2
+        "#;
+        let row = sqlx::query_as::<_, ProjectMember>(query)
+            .bind(project_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn add_member(&self, member: &ProjectMember) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO project_members (project_id, user_id, role, invited_by, created_at, accepted_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+        "#;
+        sqlx::query(query)
+            .bind(member.project_id)
+            .bind(member.user_id)
+            .bind(member.role)
+            .bind(member.invited_by)
+            .bind(member.created_at)
+            .bind(member.accepted_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "23505" => DomainError::Conflict(
+                    "User is already a member of this project or invited to it".to_string(),
+                ),
+                Some(code) if code == "23503" => {
+                    DomainError::NotFound("User not found".to_string())
+                }
+                _ => DomainError::Infra(format!("DB error: {:?}", e)),
+            })?;
+        Ok(())
+    }
+
+    async fn update_member(&self, member: &ProjectMember) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE project_members
+            SET role = $1,
+                accepted_at = $2
+            WHERE project_id = $3 AND user_id = $4
+        "#;
+        sqlx::query(query)
+            .bind(member.role)
+            .bind(member.accepted_at)
+            .bind(member.project_id)
+            .bind(member.user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn remove_member(&self, project_id: Uuid, user_id: Uuid) -> Result<(), DomainError> {
+        let query = "DELETE FROM project_members WHERE project_id = $1 AND user_id = $2 AND role <> 'owner'";
         sqlx::query(query)
             .bind(project_id)
+            .bind(user_id)
             .execute(&self.pool)
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         Ok(())
     }
 
-    async fn list_projects(&self) -> Result<Vec<Project>, DomainError> {
+    async fn transfer_ownership(
+        &self,
+        project_id: Uuid,
+        new_owner: ProjectOwner,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        // Locks the project so two transfers cannot interleave.
+        let current_owner: Option<Uuid> =
+            sqlx::query_scalar("SELECT owner_id FROM projects WHERE id = $1 FOR UPDATE")
+                .bind(project_id)
+                .fetch_one(&mut tx)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        if let Some(current_owner) = current_owner {
+            let query = r#"
+                UPDATE project_members
+                SET role = $1
+                WHERE project_id = $2 AND user_id = $3
+            "#;
+            sqlx::query(query)
+                .bind(ProjectRole::Maintainer)
+                .bind(project_id)
+                .bind(current_owner)
+                .execute(&mut tx)
+                .await
+                .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        }
+        if let ProjectOwner::User(new_owner_id) = new_owner {
+            let promoted = sqlx::query(
+                r#"
+                UPDATE project_members
+                SET role = $1
+                WHERE project_id = $2 AND user_id = $3 AND accepted_at IS NOT NULL
+                "#,
+            )
+            .bind(ProjectRole::Owner)
+            .bind(project_id)
+            .bind(new_owner_id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+            if promoted.rows_affected() == 0 {
+                return Err(DomainError::Conflict(
+                    "The new owner must be a member of the project".to_string(),
+                ));
+            }
+        }
+
+        let query = r#"
+            UPDATE projects
+            SET owner_id = $1,
+                organization_id = $2,
+                updated_at = NOW()
+            WHERE id = $3
+        "#;
+        sqlx::query(query)
+            .bind(new_owner.user_id())
+            .bind(new_owner.organization_id())
+            .bind(project_id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_organization_role(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationRole>, DomainError> {
+        let query = r#"
+            SELECT role
+            FROM organization_members
+            WHERE organization_id = $1 AND user_id = $2 AND accepted_at IS NOT NULL
+        "#;
+        let role = sqlx::query_scalar::<_, OrganizationRole>(query)
+            .bind(organization_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(role)
+    }
+
+    async fn get_owner_organization_role(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrganizationRole>, DomainError> {
+        let query = r#"
+            SELECT om.role
+            FROM projects p
+            JOIN organization_members om ON om.organization_id = p.organization_id
+            WHERE p.id = $1 AND om.user_id = $2 AND om.accepted_at IS NOT NULL
+        "#;
+        let role = sqlx::query_scalar::<_, OrganizationRole>(query)
+            .bind(project_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(role)
+    }
+
+    async fn get_maintainer_ids(&self, project_id: Uuid) -> Result<Vec<Uuid>, DomainError> {
+        let query = r#"
+            SELECT user_id
+            FROM project_members
+            WHERE project_id = $1
+              AND role IN ('owner', 'maintainer')
+              AND accepted_at IS NOT NULL
+            UNION
+            SELECT om.user_id
+            FROM projects p
+            JOIN organization_members om ON om.organization_id = p.organization_id
+            WHERE p.id = $1
+              AND om.role IN ('owner', 'admin')
+              AND om.accepted_at IS NOT NULL
+        "#;
+        let ids = sqlx::query_scalar::<_, Uuid>(query)
+            .bind(project_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(ids)
+    }
+
+    async fn save_repo_stats(
+        &self,
+        project_id: Uuid,
+        stats: &RepositoryStats,
+    ) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO project_repo_stats
+                (project_id, stars, forks, open_issues, primary_language, license, topics, pushed_at, synced_at, checked_at, sync_error)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $9, NULL)
+            ON CONFLICT (project_id) DO UPDATE
+            SET stars = EXCLUDED.stars,
+                forks = EXCLUDED.forks,
+                open_issues = EXCLUDED.open_issues,
+                primary_language = EXCLUDED.primary_language,
+                license = EXCLUDED.license,
+                topics = EXCLUDED.topics,
+                pushed_at = EXCLUDED.pushed_at,
+                synced_at = EXCLUDED.synced_at,
+                checked_at = EXCLUDED.checked_at,
+                sync_error = NULL
+        "#;
+        sqlx::query(query)
+            .bind(project_id)
+            .bind(stats.stars)
+            .bind(stats.forks)
+            .bind(stats.open_issues)
+            .bind(&stats.primary_language)
+            .bind(&stats.license)
+            .bind(&stats.topics)
+            .bind(stats.pushed_at)
+            .bind(stats.synced_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn record_repo_sync_error(
+        &self,
+        project_id: Uuid,
+        checked_at: DateTime<Utc>,
+        error: &str,
+    ) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO project_repo_stats (project_id, checked_at, sync_error)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (project_id) DO UPDATE
+            SET checked_at = EXCLUDED.checked_at,
+                sync_error = EXCLUDED.sync_error
+        "#;
+        sqlx::query(query)
+            .bind(project_id)
+            .bind(checked_at)
+            .bind(error)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn delete_repo_stats(&self, project_id: Uuid) -> Result<(), DomainError> {
+        let query = "DELETE FROM project_repo_stats WHERE project_id = $1";
+        sqlx::query(query)
+            .bind(project_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_projects_due_for_sync(
+        &self,
+        checked_before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<Project>, DomainError> {
         let query = r#"
-            SELECT id, owner_id, name, description, github_link, tags, created_at, updated_at
+            SELECT id, owner_id, organization_id, name, description, github_link, tags, hidden, created_at, updated_at,
+                   stars, forks, open_issues, primary_language, license, topics, pushed_at, synced_at
             FROM projects
-            ORDER BY created_at DESC
+            LEFT JOIN project_repo_stats ON project_id = id
+            WHERE github_link IS NOT NULL
+              AND (checked_at IS NULL OR checked_at < $1)
+            ORDER BY checked_at NULLS FIRST, id
+            LIMIT $2
         "#;
         let rows = sqlx::query_as::<_, Project>(query)
+            .bind(checked_before)
+            .bind(i64::from(limit))
             .fetch_all(&self.pool)
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
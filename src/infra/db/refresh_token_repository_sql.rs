@@ -0,0 +1,135 @@
+use crate::domain::entities::refresh_token::RefreshToken;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct RefreshTokenRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl RefreshTokenRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for RefreshTokenRepositorySql {
+    async fn create_token(&self, token: &RefreshToken) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, created_at, revoked_at, replaced_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#;
+        sqlx::query(query)
+            .bind(token.id)
+            .bind(token.user_id)
+            .bind(token.family_id)
+            .bind(&token.token_hash)
+            .bind(token.expires_at)
+            .bind(token.created_at)
+            .bind(token.revoked_at)
+            .bind(token.replaced_by)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, DomainError> {
+        let query = r#"
+            SELECT id, user_id, family_id, token_hash, expires_at, created_at, revoked_at, replaced_by
+            FROM refresh_tokens
+            WHERE token_hash = $1
+        "#;
+        let row = sqlx::query_as::<_, RefreshToken>(query)
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn rotate_token(
+        &self,
+        old_token_id: Uuid,
+        new_token: &RefreshToken,
+    ) -> Result<bool, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            UPDATE refresh_tokens
+            SET revoked_at = $1,
+                replaced_by = $2
+            WHERE id = $3 AND revoked_at IS NULL
+        "#;
+        let result = sqlx::query(query)
+            .bind(new_token.created_at)
+            .bind(new_token.id)
+            .bind(old_token_id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        let query = r#"
+            INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, created_at, revoked_at, replaced_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#;
+        sqlx::query(query)
+            .bind(new_token.id)
+            .bind(new_token.user_id)
+            .bind(new_token.family_id)
+            .bind(&new_token.token_hash)
+            .bind(new_token.expires_at)
+            .bind(new_token.created_at)
+            .bind(new_token.revoked_at)
+            .bind(new_token.replaced_by)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(true)
+    }
+
+    async fn revoke_family(&self, family_id: Uuid) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE family_id = $1 AND revoked_at IS NULL
+        "#;
+        sqlx::query(query)
+            .bind(family_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE user_id = $1 AND revoked_at IS NULL
+        "#;
+        sqlx::query(query)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+}
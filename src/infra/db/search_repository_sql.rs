@@ -0,0 +1,204 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, CursorValue, Page};
+use crate::domain::repositories::search_repository::{
+    SearchFacets, SearchHit, SearchKind, SearchQuery, SearchRepository, SearchResults,
+};
+use crate::infra::db::pagination::{SortColumn, ValueKind};
+use async_trait::async_trait;
+use sqlx::{FromRow, Pool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+/// Options passed to `ts_headline` when building snippets.
+const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MinWords=10, MaxWords=30, MaxFragments=2, FragmentDelimiter=\" ... \"";
+
+/// `h.body` with HTML special characters escaped, so the only markup in a
+/// snippet is the `<mark>` tags added by `ts_headline`.
+const ESCAPED_BODY: &str = "replace(replace(replace(replace(replace(h.body, \
+     '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '\"', '&quot;'), '''', '&#39;')";
+
+pub struct SearchRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl SearchRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct SearchRow {
+    kind: String,
+    id: Uuid,
+    project_id: Uuid,
+    title: String,
+    snippet: String,
+    tags: Vec<String>,
+    rank: f64,
+}
+
+impl SearchRow {
+    fn into_hit(self) -> SearchHit {
+        let kind = if self.kind == SearchKind::Issue.as_str() {
+            SearchKind::Issue
+        } else {
+            SearchKind::Project
+        };
+        SearchHit {
+            kind,
+            id: self.id,
+            project_id: self.project_id,
+            title: self.title,
+            snippet: self.snippet,
+            tags: self.tags,
+            rank: self.rank,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct FacetRow {
+    kind: String,
+    total: i64,
+}
+
+/// Restricts hits on project `p` to `visible` ones, unless the query includes
+/// hidden items or the viewer is an active member of the project, directly or
+/// through the organization that owns it.
+fn push_visibility(builder: &mut QueryBuilder<'_, Postgres>, query: &SearchQuery, visible: &str) {
+    if query.include_hidden {
+        return;
+    }
+    builder.push(format!(" AND (({})", visible));
+    if let Some(viewer_id) = query.viewer_id {
+        builder
+            .push(
+                r#"
+            OR EXISTS (
+                SELECT 1 FROM project_members pm
+                WHERE pm.project_id = p.id AND pm.accepted_at IS NOT NULL
+                  AND pm.user_id = "#,
+            )
+            .push_bind(viewer_id)
+            .push(
+                r#")
+            OR EXISTS (
+                SELECT 1 FROM organization_members om
+                WHERE om.organization_id = p.organization_id AND om.accepted_at IS NOT NULL
+                  AND om.user_id = "#,
+            )
+            .push_bind(viewer_id)
+            .push(")");
+    }
+    builder.push(")");
+}
+
+/// Pushes the `q` (parsed query) and `hits` (every match across projects and issues,
+/// with its rank) CTEs shared by the result and facet queries.
+fn push_hits_cte(builder: &mut QueryBuilder<'_, Postgres>, query: &SearchQuery) {
+    builder
+        .push("WITH q AS (SELECT websearch_to_tsquery('english', ")
+        .push_bind(query.text.clone())
+        .push(") AS query), hits AS (");
+
+    builder.push(
+        r#"
+        SELECT 'project' AS kind, p.id, p.id AS project_id, p.name AS title,
+               COALESCE(NULLIF(p.description, ''), p.name) AS body,
+               COALESCE(p.tags, '{}') AS tags,
+               ts_rank(p.search_vector, q.query)::FLOAT8 AS rank
+        FROM projects p, q
+        WHERE p.search_vector @@ q.query
+        "#,
+    );
+    push_visibility(builder, query, "NOT p.hidden");
+    if let Some(tag) = &query.tag {
+        builder
+            .push(" AND ")
+            .push_bind(tag.clone())
+            .push(" = ANY(p.tags)");
+    }
+
+    builder.push(
+        r#"
+        UNION ALL
+        SELECT 'issue' AS kind, i.id, i.project_id, i.title,
+               COALESCE(NULLIF(i.description, ''), i.title) AS body,
+               COALESCE(p.tags, '{}') AS tags,
+               ts_rank(i.search_vector, q.query)::FLOAT8 AS rank
+        FROM issues i
+        JOIN projects p ON p.id = i.project_id, q
+        WHERE i.search_vector @@ q.query
+        "#,
+    );
+    push_visibility(builder, query, "NOT i.hidden AND NOT p.hidden");
+    if let Some(tag) = &query.tag {
+        builder
+            .push(" AND ")
+            .push_bind(tag.clone())
+            .push(" = ANY(p.tags)");
+    }
+    builder.push(")");
+}
+
+#[async_trait]
+impl SearchRepository for SearchRepositorySql {
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResults, DomainError> {
+        let sort = SortColumn::new("rank", "id", true, ValueKind::Number);
+
+        // Snippets are only built for the rows of the requested page.
+        let mut builder = QueryBuilder::<Postgres>::new("");
+        push_hits_cte(&mut builder, query);
+        builder.push(
+            r#"
+            SELECT h.kind, h.id, h.project_id, h.title,
+                   ts_headline('english', "#,
+        );
+        builder.push(ESCAPED_BODY).push(", q.query, ");
+        builder.push_bind(HEADLINE_OPTIONS).push(
+            r#") AS snippet,
+                   h.tags, h.rank
+            FROM (SELECT * FROM hits WHERE TRUE
+            "#,
+        );
+        if let Some(kind) = query.kind {
+            builder.push(" AND kind = ").push_bind(kind.as_str());
+        }
+        if let Some(cursor) = &query.cursor {
+            sort.push_keyset(&mut builder, cursor)?;
+        }
+        sort.push_order_and_limit(&mut builder, query.limit);
+        builder.push(") h, q ORDER BY h.rank DESC, h.id DESC");
+
+        let rows = builder
+            .build_query_as::<SearchRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        let page = Page::from_rows(rows, query.limit, |row| {
+            Cursor::new(CursorValue::Number(row.rank), row.id)
+        })
+        .map(SearchRow::into_hit);
+
+        let mut builder = QueryBuilder::<Postgres>::new("");
+        push_hits_cte(&mut builder, query);
+        builder.push(" SELECT kind, COUNT(*) AS total FROM hits GROUP BY kind");
+        let facet_rows = builder
+            .build_query_as::<FacetRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let mut facets = SearchFacets::default();
+        for row in facet_rows {
+            if row.kind == SearchKind::Issue.as_str() {
+                facets.issues = row.total;
+            } else {
+                facets.projects = row.total;
+            }
+        }
+
+        Ok(SearchResults { page, facets })
+    }
+}
@@ -0,0 +1,309 @@
+use crate::domain::entities::issue_transition::IssueTransition;
+use crate::domain::entities::ledger::JournalEntry;
+use crate::domain::entities::submission::{Submission, SubmissionReview, SubmissionStatus};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::events::DomainEvent;
+use crate::domain::repositories::submission_repository::SubmissionRepository;
+use crate::infra::db::issue_repository_sql::{apply_transition_in, record_transition_in};
+use crate::infra::db::ledger_repository_sql::post_entry_in;
+use crate::infra::db::outbox_repository_sql::record_events_in;
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres, Transaction};
+use uuid::Uuid;
+
+pub struct SubmissionRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl SubmissionRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SubmissionRepository for SubmissionRepositorySql {
+    async fn create_submission(
+        &self,
+        submission: &Submission,
+        transition: Option<&IssueTransition>,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        let query = r#"
+            INSERT INTO submissions (id, issue_id, submitter_id, pr_url, notes, status, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#;
+        sqlx::query(query)
+            .bind(submission.id)
+            .bind(submission.issue_id)
+            .bind(submission.submitter_id)
+            .bind(&submission.pr_url)
+            .bind(&submission.notes)
+            .bind(submission.status)
+            .bind(submission.created_at)
+            .bind(submission.updated_at)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "23505" => DomainError::Conflict(
+                    "You already have a submission waiting for review".to_string(),
+                ),
+                _ => DomainError::Infra(format!("DB error: {:?}", e)),
+            })?;
+
+        let query = r#"
+            UPDATE issue_claims
+            SET last_activity_at = $1
+            WHERE issue_id = $2 AND claimant_id = $3 AND status = 'accepted'
+        "#;
+        let claimed = sqlx::query(query)
+            .bind(submission.created_at)
+            .bind(submission.issue_id)
+            .bind(submission.submitter_id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if claimed.rows_affected() == 0 {
+            return Err(DomainError::Conflict(
+                "Your claim on this issue is no longer accepted".to_string(),
+            ));
+        }
+
+        if let Some(transition) = transition {
+            apply_transition_in(&mut tx, transition).await?;
+        }
+
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_submission_by_id(
+        &self,
+        submission_id: Uuid,
+    ) -> Result<Option<Submission>, DomainError> {
+        let query = r#"
+            SELECT id, issue_id, submitter_id, pr_url, notes, status, created_at, updated_at
+            FROM submissions
+            WHERE id = $1
+        "#;
+        let row = sqlx::query_as::<_, Submission>(query)
+            .bind(submission_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn get_submissions_by_issue(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Vec<Submission>, DomainError> {
+        let query = r#"
+            SELECT id, issue_id, submitter_id, pr_url, notes, status, created_at, updated_at
+            FROM submissions
+            WHERE issue_id = $1
+            ORDER BY created_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, Submission>(query)
+            .bind(issue_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn get_submissions_by_pr_url(
+        &self,
+        pr_url: &str,
+    ) -> Result<Vec<Submission>, DomainError> {
+        let query = r#"
+            SELECT id, issue_id, submitter_id, pr_url, notes, status, created_at, updated_at
+            FROM submissions
+            WHERE rtrim(pr_url, '/') = rtrim($1, '/')
+            ORDER BY created_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, Submission>(query)
+            .bind(pr_url)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn get_reviews_by_issue(
+        &self,
+        issue_id: Uuid,
+    ) -> Result<Vec<SubmissionReview>, DomainError> {
+        let query = r#"
+            SELECT r.id, r.submission_id, r.reviewer_id, r.decision, r.comment, r.created_at
+            FROM submission_reviews r
+            JOIN submissions s ON s.id = r.submission_id
+            WHERE s.issue_id = $1
+            ORDER BY r.created_at ASC
+        "#;
+        let rows = sqlx::query_as::<_, SubmissionReview>(query)
+            .bind(issue_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn approve_submission(
+        &self,
+        submission: &Submission,
+        review: &SubmissionReview,
+        transition: &IssueTransition,
+        payout: Option<&JournalEntry>,
+        events: &[DomainEvent],
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        close_pending_submission(&mut tx, submission, SubmissionStatus::Approved, review).await?;
+
+        let query = r#"
+            UPDATE submissions
+            SET status = 'superseded',
+                updated_at = $1
+            WHERE issue_id = $2 AND status = 'pending'
+        "#;
+        sqlx::query(query)
+            .bind(review.created_at)
+            .bind(submission.issue_id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        apply_transition_in(&mut tx, transition).await?;
+
+        let query = r#"
+            UPDATE issues
+            SET assignee_id = $1
+            WHERE id = $2
+        "#;
+        sqlx::query(query)
+            .bind(submission.submitter_id)
+            .bind(submission.issue_id)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        if let Some(entry) = payout {
+            post_entry_in(&mut tx, entry).await?;
+        }
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn request_changes(
+        &self,
+        submission: &Submission,
+        review: &SubmissionReview,
+        reopen: &IssueTransition,
+        events: &[DomainEvent],
+        reopen_events: &[DomainEvent],
+    ) -> Result<bool, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+
+        close_pending_submission(
+            &mut tx,
+            submission,
+            SubmissionStatus::ChangesRequested,
+            review,
+        )
+        .await?;
+
+        let query = r#"
+            UPDATE issues
+            SET status = $1,
+                updated_at = $2
+            WHERE id = $3
+              AND status = $4
+              AND NOT EXISTS (
+                  SELECT 1 FROM submissions WHERE issue_id = $3 AND status = 'pending'
+              )
+        "#;
+        let result = sqlx::query(query)
+            .bind(reopen.to_status)
+            .bind(reopen.created_at)
+            .bind(reopen.issue_id)
+            .bind(reopen.from_status)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        let reopened = result.rows_affected() > 0;
+        record_events_in(&mut tx, events).await?;
+        if reopened {
+            record_transition_in(&mut tx, reopen).await?;
+            record_events_in(&mut tx, reopen_events).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(reopened)
+    }
+}
+
+/// Moves a pending submission to `status` and records the review deciding it.
+async fn close_pending_submission(
+    tx: &mut Transaction<'_, Postgres>,
+    submission: &Submission,
+    status: SubmissionStatus,
+    review: &SubmissionReview,
+) -> Result<(), DomainError> {
+    let query = r#"
+        UPDATE submissions
+        SET status = $1,
+            updated_at = $2
+        WHERE id = $3 AND status = 'pending'
+    "#;
+    let result = sqlx::query(query)
+        .bind(status)
+        .bind(review.created_at)
+        .bind(submission.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    if result.rows_affected() == 0 {
+        return Err(DomainError::Conflict(
+            "Submission was already reviewed".to_string(),
+        ));
+    }
+
+    let query = r#"
+        INSERT INTO submission_reviews (id, submission_id, reviewer_id, decision, comment, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+    "#;
+    sqlx::query(query)
+        .bind(review.id)
+        .bind(review.submission_id)
+        .bind(review.reviewer_id)
+        .bind(review.decision)
+        .bind(&review.comment)
+        .bind(review.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+    Ok(())
+}
@@ -1,8 +1,12 @@
 use crate::domain::entities::user::User;
 use crate::domain::errors::domain_error::DomainError;
-use crate::domain::repositories::user_repository::UserRepository;
+use crate::domain::events::DomainEvent;
+use crate::domain::pagination::{Cursor, CursorValue, Page};
+use crate::domain::repositories::user_repository::{UserQuery, UserRepository, UserSort};
+use crate::infra::db::outbox_repository_sql::record_events_in;
+use crate::infra::db::pagination::{SortColumn, ValueKind};
 use async_trait::async_trait;
-use sqlx::{Pool, Postgres};
+use sqlx::{Pool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 pub struct UserRepositorySql {
@@ -17,19 +21,30 @@ impl UserRepositorySql {
 
 #[async_trait]
 impl UserRepository for UserRepositorySql {
-    async fn create_user(&self, user: &User) -> Result<(), DomainError> {
+    async fn create_user(&self, user: &User, events: &[DomainEvent]) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         let query = r#"
-            INSERT INTO users (id, username, email, password_hash, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO users (id, username, email, password_hash, role, chain_address, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         "#;
         sqlx::query(query)
             .bind(user.id)
             .bind(&user.username)
             .bind(&user.email)
             .bind(&user.password_hash)
+            .bind(user.role)
+            .bind(&user.chain_address)
             .bind(user.created_at)
             .bind(user.updated_at)
-            .execute(&self.pool)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        record_events_in(&mut tx, events).await?;
+        tx.commit()
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
         Ok(())
@@ -37,7 +52,7 @@ impl UserRepository for UserRepositorySql {
 
     async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<User>, DomainError> {
         let query = r#"
-            SELECT id, username, email, password_hash, created_at, updated_at
+            SELECT id, username, email, password_hash, role, chain_address, created_at, updated_at
             FROM users
             WHERE id = $1
         "#;
@@ -51,7 +66,7 @@ impl UserRepository for UserRepositorySql {
 
     async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, DomainError> {
         let query = r#"
-            SELECT id, username, email, password_hash, created_at, updated_at
+            SELECT id, username, email, password_hash, role, chain_address, created_at, updated_at
             FROM users
             WHERE email = $1
         "#;
@@ -63,19 +78,38 @@ impl UserRepository for UserRepositorySql {
         Ok(row)
     }
 
+    async fn get_users_by_usernames(&self, usernames: &[String]) -> Result<Vec<User>, DomainError> {
+        let lowered: Vec<String> = usernames.iter().map(|u| u.to_lowercase()).collect();
+        let query = r#"
+            SELECT id, username, email, password_hash, role, chain_address, created_at, updated_at
+            FROM users
+            WHERE LOWER(username) = ANY($1)
+        "#;
+        let rows = sqlx::query_as::<_, User>(query)
+            .bind(&lowered)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
     async fn update_user(&self, user: &User) -> Result<(), DomainError> {
         let query = r#"
             UPDATE users
             SET username = $1,
                 email = $2,
                 password_hash = $3,
-                updated_at = $4
-            WHERE id = $5
+                role = $4,
+                chain_address = $5,
+                updated_at = $6
+            WHERE id = $7
         "#;
         sqlx::query(query)
             .bind(&user.username)
             .bind(&user.email)
             .bind(&user.password_hash)
+            .bind(user.role)
+            .bind(&user.chain_address)
             .bind(user.updated_at)
             .bind(user.id)
             .execute(&self.pool)
@@ -97,16 +131,49 @@ impl UserRepository for UserRepositorySql {
         Ok(())
     }
 
-    async fn list_users(&self) -> Result<Vec<User>, DomainError> {
-        let query = r#"
-            SELECT id, username, email, password_hash, created_at, updated_at
+    async fn list_users(&self, query: &UserQuery) -> Result<Page<User>, DomainError> {
+        let sort = match query.sort {
+            UserSort::Newest => SortColumn::new("created_at", "id", true, ValueKind::Timestamp),
+            UserSort::Oldest => SortColumn::new("created_at", "id", false, ValueKind::Timestamp),
+            UserSort::Username => SortColumn::new("username", "id", false, ValueKind::Text),
+        };
+
+        let mut builder = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT id, username, email, password_hash, role, chain_address, created_at, updated_at
             FROM users
-            ORDER BY created_at DESC
-        "#;
-        let rows = sqlx::query_as::<_, User>(query)
+            WHERE TRUE
+            "#,
+        );
+        if let Some(username) = &query.username {
+            let pattern = format!(
+                "{}%",
+                username
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_")
+            );
+            builder.push(" AND username ILIKE ").push_bind(pattern);
+        }
+        if let Some(role) = query.role {
+            builder.push(" AND role = ").push_bind(role);
+        }
+        if let Some(cursor) = &query.cursor {
+            sort.push_keyset(&mut builder, cursor)?;
+        }
+        sort.push_order_and_limit(&mut builder, query.limit);
+
+        let rows = builder
+            .build_query_as::<User>()
             .fetch_all(&self.pool)
             .await
             .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
-        Ok(rows)
+        Ok(Page::from_rows(rows, query.limit, |user| {
+            let value = match query.sort {
+                UserSort::Newest | UserSort::Oldest => CursorValue::Timestamp(user.created_at),
+                UserSort::Username => CursorValue::Text(user.username.clone()),
+            };
+            Cursor::new(value, user.id)
+        }))
     }
 }
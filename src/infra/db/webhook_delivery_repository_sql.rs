@@ -0,0 +1,47 @@
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::repositories::webhook_delivery_repository::WebhookDeliveryRepository;
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{Pool, Postgres};
+
+pub struct WebhookDeliveryRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl WebhookDeliveryRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryRepository for WebhookDeliveryRepositorySql {
+    async fn record_delivery(&self, delivery_id: &str, event: &str) -> Result<bool, DomainError> {
+        let query = r#"
+            INSERT INTO github_webhook_deliveries (delivery_id, event, received_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (delivery_id) DO NOTHING
+        "#;
+        let result = sqlx::query(query)
+            .bind(delivery_id)
+            .bind(event)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn forget_delivery(&self, delivery_id: &str) -> Result<(), DomainError> {
+        let query = r#"
+            DELETE FROM github_webhook_deliveries
+            WHERE delivery_id = $1
+        "#;
+        sqlx::query(query)
+            .bind(delivery_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+use crate::domain::entities::webhook::{
+    WebhookDelivery, WebhookEndpoint, WebhookEvent, WebhookOwner,
+};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::pagination::{Cursor, CursorValue, Page};
+use crate::domain::repositories::webhook_repository::{DeliveryQuery, WebhookRepository};
+use crate::infra::db::pagination::{SortColumn, ValueKind};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+const ENDPOINT_COLUMNS: &str = "id, owner_id, organization_id, url, secret, events, description, active, created_at, updated_at";
+const DELIVERY_COLUMNS: &str = "id, endpoint_id, event_id, event_type, payload, status, attempts, next_attempt_at, last_attempt_at, response_status, last_error, redelivery_of, created_at, delivered_at";
+
+pub struct WebhookRepositorySql {
+    pub pool: Pool<Postgres>,
+}
+
+impl WebhookRepositorySql {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn event_names(endpoint: &WebhookEndpoint) -> Vec<String> {
+    endpoint
+        .events
+        .iter()
+        .map(|e| e.as_str().to_string())
+        .collect()
+}
+
+#[async_trait]
+impl WebhookRepository for WebhookRepositorySql {
+    async fn create_endpoint(&self, endpoint: &WebhookEndpoint) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO webhook_endpoints (id, owner_id, organization_id, url, secret, events, description, active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#;
+        let (owner_id, organization_id) = match endpoint.owner {
+            WebhookOwner::User(id) => (Some(id), None),
+            WebhookOwner::Organization(id) => (None, Some(id)),
+        };
+        sqlx::query(query)
+            .bind(endpoint.id)
+            .bind(owner_id)
+            .bind(organization_id)
+            .bind(&endpoint.url)
+            .bind(&endpoint.secret)
+            .bind(event_names(endpoint))
+            .bind(&endpoint.description)
+            .bind(endpoint.active)
+            .bind(endpoint.created_at)
+            .bind(endpoint.updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_endpoint_by_id(
+        &self,
+        endpoint_id: Uuid,
+    ) -> Result<Option<WebhookEndpoint>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM webhook_endpoints WHERE id = $1",
+            ENDPOINT_COLUMNS
+        );
+        let row = sqlx::query_as::<_, WebhookEndpoint>(&query)
+            .bind(endpoint_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn get_endpoints_by_owner(
+        &self,
+        owner: WebhookOwner,
+    ) -> Result<Vec<WebhookEndpoint>, DomainError> {
+        let (column, id) = match owner {
+            WebhookOwner::User(id) => ("owner_id", id),
+            WebhookOwner::Organization(id) => ("organization_id", id),
+        };
+        let query = format!(
+            "SELECT {} FROM webhook_endpoints WHERE {} = $1 ORDER BY created_at ASC, id ASC",
+            ENDPOINT_COLUMNS, column
+        );
+        let rows = sqlx::query_as::<_, WebhookEndpoint>(&query)
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(rows)
+    }
+
+    async fn update_endpoint(&self, endpoint: &WebhookEndpoint) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE webhook_endpoints
+            SET url = $1,
+                events = $2,
+                description = $3,
+                active = $4,
+                updated_at = $5
+            WHERE id = $6
+        "#;
+        sqlx::query(query)
+            .bind(&endpoint.url)
+            .bind(event_names(endpoint))
+            .bind(&endpoint.description)
+            .bind(endpoint.active)
+            .bind(endpoint.updated_at)
+            .bind(endpoint.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn delete_endpoint(&self, endpoint_id: Uuid) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1")
+            .bind(endpoint_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn enqueue_event(&self, event: &WebhookEvent) -> Result<u64, DomainError> {
+        let query = r#"
+            INSERT INTO webhook_deliveries (id, endpoint_id, event_id, event_type, payload, status, attempts, next_attempt_at, created_at)
+            SELECT gen_random_uuid(), e.id, $1, $2, $3, 'pending', 0, $4, $4
+            FROM webhook_endpoints e
+            JOIN projects p ON p.owner_id = e.owner_id OR p.organization_id = e.organization_id
+            WHERE p.id = $5 AND e.active AND $2 = ANY(e.events)
+            ON CONFLICT (endpoint_id, event_id) WHERE redelivery_of IS NULL DO NOTHING
+        "#;
+        let result = sqlx::query(query)
+            .bind(event.id)
+            .bind(event.event_type.as_str())
+            .bind(event.payload())
+            .bind(event.created_at)
+            .bind(event.project_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn create_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO webhook_deliveries (id, endpoint_id, event_id, event_type, payload, status, attempts, next_attempt_at, last_attempt_at, response_status, last_error, redelivery_of, created_at, delivered_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        "#;
+        sqlx::query(query)
+            .bind(delivery.id)
+            .bind(delivery.endpoint_id)
+            .bind(delivery.event_id)
+            .bind(delivery.event_type.as_str())
+            .bind(&delivery.payload)
+            .bind(delivery.status)
+            .bind(delivery.attempts)
+            .bind(delivery.next_attempt_at)
+            .bind(delivery.last_attempt_at)
+            .bind(delivery.response_status)
+            .bind(&delivery.last_error)
+            .bind(delivery.redelivery_of)
+            .bind(delivery.created_at)
+            .bind(delivery.delivered_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+
+    async fn get_delivery_by_id(
+        &self,
+        delivery_id: Uuid,
+    ) -> Result<Option<WebhookDelivery>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM webhook_deliveries WHERE id = $1",
+            DELIVERY_COLUMNS
+        );
+        let row = sqlx::query_as::<_, WebhookDelivery>(&query)
+            .bind(delivery_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(row)
+    }
+
+    async fn list_deliveries(
+        &self,
+        endpoint_id: Uuid,
+        query: &DeliveryQuery,
+    ) -> Result<Page<WebhookDelivery>, DomainError> {
+        let sort = SortColumn::new("created_at", "id", true, ValueKind::Timestamp);
+        let mut builder = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {} FROM webhook_deliveries WHERE endpoint_id = ",
+            DELIVERY_COLUMNS
+        ));
+        builder.push_bind(endpoint_id);
+        if let Some(cursor) = &query.cursor {
+            sort.push_keyset(&mut builder, cursor)?;
+        }
+        sort.push_order_and_limit(&mut builder, query.limit);
+
+        let rows = builder
+            .build_query_as::<WebhookDelivery>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(Page::from_rows(rows, query.limit, |delivery| {
+            Cursor::new(CursorValue::Timestamp(delivery.created_at), delivery.id)
+        }))
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<(WebhookDelivery, WebhookEndpoint)>, DomainError> {
+        // SKIP LOCKED lets several workers claim disjoint batches concurrently.
+        let query = format!(
+            r#"
+            WITH due AS (
+                SELECT d.id AS due_id
+                FROM webhook_deliveries d
+                JOIN webhook_endpoints e ON e.id = d.endpoint_id
+                WHERE d.status = 'pending' AND d.next_attempt_at <= $1 AND e.active
+                ORDER BY d.next_attempt_at
+                LIMIT $3
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            UPDATE webhook_deliveries
+            SET next_attempt_at = $2
+            FROM due
+            WHERE id = due_id
+            RETURNING {}
+            "#,
+            DELIVERY_COLUMNS
+        );
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(&query)
+            .bind(now)
+            .bind(lease_until)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        if deliveries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let endpoint_ids: Vec<Uuid> = deliveries.iter().map(|d| d.endpoint_id).collect();
+        let query = format!(
+            "SELECT {} FROM webhook_endpoints WHERE id = ANY($1)",
+            ENDPOINT_COLUMNS
+        );
+        let endpoints = sqlx::query_as::<_, WebhookEndpoint>(&query)
+            .bind(&endpoint_ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        // An endpoint deleted in between takes its deliveries with it.
+        Ok(deliveries
+            .into_iter()
+            .filter_map(|delivery| {
+                let endpoint = endpoints.iter().find(|e| e.id == delivery.endpoint_id)?;
+                Some((delivery, endpoint.clone()))
+            })
+            .collect())
+    }
+
+    async fn record_attempt(&self, delivery: &WebhookDelivery) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE webhook_deliveries
+            SET status = $1,
+                attempts = $2,
+                next_attempt_at = $3,
+                last_attempt_at = $4,
+                response_status = $5,
+                last_error = $6,
+                delivered_at = $7
+            WHERE id = $8
+        "#;
+        sqlx::query(query)
+            .bind(delivery.status)
+            .bind(delivery.attempts)
+            .bind(delivery.next_attempt_at)
+            .bind(delivery.last_attempt_at)
+            .bind(delivery.response_status)
+            .bind(&delivery.last_error)
+            .bind(delivery.delivered_at)
+            .bind(delivery.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Infra(format!("DB error: {:?}", e)))?;
+        Ok(())
+    }
+}
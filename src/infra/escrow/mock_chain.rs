@@ -0,0 +1,393 @@
+//! In-process stand-in for a chain, for local development and tests. Blocks are
+//! produced every `block_time`; a transaction is included in the next block and
+//! confirmed once it has `required_confirmations`. A configurable share of
+//! transactions (or just the next one) revert on inclusion.
+
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::gateways::escrow_gateway::{
+    is_ss58_address, ChainTransaction, EscrowGateway, EscrowState, EscrowStatus, TransactionStatus,
+};
+use crate::domain::value_objects::money::Money;
+use async_trait::async_trait;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+struct PendingTransaction {
+    hash: String,
+    included_in: u64,
+    will_fail: bool,
+    /// State the escrow moves to once confirmed.
+    on_success: EscrowState,
+    /// State the escrow goes back to if the transaction reverts.
+    on_failure: EscrowState,
+}
+
+struct MockEscrow {
+    state: EscrowState,
+    amount: Option<Money>,
+    payee: Option<String>,
+    last_transaction: Option<ChainTransaction>,
+    pending: Option<PendingTransaction>,
+}
+
+struct ChainState {
+    extra_blocks: u64,
+    fail_next: bool,
+    nonce: u64,
+    escrows: HashMap<Uuid, MockEscrow>,
+}
+
+pub struct MockChain {
+    started_at: Instant,
+    block_time: Duration,
+    required_confirmations: u64,
+    failure_rate: f64,
+    state: Mutex<ChainState>,
+}
+
+impl MockChain {
+    pub fn new(block_time: Duration, required_confirmations: u64, failure_rate: f64) -> Self {
+        Self {
+            started_at: Instant::now(),
+            block_time,
+            required_confirmations: required_confirmations.max(1),
+            failure_rate: failure_rate.clamp(0.0, 1.0),
+            state: Mutex::new(ChainState {
+                extra_blocks: 0,
+                fail_next: false,
+                nonce: 0,
+                escrows: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Produces `blocks` blocks immediately, on top of the timed ones.
+    pub fn advance_blocks(&self, blocks: u64) {
+        self.lock_state().extra_blocks += blocks;
+    }
+
+    /// Makes the next submitted transaction revert.
+    pub fn fail_next_transaction(&self) {
+        self.lock_state().fail_next = true;
+    }
+
+    pub fn block_height(&self) -> u64 {
+        self.height(&self.lock_state())
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, ChainState> {
+        // A poisoned lock only means another request panicked mid-update; the map is
+        // still usable for a mock.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn height(&self, state: &ChainState) -> u64 {
+        let timed = if self.block_time.is_zero() {
+            0
+        } else {
+            (self.started_at.elapsed().as_millis() / self.block_time.as_millis()) as u64
+        };
+        timed + state.extra_blocks
+    }
+
+    /// Brings the escrow's pending transaction up to date with the current height.
+    fn settle(&self, escrow: &mut MockEscrow, height: u64) {
+        let Some(pending) = &escrow.pending else {
+            return;
+        };
+        let included = height >= pending.included_in;
+        let confirmations = if included {
+            height - pending.included_in + 1
+        } else {
+            0
+        };
+
+        let mut transaction = ChainTransaction {
+            hash: pending.hash.clone(),
+            block_number: included.then_some(pending.included_in),
+            confirmations,
+            status: TransactionStatus::Pending,
+            error: None,
+        };
+        if included && pending.will_fail {
+            transaction.status = TransactionStatus::Failed;
+            transaction.error = Some("ExtrinsicFailed: simulated failure".to_string());
+            escrow.state = pending.on_failure;
+            if escrow.state == EscrowState::Unfunded {
+                escrow.amount = None;
+            }
+            escrow.payee = None;
+            escrow.pending = None;
+        } else if confirmations >= self.required_confirmations {
+            transaction.status = TransactionStatus::Confirmed;
+            escrow.state = pending.on_success;
+            escrow.pending = None;
+        }
+        escrow.last_transaction = Some(transaction);
+    }
+
+    /// Records a transaction for `issue_id` moving it from one of `from` to `pending`.
+    fn submit(
+        &self,
+        issue_id: Uuid,
+        action: &str,
+        from: &[EscrowState],
+        pending_state: EscrowState,
+        on_success: EscrowState,
+        update: impl FnOnce(&mut MockEscrow),
+    ) -> Result<ChainTransaction, DomainError> {
+        let mut state = self.lock_state();
+        let height = self.height(&state);
+        let will_fail =
+            std::mem::take(&mut state.fail_next) || rand::thread_rng().gen_bool(self.failure_rate);
+        state.nonce += 1;
+        let mut hasher = Sha256::new();
+        hasher.update(issue_id.as_bytes());
+        hasher.update(state.nonce.to_be_bytes());
+        let hash = format!("0x{}", hex::encode(hasher.finalize()));
+
+        let escrow = state.escrows.entry(issue_id).or_insert_with(|| MockEscrow {
+            state: EscrowState::Unfunded,
+            amount: None,
+            payee: None,
+            last_transaction: None,
+            pending: None,
+        });
+        self.settle(escrow, height);
+        if escrow.state.is_pending() {
+            return Err(DomainError::Conflict(
+                "A chain transaction for this escrow is still pending".to_string(),
+            ));
+        }
+        if !from.contains(&escrow.state) {
+            return Err(DomainError::Conflict(format!(
+                "Cannot {} an escrow that is {}",
+                action,
+                escrow.state.as_str()
+            )));
+        }
+
+        let transaction = ChainTransaction {
+            hash: hash.clone(),
+            block_number: None,
+            confirmations: 0,
+            status: TransactionStatus::Pending,
+            error: None,
+        };
+        escrow.pending = Some(PendingTransaction {
+            hash,
+            included_in: height + 1,
+            will_fail,
+            on_success,
+            on_failure: escrow.state,
+        });
+        escrow.state = pending_state;
+        escrow.last_transaction = Some(transaction.clone());
+        update(escrow);
+        Ok(transaction)
+    }
+}
+
+#[async_trait]
+impl EscrowGateway for MockChain {
+    async fn lock_funds(
+        &self,
+        issue_id: Uuid,
+        amount: Money,
+    ) -> Result<ChainTransaction, DomainError> {
+        self.submit(
+            issue_id,
+            "lock",
+            &[EscrowState::Unfunded],
+            EscrowState::Locking,
+            EscrowState::Locked,
+            |escrow| escrow.amount = Some(amount),
+        )
+    }
+
+    async fn release(
+        &self,
+        issue_id: Uuid,
+        payee_address: &str,
+    ) -> Result<ChainTransaction, DomainError> {
+        if !is_ss58_address(payee_address) {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "payee_address",
+                "is not a valid SS58 address",
+            )]));
+        }
+        let payee = payee_address.to_string();
+        self.submit(
+            issue_id,
+            "release",
+            &[EscrowState::Locked],
+            EscrowState::Releasing,
+            EscrowState::Released,
+            |escrow| escrow.payee = Some(payee),
+        )
+    }
+
+    async fn refund(&self, issue_id: Uuid) -> Result<ChainTransaction, DomainError> {
+        self.submit(
+            issue_id,
+            "refund",
+            &[EscrowState::Locked],
+            EscrowState::Refunding,
+            EscrowState::Refunded,
+            |_| {},
+        )
+    }
+
+    async fn status(&self, issue_id: Uuid) -> Result<EscrowStatus, DomainError> {
+        let mut state = self.lock_state();
+        let height = self.height(&state);
+        let Some(escrow) = state.escrows.get_mut(&issue_id) else {
+            return Ok(EscrowStatus::unfunded());
+        };
+        self.settle(escrow, height);
+        Ok(EscrowStatus {
+            state: escrow.state,
+            amount: escrow.amount,
+            payee: escrow.payee.clone(),
+            last_transaction: escrow.last_transaction.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::money::Currency;
+
+    const PAYEE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    /// No timed blocks: the chain only moves on `advance_blocks`.
+    fn chain() -> MockChain {
+        MockChain::new(Duration::ZERO, 2, 0.0)
+    }
+
+    fn dot(major: i64) -> Money {
+        Money::new(major * 10_000_000_000, Currency::Dot)
+    }
+
+    async fn state(chain: &MockChain, issue_id: Uuid) -> EscrowState {
+        chain.status(issue_id).await.unwrap().state
+    }
+
+    #[tokio::test]
+    async fn lock_confirms_after_the_required_blocks() {
+        let chain = chain();
+        let issue_id = Uuid::new_v4();
+        assert_eq!(state(&chain, issue_id).await, EscrowState::Unfunded);
+
+        let transaction = chain.lock_funds(issue_id, dot(5)).await.unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Pending);
+        assert_eq!(state(&chain, issue_id).await, EscrowState::Locking);
+
+        chain.advance_blocks(1);
+        let status = chain.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Locking);
+        let included = status.last_transaction.unwrap();
+        assert_eq!(included.hash, transaction.hash);
+        assert_eq!(included.block_number, Some(1));
+        assert_eq!(included.confirmations, 1);
+
+        chain.advance_blocks(1);
+        let status = chain.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Locked);
+        assert_eq!(status.amount, Some(dot(5)));
+        assert_eq!(
+            status.last_transaction.unwrap().status,
+            TransactionStatus::Confirmed
+        );
+    }
+
+    #[tokio::test]
+    async fn release_pays_a_locked_escrow() {
+        let chain = chain();
+        let issue_id = Uuid::new_v4();
+        assert!(matches!(
+            chain.release(issue_id, PAYEE).await,
+            Err(DomainError::Conflict(_))
+        ));
+
+        chain.lock_funds(issue_id, dot(5)).await.unwrap();
+        // Nothing else goes through while the lock is pending
+        assert!(matches!(
+            chain.release(issue_id, PAYEE).await,
+            Err(DomainError::Conflict(_))
+        ));
+        chain.advance_blocks(2);
+
+        assert!(matches!(
+            chain.release(issue_id, "not-an-address").await,
+            Err(DomainError::Validation(_))
+        ));
+        chain.release(issue_id, PAYEE).await.unwrap();
+        assert_eq!(state(&chain, issue_id).await, EscrowState::Releasing);
+        chain.advance_blocks(2);
+        let status = chain.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Released);
+        assert_eq!(status.payee.as_deref(), Some(PAYEE));
+
+        assert!(matches!(
+            chain.refund(issue_id).await,
+            Err(DomainError::Conflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn refund_returns_a_locked_escrow() {
+        let chain = chain();
+        let issue_id = Uuid::new_v4();
+        assert!(matches!(
+            chain.refund(issue_id).await,
+            Err(DomainError::Conflict(_))
+        ));
+
+        chain.lock_funds(issue_id, dot(5)).await.unwrap();
+        chain.advance_blocks(2);
+        chain.refund(issue_id).await.unwrap();
+        assert_eq!(state(&chain, issue_id).await, EscrowState::Refunding);
+        chain.advance_blocks(2);
+        assert_eq!(state(&chain, issue_id).await, EscrowState::Refunded);
+
+        assert!(matches!(
+            chain.release(issue_id, PAYEE).await,
+            Err(DomainError::Conflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reverted_transactions_leave_the_escrow_as_it_was() {
+        let chain = chain();
+        let issue_id = Uuid::new_v4();
+
+        chain.fail_next_transaction();
+        chain.lock_funds(issue_id, dot(5)).await.unwrap();
+        chain.advance_blocks(1);
+        let status = chain.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Unfunded);
+        assert_eq!(status.amount, None);
+        let transaction = status.last_transaction.unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Failed);
+        assert!(transaction.error.is_some());
+
+        // Only the next transaction was meant to fail
+        chain.lock_funds(issue_id, dot(5)).await.unwrap();
+        chain.advance_blocks(2);
+        assert_eq!(state(&chain, issue_id).await, EscrowState::Locked);
+
+        chain.fail_next_transaction();
+        chain.release(issue_id, PAYEE).await.unwrap();
+        chain.advance_blocks(1);
+        let status = chain.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Locked);
+        assert_eq!(status.amount, Some(dot(5)));
+        assert_eq!(status.payee, None);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::domain::gateways::escrow_gateway::SharedEscrowGateway;
+use crate::domain::value_objects::money::Currency;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod blake2;
+pub mod mock_chain;
+mod scale;
+pub mod substrate_rpc;
+
+use mock_chain::MockChain;
+use substrate_rpc::{SubstrateConfig, SubstrateRpcGateway};
+
+/// Picks the escrow backend from `ESCROW_GATEWAY`: `mock` (default) runs the
+/// in-process chain, `substrate` talks to the node at `SUBSTRATE_RPC_URL` and signs
+/// with the ed25519 seed in `SUBSTRATE_SIGNER_SEED`.
+pub fn escrow_gateway_from_env() -> anyhow::Result<SharedEscrowGateway> {
+    let kind = env::var("ESCROW_GATEWAY").unwrap_or_else(|_| "mock".to_string());
+    match kind.as_str() {
+        "mock" => {
+            let block_ms: u64 = env_or("MOCK_CHAIN_BLOCK_MS", 2000);
+            let confirmations: u64 = env_or("MOCK_CHAIN_CONFIRMATIONS", 2);
+            let failure_rate: f64 = env_or("MOCK_CHAIN_FAILURE_RATE", 0.0);
+            Ok(Arc::new(MockChain::new(
+                Duration::from_millis(block_ms),
+                confirmations,
+                failure_rate,
+            )))
+        }
+        "substrate" => {
+            let url = env::var("SUBSTRATE_RPC_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:9944".to_string());
+            let seed = env::var("SUBSTRATE_SIGNER_SEED")
+                .map_err(|_| anyhow::anyhow!("SUBSTRATE_SIGNER_SEED is required"))?;
+            let signer_seed = hex::decode(seed.trim_start_matches("0x"))
+                .ok()
+                .and_then(|seed| <[u8; 32]>::try_from(seed).ok())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("SUBSTRATE_SIGNER_SEED must be 32 hex-encoded bytes")
+                })?;
+            let code = env::var("SUBSTRATE_CURRENCY").unwrap_or_else(|_| "DOT".to_string());
+            let currency = Currency::from_code(&code)
+                .ok_or_else(|| anyhow::anyhow!("Unknown SUBSTRATE_CURRENCY {:?}", code))?;
+            Ok(Arc::new(SubstrateRpcGateway::new(SubstrateConfig {
+                url,
+                signer_seed,
+                balances_pallet: env_or("SUBSTRATE_BALANCES_PALLET", 4),
+                metadata_hash_extension: env_or("SUBSTRATE_METADATA_HASH_EXTENSION", true),
+                currency,
+            })?))
+        }
+        other => anyhow::bail!(
+            "Unknown ESCROW_GATEWAY {:?} (expected mock or substrate)",
+            other
+        ),
+    }
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
@@ -0,0 +1,156 @@
+//! The parts of SCALE encoding and SS58 addressing the Substrate gateway needs.
+
+use super::blake2::blake2b;
+
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+
+/// Appends `value` in SCALE's compact integer encoding.
+pub fn encode_compact(value: u128, out: &mut Vec<u8>) {
+    match value {
+        0..=0x3f => out.push((value as u8) << 2),
+        0x40..=0x3fff => out.extend(((value as u16) << 2 | 0b01).to_le_bytes()),
+        0x4000..=0x3fff_ffff => out.extend(((value as u32) << 2 | 0b10).to_le_bytes()),
+        _ => {
+            let bytes = value.to_le_bytes();
+            let len = 16 - (value.leading_zeros() / 8) as usize;
+            out.push(((len - 4) as u8) << 2 | 0b11);
+            out.extend(&bytes[..len]);
+        }
+    }
+}
+
+/// The 32-byte account id behind an SS58 address, of any network prefix. `None`
+/// if the address is malformed or its checksum does not match.
+pub fn ss58_decode(address: &str) -> Option<[u8; 32]> {
+    let data = base58_decode(address)?;
+    let prefix_len = match data.first()? {
+        0..=63 => 1,
+        64..=127 => 2,
+        _ => return None,
+    };
+    if data.len() != prefix_len + 32 + 2 {
+        return None;
+    }
+    let (body, checksum) = data.split_at(prefix_len + 32);
+    if ss58_checksum(body) != checksum {
+        return None;
+    }
+    body[prefix_len..].try_into().ok()
+}
+
+/// SS58 address of `account` under a one-byte network `prefix` (below 64).
+pub fn ss58_encode(account: &[u8; 32], prefix: u8) -> String {
+    debug_assert!(prefix < 64, "two-byte SS58 prefixes are not supported");
+    let mut data = vec![prefix];
+    data.extend(account);
+    let checksum = ss58_checksum(&data);
+    data.extend(checksum);
+    base58_encode(&data)
+}
+
+fn ss58_checksum(body: &[u8]) -> [u8; 2] {
+    let hash = blake2b(64, &[SS58_CHECKSUM_PREFIX, body].concat());
+    [hash[0], hash[1]]
+}
+
+fn base58_decode(input: &str) -> Option<Vec<u8>> {
+    // Big-endian base-256 digits, built up one base-58 digit at a time
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in input.bytes() {
+        let mut carry = BASE58.iter().position(|&b| b == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = input.bytes().take_while(|&c| c == BASE58[0]).count();
+    let mut out = vec![0u8; zeros];
+    out.extend(bytes);
+    Some(out)
+}
+
+fn base58_encode(input: &[u8]) -> String {
+    // Little-endian base-58 digits
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in input {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = input.iter().take_while(|&&b| b == 0).count();
+    std::iter::repeat_n(BASE58[0], zeros)
+        .chain(digits.iter().rev().map(|&d| BASE58[d as usize]))
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const ALICE_ID: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    #[test]
+    fn compact_integers() {
+        let cases: [(u128, &str); 9] = [
+            (0, "00"),
+            (1, "04"),
+            (42, "a8"),
+            (63, "fc"),
+            (64, "0101"),
+            (69, "1501"),
+            (65535, "feff0300"),
+            (1 << 30, "0300000040"),
+            (100_000_000_000_000, "0b00407a10f35a"),
+        ];
+        for (value, expected) in cases {
+            let mut out = Vec::new();
+            encode_compact(value, &mut out);
+            assert_eq!(hex::encode(out), expected, "{}", value);
+        }
+        let mut out = Vec::new();
+        encode_compact(u128::MAX, &mut out);
+        assert_eq!(out.len(), 17);
+        assert_eq!(out[0], (16 - 4) << 2 | 0b11);
+    }
+
+    #[test]
+    fn ss58_round_trip() {
+        let account = ss58_decode(ALICE).unwrap();
+        assert_eq!(hex::encode(account), ALICE_ID);
+        assert_eq!(ss58_encode(&account, 42), ALICE);
+        // Polkadot's prefix 0 gives another address for the same account
+        let polkadot = ss58_encode(&account, 0);
+        assert_eq!(polkadot, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
+        assert_eq!(ss58_decode(&polkadot), Some(account));
+    }
+
+    #[test]
+    fn ss58_rejects_malformed_addresses() {
+        let mut tampered = ALICE.to_string();
+        tampered.replace_range(10..11, "a");
+        for address in [
+            "",
+            "not-an-address",
+            &tampered,
+            &ALICE[..ALICE.len() - 1],
+            "0GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+        ] {
+            assert_eq!(ss58_decode(address), None, "{:?}", address);
+        }
+    }
+}
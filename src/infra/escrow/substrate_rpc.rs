@@ -0,0 +1,1039 @@
+//! `EscrowGateway` for a Substrate chain with `pallet-balances`, such as a
+//! `solochain-template` dev node, spoken to over JSON-RPC (HTTP).
+//!
+//! Every issue gets its own escrow account: an ed25519 key derived from the
+//! platform's signer seed and the issue id. Locking transfers the bounty from the
+//! signer account to the escrow account (`Balances::transfer_keep_alive`).
+//! Releasing and refunding empty the escrow account into the payee or back into
+//! the signer account (`Balances::transfer_all`). The fee of those transactions
+//! is paid from the escrow, so the payee receives the bounty minus that fee.
+//!
+//! Extrinsics are signed here and sent with `author_submitExtrinsic`. Whether one
+//! succeeded is read from the escrow account's balance (`System::Account`) at the
+//! block that included it; it is confirmed once that block is finalized. They are
+//! mortal for `ERA_PERIOD` blocks, so one not included by then never will be.
+//!
+//! Submitted transactions are only tracked in memory. After a restart, an escrow
+//! account holding funds reads as `locked` and an empty one as `unfunded`.
+
+use super::blake2::{blake2_128, blake2_256};
+use super::scale::{encode_compact, ss58_decode, ss58_encode};
+use crate::domain::errors::domain_error::{DomainError, FieldError};
+use crate::domain::gateways::escrow_gateway::{
+    ChainTransaction, EscrowGateway, EscrowState, EscrowStatus, TransactionStatus,
+};
+use crate::domain::value_objects::money::{Currency, Money};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use uuid::Uuid;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON-RPC "invalid params"; anything else the node rejects is a state conflict.
+const INVALID_PARAMS: i64 = -32602;
+
+/// Blocks a transaction stays valid for after it is signed.
+const ERA_PERIOD: u64 = 64;
+/// Blocks searched for a submitted transaction per status request.
+const MAX_BLOCKS_PER_POLL: u64 = 32;
+
+/// The generic Substrate SS58 prefix, used for the addresses of the gateway's
+/// own accounts.
+const SS58_PREFIX: u8 = 42;
+
+/// `twox_128("System") ++ twox_128("Account")`
+const SYSTEM_ACCOUNT_PREFIX: &str =
+    "26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9";
+
+const SIGNED_EXTRINSIC_V4: u8 = 0b1000_0100;
+const MULTI_ADDRESS_ID: u8 = 0;
+const MULTI_SIGNATURE_ED25519: u8 = 0;
+const TRANSFER_KEEP_ALIVE: u8 = 3;
+const TRANSFER_ALL: u8 = 4;
+
+pub struct SubstrateConfig {
+    pub url: String,
+    /// Seed of the ed25519 account that funds locks and receives refunds.
+    pub signer_seed: [u8; 32],
+    /// Index of `pallet-balances` in the runtime (4 in `solochain-template`).
+    pub balances_pallet: u8,
+    /// Whether the runtime has the `CheckMetadataHash` transaction extension.
+    pub metadata_hash_extension: bool,
+    /// Currency of the chain's native token; its smallest unit is a minor unit.
+    pub currency: Currency,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Lock,
+    Release,
+    Refund,
+}
+
+#[derive(Clone)]
+struct Inclusion {
+    block_number: u64,
+    /// Whether the escrow balance at that block shows the transfer happened.
+    success: bool,
+}
+
+#[derive(Clone)]
+struct TrackedTransaction {
+    action: Action,
+    hash: String,
+    amount: Option<Money>,
+    payee: Option<String>,
+    /// First block of the transaction's era.
+    era_start: u64,
+    /// Last block searched for the transaction.
+    searched_to: u64,
+    inclusion: Option<Inclusion>,
+}
+
+#[derive(Default)]
+struct GatewayState {
+    /// Issues with a transaction being signed and submitted right now.
+    submitting: HashSet<Uuid>,
+    /// Last transaction submitted for each issue.
+    transactions: HashMap<Uuid, TrackedTransaction>,
+}
+
+pub struct SubstrateRpcGateway {
+    client: reqwest::Client,
+    config: SubstrateConfig,
+    /// Account that funds locks and receives refunds.
+    signer: [u8; 32],
+    next_id: AtomicU64,
+    state: Mutex<GatewayState>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcHeader {
+    number: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcRuntimeVersion {
+    spec_version: u32,
+    transaction_version: u32,
+}
+
+#[derive(Deserialize)]
+struct RpcSignedBlock {
+    block: RpcBlock,
+}
+
+#[derive(Deserialize)]
+struct RpcBlock {
+    extrinsics: Vec<String>,
+}
+
+/// What a signature commits to besides the call and the nonce.
+struct SigningContext {
+    spec_version: u32,
+    transaction_version: u32,
+    genesis_hash: [u8; 32],
+    /// Block the mortal era starts at, and its hash.
+    era_block: u64,
+    era_block_hash: [u8; 32],
+    metadata_hash_extension: bool,
+}
+
+/// Clears the issue's `submitting` mark when the submission ends, however it ends.
+struct Submission<'a> {
+    gateway: &'a SubstrateRpcGateway,
+    issue_id: Uuid,
+}
+
+impl Drop for Submission<'_> {
+    fn drop(&mut self) {
+        self.gateway.lock_state().submitting.remove(&self.issue_id);
+    }
+}
+
+impl SubstrateRpcGateway {
+    pub fn new(config: SubstrateConfig) -> Result<Self, DomainError> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| DomainError::Infra(format!("HTTP client error: {:?}", e)))?;
+        let signer = Ed25519KeyPair::from_seed_unchecked(&config.signer_seed)
+            .map_err(|e| DomainError::Infra(format!("Invalid signer seed: {}", e)))?;
+        Ok(Self {
+            client,
+            config,
+            signer: account_id(&signer),
+            next_id: AtomicU64::new(1),
+            state: Mutex::new(GatewayState::default()),
+        })
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, GatewayState> {
+        // A poisoned lock only means another request panicked mid-update; the
+        // tracked transactions are still consistent.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Marks `issue_id` as having a transaction in flight, so concurrent calls
+    /// for the same escrow cannot both pass the state check.
+    fn begin_submission(&self, issue_id: Uuid) -> Result<Submission<'_>, DomainError> {
+        if !self.lock_state().submitting.insert(issue_id) {
+            return Err(pending_conflict());
+        }
+        Ok(Submission {
+            gateway: self,
+            issue_id,
+        })
+    }
+
+    /// Another handle on the signer key, for `submit_checked` to own.
+    fn signer_keypair(&self) -> Result<Ed25519KeyPair, DomainError> {
+        Ed25519KeyPair::from_seed_unchecked(&self.config.signer_seed)
+            .map_err(|e| DomainError::Infra(format!("Invalid signer seed: {}", e)))
+    }
+
+    /// The escrow account of `issue_id`, which only this gateway can sign for.
+    fn escrow_keypair(&self, issue_id: Uuid) -> Result<Ed25519KeyPair, DomainError> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.config.signer_seed)
+            .map_err(|e| DomainError::Infra(format!("HMAC error: {:?}", e)))?;
+        mac.update(b"rust4u/escrow/");
+        mac.update(issue_id.as_bytes());
+        Ed25519KeyPair::from_seed_unchecked(&mac.finalize().into_bytes())
+            .map_err(|e| DomainError::Infra(format!("Invalid escrow seed: {}", e)))
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<T>, DomainError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+        let response: RpcResponse<T> = self
+            .client
+            .post(&self.config.url)
+            .json(&request)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| DomainError::Infra(format!("Substrate RPC error: {:?}", e)))?
+            .json()
+            .await
+            .map_err(|e| DomainError::Infra(format!("Substrate RPC decode error: {:?}", e)))?;
+
+        match response.error {
+            Some(error) if error.code == INVALID_PARAMS => {
+                Err(DomainError::InvalidData(error.message))
+            }
+            Some(error) => Err(DomainError::Conflict(format!(
+                "Chain rejected {}: {}",
+                method, error.message
+            ))),
+            None => Ok(response.result),
+        }
+    }
+
+    /// Like `call`, for methods that always return a result.
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T, DomainError> {
+        self.call(method, params)
+            .await?
+            .ok_or_else(|| DomainError::Infra(format!("Substrate RPC {} returned nothing", method)))
+    }
+
+    async fn block_number(&self, hash: Option<String>) -> Result<u64, DomainError> {
+        let params = match hash {
+            Some(hash) => json!([hash]),
+            None => json!([]),
+        };
+        let header: RpcHeader = self.fetch("chain_getHeader", params).await?;
+        u64::from_str_radix(header.number.trim_start_matches("0x"), 16)
+            .map_err(|e| DomainError::Infra(format!("Invalid block number: {:?}", e)))
+    }
+
+    async fn block_hash(&self, number: u64) -> Result<String, DomainError> {
+        self.fetch("chain_getBlockHash", json!([number])).await
+    }
+
+    async fn finalized_number(&self) -> Result<u64, DomainError> {
+        let hash: String = self.fetch("chain_getFinalizedHead", json!([])).await?;
+        self.block_number(Some(hash)).await
+    }
+
+    /// Free balance of `account`, at block `at` or the best block.
+    async fn free_balance(&self, account: &[u8], at: Option<&str>) -> Result<u128, DomainError> {
+        let key = format!(
+            "0x{}{}{}",
+            SYSTEM_ACCOUNT_PREFIX,
+            hex::encode(blake2_128(account)),
+            hex::encode(account)
+        );
+        let params = match at {
+            Some(at) => json!([key, at]),
+            None => json!([key]),
+        };
+        let Some(info) = self.call::<String>("state_getStorage", params).await? else {
+            // Accounts without a balance are not stored at all
+            return Ok(0);
+        };
+        free_balance_of(&decode_hex(&info)?)
+    }
+
+    /// Signs `call` as `signer` and submits it, remembering it as the issue's
+    /// last transaction.
+    async fn submit(
+        &self,
+        issue_id: Uuid,
+        signer: &Ed25519KeyPair,
+        call: Vec<u8>,
+        action: Action,
+        amount: Option<Money>,
+        payee: Option<String>,
+    ) -> Result<ChainTransaction, DomainError> {
+        let runtime: RpcRuntimeVersion = self.fetch("state_getRuntimeVersion", json!([])).await?;
+        let genesis_hash = self.block_hash(0).await?;
+        let era_block = self.block_number(None).await?;
+        let era_block_hash = self.block_hash(era_block).await?;
+        let signer_address = ss58_encode(&account_id(signer), SS58_PREFIX);
+        let nonce: u64 = self
+            .fetch("system_accountNextIndex", json!([signer_address]))
+            .await?;
+
+        let context = SigningContext {
+            spec_version: runtime.spec_version,
+            transaction_version: runtime.transaction_version,
+            genesis_hash: decode_hash(&genesis_hash)?,
+            era_block,
+            era_block_hash: decode_hash(&era_block_hash)?,
+            metadata_hash_extension: self.config.metadata_hash_extension,
+        };
+        let extrinsic = signed_extrinsic(signer, nonce, &call, &context);
+        let hash: String = self
+            .fetch(
+                "author_submitExtrinsic",
+                json!([format!("0x{}", hex::encode(extrinsic))]),
+            )
+            .await?;
+
+        self.lock_state().transactions.insert(
+            issue_id,
+            TrackedTransaction {
+                action,
+                hash: hash.clone(),
+                amount,
+                payee,
+                era_start: era_block,
+                searched_to: era_block,
+                inclusion: None,
+            },
+        );
+        Ok(ChainTransaction {
+            hash,
+            block_number: None,
+            confirmations: 0,
+            status: TransactionStatus::Pending,
+            error: None,
+        })
+    }
+
+    /// Searches the blocks produced since the last poll for the transaction and,
+    /// once found, checks the escrow balance it left behind.
+    async fn find_inclusion(
+        &self,
+        transaction: &mut TrackedTransaction,
+        escrow_account: &[u8],
+        best: u64,
+    ) -> Result<(), DomainError> {
+        let expected = decode_hash(&transaction.hash)?;
+        let last = best.min(transaction.searched_to + MAX_BLOCKS_PER_POLL);
+        for number in transaction.searched_to + 1..=last {
+            let block_hash = self.block_hash(number).await?;
+            let block: RpcSignedBlock = self.fetch("chain_getBlock", json!([block_hash])).await?;
+            for extrinsic in &block.block.extrinsics {
+                if blake2_256(&decode_hex(extrinsic)?) != expected {
+                    continue;
+                }
+                let balance = self.free_balance(escrow_account, Some(&block_hash)).await?;
+                let success = match transaction.action {
+                    Action::Lock => transaction
+                        .amount
+                        .is_some_and(|amount| balance >= amount.amount_minor as u128),
+                    Action::Release | Action::Refund => balance == 0,
+                };
+                transaction.searched_to = number;
+                transaction.inclusion = Some(Inclusion {
+                    block_number: number,
+                    success,
+                });
+                return Ok(());
+            }
+            transaction.searched_to = number;
+        }
+        Ok(())
+    }
+
+    /// Submits `call`, signed by `signer`, if the escrow is in the state `action`
+    /// starts from. `amount` defaults to what the escrow holds.
+    async fn submit_checked(
+        &self,
+        issue_id: Uuid,
+        action: Action,
+        signer: Ed25519KeyPair,
+        call: Vec<u8>,
+        amount: Option<Money>,
+        payee: Option<String>,
+    ) -> Result<ChainTransaction, DomainError> {
+        let _submission = self.begin_submission(issue_id)?;
+        let status = self.status(issue_id).await?;
+        if status.state.is_pending() {
+            return Err(pending_conflict());
+        }
+        if status.state != action.starts_from() {
+            return Err(DomainError::Conflict(format!(
+                "Cannot {} an escrow that is {}",
+                action.as_str(),
+                status.state.as_str()
+            )));
+        }
+        let amount = amount.or(status.amount);
+        self.submit(issue_id, &signer, call, action, amount, payee)
+            .await
+    }
+}
+
+impl Action {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Action::Lock => "lock",
+            Action::Release => "release",
+            Action::Refund => "refund",
+        }
+    }
+
+    fn starts_from(&self) -> EscrowState {
+        match self {
+            Action::Lock => EscrowState::Unfunded,
+            Action::Release | Action::Refund => EscrowState::Locked,
+        }
+    }
+
+    /// Escrow state while the action is pending, once it is confirmed, and if it failed.
+    fn states(&self) -> (EscrowState, EscrowState, EscrowState) {
+        match self {
+            Action::Lock => (
+                EscrowState::Locking,
+                EscrowState::Locked,
+                EscrowState::Unfunded,
+            ),
+            Action::Release => (
+                EscrowState::Releasing,
+                EscrowState::Released,
+                EscrowState::Locked,
+            ),
+            Action::Refund => (
+                EscrowState::Refunding,
+                EscrowState::Refunded,
+                EscrowState::Locked,
+            ),
+        }
+    }
+}
+
+fn pending_conflict() -> DomainError {
+    DomainError::Conflict("A chain transaction for this escrow is still pending".to_string())
+}
+
+fn account_id(keypair: &Ed25519KeyPair) -> [u8; 32] {
+    let mut id = [0u8; 32];
+    id.copy_from_slice(keypair.public_key().as_ref());
+    id
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, DomainError> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| DomainError::Infra(format!("Chain returned invalid hex: {:?}", e)))
+}
+
+fn decode_hash(value: &str) -> Result<[u8; 32], DomainError> {
+    decode_hex(value)?
+        .try_into()
+        .map_err(|_| DomainError::Infra(format!("Chain returned an invalid hash: {}", value)))
+}
+
+/// `AccountInfo { nonce, consumers, providers, sufficients: u32, data: { free, .. } }`
+fn free_balance_of(account_info: &[u8]) -> Result<u128, DomainError> {
+    let free = account_info
+        .get(16..32)
+        .ok_or_else(|| DomainError::Infra("Chain returned a truncated account".to_string()))?;
+    Ok(u128::from_le_bytes(free.try_into().expect("16 bytes")))
+}
+
+/// SCALE encoding of a mortal era of `ERA_PERIOD` blocks starting at `block`.
+fn mortal_era(block: u64) -> [u8; 2] {
+    let phase = block % ERA_PERIOD;
+    let period_bits = (ERA_PERIOD.trailing_zeros() - 1).clamp(1, 15) as u64;
+    ((period_bits | phase << 4) as u16).to_le_bytes()
+}
+
+fn transfer_keep_alive(pallet: u8, dest: &[u8; 32], amount: u128) -> Vec<u8> {
+    let mut call = vec![pallet, TRANSFER_KEEP_ALIVE, MULTI_ADDRESS_ID];
+    call.extend(dest);
+    encode_compact(amount, &mut call);
+    call
+}
+
+/// Moves the signer's whole balance to `dest`, closing the signer's account.
+fn transfer_all(pallet: u8, dest: &[u8; 32]) -> Vec<u8> {
+    let mut call = vec![pallet, TRANSFER_ALL, MULTI_ADDRESS_ID];
+    call.extend(dest);
+    call.push(0); // keep_alive: false
+    call
+}
+
+/// A signed v4 extrinsic for the transaction extensions of `solochain-template`:
+/// era, nonce and tip are sent along; spec and transaction version, genesis and
+/// era block hash are only signed. `CheckMetadataHash` is sent disabled.
+fn signed_extrinsic(
+    signer: &Ed25519KeyPair,
+    nonce: u64,
+    call: &[u8],
+    context: &SigningContext,
+) -> Vec<u8> {
+    let mut extra = mortal_era(context.era_block).to_vec();
+    encode_compact(nonce as u128, &mut extra);
+    encode_compact(0, &mut extra); // tip
+    if context.metadata_hash_extension {
+        extra.push(0); // mode: disabled
+    }
+
+    let mut additional = Vec::new();
+    additional.extend(context.spec_version.to_le_bytes());
+    additional.extend(context.transaction_version.to_le_bytes());
+    additional.extend(context.genesis_hash);
+    additional.extend(context.era_block_hash);
+    if context.metadata_hash_extension {
+        additional.push(0); // no metadata hash
+    }
+
+    let mut payload = [call, &extra, &additional].concat();
+    if payload.len() > 256 {
+        payload = blake2_256(&payload).to_vec();
+    }
+    let signature = signer.sign(&payload);
+
+    let mut body = vec![SIGNED_EXTRINSIC_V4, MULTI_ADDRESS_ID];
+    body.extend(account_id(signer));
+    body.push(MULTI_SIGNATURE_ED25519);
+    body.extend(signature.as_ref());
+    body.extend(extra);
+    body.extend(call);
+
+    let mut extrinsic = Vec::new();
+    encode_compact(body.len() as u128, &mut extrinsic);
+    extrinsic.extend(body);
+    extrinsic
+}
+
+#[async_trait]
+impl EscrowGateway for SubstrateRpcGateway {
+    async fn lock_funds(
+        &self,
+        issue_id: Uuid,
+        amount: Money,
+    ) -> Result<ChainTransaction, DomainError> {
+        if amount.currency != self.config.currency {
+            return Err(DomainError::Validation(vec![FieldError::new(
+                "currency",
+                &format!("must be {} on this chain", self.config.currency),
+            )]));
+        }
+        let escrow = account_id(&self.escrow_keypair(issue_id)?);
+        let call = transfer_keep_alive(
+            self.config.balances_pallet,
+            &escrow,
+            amount.amount_minor as u128,
+        );
+        self.submit_checked(
+            issue_id,
+            Action::Lock,
+            self.signer_keypair()?,
+            call,
+            Some(amount),
+            None,
+        )
+        .await
+    }
+
+    async fn release(
+        &self,
+        issue_id: Uuid,
+        payee_address: &str,
+    ) -> Result<ChainTransaction, DomainError> {
+        let payee = ss58_decode(payee_address).ok_or_else(|| {
+            DomainError::Validation(vec![FieldError::new(
+                "payee_address",
+                "is not a valid SS58 address",
+            )])
+        })?;
+        self.submit_checked(
+            issue_id,
+            Action::Release,
+            self.escrow_keypair(issue_id)?,
+            transfer_all(self.config.balances_pallet, &payee),
+            None,
+            Some(payee_address.to_string()),
+        )
+        .await
+    }
+
+    async fn refund(&self, issue_id: Uuid) -> Result<ChainTransaction, DomainError> {
+        self.submit_checked(
+            issue_id,
+            Action::Refund,
+            self.escrow_keypair(issue_id)?,
+            transfer_all(self.config.balances_pallet, &self.signer),
+            None,
+            None,
+        )
+        .await
+    }
+
+    async fn status(&self, issue_id: Uuid) -> Result<EscrowStatus, DomainError> {
+        let escrow_account = account_id(&self.escrow_keypair(issue_id)?);
+        let tracked = self.lock_state().transactions.get(&issue_id).cloned();
+        let Some(mut transaction) = tracked else {
+            let balance = self.free_balance(&escrow_account, None).await?;
+            if balance == 0 {
+                return Ok(EscrowStatus::unfunded());
+            }
+            let amount = i64::try_from(balance)
+                .map_err(|_| DomainError::Infra("Escrow balance out of range".to_string()))?;
+            return Ok(EscrowStatus {
+                state: EscrowState::Locked,
+                amount: Some(Money::new(amount, self.config.currency)),
+                payee: None,
+                last_transaction: None,
+            });
+        };
+
+        let best = self.block_number(None).await?;
+        if transaction.inclusion.is_none() {
+            self.find_inclusion(&mut transaction, &escrow_account, best)
+                .await?;
+            self.lock_state()
+                .transactions
+                .insert(issue_id, transaction.clone());
+        }
+
+        let (pending, confirmed, failed) = transaction.action.states();
+        let mut chain_transaction = ChainTransaction {
+            hash: transaction.hash.clone(),
+            block_number: None,
+            confirmations: 0,
+            status: TransactionStatus::Pending,
+            error: None,
+        };
+        let state = match &transaction.inclusion {
+            Some(inclusion) => {
+                chain_transaction.block_number = Some(inclusion.block_number);
+                chain_transaction.confirmations = (best + 1).saturating_sub(inclusion.block_number);
+                if !inclusion.success {
+                    chain_transaction.status = TransactionStatus::Failed;
+                    chain_transaction.error =
+                        Some("Transaction was included but moved no funds".to_string());
+                    failed
+                } else if inclusion.block_number <= self.finalized_number().await? {
+                    chain_transaction.status = TransactionStatus::Confirmed;
+                    confirmed
+                } else {
+                    pending
+                }
+            }
+            None if transaction.searched_to >= transaction.era_start + ERA_PERIOD => {
+                chain_transaction.status = TransactionStatus::Failed;
+                chain_transaction.error =
+                    Some("Transaction expired before it was included".to_string());
+                failed
+            }
+            None => pending,
+        };
+
+        let failed_lock = state == EscrowState::Unfunded;
+        let released = transaction.action == Action::Release && state != EscrowState::Locked;
+        Ok(EscrowStatus {
+            state,
+            amount: transaction.amount.filter(|_| !failed_lock),
+            payee: transaction.payee.filter(|_| released),
+            last_transaction: Some(chain_transaction),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{UnparsedPublicKey, ED25519};
+    use std::sync::Arc;
+
+    /// RFC 8032, test 1.
+    const SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    const PUBLIC_KEY: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+    const ALICE_ID: [u8; 32] = [
+        0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f,
+        0xd6, 0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d,
+        0xa2, 0x7d,
+    ];
+
+    fn keypair() -> Ed25519KeyPair {
+        Ed25519KeyPair::from_seed_unchecked(&hex::decode(SEED).unwrap()).unwrap()
+    }
+
+    fn context(metadata_hash_extension: bool) -> SigningContext {
+        SigningContext {
+            spec_version: 100,
+            transaction_version: 1,
+            genesis_hash: [0x11; 32],
+            era_block: 42,
+            era_block_hash: [0x22; 32],
+            metadata_hash_extension,
+        }
+    }
+
+    #[test]
+    fn mortal_eras() {
+        // Matches `Era::mortal(64, n).encode()` in sp-runtime
+        assert_eq!(mortal_era(42), [5 + 42 % 16 * 16, 42 / 16]);
+        assert_eq!(mortal_era(64 + 42), mortal_era(42));
+        assert_eq!(mortal_era(0), [5, 0]);
+    }
+
+    #[test]
+    fn balance_calls() {
+        let mut expected = vec![4, 3, 0];
+        expected.extend(ALICE_ID);
+        expected.extend([0x0b, 0x00, 0xa0, 0x72, 0x4e, 0x18, 0x09]); // 10^13, compact
+        assert_eq!(
+            transfer_keep_alive(4, &ALICE_ID, 10_000_000_000_000),
+            expected
+        );
+
+        let mut expected = vec![4, 4, 0];
+        expected.extend(ALICE_ID);
+        expected.push(0);
+        assert_eq!(transfer_all(4, &ALICE_ID), expected);
+    }
+
+    #[test]
+    fn signed_extrinsic_layout() {
+        let signer = keypair();
+        let call = transfer_keep_alive(4, &ALICE_ID, 500);
+        for metadata_hash_extension in [false, true] {
+            let context = context(metadata_hash_extension);
+            let extrinsic = signed_extrinsic(&signer, 7, &call, &context);
+
+            // Compact length prefix, then the body
+            let mut prefix = Vec::new();
+            encode_compact((extrinsic.len() - 2) as u128, &mut prefix);
+            assert_eq!(prefix.len(), 2);
+            assert_eq!(extrinsic[..2], prefix[..]);
+            let body = &extrinsic[2..];
+
+            assert_eq!(body[0], SIGNED_EXTRINSIC_V4);
+            assert_eq!(body[1], MULTI_ADDRESS_ID);
+            assert_eq!(hex::encode(&body[2..34]), PUBLIC_KEY);
+            assert_eq!(body[34], MULTI_SIGNATURE_ED25519);
+            let signature = &body[35..99];
+
+            let mut extra = mortal_era(42).to_vec();
+            extra.extend([7 << 2, 0]); // nonce 7, no tip
+            if metadata_hash_extension {
+                extra.push(0);
+            }
+            assert_eq!(body[99..99 + extra.len()], extra[..]);
+            assert_eq!(body[99 + extra.len()..], call[..]);
+
+            let mut payload = [&call[..], &extra].concat();
+            payload.extend(100u32.to_le_bytes());
+            payload.extend(1u32.to_le_bytes());
+            payload.extend([0x11; 32]);
+            payload.extend([0x22; 32]);
+            if metadata_hash_extension {
+                payload.push(0);
+            }
+            UnparsedPublicKey::new(&ED25519, hex::decode(PUBLIC_KEY).unwrap())
+                .verify(&payload, signature)
+                .expect("the signature covers call, extra and additional data");
+        }
+    }
+
+    #[test]
+    fn account_info_balances() {
+        let mut info = vec![0u8; 16];
+        info.extend(1_234_567_890_123u128.to_le_bytes());
+        info.extend([0u8; 48]);
+        assert_eq!(free_balance_of(&info).unwrap(), 1_234_567_890_123);
+        assert!(matches!(
+            free_balance_of(&info[..20]),
+            Err(DomainError::Infra(_))
+        ));
+    }
+
+    fn gateway(url: String) -> SubstrateRpcGateway {
+        SubstrateRpcGateway::new(SubstrateConfig {
+            url,
+            signer_seed: hex::decode(SEED).unwrap().try_into().unwrap(),
+            balances_pallet: 4,
+            metadata_hash_extension: true,
+            currency: Currency::Dot,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn escrow_accounts_are_per_issue() {
+        let gateway = gateway("http://127.0.0.1:9944".to_string());
+        let issue = Uuid::new_v4();
+        let escrow = account_id(&gateway.escrow_keypair(issue).unwrap());
+        assert_eq!(escrow, account_id(&gateway.escrow_keypair(issue).unwrap()));
+        assert_ne!(
+            escrow,
+            account_id(&gateway.escrow_keypair(Uuid::new_v4()).unwrap())
+        );
+        assert_ne!(escrow, gateway.signer);
+    }
+
+    /// Just enough of a Substrate node for the gateway: blocks are produced and
+    /// finalized on request, and account storage is set by the test.
+    #[derive(Default)]
+    struct StubNode {
+        best: u64,
+        finalized: u64,
+        pool: Vec<String>,
+        blocks: HashMap<u64, Vec<String>>,
+        storage: HashMap<String, String>,
+    }
+
+    impl StubNode {
+        fn produce_block(&mut self) {
+            self.best += 1;
+            let extrinsics = std::mem::take(&mut self.pool);
+            self.blocks.insert(self.best, extrinsics);
+        }
+
+        fn set_balance(&mut self, account: &[u8; 32], free: u128) {
+            let key = format!(
+                "0x{}{}{}",
+                SYSTEM_ACCOUNT_PREFIX,
+                hex::encode(blake2_128(account)),
+                hex::encode(account)
+            );
+            if free == 0 {
+                self.storage.remove(&key);
+            } else {
+                let mut info = vec![0u8; 16];
+                info.extend(free.to_le_bytes());
+                info.extend([0u8; 48]);
+                self.storage.insert(key, format!("0x{}", hex::encode(info)));
+            }
+        }
+
+        fn respond(&mut self, method: &str, params: &[Value]) -> Value {
+            let hash_of = |n: u64| format!("0x{:064x}", n);
+            let number_of =
+                |hash: &Value| u64::from_str_radix(&hash.as_str().unwrap()[2..], 16).unwrap();
+            match method {
+                "state_getRuntimeVersion" => json!({"specVersion": 100, "transactionVersion": 1}),
+                "chain_getBlockHash" => json!(hash_of(params[0].as_u64().unwrap())),
+                "chain_getHeader" => {
+                    let number = params.first().map(number_of).unwrap_or(self.best);
+                    json!({ "number": format!("0x{:x}", number) })
+                }
+                "chain_getFinalizedHead" => json!(hash_of(self.finalized)),
+                "chain_getBlock" => {
+                    let extrinsics = self.blocks.get(&number_of(&params[0])).cloned();
+                    json!({ "block": { "extrinsics": extrinsics.unwrap_or_default() } })
+                }
+                "system_accountNextIndex" => json!(0),
+                "author_submitExtrinsic" => {
+                    let extrinsic = params[0].as_str().unwrap().to_string();
+                    let hash = blake2_256(&decode_hex(&extrinsic).unwrap());
+                    self.pool.push(extrinsic);
+                    json!(format!("0x{}", hex::encode(hash)))
+                }
+                "state_getStorage" => json!(self.storage.get(params[0].as_str().unwrap())),
+                other => panic!("unexpected RPC method {}", other),
+            }
+        }
+    }
+
+    async fn serve(node: Arc<Mutex<StubNode>>) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = axum::Router::new().route(
+            "/",
+            axum::routing::post(move |axum::Json(request): axum::Json<Value>| async move {
+                let params = request["params"].as_array().cloned().unwrap_or_default();
+                let method = request["method"].as_str().unwrap().to_string();
+                let result = node.lock().unwrap().respond(&method, &params);
+                axum::Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+            }),
+        );
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+        url
+    }
+
+    #[tokio::test]
+    async fn escrow_lifecycle_against_a_node() {
+        let node = Arc::new(Mutex::new(StubNode {
+            best: 10,
+            finalized: 10,
+            ..StubNode::default()
+        }));
+        let gateway = gateway(serve(node.clone()).await);
+        let issue_id = Uuid::new_v4();
+        let escrow = account_id(&gateway.escrow_keypair(issue_id).unwrap());
+        let bounty = Money::new(50_000_000_000, Currency::Dot);
+        let alice = ss58_encode(&ALICE_ID, 42);
+
+        assert_eq!(
+            gateway.status(issue_id).await.unwrap().state,
+            EscrowState::Unfunded
+        );
+        assert!(matches!(
+            gateway.release(issue_id, &alice).await,
+            Err(DomainError::Conflict(_))
+        ));
+        assert!(matches!(
+            gateway
+                .lock_funds(issue_id, Money::new(1, Currency::Usd))
+                .await,
+            Err(DomainError::Validation(_))
+        ));
+
+        let lock = gateway.lock_funds(issue_id, bounty).await.unwrap();
+        assert_eq!(
+            gateway.status(issue_id).await.unwrap().state,
+            EscrowState::Locking
+        );
+        assert!(matches!(
+            gateway.lock_funds(issue_id, bounty).await,
+            Err(DomainError::Conflict(_))
+        ));
+
+        // Included, but not final yet
+        {
+            let mut node = node.lock().unwrap();
+            node.produce_block();
+            node.set_balance(&escrow, bounty.amount_minor as u128);
+        }
+        let status = gateway.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Locking);
+        let transaction = status.last_transaction.unwrap();
+        assert_eq!(transaction.hash, lock.hash);
+        assert_eq!(transaction.block_number, Some(11));
+        assert_eq!(transaction.status, TransactionStatus::Pending);
+
+        node.lock().unwrap().finalized = 11;
+        let status = gateway.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Locked);
+        assert_eq!(status.amount, Some(bounty));
+        assert_eq!(
+            status.last_transaction.unwrap().status,
+            TransactionStatus::Confirmed
+        );
+
+        assert!(matches!(
+            gateway.release(issue_id, "not-an-address").await,
+            Err(DomainError::Validation(_))
+        ));
+        gateway.release(issue_id, &alice).await.unwrap();
+        assert_eq!(
+            gateway.status(issue_id).await.unwrap().state,
+            EscrowState::Releasing
+        );
+        {
+            let mut node = node.lock().unwrap();
+            node.produce_block();
+            node.set_balance(&escrow, 0);
+            node.finalized = 12;
+        }
+        let status = gateway.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Released);
+        assert_eq!(status.payee.as_deref(), Some(alice.as_str()));
+        assert!(matches!(
+            gateway.refund(issue_id).await,
+            Err(DomainError::Conflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn transactions_that_move_no_funds_fail() {
+        let node = Arc::new(Mutex::new(StubNode::default()));
+        let gateway = gateway(serve(node.clone()).await);
+        let issue_id = Uuid::new_v4();
+        let bounty = Money::new(50_000_000_000, Currency::Dot);
+
+        gateway.lock_funds(issue_id, bounty).await.unwrap();
+        // Included without funding the escrow, e.g. because the signer is broke
+        node.lock().unwrap().produce_block();
+        let status = gateway.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Unfunded);
+        assert_eq!(status.amount, None);
+        assert_eq!(
+            status.last_transaction.unwrap().status,
+            TransactionStatus::Failed
+        );
+
+        // Never included before the era ran out
+        let issue_id = Uuid::new_v4();
+        gateway.lock_funds(issue_id, bounty).await.unwrap();
+        node.lock().unwrap().pool.clear();
+        for _ in 0..=ERA_PERIOD {
+            node.lock().unwrap().produce_block();
+        }
+        // Long gaps are searched over several polls
+        gateway.status(issue_id).await.unwrap();
+        gateway.status(issue_id).await.unwrap();
+        let status = gateway.status(issue_id).await.unwrap();
+        assert_eq!(status.state, EscrowState::Unfunded);
+        let transaction = status.last_transaction.unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Failed);
+        assert!(transaction.error.unwrap().contains("expired"));
+
+        // The escrow can be locked again
+        gateway.lock_funds(issue_id, bounty).await.unwrap();
+    }
+}
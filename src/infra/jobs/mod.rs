@@ -0,0 +1,82 @@
+//! Background jobs stored in the `jobs` table. A job type is a serializable
+//! payload implementing `JobPayload`, run by the `JobHandler` registered for it on
+//! the `JobWorker`. `JobQueue` puts jobs into the table.
+
+use crate::domain::entities::job::Job;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::repositories::job_repository::JobRepository;
+use crate::infra::db::job_repository_sql::JobRepositorySql;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub mod worker;
+
+/// Input of a job type, stored as JSON.
+pub trait JobPayload: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Unique name stored with the job, used to find its handler.
+    const KIND: &'static str;
+    /// Attempts before the job is dead-lettered.
+    const MAX_ATTEMPTS: i32 = 5;
+}
+
+#[async_trait]
+pub trait JobHandler: Send + Sync + 'static {
+    type Payload: JobPayload;
+
+    /// Jobs run at least once: a worker that stops mid-job leaves it to be run
+    /// again, so running a job twice must be harmless. An error schedules a retry.
+    async fn handle(&self, payload: Self::Payload) -> Result<(), DomainError>;
+}
+
+pub struct JobQueue<R: JobRepository> {
+    repository: R,
+}
+
+impl<R: JobRepository> JobQueue<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Queues a job to run as soon as a worker is free.
+    pub async fn enqueue<P: JobPayload>(&self, payload: &P) -> Result<Uuid, DomainError> {
+        self.schedule(payload, Utc::now()).await
+    }
+
+    /// Queues a job to run no earlier than `run_at`.
+    pub async fn schedule<P: JobPayload>(
+        &self,
+        payload: &P,
+        run_at: DateTime<Utc>,
+    ) -> Result<Uuid, DomainError> {
+        let job = new_job(payload, run_at)?;
+        self.repository.create_job(&job).await?;
+        Ok(job.id)
+    }
+
+    /// Like `enqueue`, but skips the job if one was already queued under
+    /// `dedup_key` (and not purged yet). Returns whether it was queued.
+    pub async fn enqueue_once<P: JobPayload>(
+        &self,
+        payload: &P,
+        dedup_key: &str,
+    ) -> Result<bool, DomainError> {
+        let mut job = new_job(payload, Utc::now())?;
+        job.dedup_key = Some(dedup_key.to_string());
+        self.repository.create_job(&job).await
+    }
+}
+
+fn new_job<P: JobPayload>(payload: &P, run_at: DateTime<Utc>) -> Result<Job, DomainError> {
+    let payload = serde_json::to_string(payload)
+        .map_err(|e| DomainError::Infra(format!("Job serialization error: {}", e)))?;
+    Ok(Job::new(P::KIND, payload, P::MAX_ATTEMPTS, run_at))
+}
+
+/// Queue writing to the `jobs` table in Postgres.
+pub fn job_queue(pool: Pool<Postgres>) -> JobQueue<JobRepositorySql> {
+    JobQueue::new(JobRepositorySql::new(pool))
+}
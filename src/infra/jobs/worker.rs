@@ -0,0 +1,396 @@
+use super::{JobHandler, JobPayload};
+use crate::domain::entities::job::{Job, JobStatus};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::repositories::job_repository::JobRepository;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+/// How long a claimed job is held back from other workers while it runs.
+const JOB_LEASE_MINUTES: i64 = 5;
+const RETRY_BASE_SECONDS: i64 = 10;
+const RETRY_MAX_SECONDS: i64 = 60 * 60;
+
+enum JobFailure {
+    /// The payload does not decode; retrying cannot help.
+    Malformed(String),
+    Failed(DomainError),
+}
+
+/// A `JobHandler` with its payload type erased, so handlers of different job
+/// types fit in one registry.
+#[async_trait]
+trait RegisteredHandler: Send + Sync {
+    async fn run(&self, payload: &str) -> Result<(), JobFailure>;
+}
+
+struct Registered<H: JobHandler>(H);
+
+#[async_trait]
+impl<H: JobHandler> RegisteredHandler for Registered<H> {
+    async fn run(&self, payload: &str) -> Result<(), JobFailure> {
+        let payload = serde_json::from_str(payload)
+            .map_err(|e| JobFailure::Malformed(format!("Malformed payload: {}", e)))?;
+        self.0.handle(payload).await.map_err(JobFailure::Failed)
+    }
+}
+
+/// Claims due jobs and runs them with their handlers.
+pub struct JobWorker<R: JobRepository> {
+    repository: R,
+    handlers: HashMap<&'static str, Box<dyn RegisteredHandler>>,
+}
+
+impl<R: JobRepository> JobWorker<R> {
+    pub fn new(repository: R) -> Self {
+        Self {
+            repository,
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Runs the jobs of `H::Payload::KIND` with `handler`. Only kinds with a
+    /// handler are claimed, so workers of an older build leave newer kinds alone.
+    pub fn with_handler<H: JobHandler>(mut self, handler: H) -> Self {
+        self.handlers
+            .insert(H::Payload::KIND, Box::new(Registered(handler)));
+        self
+    }
+
+    /// Runs up to `limit` due jobs. A failed job is retried with exponential
+    /// backoff until its attempts run out, then dead-lettered; a job whose payload
+    /// does not decode is dead-lettered right away. The outcome of a job another
+    /// worker claimed in the meantime is dropped, and one that cannot be stored is
+    /// left to run again once its lease runs out. Returns how many were claimed.
+    pub async fn run_due(&self, limit: u32) -> Result<usize, DomainError> {
+        if self.handlers.is_empty() {
+            return Ok(0);
+        }
+        let kinds: Vec<String> = self.handlers.keys().map(|k| k.to_string()).collect();
+        let now = Utc::now();
+        let claimed = self
+            .repository
+            .claim_due_jobs(
+                &kinds,
+                now,
+                now + Duration::minutes(JOB_LEASE_MINUTES),
+                limit,
+            )
+            .await?;
+
+        let count = claimed.len();
+        for mut job in claimed {
+            let seen_attempts = job.attempts;
+            // Attempts are counted when claimed, so this is a job whose last
+            // attempt was cut short by its worker stopping.
+            let result = if job.attempts > job.max_attempts {
+                Err(JobFailure::Malformed(
+                    "Worker stopped during the last attempt".to_string(),
+                ))
+            } else {
+                match self.handlers.get(job.kind.as_str()) {
+                    Some(handler) => handler.run(&job.payload).await,
+                    None => Err(JobFailure::Malformed(format!(
+                        "No handler for job kind {}",
+                        job.kind
+                    ))),
+                }
+            };
+
+            let now = Utc::now();
+            match result {
+                Ok(()) => {
+                    job.status = JobStatus::Completed;
+                    job.finished_at = Some(now);
+                }
+                Err(JobFailure::Failed(e)) if job.attempts < job.max_attempts => {
+                    tracing::warn!(
+                        "Job {} ({}) failed on attempt {}: {}",
+                        job.id,
+                        job.kind,
+                        job.attempts,
+                        e
+                    );
+                    job.status = JobStatus::Pending;
+                    job.last_error = Some(e.to_string());
+                    job.run_at = now + retry_delay(&job);
+                }
+                Err(failure) => {
+                    let error = match failure {
+                        JobFailure::Malformed(message) => message,
+                        JobFailure::Failed(e) => e.to_string(),
+                    };
+                    tracing::warn!("Job {} ({}) is dead: {}", job.id, job.kind, error);
+                    job.status = JobStatus::Dead;
+                    job.last_error = Some(error);
+                    job.finished_at = Some(now);
+                }
+            }
+            match self.repository.update_job(&job, seen_attempts).await {
+                Ok(true) => {}
+                Ok(false) => tracing::warn!(
+                    "Job {} ({}) was claimed again while it ran; its outcome is dropped",
+                    job.id,
+                    job.kind
+                ),
+                Err(e) => {
+                    tracing::warn!("Job {} ({}) could not be stored: {}", job.id, job.kind, e)
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Deletes jobs completed more than `max_age` ago. Returns how many were deleted.
+    pub async fn purge_completed(&self, max_age: Duration) -> Result<u64, DomainError> {
+        self.repository.purge_completed(Utc::now() - max_age).await
+    }
+}
+
+/// Waits 10s after the first failure, doubling up to an hour.
+fn retry_delay(job: &Job) -> Duration {
+    let exponent = (job.attempts - 1).clamp(0, 20) as u32;
+    Duration::seconds((RETRY_BASE_SECONDS << exponent).min(RETRY_MAX_SECONDS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::pagination::Page;
+    use crate::domain::repositories::job_repository::JobQuery;
+    use crate::infra::jobs::{JobHandler, JobPayload};
+    use chrono::DateTime;
+    use serde::{Deserialize, Serialize};
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    #[derive(Clone, Default)]
+    struct MockJobRepository {
+        jobs: Arc<Mutex<Vec<Job>>>,
+        /// `update_job` fails for these jobs, as if the database were unreachable.
+        unwritable: Arc<Mutex<Vec<Uuid>>>,
+    }
+
+    impl MockJobRepository {
+        fn add(&self, payload: &str) -> Uuid {
+            let job = Job::new(
+                Echo::KIND,
+                payload.to_string(),
+                Echo::MAX_ATTEMPTS,
+                Utc::now(),
+            );
+            let id = job.id;
+            self.jobs.lock().unwrap().push(job);
+            id
+        }
+
+        fn get(&self, id: Uuid) -> Job {
+            let jobs = self.jobs.lock().unwrap();
+            jobs.iter().find(|j| j.id == id).cloned().unwrap()
+        }
+
+        fn make_due(&self, id: Uuid) {
+            let mut jobs = self.jobs.lock().unwrap();
+            let job = jobs.iter_mut().find(|j| j.id == id).unwrap();
+            job.run_at = Utc::now();
+        }
+    }
+
+    #[async_trait]
+    impl JobRepository for MockJobRepository {
+        async fn create_job(&self, job: &Job) -> Result<bool, DomainError> {
+            self.jobs.lock().unwrap().push(job.clone());
+            Ok(true)
+        }
+
+        async fn get_job_by_id(&self, job_id: Uuid) -> Result<Option<Job>, DomainError> {
+            Ok(Some(self.get(job_id)))
+        }
+
+        async fn list_jobs(&self, _: &JobQuery) -> Result<Page<Job>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn claim_due_jobs(
+            &self,
+            kinds: &[String],
+            now: DateTime<Utc>,
+            lease_until: DateTime<Utc>,
+            limit: u32,
+        ) -> Result<Vec<Job>, DomainError> {
+            let mut jobs = self.jobs.lock().unwrap();
+            Ok(jobs
+                .iter_mut()
+                .filter(|j| {
+                    matches!(j.status, JobStatus::Pending | JobStatus::Running)
+                        && j.run_at <= now
+                        && kinds.contains(&j.kind)
+                })
+                .take(limit as usize)
+                .map(|j| {
+                    j.status = JobStatus::Running;
+                    j.attempts += 1;
+                    j.run_at = lease_until;
+                    j.clone()
+                })
+                .collect())
+        }
+
+        async fn update_job(&self, job: &Job, seen_attempts: i32) -> Result<bool, DomainError> {
+            if self.unwritable.lock().unwrap().contains(&job.id) {
+                return Err(DomainError::Infra("connection reset".to_string()));
+            }
+            let mut jobs = self.jobs.lock().unwrap();
+            let stored = jobs.iter_mut().find(|j| j.id == job.id).unwrap();
+            if stored.attempts != seen_attempts {
+                return Ok(false);
+            }
+            *stored = job.clone();
+            Ok(true)
+        }
+
+        async fn purge_completed(&self, _: DateTime<Utc>) -> Result<u64, DomainError> {
+            unimplemented!()
+        }
+    }
+
+    /// Succeeds on "ok" and fails on anything else. On "reclaimed" it first bumps
+    /// the job's attempts, as another worker claiming it after the lease would.
+    #[derive(Serialize, Deserialize)]
+    struct Echo(String);
+
+    impl JobPayload for Echo {
+        const KIND: &'static str = "echo";
+        const MAX_ATTEMPTS: i32 = 3;
+    }
+
+    struct EchoHandler(MockJobRepository);
+
+    #[async_trait]
+    impl JobHandler for EchoHandler {
+        type Payload = Echo;
+
+        async fn handle(&self, Echo(message): Echo) -> Result<(), DomainError> {
+            match message.as_str() {
+                "ok" => Ok(()),
+                "reclaimed" => {
+                    let mut jobs = self.0.jobs.lock().unwrap();
+                    let job = jobs.iter_mut().find(|j| j.payload.contains("reclaimed"));
+                    job.unwrap().attempts += 1;
+                    Ok(())
+                }
+                _ => Err(DomainError::Infra(message)),
+            }
+        }
+    }
+
+    fn worker(repository: &MockJobRepository) -> JobWorker<MockJobRepository> {
+        JobWorker::new(repository.clone()).with_handler(EchoHandler(repository.clone()))
+    }
+
+    fn payload(message: &str) -> String {
+        serde_json::to_string(&Echo(message.to_string())).unwrap()
+    }
+
+    #[test]
+    fn retries_back_off_exponentially_up_to_an_hour() {
+        let mut job = Job::new("echo", "\"ok\"".to_string(), 20, Utc::now());
+        for (attempts, seconds) in [
+            (1, 10),
+            (2, 20),
+            (3, 40),
+            (4, 80),
+            (9, 2560),
+            (10, 3600),
+            (30, 3600),
+            // A job that was never claimed still gets the base delay
+            (0, 10),
+        ] {
+            job.attempts = attempts;
+            assert_eq!(
+                retry_delay(&job),
+                Duration::seconds(seconds),
+                "{}",
+                attempts
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_jobs_are_retried_then_dead_lettered() {
+        let repository = MockJobRepository::default();
+        let worker = worker(&repository);
+        let ok = repository.add(&payload("ok"));
+        let failing = repository.add(&payload("boom"));
+
+        assert_eq!(worker.run_due(10).await.unwrap(), 2);
+        let job = repository.get(ok);
+        assert_eq!(job.status, JobStatus::Completed);
+        assert!(job.finished_at.is_some());
+
+        let job = repository.get(failing);
+        assert_eq!(job.status, JobStatus::Pending);
+        assert_eq!(job.attempts, 1);
+        assert_eq!(job.last_error.as_deref(), Some("Infra error: boom"));
+        assert!(job.run_at > Utc::now() + Duration::seconds(9));
+        // Not due yet
+        assert_eq!(worker.run_due(10).await.unwrap(), 0);
+
+        for attempt in 2..=Echo::MAX_ATTEMPTS {
+            repository.make_due(failing);
+            assert_eq!(worker.run_due(10).await.unwrap(), 1);
+            assert_eq!(repository.get(failing).attempts, attempt);
+        }
+        let job = repository.get(failing);
+        assert_eq!(job.status, JobStatus::Dead);
+        assert!(job.finished_at.is_some());
+        repository.make_due(failing);
+        assert_eq!(worker.run_due(10).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn undecodable_and_abandoned_jobs_are_dead_lettered_at_once() {
+        let repository = MockJobRepository::default();
+        let worker = worker(&repository);
+        let malformed = repository.add("{not json");
+        let abandoned = repository.add(&payload("ok"));
+        {
+            // Its worker stopped during the last attempt
+            let mut jobs = repository.jobs.lock().unwrap();
+            let job = jobs.iter_mut().find(|j| j.id == abandoned).unwrap();
+            job.status = JobStatus::Running;
+            job.attempts = Echo::MAX_ATTEMPTS;
+        }
+
+        assert_eq!(worker.run_due(10).await.unwrap(), 2);
+        let job = repository.get(malformed);
+        assert_eq!(job.status, JobStatus::Dead);
+        assert_eq!(job.attempts, 1);
+        assert!(job.last_error.unwrap().starts_with("Malformed payload"));
+        let job = repository.get(abandoned);
+        assert_eq!(job.status, JobStatus::Dead);
+        assert_eq!(
+            job.last_error.as_deref(),
+            Some("Worker stopped during the last attempt")
+        );
+    }
+
+    #[tokio::test]
+    async fn outcomes_of_reclaimed_or_unstorable_jobs_do_not_stop_the_batch() {
+        let repository = MockJobRepository::default();
+        let worker = worker(&repository);
+        let unstorable = repository.add(&payload("ok"));
+        let reclaimed = repository.add(&payload("reclaimed"));
+        let ok = repository.add(&payload("ok"));
+        repository.unwritable.lock().unwrap().push(unstorable);
+
+        assert_eq!(worker.run_due(10).await.unwrap(), 3);
+        // Left running, to be claimed again once the lease runs out
+        assert_eq!(repository.get(unstorable).status, JobStatus::Running);
+        // The other worker's claim stands
+        let job = repository.get(reclaimed);
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(job.attempts, 2);
+        assert_eq!(repository.get(ok).status, JobStatus::Completed);
+    }
+}
@@ -2,29 +2,49 @@ use std::env;
 use chrono::{Utc, Duration};
 use jsonwebtoken::{encode, decode, Header, Validation, EncodingKey, DecodingKey};
 use serde::{Serialize, Deserialize};
+use crate::domain::entities::user::Role;
 use crate::domain::errors::domain_error::DomainError;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: Uuid,
+    pub role: Role,
+    pub iat: usize,
     pub exp: usize,
 }
 
-pub fn generate_jwt(user_id: Uuid) -> Result<String, DomainError> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
-    let expiration_hours: i64 = env::var("JWT_EXPIRATION_HOURS")
-        .unwrap_or_else(|_| "24".to_string())
+/// Lifetime of access tokens; kept short since they cannot be revoked.
+pub fn access_token_ttl() -> Duration {
+    let minutes: i64 = env::var("JWT_EXPIRATION_MINUTES")
+        .unwrap_or_else(|_| "15".to_string())
+        .parse()
+        .unwrap_or(15);
+    Duration::minutes(minutes)
+}
+
+/// Lifetime of refresh tokens, see `SessionUsecases`.
+pub fn refresh_token_ttl() -> Duration {
+    let days: i64 = env::var("REFRESH_TOKEN_EXPIRATION_DAYS")
+        .unwrap_or_else(|_| "30".to_string())
         .parse()
-        .unwrap_or(24);
+        .unwrap_or(30);
+    Duration::days(days)
+}
+
+pub fn generate_jwt(user_id: Uuid, role: Role) -> Result<String, DomainError> {
+    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
 
-    let exp = Utc::now()
-        .checked_add_signed(Duration::hours(expiration_hours))
+    let now = Utc::now();
+    let exp = now
+        .checked_add_signed(access_token_ttl())
         .unwrap()
         .timestamp();
 
     let claims = Claims {
         sub: user_id,
+        role,
+        iat: now.timestamp() as usize,
         exp: exp as usize,
     };
 
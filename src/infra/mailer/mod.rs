@@ -0,0 +1,83 @@
+//! Email delivery: the `Mailer` backends, the templates emails are rendered from,
+//! and the `send_email` job through which everything is sent.
+
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::mailer::{Email, SharedMailer};
+use lettre::message::{Mailbox, MultiPart};
+use lettre::Message;
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub mod queue;
+pub mod sink;
+pub mod smtp;
+pub mod templates;
+
+use sink::SinkMailer;
+use smtp::{SmtpMailer, SmtpSecurity};
+
+/// Picks the mail backend from `MAILER`: `stdout` (default) prints emails,
+/// `file` writes them as `.eml` files into `MAIL_SINK_DIR`, and `smtp` sends
+/// them through `SMTP_HOST`. Emails are sent from `MAIL_FROM`.
+pub fn mailer_from_env() -> anyhow::Result<SharedMailer> {
+    let from: Mailbox = env::var("MAIL_FROM")
+        .unwrap_or_else(|_| "rust4u <no-reply@rust4u.local>".to_string())
+        .parse()?;
+    let kind = env::var("MAILER").unwrap_or_else(|_| "stdout".to_string());
+    match kind.as_str() {
+        "stdout" => Ok(Arc::new(SinkMailer::stdout(from))),
+        "file" => {
+            let dir = env::var("MAIL_SINK_DIR").unwrap_or_else(|_| "mail".to_string());
+            Ok(Arc::new(SinkMailer::dir(PathBuf::from(dir), from)))
+        }
+        "smtp" => {
+            let host = env::var("SMTP_HOST")
+                .ok()
+                .filter(|host| !host.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("SMTP_HOST must be set when MAILER=smtp"))?;
+            let security = match env::var("SMTP_TLS").as_deref() {
+                Ok("starttls") | Ok("") | Err(_) => SmtpSecurity::StartTls,
+                Ok("tls") => SmtpSecurity::Tls,
+                Ok("none") => SmtpSecurity::None,
+                Ok(other) => anyhow::bail!(
+                    "Unknown SMTP_TLS {:?} (expected starttls, tls or none)",
+                    other
+                ),
+            };
+            let port = env::var("SMTP_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(security.default_port());
+            let credentials = env::var("SMTP_USERNAME")
+                .ok()
+                .filter(|username| !username.is_empty())
+                .map(|username| (username, env::var("SMTP_PASSWORD").unwrap_or_default()));
+            Ok(Arc::new(SmtpMailer::new(
+                &host,
+                port,
+                security,
+                credentials,
+                from,
+            )?))
+        }
+        other => anyhow::bail!("Unknown MAILER {:?} (expected stdout, file or smtp)", other),
+    }
+}
+
+/// The MIME message for `email`: its plain-text and HTML bodies as alternatives.
+fn build_message(from: &Mailbox, email: &Email) -> Result<Message, DomainError> {
+    let to: Mailbox = email
+        .to
+        .parse()
+        .map_err(|e| DomainError::Infra(format!("Invalid recipient {:?}: {}", email.to, e)))?;
+    Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(email.subject.as_str())
+        .multipart(MultiPart::alternative_plain_html(
+            email.text.clone(),
+            email.html.clone(),
+        ))
+        .map_err(|e| DomainError::Infra(format!("Email build error: {}", e)))
+}
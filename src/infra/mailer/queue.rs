@@ -0,0 +1,87 @@
+use super::templates::render_email;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::mailer::{EmailMessage, EmailQueue, SharedEmailQueue, SharedMailer};
+use crate::domain::repositories::job_repository::JobRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::infra::jobs::{job_queue, JobHandler, JobPayload, JobQueue};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Job sending one email. Only the user is stored: the address and the text are
+/// looked up and rendered when the job runs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SendEmail {
+    pub user_id: Uuid,
+    pub message: EmailMessage,
+}
+
+impl JobPayload for SendEmail {
+    const KIND: &'static str = "send_email";
+    /// Mail servers can be down for a while; the last attempt comes about 3.5 hours in.
+    const MAX_ATTEMPTS: i32 = 12;
+}
+
+pub struct SendEmailHandler<U: UserRepository> {
+    user_repository: U,
+    mailer: SharedMailer,
+    app_url: String,
+}
+
+impl<U: UserRepository> SendEmailHandler<U> {
+    pub fn new(user_repository: U, mailer: SharedMailer, app_url: String) -> Self {
+        Self {
+            user_repository,
+            mailer,
+            app_url,
+        }
+    }
+}
+
+#[async_trait]
+impl<U> JobHandler for SendEmailHandler<U>
+where
+    U: UserRepository + Send + Sync + 'static,
+{
+    type Payload = SendEmail;
+
+    async fn handle(&self, job: SendEmail) -> Result<(), DomainError> {
+        let Some(user) = self.user_repository.get_user_by_id(job.user_id).await? else {
+            return Ok(());
+        };
+        let email = render_email(&job.message, &user.email, &user.username, &self.app_url)?;
+        self.mailer.send(&email).await
+    }
+}
+
+/// Queues emails as `send_email` jobs.
+pub struct JobEmailQueue<R: JobRepository> {
+    queue: JobQueue<R>,
+}
+
+#[async_trait]
+impl<R> EmailQueue for JobEmailQueue<R>
+where
+    R: JobRepository + Send + Sync,
+{
+    async fn queue_email(
+        &self,
+        user_id: Uuid,
+        message: EmailMessage,
+        dedup_key: &str,
+    ) -> Result<(), DomainError> {
+        self.queue
+            .enqueue_once(&SendEmail { user_id, message }, dedup_key)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Email queue writing to the `jobs` table in Postgres.
+pub fn email_queue(pool: Pool<Postgres>) -> SharedEmailQueue {
+    Arc::new(JobEmailQueue {
+        queue: job_queue(pool),
+    })
+}
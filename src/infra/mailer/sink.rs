@@ -0,0 +1,66 @@
+use super::build_message;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::mailer::{Email, Mailer};
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::message::Mailbox;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+enum Target {
+    Stdout,
+    Dir(PathBuf),
+}
+
+/// Keeps emails instead of sending them, for development and tests: prints them,
+/// or writes each one as an `.eml` file that mail clients can open.
+pub struct SinkMailer {
+    target: Target,
+    from: Mailbox,
+}
+
+impl SinkMailer {
+    pub fn stdout(from: Mailbox) -> Self {
+        Self {
+            target: Target::Stdout,
+            from,
+        }
+    }
+
+    /// `dir` is created with the first email if it does not exist yet.
+    pub fn dir(dir: PathBuf, from: Mailbox) -> Self {
+        Self {
+            target: Target::Dir(dir),
+            from,
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for SinkMailer {
+    async fn send(&self, email: &Email) -> Result<(), DomainError> {
+        // Built even when printed, so invalid emails fail here as they would over SMTP
+        let message = build_message(&self.from, email)?;
+        match &self.target {
+            // Only the plain-text body: the MIME encoding would make it unreadable
+            Target::Stdout => println!(
+                "----- email -----\nFrom: {}\nTo: {}\nSubject: {}\n\n{}\n-----------------",
+                self.from, email.to, email.subject, email.text
+            ),
+            Target::Dir(dir) => {
+                let sink_error =
+                    |e: std::io::Error| DomainError::Infra(format!("Mail sink error: {}", e));
+                tokio::fs::create_dir_all(dir).await.map_err(sink_error)?;
+                let name = format!(
+                    "{}-{}.eml",
+                    Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+                    Uuid::new_v4()
+                );
+                tokio::fs::write(dir.join(name), message.formatted())
+                    .await
+                    .map_err(sink_error)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! The email templates, in `templates/email`. Every message has an HTML and a
+//! plain-text template; askama compiles them into the binary and escapes the
+//! HTML ones.
+
+use crate::domain::entities::notification::NotificationType;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::mailer::{Email, EmailMessage};
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "email/welcome.html")]
+struct WelcomeHtml<'a> {
+    username: &'a str,
+    app_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/welcome.txt")]
+struct WelcomeText<'a> {
+    username: &'a str,
+    app_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/verify_email.html")]
+struct VerifyEmailHtml<'a> {
+    username: &'a str,
+    link: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/verify_email.txt")]
+struct VerifyEmailText<'a> {
+    username: &'a str,
+    link: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/reset_password.html")]
+struct ResetPasswordHtml<'a> {
+    username: &'a str,
+    link: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/reset_password.txt")]
+struct ResetPasswordText<'a> {
+    username: &'a str,
+    link: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/bounty_paid.html")]
+struct BountyPaidHtml<'a> {
+    username: &'a str,
+    amount: &'a str,
+    issue_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/bounty_paid.txt")]
+struct BountyPaidText<'a> {
+    username: &'a str,
+    amount: &'a str,
+    issue_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/notification.html")]
+struct NotificationHtml<'a> {
+    username: &'a str,
+    summary: &'a str,
+    notifications_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/notification.txt")]
+struct NotificationText<'a> {
+    username: &'a str,
+    summary: &'a str,
+    notifications_url: &'a str,
+}
+
+/// Renders `message` for the user `username` at `to`. Links point into the web
+/// app at `app_url`.
+pub fn render_email(
+    message: &EmailMessage,
+    to: &str,
+    username: &str,
+    app_url: &str,
+) -> Result<Email, DomainError> {
+    let app_url = app_url.trim_end_matches('/');
+    match message {
+        EmailMessage::Welcome => email(
+            to,
+            "Welcome to rust4u".to_string(),
+            WelcomeHtml { username, app_url },
+            WelcomeText { username, app_url },
+        ),
+        EmailMessage::VerifyEmail { link } => email(
+            to,
+            "Confirm your email address".to_string(),
+            VerifyEmailHtml { username, link },
+            VerifyEmailText { username, link },
+        ),
+        EmailMessage::ResetPassword { link } => email(
+            to,
+            "Reset your password".to_string(),
+            ResetPasswordHtml { username, link },
+            ResetPasswordText { username, link },
+        ),
+        EmailMessage::BountyPaid { issue_id, amount } => {
+            let amount = amount.to_string();
+            let issue_url = format!("{}/issues/{}", app_url, issue_id);
+            email(
+                to,
+                format!("You were paid {}", amount),
+                BountyPaidHtml {
+                    username,
+                    amount: &amount,
+                    issue_url: &issue_url,
+                },
+                BountyPaidText {
+                    username,
+                    amount: &amount,
+                    issue_url: &issue_url,
+                },
+            )
+        }
+        EmailMessage::Notification { notification_type } => {
+            let summary = summary(*notification_type);
+            let notifications_url = format!("{}/notifications", app_url);
+            email(
+                to,
+                summary.to_string(),
+                NotificationHtml {
+                    username,
+                    summary,
+                    notifications_url: &notifications_url,
+                },
+                NotificationText {
+                    username,
+                    summary,
+                    notifications_url: &notifications_url,
+                },
+            )
+        }
+    }
+}
+
+fn email(
+    to: &str,
+    subject: String,
+    html: impl Template,
+    text: impl Template,
+) -> Result<Email, DomainError> {
+    let render_error =
+        |e: askama::Error| DomainError::Infra(format!("Email template error: {}", e));
+    Ok(Email {
+        to: to.to_string(),
+        subject,
+        text: text.render().map_err(render_error)?,
+        html: html.render().map_err(render_error)?,
+    })
+}
+
+/// One line telling what happened, used as subject and body of notification emails.
+fn summary(notification_type: NotificationType) -> &'static str {
+    match notification_type {
+        NotificationType::ClaimCreated => "An issue of your project was claimed",
+        NotificationType::ClaimAccepted => "Your claim was accepted",
+        NotificationType::ClaimRejected => "Your claim was rejected",
+        NotificationType::SubmissionCreated => "Work was submitted for an issue of your project",
+        NotificationType::SubmissionApproved => "Your submission was approved",
+        NotificationType::ChangesRequested => "Changes were requested on your submission",
+        NotificationType::CommentCreated => "There is a new comment on your project",
+        NotificationType::CommentReply => "Someone replied to your comment",
+        NotificationType::Mentioned => "You were mentioned in a comment",
+        NotificationType::BountyPaid => "A bounty was paid to you",
+    }
+}
@@ -0,0 +1,15 @@
+//! Markdown rendering for user-written text. The output is sanitized, so it can be
+//! embedded in a page as is.
+
+use pulldown_cmark::{html, Options, Parser};
+
+/// Renders CommonMark (plus tables, strikethrough and task lists) to HTML, keeping
+/// only safe tags and attributes; scripts, event handlers and `javascript:` links
+/// are stripped. Links get `rel="noopener noreferrer"`.
+pub fn render_markdown(source: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut unsafe_html = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, Parser::new_ext(source, options));
+    ammonia::clean(&unsafe_html)
+}
@@ -1,2 +1,9 @@
+pub mod config;
 pub mod db;
+pub mod escrow;
+pub mod jobs;
 pub mod jwt;
+pub mod mailer;
+pub mod markdown;
+pub mod repository_host;
+pub mod webhooks;
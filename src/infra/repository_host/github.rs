@@ -0,0 +1,714 @@
+//! `RepositoryHost` for GitHub, backed by the REST API (`GET /repos/{owner}/{repo}`
+//! and its `/issues` endpoints) and by repository webhooks (`issues` and
+//! `pull_request` events, signed with `X-Hub-Signature-256`).
+//!
+//! The API base URL is configurable so the gateway can point at GitHub Enterprise
+//! or a local stub server. Project links are always `https://github.com/...`.
+
+use crate::domain::entities::issue_pull_request::PullRequestState;
+use crate::domain::entities::issue_upstream_link::UpstreamIssueState;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::repository_host::{
+    RepositoryEvent, RepositoryHost, RepositoryRef, RepositoryStats, UpstreamIssue,
+    UpstreamIssuePage, UpstreamPullRequest,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderMap, LINK};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::time::Duration;
+use url::Url;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const USER_AGENT: &str = "rust4u-backend";
+
+/// Words that make GitHub close the referenced issue when a pull request is merged.
+const CLOSING_KEYWORDS: [&str; 9] = [
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
+
+pub struct GithubHost {
+    client: reqwest::Client,
+    api_url: String,
+    token: Option<String>,
+    webhook_secret: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GithubRepo {
+    stargazers_count: i64,
+    forks_count: i64,
+    open_issues_count: i64,
+    language: Option<String>,
+    license: Option<GithubLicense>,
+    #[serde(default)]
+    topics: Vec<String>,
+    pushed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct GithubLicense {
+    spdx_id: Option<String>,
+    name: Option<String>,
+}
+
+impl GithubLicense {
+    /// GitHub reports `NOASSERTION` for licenses it cannot identify.
+    fn into_label(self) -> Option<String> {
+        self.spdx_id.filter(|id| id != "NOASSERTION").or(self.name)
+    }
+}
+
+#[derive(Deserialize)]
+struct GithubIssue {
+    number: i32,
+    title: String,
+    body: Option<String>,
+    html_url: String,
+    state: String,
+    #[serde(default)]
+    labels: Vec<GithubLabel>,
+    updated_at: DateTime<Utc>,
+    /// Only present on pull requests, which the issues API also returns.
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct GithubLabel {
+    name: String,
+}
+
+impl GithubIssue {
+    fn into_upstream(self) -> Option<UpstreamIssue> {
+        if self.pull_request.is_some() {
+            return None;
+        }
+        let state = match self.state.as_str() {
+            "closed" => UpstreamIssueState::Closed,
+            _ => UpstreamIssueState::Open,
+        };
+        Some(UpstreamIssue {
+            number: self.number,
+            title: self.title,
+            body: self.body,
+            url: self.html_url,
+            state,
+            labels: self.labels.into_iter().map(|l| l.name).collect(),
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct GithubPullRequest {
+    number: i32,
+    title: String,
+    body: Option<String>,
+    html_url: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    merged: bool,
+    user: Option<GithubUser>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct GithubUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GithubWebhookRepo {
+    name: String,
+    owner: GithubUser,
+}
+
+#[derive(Deserialize)]
+struct IssuesPayload {
+    action: String,
+    issue: GithubIssue,
+    repository: GithubWebhookRepo,
+}
+
+#[derive(Deserialize)]
+struct PullRequestPayload {
+    action: String,
+    pull_request: GithubPullRequest,
+    repository: GithubWebhookRepo,
+}
+
+impl GithubWebhookRepo {
+    fn into_ref(self) -> RepositoryRef {
+        RepositoryRef {
+            owner: self.owner.login,
+            name: self.name,
+        }
+    }
+}
+
+impl GithubPullRequest {
+    fn into_upstream(self, repository: &RepositoryRef) -> UpstreamPullRequest {
+        let state = match (self.state.as_str(), self.merged) {
+            (_, true) => PullRequestState::Merged,
+            ("closed", false) => PullRequestState::Closed,
+            _ => PullRequestState::Open,
+        };
+        let closing_issues = self
+            .body
+            .as_deref()
+            .map(|body| closing_references(repository, body))
+            .unwrap_or_default();
+        UpstreamPullRequest {
+            number: self.number,
+            title: self.title,
+            body: self.body,
+            url: self.html_url,
+            author: self.user.map(|u| u.login),
+            state,
+            draft: self.draft,
+            updated_at: self.updated_at,
+            closing_issues,
+        }
+    }
+}
+
+impl GithubHost {
+    pub fn new(
+        api_url: String,
+        token: Option<String>,
+        webhook_secret: Option<String>,
+    ) -> Result<Self, DomainError> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(USER_AGENT)
+            .build()
+            .map_err(|e| DomainError::Infra(format!("HTTP client error: {:?}", e)))?;
+        Ok(Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+            webhook_secret,
+        })
+    }
+
+    /// GETs `path` from the API. `not_found` is the message for a 404 (or a 410,
+    /// which GitHub returns for deleted issues).
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+        not_found: &str,
+    ) -> Result<(T, HeaderMap), DomainError> {
+        let mut request = self
+            .client
+            .get(format!("{}{}", self.api_url, path))
+            .query(query)
+            .header("Accept", "application/vnd.github+json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| DomainError::Infra(format!("GitHub API error: {:?}", e)))?;
+
+        match response.status() {
+            status if status.is_success() => {}
+            StatusCode::NOT_FOUND | StatusCode::GONE => {
+                return Err(DomainError::NotFound(not_found.to_string()))
+            }
+            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+                return Err(DomainError::Infra(
+                    "GitHub API rate limit exceeded".to_string(),
+                ))
+            }
+            status => {
+                return Err(DomainError::Infra(format!(
+                    "GitHub API returned {}",
+                    status
+                )))
+            }
+        }
+
+        let headers = response.headers().clone();
+        let body = response
+            .json()
+            .await
+            .map_err(|e| DomainError::Infra(format!("GitHub API decode error: {:?}", e)))?;
+        Ok((body, headers))
+    }
+}
+
+/// GitHub user, organization and repository names.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn saturating_i32(value: i64) -> i32 {
+    i32::try_from(value).unwrap_or(i32::MAX)
+}
+
+/// Issue numbers of `repository` that `text` closes, in order of appearance. Takes
+/// the forms GitHub does: `Fixes #12`, `Fixes owner/name#12` and a link to the
+/// issue; references to other repositories are skipped.
+fn closing_references(repository: &RepositoryRef, text: &str) -> Vec<i32> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut numbers = Vec::new();
+    for pair in words.windows(2) {
+        let keyword = pair[0]
+            .trim_start_matches(|c: char| !c.is_ascii_alphanumeric())
+            .trim_end_matches(':')
+            .to_ascii_lowercase();
+        if !CLOSING_KEYWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+        let reference = pair[1].trim_end_matches(['.', ',', ';', ')']);
+        if let Some(number) = parse_issue_reference(repository, reference) {
+            if !numbers.contains(&number) {
+                numbers.push(number);
+            }
+        }
+    }
+    numbers
+}
+
+fn parse_issue_reference(repository: &RepositoryRef, reference: &str) -> Option<i32> {
+    let same_repository = |owner: &str, name: &str| {
+        owner.eq_ignore_ascii_case(&repository.owner) && name.eq_ignore_ascii_case(&repository.name)
+    };
+    let parse_number = |number: &str| number.parse().ok().filter(|n: &i32| *n > 0);
+    if let Some(number) = reference.strip_prefix('#') {
+        return parse_number(number);
+    }
+    if let Ok(url) = Url::parse(reference) {
+        if !matches!(url.host_str(), Some("github.com" | "www.github.com")) {
+            return None;
+        }
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+        let [owner, name, "issues", number] = segments[..] else {
+            return None;
+        };
+        return same_repository(owner, name)
+            .then(|| parse_number(number))
+            .flatten();
+    }
+    let (full_name, number) = reference.split_once('#')?;
+    let (owner, name) = full_name.split_once('/')?;
+    same_repository(owner, name)
+        .then(|| parse_number(number))
+        .flatten()
+}
+
+fn decode_payload<T: DeserializeOwned>(payload: &[u8]) -> Result<T, DomainError> {
+    serde_json::from_slice(payload)
+        .map_err(|e| DomainError::InvalidData(format!("Malformed webhook payload: {}", e)))
+}
+
+#[async_trait]
+impl RepositoryHost for GithubHost {
+    fn parse_link(&self, link: &str) -> Option<RepositoryRef> {
+        let url = Url::parse(link.trim()).ok()?;
+        if !matches!(url.scheme(), "http" | "https")
+            || !matches!(url.host_str(), Some("github.com" | "www.github.com"))
+        {
+            return None;
+        }
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+        let [owner, name] = segments[..] else {
+            return None;
+        };
+        let name = name.strip_suffix(".git").unwrap_or(name);
+        if !is_valid_name(owner) || !is_valid_name(name) {
+            return None;
+        }
+        Some(RepositoryRef {
+            owner: owner.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    fn link(&self, repository: &RepositoryRef) -> String {
+        format!(
+            "https://github.com/{}/{}",
+            repository.owner, repository.name
+        )
+    }
+
+    async fn fetch_stats(
+        &self,
+        repository: &RepositoryRef,
+    ) -> Result<RepositoryStats, DomainError> {
+        let path = format!("/repos/{}/{}", repository.owner, repository.name);
+        let (repo, _): (GithubRepo, _) = self.get(&path, &[], "Repository not found").await?;
+        Ok(RepositoryStats {
+            stars: saturating_i32(repo.stargazers_count),
+            forks: saturating_i32(repo.forks_count),
+            open_issues: saturating_i32(repo.open_issues_count),
+            primary_language: repo.language,
+            license: repo.license.and_then(GithubLicense::into_label),
+            topics: repo.topics,
+            pushed_at: repo.pushed_at,
+            synced_at: Utc::now(),
+        })
+    }
+
+    async fn list_issues(
+        &self,
+        repository: &RepositoryRef,
+        page: u32,
+        per_page: u32,
+    ) -> Result<UpstreamIssuePage, DomainError> {
+        let path = format!("/repos/{}/{}/issues", repository.owner, repository.name);
+        let query = [
+            ("state", "open".to_string()),
+            ("page", page.to_string()),
+            ("per_page", per_page.to_string()),
+        ];
+        let (issues, headers): (Vec<GithubIssue>, _) =
+            self.get(&path, &query, "Repository not found").await?;
+        // GitHub paginates with a `Link: <...>; rel="next"` header.
+        let has_more = headers
+            .get(LINK)
+            .and_then(|link| link.to_str().ok())
+            .is_some_and(|link| link.contains("rel=\"next\""));
+        Ok(UpstreamIssuePage {
+            issues: issues
+                .into_iter()
+                .filter_map(GithubIssue::into_upstream)
+                .collect(),
+            has_more,
+        })
+    }
+
+    async fn get_issue(
+        &self,
+        repository: &RepositoryRef,
+        number: i32,
+    ) -> Result<UpstreamIssue, DomainError> {
+        let path = format!(
+            "/repos/{}/{}/issues/{}",
+            repository.owner, repository.name, number
+        );
+        let (issue, _): (GithubIssue, _) = self.get(&path, &[], "Issue not found").await?;
+        issue
+            .into_upstream()
+            .ok_or_else(|| DomainError::NotFound("Issue not found".to_string()))
+    }
+
+    fn verify_webhook(&self, payload: &[u8], signature: Option<&str>) -> Result<(), DomainError> {
+        // An empty key would make every signature forgeable
+        let secret = self
+            .webhook_secret
+            .as_deref()
+            .filter(|secret| !secret.is_empty())
+            .ok_or_else(|| {
+                DomainError::Infra("GitHub webhook secret is not configured".to_string())
+            })?;
+        let invalid = || DomainError::Unauthorized("Invalid webhook signature".to_string());
+        let signature = signature
+            .and_then(|s| s.strip_prefix("sha256="))
+            .and_then(|s| hex::decode(s).ok())
+            .ok_or_else(invalid)?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .map_err(|e| DomainError::Infra(format!("HMAC error: {:?}", e)))?;
+        mac.update(payload);
+        mac.verify_slice(&signature).map_err(|_| invalid())
+    }
+
+    fn parse_webhook(
+        &self,
+        event: &str,
+        payload: &[u8],
+    ) -> Result<Option<RepositoryEvent>, DomainError> {
+        match event {
+            "issues" => {
+                let payload: IssuesPayload = decode_payload(payload)?;
+                if !matches!(payload.action.as_str(), "edited" | "closed" | "reopened") {
+                    return Ok(None);
+                }
+                Ok(payload
+                    .issue
+                    .into_upstream()
+                    .map(|issue| RepositoryEvent::Issue {
+                        repository: payload.repository.into_ref(),
+                        issue,
+                    }))
+            }
+            "pull_request" => {
+                let payload: PullRequestPayload = decode_payload(payload)?;
+                if !matches!(
+                    payload.action.as_str(),
+                    "opened"
+                        | "reopened"
+                        | "edited"
+                        | "closed"
+                        | "ready_for_review"
+                        | "converted_to_draft"
+                ) {
+                    return Ok(None);
+                }
+                let repository = payload.repository.into_ref();
+                let pull_request = payload.pull_request.into_upstream(&repository);
+                Ok(Some(RepositoryEvent::PullRequest {
+                    repository,
+                    pull_request,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, Query};
+    use axum::http::{header, StatusCode as HttpStatus};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    fn host(api_url: String) -> GithubHost {
+        GithubHost::new(api_url, None, None).unwrap()
+    }
+
+    fn repository(owner: &str, name: &str) -> RepositoryRef {
+        RepositoryRef {
+            owner: owner.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    fn issue(number: i32, pull_request: bool) -> Value {
+        let mut issue = json!({
+            "number": number,
+            "title": format!("Issue {}", number),
+            "body": null,
+            "html_url": format!("https://github.com/rust4u/app/issues/{}", number),
+            "state": "open",
+            "labels": [{"name": "bug"}],
+            "updated_at": "2023-02-06T12:00:00Z",
+        });
+        if pull_request {
+            issue["pull_request"] = json!({});
+        }
+        issue
+    }
+
+    /// A stand-in for the GitHub API serving `rust4u/app`: three pages of two
+    /// issues, one of them a pull request. Any other repository is a 404, and
+    /// `rust4u/limited` is rate limited.
+    async fn serve() -> String {
+        async fn repo(Path((owner, name)): Path<(String, String)>) -> impl IntoResponse {
+            match (owner.as_str(), name.as_str()) {
+                ("rust4u", "app") => Json(json!({
+                    "stargazers_count": 42,
+                    "forks_count": 7,
+                    "open_issues_count": 3,
+                    "language": "Rust",
+                    "license": {"spdx_id": "NOASSERTION", "name": "Custom license"},
+                    "topics": ["axum", "bounties"],
+                    "pushed_at": "2023-02-01T10:00:00Z",
+                }))
+                .into_response(),
+                ("rust4u", "limited") => HttpStatus::FORBIDDEN.into_response(),
+                _ => HttpStatus::NOT_FOUND.into_response(),
+            }
+        }
+
+        async fn issues(
+            Path((owner, name)): Path<(String, String)>,
+            Query(query): Query<HashMap<String, String>>,
+        ) -> impl IntoResponse {
+            if (owner.as_str(), name.as_str()) != ("rust4u", "app") {
+                return HttpStatus::NOT_FOUND.into_response();
+            }
+            assert_eq!(query.get("state").map(String::as_str), Some("open"));
+            assert_eq!(query.get("per_page").map(String::as_str), Some("2"));
+            let page: i32 = query["page"].parse().unwrap();
+            let body = match page {
+                1 => json!([issue(1, false), issue(2, true)]),
+                2 => json!([issue(3, false), issue(4, false)]),
+                3 => json!([issue(5, false)]),
+                _ => json!([]),
+            };
+            let link = |page: i32, rel: &str| {
+                format!(
+                    "<https://api.github.com/repositories/1/issues?page={}>; rel=\"{}\"",
+                    page, rel
+                )
+            };
+            let mut links = vec![link(1, "first")];
+            if page < 3 {
+                links.push(link(page + 1, "next"));
+            }
+            links.push(link(3, "last"));
+            ([(header::LINK, links.join(", "))], Json(body)).into_response()
+        }
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = Router::new()
+            .route("/repos/:owner/:name", get(repo))
+            .route("/repos/:owner/:name/issues", get(issues));
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+        url
+    }
+
+    #[test]
+    fn parse_link_accepts_repository_urls_only() {
+        let host = host("https://api.github.com".to_string());
+        let cases = [
+            ("https://github.com/rust4u/app", Some(("rust4u", "app"))),
+            ("http://github.com/rust4u/app", Some(("rust4u", "app"))),
+            (
+                "https://www.github.com/rust4u/app/",
+                Some(("rust4u", "app")),
+            ),
+            ("https://github.com/rust4u/app.git", Some(("rust4u", "app"))),
+            (
+                "  https://github.com/rust-lang/rust.vim  ",
+                Some(("rust-lang", "rust.vim")),
+            ),
+            ("https://github.com/rust4u", None),
+            ("https://github.com/rust4u/app/issues", None),
+            ("https://github.com/rust4u/..", None),
+            ("https://github.com/rust4u/a%20b", None),
+            ("https://gitlab.com/rust4u/app", None),
+            ("https://github.com.evil.io/rust4u/app", None),
+            ("ftp://github.com/rust4u/app", None),
+            ("github.com/rust4u/app", None),
+            ("", None),
+        ];
+        for (link, expected) in cases {
+            assert_eq!(
+                host.parse_link(link),
+                expected.map(|(owner, name)| repository(owner, name)),
+                "{:?}",
+                link
+            );
+        }
+        assert_eq!(
+            host.link(&repository("rust4u", "app")),
+            "https://github.com/rust4u/app"
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_stats_maps_repository_metadata() {
+        let host = host(format!("{}/", serve().await));
+        let stats = host
+            .fetch_stats(&repository("rust4u", "app"))
+            .await
+            .unwrap();
+        assert_eq!((stats.stars, stats.forks, stats.open_issues), (42, 7, 3));
+        assert_eq!(stats.primary_language.as_deref(), Some("Rust"));
+        assert_eq!(stats.license.as_deref(), Some("Custom license"));
+        assert_eq!(stats.topics, ["axum", "bounties"]);
+        assert!(stats.pushed_at.is_some());
+
+        assert!(matches!(
+            host.fetch_stats(&repository("rust4u", "gone")).await,
+            Err(DomainError::NotFound(_))
+        ));
+        assert!(matches!(
+            host.fetch_stats(&repository("rust4u", "limited")).await,
+            Err(DomainError::Infra(message)) if message.contains("rate limit")
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_issues_follows_the_link_header() {
+        let host = host(serve().await);
+        let app = repository("rust4u", "app");
+        let mut numbers = Vec::new();
+        let mut page = 1;
+        loop {
+            let result = host.list_issues(&app, page, 2).await.unwrap();
+            numbers.extend(result.issues.iter().map(|issue| issue.number));
+            if !result.has_more {
+                break;
+            }
+            page += 1;
+        }
+        // The pull request is left out
+        assert_eq!(numbers, [1, 3, 4, 5]);
+        assert_eq!(page, 3);
+
+        let first = host.list_issues(&app, 1, 2).await.unwrap();
+        assert_eq!(first.issues[0].labels, ["bug"]);
+        assert_eq!(first.issues[0].state, UpstreamIssueState::Open);
+        assert!(matches!(
+            host.list_issues(&repository("rust4u", "gone"), 1, 2).await,
+            Err(DomainError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn verify_webhook_checks_the_hmac_signature() {
+        // The example from GitHub's webhook documentation
+        let secret = "It's a Secret to Everybody";
+        let payload = b"Hello, World!";
+        let digest = "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+        let valid = format!("sha256={}", digest);
+        let host = GithubHost::new(
+            "https://api.github.com".to_string(),
+            None,
+            Some(secret.to_string()),
+        )
+        .unwrap();
+
+        assert!(host.verify_webhook(payload, Some(&valid)).is_ok());
+        let uppercase = format!("sha256={}", digest.to_uppercase());
+        assert!(host.verify_webhook(payload, Some(&uppercase)).is_ok());
+
+        let truncated = &valid[..valid.len() - 2];
+        let cases: [(&[u8], Option<&str>); 7] = [
+            (b"Hello, World?", Some(&valid)),
+            (payload, None),
+            (payload, Some(digest)),
+            (
+                payload,
+                Some("sha1=757107ea0eb2509fc211221cce984b8a37570b6d"),
+            ),
+            (payload, Some("sha256=not-hex")),
+            (payload, Some(truncated)),
+            (payload, Some("sha256=")),
+        ];
+        for (body, signature) in cases {
+            assert!(
+                matches!(
+                    host.verify_webhook(body, signature),
+                    Err(DomainError::Unauthorized(_))
+                ),
+                "{:?}",
+                signature
+            );
+        }
+
+        for secret in [None, Some(String::new())] {
+            let host = GithubHost::new("https://api.github.com".to_string(), None, secret).unwrap();
+            assert!(matches!(
+                host.verify_webhook(payload, Some(&valid)),
+                Err(DomainError::Infra(_))
+            ));
+        }
+    }
+}
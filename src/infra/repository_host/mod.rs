@@ -0,0 +1,21 @@
+use crate::domain::gateways::repository_host::SharedRepositoryHost;
+use std::env;
+use std::sync::Arc;
+
+pub mod github;
+
+use github::GithubHost;
+
+/// GitHub client for project repository links. `GITHUB_API_URL` (default
+/// `https://api.github.com`) points it elsewhere; `GITHUB_TOKEN` raises the
+/// API rate limit, and `GITHUB_WEBHOOK_SECRET` is the secret repository webhooks
+/// are signed with.
+pub fn repository_host_from_env() -> anyhow::Result<SharedRepositoryHost> {
+    let api_url =
+        env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".to_string());
+    let token = env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty());
+    let webhook_secret = env::var("GITHUB_WEBHOOK_SECRET")
+        .ok()
+        .filter(|s| !s.is_empty());
+    Ok(Arc::new(GithubHost::new(api_url, token, webhook_secret)?))
+}
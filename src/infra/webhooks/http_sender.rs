@@ -0,0 +1,166 @@
+//! `WebhookSender` over HTTP. Every request carries the event type, the delivery
+//! id and an HMAC-SHA256 signature of the body keyed with the endpoint's secret:
+//!
+//! ```text
+//! X-Rust4u-Event: issue.created
+//! X-Rust4u-Delivery: <delivery id>
+//! X-Rust4u-Signature-256: sha256=<hex digest>
+//! ```
+//!
+//! Requests only go to public addresses: host names are resolved by
+//! `PublicResolver`, and IP literals are checked before sending.
+
+use crate::domain::entities::webhook::{is_public_address, WebhookDelivery, WebhookEndpoint};
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::webhook_sender::{WebhookResponse, WebhookSender};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use sha2::Sha256;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use url::{Host, Url};
+
+const USER_AGENT: &str = "rust4u-webhooks";
+
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+}
+
+impl HttpWebhookSender {
+    pub fn new(timeout: Duration) -> Result<Self, DomainError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent(USER_AGENT)
+            // A redirect would send the signed payload somewhere unregistered.
+            .redirect(reqwest::redirect::Policy::none())
+            // A proxy would resolve the host itself, past `PublicResolver`.
+            .no_proxy()
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()
+            .map_err(|e| DomainError::Infra(format!("HTTP client error: {:?}", e)))?;
+        Ok(Self { client })
+    }
+}
+
+/// The system resolver, refusing hosts that point to any non-public address. The
+/// check happens on every connection, so a host re-pointed after its endpoint
+/// was registered is caught.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            if addresses.is_empty() || !addresses.iter().all(|a| is_public_address(a.ip())) {
+                return Err(
+                    format!("{} does not resolve to a public address", name.as_str()).into(),
+                );
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// IP literals skip the resolver, so they are checked here.
+fn check_ip_literal(url: &str) -> Result<(), DomainError> {
+    let ip = match Url::parse(url)
+        .ok()
+        .and_then(|u| u.host().map(|h| h.to_owned()))
+    {
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+        _ => return Ok(()),
+    };
+    if !is_public_address(ip) {
+        return Err(DomainError::Infra(format!(
+            "Webhook request refused: {} is not a public address",
+            ip
+        )));
+    }
+    Ok(())
+}
+
+fn sign(secret: &str, payload: &[u8]) -> Result<String, DomainError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| DomainError::Infra(format!("HMAC error: {:?}", e)))?;
+    mac.update(payload);
+    Ok(format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(
+        &self,
+        endpoint: &WebhookEndpoint,
+        delivery: &WebhookDelivery,
+    ) -> Result<WebhookResponse, DomainError> {
+        check_ip_literal(&endpoint.url)?;
+        let signature = sign(&endpoint.secret, delivery.payload.as_bytes())?;
+        let response = self
+            .client
+            .post(&endpoint.url)
+            .header("Content-Type", "application/json")
+            .header("X-Rust4u-Event", delivery.event_type.as_str())
+            .header("X-Rust4u-Delivery", delivery.id.to_string())
+            .header("X-Rust4u-Signature-256", signature)
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map_err(|e| DomainError::Infra(format!("Webhook request failed: {}", e)))?;
+        // The body is dropped unread, so a large one costs nothing
+        Ok(WebhookResponse {
+            status: response.status().as_u16(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_are_hex_hmac_sha256_of_the_body() {
+        let payload = br#"{"event":"issue.created","data":{"id":1}}"#;
+        assert_eq!(
+            sign("whsec_test", payload).unwrap(),
+            "sha256=1aa9e279de42e5ef46c47bb27e71220f2a22021e98b3eb4d4ef5eb508c6c4c2f"
+        );
+        assert_eq!(
+            sign("", b"").unwrap(),
+            "sha256=b613679a0814d9ec772f95d778c35fc5ff1697c493715653c6c712144292c5ad"
+        );
+    }
+
+    #[test]
+    fn ip_literals_must_be_public() {
+        for url in [
+            "https://example.com/hook",
+            "https://93.184.216.34/hook",
+            "https://[2606:4700:4700::1111]/hook",
+        ] {
+            assert!(check_ip_literal(url).is_ok(), "{}", url);
+        }
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]:8080/hook",
+            "http://[::ffff:10.0.0.1]/hook",
+            // Shorthand forms normalized by the URL parser
+            "http://2130706433/hook",
+            "http://0x7f.1/hook",
+        ] {
+            assert!(
+                matches!(check_ip_literal(url), Err(DomainError::Infra(_))),
+                "{}",
+                url
+            );
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::domain::gateways::webhook_sender::SharedWebhookSender;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub mod http_sender;
+
+use http_sender::HttpWebhookSender;
+
+/// HTTP sender for the delivery worker. `WEBHOOK_TIMEOUT_SECONDS` (default 10)
+/// bounds each attempt.
+pub fn webhook_sender_from_env() -> anyhow::Result<SharedWebhookSender> {
+    let timeout: u64 = env::var("WEBHOOK_TIMEOUT_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|seconds| *seconds > 0)
+        .unwrap_or(10);
+    Ok(Arc::new(HttpWebhookSender::new(Duration::from_secs(
+        timeout,
+    ))?))
+}
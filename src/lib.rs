@@ -2,3 +2,4 @@ pub mod domain;
 pub mod application;
 pub mod infra;
 pub mod api;
+pub mod worker;
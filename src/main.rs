@@ -1,8 +1,13 @@
 use dotenv::dotenv;
 use rust4u_backend::api::create_routes;
+use rust4u_backend::infra::config::run_worker;
 use rust4u_backend::infra::db::create_db_pool;
+use rust4u_backend::infra::escrow::escrow_gateway_from_env;
+use rust4u_backend::infra::mailer::mailer_from_env;
+use rust4u_backend::infra::repository_host::repository_host_from_env;
+use rust4u_backend::infra::webhooks::webhook_sender_from_env;
+use rust4u_backend::worker;
 use std::env;
-use tracing_subscriber;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -14,8 +19,27 @@ async fn main() -> anyhow::Result<()> {
     // Create DB pool
     let pool = create_db_pool(&database_url).await?;
 
+    // On-chain escrow backend (mock chain unless ESCROW_GATEWAY=substrate)
+    let escrow = escrow_gateway_from_env()?;
+
+    // GitHub client for project links and issue imports
+    let repository_host = repository_host_from_env()?;
+
+    // Background jobs (emails included), event dispatch, webhook delivery and
+    // syncs, unless they run in a separate rust4u-worker process
+    if run_worker() {
+        let webhook_sender = webhook_sender_from_env()?;
+        let mailer = mailer_from_env()?;
+        tokio::spawn(worker::run(
+            pool.clone(),
+            repository_host.clone(),
+            webhook_sender,
+            mailer,
+        ));
+    }
+
     // Build our application with routes
-    let app = create_routes(pool);
+    let app = create_routes(pool, escrow, repository_host);
 
     let addr = "0.0.0.0:3000".parse().unwrap();
     tracing::info!("Listening on {}", addr);
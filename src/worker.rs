@@ -0,0 +1,278 @@
+//! Background work that runs outside requests: the job queue (which sends emails
+//! and expires idle claims), the domain event dispatcher, webhook delivery and the
+//! repository sync. It runs inside the API server unless `RUN_WORKER=false`, or on
+//! its own as the `rust4u-worker` binary; several workers can share the database.
+
+use crate::application::usecases::claim_usecases::ClaimUsecases;
+use crate::application::usecases::email_usecases::EmailUsecases;
+use crate::application::usecases::issue_usecases::IssueUsecases;
+use crate::application::usecases::ledger_usecases::LedgerUsecases;
+use crate::application::usecases::notification_usecases::NotificationUsecases;
+use crate::application::usecases::outbox_usecases::OutboxUsecases;
+use crate::application::usecases::project_usecases::ProjectUsecases;
+use crate::application::usecases::webhook_usecases::WebhookUsecases;
+use crate::domain::errors::domain_error::DomainError;
+use crate::domain::gateways::mailer::SharedMailer;
+use crate::domain::gateways::repository_host::SharedRepositoryHost;
+use crate::domain::gateways::webhook_sender::SharedWebhookSender;
+use crate::domain::repositories::issue_claim_repository::IssueClaimRepository;
+use crate::domain::repositories::issue_repository::IssueRepository;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::infra::config::{app_url, claim_inactivity_ttl, platform_fee_bps, repo_sync_interval};
+use crate::infra::db::issue_claim_repository_sql::IssueClaimRepositorySql;
+use crate::infra::db::issue_repository_sql::IssueRepositorySql;
+use crate::infra::db::job_repository_sql::JobRepositorySql;
+use crate::infra::db::ledger_repository_sql::LedgerRepositorySql;
+use crate::infra::db::notification_repository_sql::NotificationRepositorySql;
+use crate::infra::db::organization_repository_sql::OrganizationRepositorySql;
+use crate::infra::db::outbox_repository_sql::OutboxRepositorySql;
+use crate::infra::db::project_repository_sql::ProjectRepositorySql;
+use crate::infra::db::user_repository_sql::UserRepositorySql;
+use crate::infra::db::webhook_repository_sql::WebhookRepositorySql;
+use crate::infra::jobs::worker::JobWorker;
+use crate::infra::jobs::{job_queue, JobHandler, JobPayload};
+use crate::infra::mailer::queue::{email_queue, SendEmailHandler};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the job worker looks for due jobs, and how many it runs per batch.
+/// Full batches are followed by another one right away.
+const JOB_TICK: Duration = Duration::from_secs(1);
+const JOB_BATCH_SIZE: u32 = 20;
+/// Completed jobs are kept for a week, and purged hourly.
+const JOB_RETENTION_DAYS: i64 = 7;
+const JOB_PURGE_TICK: Duration = Duration::from_secs(60 * 60);
+
+/// How often the repository sync wakes up, and how many projects and imported
+/// issues it refreshes each time.
+const REPO_SYNC_TICK: Duration = Duration::from_secs(60);
+const REPO_SYNC_BATCH_SIZE: u32 = 20;
+
+/// How often the webhook worker looks for due deliveries, and how many it sends
+/// per batch. Full batches are followed by another one right away.
+const WEBHOOK_DELIVERY_TICK: Duration = Duration::from_secs(5);
+const WEBHOOK_DELIVERY_BATCH_SIZE: u32 = 20;
+
+/// How often the outbox dispatcher looks for due events, and how many it hands
+/// out per batch. Full batches are followed by another one right away.
+const OUTBOX_DISPATCH_TICK: Duration = Duration::from_secs(1);
+const OUTBOX_DISPATCH_BATCH_SIZE: u32 = 50;
+/// Dispatched events are kept for a week, and purged hourly.
+const OUTBOX_RETENTION_DAYS: i64 = 7;
+const OUTBOX_PURGE_TICK: Duration = Duration::from_secs(60 * 60);
+
+/// How often an `expire_stale_claims` job is queued. Every worker queues it, but
+/// only once per tick between them.
+const CLAIM_EXPIRY_TICK: Duration = Duration::from_secs(5 * 60);
+
+/// Job expiring the claims idle for longer than `CLAIM_INACTIVITY_DAYS`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExpireStaleClaims {}
+
+impl JobPayload for ExpireStaleClaims {
+    const KIND: &'static str = "expire_stale_claims";
+    /// The next tick queues another run anyway.
+    const MAX_ATTEMPTS: i32 = 1;
+}
+
+pub struct ExpireStaleClaimsHandler<C, R, P>
+where
+    C: IssueClaimRepository,
+    R: IssueRepository,
+    P: ProjectRepository,
+{
+    claims: ClaimUsecases<C, R, P>,
+}
+
+#[async_trait]
+impl<C, R, P> JobHandler for ExpireStaleClaimsHandler<C, R, P>
+where
+    C: IssueClaimRepository + Send + Sync + 'static,
+    R: IssueRepository + Send + Sync + 'static,
+    P: ProjectRepository + Send + Sync + 'static,
+{
+    type Payload = ExpireStaleClaims;
+
+    async fn handle(&self, _: ExpireStaleClaims) -> Result<(), DomainError> {
+        let expired = self.claims.expire_stale_claims().await?;
+        if expired > 0 {
+            tracing::info!("Expired {} stale claims", expired);
+        }
+        Ok(())
+    }
+}
+
+/// Runs every background loop until the process stops.
+pub async fn run(
+    pool: Pool<Postgres>,
+    repository_host: SharedRepositoryHost,
+    webhook_sender: SharedWebhookSender,
+    mailer: SharedMailer,
+) {
+    tokio::join!(
+        run_jobs(job_worker(pool.clone(), mailer)),
+        dispatch_events(pool.clone()),
+        deliver_webhooks(pool.clone(), webhook_sender),
+        sync_repositories(pool.clone(), repository_host),
+        schedule_claim_expiry(pool),
+    );
+}
+
+/// The job worker with a handler for every job kind.
+fn job_worker(pool: Pool<Postgres>, mailer: SharedMailer) -> JobWorker<JobRepositorySql> {
+    JobWorker::new(JobRepositorySql::new(pool.clone()))
+        .with_handler(SendEmailHandler::new(
+            UserRepositorySql::new(pool.clone()),
+            mailer,
+            app_url(),
+        ))
+        .with_handler(ExpireStaleClaimsHandler {
+            claims: ClaimUsecases::new(
+                IssueClaimRepositorySql::new(pool.clone()),
+                IssueRepositorySql::new(pool.clone()),
+                ProjectRepositorySql::new(pool),
+                claim_inactivity_ttl(),
+            ),
+        })
+}
+
+async fn run_jobs(worker: JobWorker<JobRepositorySql>) {
+    let retention = chrono::Duration::days(JOB_RETENTION_DAYS);
+    let mut ticker = tokio::time::interval(JOB_TICK);
+    let mut purge_ticker = tokio::time::interval(JOB_PURGE_TICK);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => loop {
+                match worker.run_due(JOB_BATCH_SIZE).await {
+                    Ok(claimed) if claimed == JOB_BATCH_SIZE as usize => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        tracing::warn!("Job run failed: {}", e);
+                        break;
+                    }
+                }
+            },
+            _ = purge_ticker.tick() => match worker.purge_completed(retention).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!("Purged {} completed jobs", purged),
+                Err(e) => tracing::warn!("Job purge failed: {}", e),
+            },
+        }
+    }
+}
+
+async fn sync_repositories(pool: Pool<Postgres>, repository_host: SharedRepositoryHost) {
+    let projects = ProjectUsecases::new(ProjectRepositorySql::new(pool.clone()))
+        .with_repository_host(repository_host.clone());
+    let ledger = LedgerUsecases::new(
+        LedgerRepositorySql::new(pool.clone()),
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool.clone()),
+        platform_fee_bps(),
+    );
+    let issues = IssueUsecases::new(
+        IssueRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool),
+        ledger,
+    )
+    .with_repository_host(repository_host);
+    let max_age = repo_sync_interval();
+    let mut ticker = tokio::time::interval(REPO_SYNC_TICK);
+    loop {
+        ticker.tick().await;
+        match projects
+            .sync_repo_stats(max_age, REPO_SYNC_BATCH_SIZE)
+            .await
+        {
+            Ok(0) => {}
+            Ok(synced) => tracing::info!("Synced repository stats of {} projects", synced),
+            Err(e) => tracing::warn!("Repository stats sync failed: {}", e),
+        }
+        match issues
+            .sync_upstream_issues(max_age, REPO_SYNC_BATCH_SIZE)
+            .await
+        {
+            Ok(0) => {}
+            Ok(synced) => tracing::info!("Synced {} imported issues", synced),
+            Err(e) => tracing::warn!("Imported issue sync failed: {}", e),
+        }
+    }
+}
+
+async fn dispatch_events(pool: Pool<Postgres>) {
+    let webhooks = WebhookUsecases::new(
+        WebhookRepositorySql::new(pool.clone()),
+        OrganizationRepositorySql::new(pool.clone()),
+    );
+    let notifications = NotificationUsecases::new(
+        NotificationRepositorySql::new(pool.clone()),
+        ProjectRepositorySql::new(pool.clone()),
+    )
+    .with_email_queue(email_queue(pool.clone()));
+    let emails = EmailUsecases::new(email_queue(pool.clone()));
+    let outbox = OutboxUsecases::new(OutboxRepositorySql::new(pool))
+        .with_subscriber(Arc::new(webhooks))
+        .with_subscriber(Arc::new(notifications))
+        .with_subscriber(Arc::new(emails));
+    let retention = chrono::Duration::days(OUTBOX_RETENTION_DAYS);
+    let mut ticker = tokio::time::interval(OUTBOX_DISPATCH_TICK);
+    let mut purge_ticker = tokio::time::interval(OUTBOX_PURGE_TICK);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => loop {
+                match outbox.dispatch_due(OUTBOX_DISPATCH_BATCH_SIZE).await {
+                    Ok(claimed) if claimed == OUTBOX_DISPATCH_BATCH_SIZE as usize => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        tracing::warn!("Event dispatch failed: {}", e);
+                        break;
+                    }
+                }
+            },
+            _ = purge_ticker.tick() => match outbox.purge_dispatched(retention).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!("Purged {} dispatched events", purged),
+                Err(e) => tracing::warn!("Outbox purge failed: {}", e),
+            },
+        }
+    }
+}
+
+async fn deliver_webhooks(pool: Pool<Postgres>, sender: SharedWebhookSender) {
+    let webhooks = WebhookUsecases::new(
+        WebhookRepositorySql::new(pool.clone()),
+        OrganizationRepositorySql::new(pool),
+    )
+    .with_webhook_sender(sender);
+    let mut ticker = tokio::time::interval(WEBHOOK_DELIVERY_TICK);
+    loop {
+        ticker.tick().await;
+        loop {
+            match webhooks.deliver_due(WEBHOOK_DELIVERY_BATCH_SIZE).await {
+                Ok(attempted) if attempted == WEBHOOK_DELIVERY_BATCH_SIZE as usize => continue,
+                Ok(_) => break,
+                Err(e) => {
+                    tracing::warn!("Webhook delivery failed: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn schedule_claim_expiry(pool: Pool<Postgres>) {
+    let queue = job_queue(pool);
+    let mut ticker = tokio::time::interval(CLAIM_EXPIRY_TICK);
+    loop {
+        ticker.tick().await;
+        let tick = Utc::now().timestamp() / CLAIM_EXPIRY_TICK.as_secs() as i64;
+        let dedup_key = format!("expire_stale_claims:{}", tick);
+        if let Err(e) = queue.enqueue_once(&ExpireStaleClaims {}, &dedup_key).await {
+            tracing::warn!("Queueing claim expiry failed: {}", e);
+        }
+    }
+}